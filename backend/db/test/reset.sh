@@ -18,4 +18,14 @@ delete_entries() {
 delete_entries "ships"
 delete_entries "empires"
 delete_entries "locations"
+delete_entries "change_requests"
+delete_entries "sessions"
+delete_entries "recovery_codes"
+delete_entries "totp_credentials"
 delete_entries "users"
+delete_entries "export_jobs"
+delete_entries "api_keys"
+delete_entries "telemetry_events"
+delete_entries "webhook_deliveries"
+delete_entries "webhooks"
+delete_entries "templates"
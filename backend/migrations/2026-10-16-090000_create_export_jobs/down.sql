@@ -0,0 +1,2 @@
+-- Drop the export_jobs table
+DROP TABLE export_jobs;
\ No newline at end of file
@@ -0,0 +1,15 @@
+-- Create the export_jobs table
+CREATE TABLE export_jobs (
+                            id SERIAL PRIMARY KEY,
+                            created_by INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                            name VARCHAR(100) NOT NULL,
+                            resource VARCHAR(20) NOT NULL,
+                            schedule VARCHAR(100) NOT NULL,
+                            notify_email VARCHAR(100),
+                            notify_webhook VARCHAR(255),
+                            enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                            next_run_at TIMESTAMP NOT NULL,
+                            last_run_at TIMESTAMP,
+                            last_artifact VARCHAR(255),
+                            last_error TEXT
+);
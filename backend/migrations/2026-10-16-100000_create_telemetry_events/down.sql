@@ -0,0 +1,2 @@
+-- Drop the telemetry_events table
+DROP TABLE telemetry_events;
\ No newline at end of file
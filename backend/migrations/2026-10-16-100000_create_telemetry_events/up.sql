@@ -0,0 +1,13 @@
+-- Create the telemetry_events table
+CREATE TABLE telemetry_events (
+                            id SERIAL PRIMARY KEY,
+                            kind VARCHAR(20) NOT NULL,
+                            name VARCHAR(255) NOT NULL,
+                            method VARCHAR(10),
+                            status INT,
+                            duration_ms DOUBLE PRECISION,
+                            message TEXT,
+                            received_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX telemetry_events_kind_received_at_idx ON telemetry_events (kind, received_at);
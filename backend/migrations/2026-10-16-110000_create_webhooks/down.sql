@@ -0,0 +1,3 @@
+-- Drop the webhook_deliveries and webhooks tables
+DROP TABLE webhook_deliveries;
+DROP TABLE webhooks;
\ No newline at end of file
@@ -0,0 +1,22 @@
+-- Create the webhooks table
+CREATE TABLE webhooks (
+                            id SERIAL PRIMARY KEY,
+                            url VARCHAR(255) NOT NULL,
+                            event_types TEXT[] NOT NULL,
+                            enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                            created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Create the webhook_deliveries table
+CREATE TABLE webhook_deliveries (
+                            id SERIAL PRIMARY KEY,
+                            webhook_id INT NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+                            event_type VARCHAR(50) NOT NULL,
+                            payload TEXT NOT NULL,
+                            status_code INT,
+                            error TEXT,
+                            redrive_of INT,
+                            delivered_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX webhook_deliveries_webhook_id_idx ON webhook_deliveries (webhook_id, id);
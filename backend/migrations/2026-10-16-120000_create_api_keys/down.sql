@@ -0,0 +1,2 @@
+-- Drop the api_keys table
+DROP TABLE api_keys;
\ No newline at end of file
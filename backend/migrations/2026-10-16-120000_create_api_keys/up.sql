@@ -0,0 +1,11 @@
+-- Create the api_keys table
+CREATE TABLE api_keys (
+                            id SERIAL PRIMARY KEY,
+                            name VARCHAR(100) NOT NULL,
+                            prefix VARCHAR(16) NOT NULL,
+                            key_hash VARCHAR(64) NOT NULL UNIQUE,
+                            role VARCHAR(10) NOT NULL,
+                            created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                            last_used_at TIMESTAMP,
+                            revoked_at TIMESTAMP
+);
@@ -0,0 +1,2 @@
+-- Drop the sessions table
+DROP TABLE sessions;
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Create the sessions table
+CREATE TABLE sessions (
+                            id SERIAL PRIMARY KEY,
+                            user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                            token_id VARCHAR(64) NOT NULL UNIQUE,
+                            user_agent VARCHAR(255),
+                            ip_address VARCHAR(64),
+                            created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                            last_seen_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                            revoked_at TIMESTAMP
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
@@ -0,0 +1,3 @@
+-- Drop the recovery_codes and totp_credentials tables
+DROP TABLE recovery_codes;
+DROP TABLE totp_credentials;
\ No newline at end of file
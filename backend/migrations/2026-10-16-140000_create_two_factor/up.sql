@@ -0,0 +1,18 @@
+-- Create the totp_credentials table
+CREATE TABLE totp_credentials (
+                            user_id INT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+                            secret VARCHAR(40) NOT NULL,
+                            confirmed_at TIMESTAMP,
+                            last_used_step BIGINT,
+                            created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Create the recovery_codes table
+CREATE TABLE recovery_codes (
+                            id SERIAL PRIMARY KEY,
+                            user_id INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                            code_hash VARCHAR(64) NOT NULL,
+                            used_at TIMESTAMP
+);
+
+CREATE INDEX recovery_codes_user_id_idx ON recovery_codes (user_id);
@@ -0,0 +1,4 @@
+-- Remove the creation timestamps from the users, locations and empires tables
+ALTER TABLE empires DROP COLUMN created_at;
+ALTER TABLE locations DROP COLUMN created_at;
+ALTER TABLE users DROP COLUMN created_at;
\ No newline at end of file
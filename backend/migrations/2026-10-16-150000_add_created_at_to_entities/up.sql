@@ -0,0 +1,4 @@
+-- Add creation timestamps to the users, locations and empires tables - existing rows are stamped with the time of migration
+ALTER TABLE users ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT NOW();
+ALTER TABLE locations ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT NOW();
+ALTER TABLE empires ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT NOW();
@@ -0,0 +1,3 @@
+-- Remove the starmap coordinates from the locations table
+ALTER TABLE locations DROP COLUMN y;
+ALTER TABLE locations DROP COLUMN x;
\ No newline at end of file
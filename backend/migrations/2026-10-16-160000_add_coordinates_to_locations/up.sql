@@ -0,0 +1,10 @@
+-- Add starmap coordinates to the locations table - locations without them are left off the map
+ALTER TABLE locations ADD COLUMN x DOUBLE PRECISION;
+ALTER TABLE locations ADD COLUMN y DOUBLE PRECISION;
+
+-- Place the initial locations
+UPDATE locations SET x = 0, y = 0 WHERE star_system = 'New Eden' AND area = 'Yulai';
+UPDATE locations SET x = -14, y = 16 WHERE star_system = 'Genesis' AND area = 'Safizon';
+UPDATE locations SET x = 9, y = -7 WHERE star_system = 'The Forge' AND area = 'Jita';
+UPDATE locations SET x = -31, y = 24 WHERE star_system = 'Domain' AND area = 'Amarr';
+UPDATE locations SET x = -58, y = 63 WHERE star_system = 'Delve' AND area = 'VFK-IV';
@@ -0,0 +1,2 @@
+-- Remove the archived flag from the empires table
+ALTER TABLE empires DROP COLUMN archived;
\ No newline at end of file
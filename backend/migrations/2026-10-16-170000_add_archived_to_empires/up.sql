@@ -0,0 +1,2 @@
+-- Add an archived flag to the empires table - archived empires are kept for the record but left out of listings
+ALTER TABLE empires ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
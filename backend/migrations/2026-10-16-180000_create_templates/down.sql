@@ -0,0 +1,2 @@
+-- Drop the templates table
+DROP TABLE templates;
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Create the templates table - 'fields' holds a JSON object of field values prefilled on creation
+CREATE TABLE templates (
+                            id SERIAL PRIMARY KEY,
+                            name VARCHAR(100) NOT NULL,
+                            resource VARCHAR(20) NOT NULL,
+                            fields TEXT NOT NULL,
+                            created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
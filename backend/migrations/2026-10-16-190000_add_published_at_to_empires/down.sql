@@ -0,0 +1,2 @@
+-- Remove the publication time from the empires table
+ALTER TABLE empires DROP COLUMN published_at;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Add a publication time to the empires table - empires are hidden from readers until it has passed, and staged while it is unset
+ALTER TABLE empires ADD COLUMN published_at TIMESTAMP;
+
+-- Existing empires have been visible all along
+UPDATE empires SET published_at = created_at;
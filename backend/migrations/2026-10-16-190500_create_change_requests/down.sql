@@ -0,0 +1,2 @@
+-- Drop the change_requests table
+DROP TABLE change_requests;
\ No newline at end of file
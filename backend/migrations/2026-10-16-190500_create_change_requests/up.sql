@@ -0,0 +1,14 @@
+-- Create the change_requests table - edits proposed by writers, held as a JSON diff until an editor approves or rejects them
+CREATE TABLE change_requests (
+                                  id SERIAL PRIMARY KEY,
+                                  resource VARCHAR(20) NOT NULL,
+                                  record_id INTEGER NOT NULL,
+                                  changes TEXT NOT NULL,
+                                  status VARCHAR(20) NOT NULL DEFAULT 'pending',
+                                  requested_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+                                  reviewed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+                                  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+                                  reviewed_at TIMESTAMP
+);
+
+CREATE INDEX change_requests_status_idx ON change_requests (status);
@@ -0,0 +1,2 @@
+-- Remove the deactivated flag from the users table
+ALTER TABLE users DROP COLUMN deactivated;
\ No newline at end of file
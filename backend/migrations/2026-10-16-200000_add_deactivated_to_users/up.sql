@@ -0,0 +1,2 @@
+-- Add a deactivated flag to the users table - deactivated users are kept along with everything they own, but may no longer sign in
+ALTER TABLE users ADD COLUMN deactivated BOOLEAN NOT NULL DEFAULT FALSE;
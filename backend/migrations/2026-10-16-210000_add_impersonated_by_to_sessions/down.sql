@@ -0,0 +1,2 @@
+-- Remove the impersonating admin from sessions
+ALTER TABLE sessions DROP COLUMN impersonated_by;
\ No newline at end of file
@@ -0,0 +1,2 @@
+-- Record the admin who opened a session by impersonating its user
+ALTER TABLE sessions ADD COLUMN impersonated_by INT REFERENCES users(id) ON DELETE SET NULL;
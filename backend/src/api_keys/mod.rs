@@ -0,0 +1,3 @@
+pub mod router;
+pub mod service;
+pub mod model;
@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_derive::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::{
+    schema::api_keys,
+    users::model::{string_to_user_role, UserRole},
+};
+
+// The hash is never selected - keys are identified by their prefix instead
+#[derive(Serialize, Debug, Clone, Queryable, Selectable, ToSchema)]
+#[diesel(table_name = api_keys)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateApiKey {
+    pub name: String,
+    pub role: String,
+}
+
+impl CreateApiKey {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.len() > 100 {
+            return Err("Invalid input for field 'name'".to_string());
+        }
+        if string_to_user_role(self.role.clone()) == UserRole::INVALID {
+            return Err("Invalid input for field 'role'".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Response to creating a key - the only time the secret is ever returned
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct CreatedApiKey {
+    pub api_key: ApiKey,
+    pub secret: String,
+}
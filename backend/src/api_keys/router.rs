@@ -0,0 +1,241 @@
+pub mod router {
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::State, extract, middleware,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::require_admin,
+            security::{generate_api_key, hash_api_key, API_KEY_PREFIX}
+        },
+        api_keys::{
+            service::service::ApiKeysTable as apiKeysTable,
+            model::{CreateApiKey, CreatedApiKey}
+        }
+    };
+
+    // Characters of the key shown in listings, enough to tell keys apart
+    const DISPLAYED_PREFIX_LENGTH: usize = 8;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn api_keys_route(shared_connection_pool: ConnectionPool) -> Router {
+        // API keys are managed by administrators only
+        let admin_routes = Router::new()
+            .route("/admin/api-keys", axum::routing::get(get_all_api_keys_handler).post(create_api_key_handler))
+            .route("/admin/api-keys/:api_key_id", axum::routing::delete(revoke_api_key_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(admin_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/admin/api-keys",
+        tag = "api-keys",
+        operation_id = "get_all_api_keys",
+        responses(
+            (status = 200, body = [ApiKey])
+        )
+    )]
+    pub async fn get_all_api_keys_handler(
+        State(shared_state): State<ConnectionPool>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match apiKeysTable::new(connection).get_all() {
+            Ok(api_keys) => Ok((StatusCode::OK, Json(api_keys))),
+            Err(err) => {
+                eprintln!("Error fetching all API keys: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch API keys"}))))
+            }
+        }
+    }
+
+    // Creates a key scoped to the given role. Only its hash is stored, so the secret in the response cannot be retrieved again.
+    #[utoipa::path(
+        post,
+        path = "/admin/api-keys",
+        tag = "api-keys",
+        operation_id = "create_api_key",
+        request_body = CreateApiKey,
+        responses(
+            (status = 201, description = "The key along with its secret, which is not shown again", body = CreatedApiKey),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn create_api_key_handler(
+        State(shared_state): State<ConnectionPool>,
+        Json(create_api_key): Json<CreateApiKey>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        if let Err(err) = create_api_key.validate() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        let secret = generate_api_key()?;
+        let prefix = &secret[..API_KEY_PREFIX.len() + DISPLAYED_PREFIX_LENGTH];
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match apiKeysTable::new(connection).create(create_api_key, prefix, &hash_api_key(&secret)) {
+            Ok(api_key) => Ok((StatusCode::CREATED, Json(CreatedApiKey { api_key, secret }))),
+            Err(err) => {
+                eprintln!("Error creating API key: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create API key"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/admin/api-keys/{api_key_id}",
+        tag = "api-keys",
+        operation_id = "revoke_api_key",
+        params(("api_key_id" = i32, Path, description = "ID of the API key")),
+        responses(
+            (status = 204),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn revoke_api_key_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (api_key_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match apiKeysTable::new(connection).revoke(api_key_id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "API key not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error revoking API key: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to revoke API key"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use serde_json::json;
+        use tower::ServiceExt;
+        use crate::{
+            api_keys::router::router::api_keys_route,
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn api_key_grants_access_until_revoked() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = api_keys_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "nøkkel.admin@concord.gov", UserRole::ADMIN).unwrap();
+
+            // Create an ADMIN key
+            let request = Request::builder()
+                .uri("/admin/api-keys")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(json!({"name": "Nightly sync", "role": "ADMIN"}).to_string()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let secret = created["secret"].as_str().unwrap().to_string();
+            let api_key_id = created["api_key"]["id"].as_i64().unwrap();
+
+            // Assert that the secret starts with the displayed prefix and that the hash is not exposed
+            assert!(secret.starts_with(created["api_key"]["prefix"].as_str().unwrap()));
+            assert!(created["api_key"]["key_hash"].is_null());
+
+            // Assert that the key authorizes requests on its own
+            let request = Request::builder()
+                .uri("/admin/api-keys")
+                .method("GET")
+                .header("X-Api-Key", &secret)
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Revoke the key
+            let request = Request::builder()
+                .uri(format!("/admin/api-keys/{}", api_key_id))
+                .method("DELETE")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            // Assert that the revoked key is rejected
+            let request = Request::builder()
+                .uri("/admin/api-keys")
+                .method("GET")
+                .header("X-Api-Key", &secret)
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        #[tokio::test]
+        async fn api_key_is_limited_to_its_role() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = api_keys_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "nøkkel.leser@concord.gov", UserRole::ADMIN).unwrap();
+
+            // Create a READER key
+            let request = Request::builder()
+                .uri("/admin/api-keys")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(json!({"name": "Dashboard", "role": "READER"}).to_string()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that the key cannot be used for admin routes
+            let request = Request::builder()
+                .uri("/admin/api-keys")
+                .method("GET")
+                .header("X-Api-Key", created["secret"].as_str().unwrap())
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+pub mod service {
+    use chrono::Utc;
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::{
+        api_keys::model::{ApiKey, CreateApiKey},
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct ApiKeysTable {
+        connection: PooledPg,
+    }
+
+    impl ApiKeysTable {
+        pub fn new(connection: PooledPg) -> ApiKeysTable {
+            ApiKeysTable { connection }
+        }
+
+        pub fn create(&mut self, create_api_key: CreateApiKey, prefix: &str, key_hash: &str) -> Result<ApiKey, diesel::result::Error> {
+            use schema::api_keys;
+
+            diesel::insert_into(api_keys::table)
+                .values((
+                    api_keys::name.eq(create_api_key.name.trim()),
+                    api_keys::prefix.eq(prefix),
+                    api_keys::key_hash.eq(key_hash),
+                    api_keys::role.eq(&create_api_key.role),
+                ))
+                .returning(ApiKey::as_returning())
+                .get_result(&mut self.connection)
+        }
+
+        // All keys, revoked ones included, newest first
+        pub fn get_all(&mut self) -> Result<Vec<ApiKey>, diesel::result::Error> {
+            use schema::api_keys;
+
+            api_keys::table
+                .order(api_keys::id.desc())
+                .select(ApiKey::as_select())
+                .load::<ApiKey>(&mut self.connection)
+        }
+
+        pub fn get_active_by_hash(&mut self, key_hash: &str) -> Result<Option<ApiKey>, diesel::result::Error> {
+            use schema::api_keys;
+
+            api_keys::table
+                .filter(api_keys::key_hash.eq(key_hash))
+                .filter(api_keys::revoked_at.is_null())
+                .select(ApiKey::as_select())
+                .get_result(&mut self.connection)
+                .optional()
+        }
+
+        pub fn touch(&mut self, api_key_id: i32) -> Result<(), diesel::result::Error> {
+            use schema::api_keys;
+
+            diesel::update(api_keys::table.find(api_key_id))
+                .set(api_keys::last_used_at.eq(Some(Utc::now().naive_utc())))
+                .execute(&mut self.connection)
+                .map(|_| ())
+        }
+
+        // Revoked keys are kept for auditing. Revoking an already revoked key keeps its original revocation time.
+        pub fn revoke(&mut self, api_key_id: i32) -> Result<ApiKey, diesel::result::Error> {
+            use schema::api_keys;
+
+            let api_key = api_keys::table
+                .find(api_key_id)
+                .select(ApiKey::as_select())
+                .get_result::<ApiKey>(&mut self.connection)?;
+
+            if api_key.revoked_at.is_some() {
+                return Ok(api_key);
+            }
+
+            diesel::update(api_keys::table.find(api_key_id))
+                .set(api_keys::revoked_at.eq(Some(Utc::now().naive_utc())))
+                .returning(ApiKey::as_returning())
+                .get_result(&mut self.connection)
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{de::DeserializeOwned, Serialize as ToJson};
+use serde_derive::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+use serde_json::Value;
+use crate::common::util::serialize_json_text;
+
+// Review states of a change request
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_APPROVED: &str = "approved";
+pub const STATUS_REJECTED: &str = "rejected";
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+pub struct ChangeRequest {
+    pub id: i32,
+    // Kind of record changed, 'empire' or 'location'
+    pub resource: String,
+    pub record_id: i32,
+    // Stored as JSON text, but served as an object of field changes
+    #[serde(serialize_with = "serialize_json_text")]
+    #[schema(value_type = Object)]
+    pub changes: String,
+    pub status: String,
+    // None for changes requested with an API key, or by users since deleted
+    pub requested_by: Option<i32>,
+    pub reviewed_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub reviewed_at: Option<NaiveDateTime>,
+}
+
+impl ChangeRequest {
+    // Table of the changed record, as named in field permissions
+    pub fn table(&self) -> &'static str {
+        match self.resource.as_str() {
+            "location" => "locations",
+            _ => "empires",
+        }
+    }
+
+    // Names of the fields the request changes
+    pub fn changed_fields(&self) -> Vec<String> {
+        serde_json::from_str::<ChangeSet>(&self.changes)
+            .map(|changes| changes.into_keys().collect())
+            .unwrap_or_default()
+    }
+}
+
+// Proposed value of a field, along with the value it replaces
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FieldChange {
+    pub from: Value,
+    pub to: Value,
+}
+
+// Changes by field name
+pub type ChangeSet = BTreeMap<String, FieldChange>;
+
+// The fields of 'proposed' that differ from 'current'
+pub fn diff<T: ToJson>(current: &T, proposed: &T) -> ChangeSet {
+    let (Ok(Value::Object(current)), Ok(Value::Object(proposed))) = (serde_json::to_value(current), serde_json::to_value(proposed)) else {
+        return ChangeSet::new();
+    };
+
+    proposed.into_iter()
+        .filter_map(|(field, to)| {
+            let from = current.get(&field).cloned().unwrap_or(Value::Null);
+            (from != to).then_some((field, FieldChange { from, to }))
+        })
+        .collect()
+}
+
+// Applies the changes to 'current'. Returns None if any of the fields no longer holds the value the change was based on,
+// as the record has then been edited since the change was requested.
+pub fn apply<T: ToJson + DeserializeOwned>(current: &T, changes: &ChangeSet) -> Option<T> {
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(current) else {
+        return None;
+    };
+
+    for (field, change) in changes {
+        if fields.get(field) != Some(&change.from) {
+            return None;
+        }
+        fields.insert(field.clone(), change.to.clone());
+    }
+
+    serde_json::from_value(Value::Object(fields)).ok()
+}
+
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChangeRequestParams {
+    pub status: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::{
+        change_requests::model::{apply, diff, FieldChange},
+        locations::model::UpsertLocation,
+    };
+
+    #[test]
+    fn diff_lists_changed_fields_and_apply_rejects_stale_changes() {
+        let current = UpsertLocation { star_system: "Domain".to_string(), area: "Amarr Prime".to_string() };
+        let proposed = UpsertLocation { star_system: "Domain".to_string(), area: "Amarr VIII (Oris)".to_string() };
+
+        let changes = diff(&current, &proposed);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes["area"], FieldChange { from: json!("Amarr Prime"), to: json!("Amarr VIII (Oris)") });
+
+        let applied = apply(&current, &changes).unwrap();
+        assert_eq!(applied.area, "Amarr VIII (Oris)");
+
+        // Assert that changes are not applied over edits made in the meantime
+        let edited = UpsertLocation { star_system: "Domain".to_string(), area: "Throne Worlds".to_string() };
+        assert!(apply(&edited, &changes).is_none());
+    }
+}
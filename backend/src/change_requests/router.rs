@@ -0,0 +1,333 @@
+pub mod router {
+    use serde::Serialize;
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::{IntoResponse, Response}, extract::{Query, State}, extract, middleware, Extension,
+    };
+    use crate::{
+        common::{
+            concurrency::Conditional,
+            db::ConnectionPool,
+            middleware::{AuthorizedUser, require_editor},
+            permissions::{forbid_fields, FieldPermissions},
+            warmup::HotCaches
+        },
+        webhooks::dispatch::dispatch_event,
+        change_requests::{
+            service::service::ChangeRequestsTable as changeRequestsTable,
+            model::{diff, ChangeRequestParams, STATUS_PENDING}
+        }
+    };
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn change_requests_route(shared_connection_pool: ConnectionPool, caches: HotCaches) -> Router {
+        // Changes proposed by WRITERs are reviewed by EDITORs and above
+        let review_routes = Router::new()
+            .route("/change-requests", axum::routing::get(get_all_change_requests_handler))
+            .route("/change-requests/:change_request_id", axum::routing::get(read_change_request_handler))
+            .route("/change-requests/:change_request_id/approve", axum::routing::post(approve_change_request_handler))
+            .route("/change-requests/:change_request_id/reject", axum::routing::post(reject_change_request_handler))
+            .layer(Extension(caches))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_editor));
+
+        Router::new()
+            .merge(review_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // Records the edit of a record by someone who may not update it directly as a change request, to be applied once
+    // approved. Responds 202 with the change request, or 422 if the edit would not change anything.
+    pub fn submit_change_request<T: Serialize>(
+        shared_state: &ConnectionPool,
+        authorized: &AuthorizedUser,
+        resource: &str,
+        record_id: i32,
+        current: &T,
+        proposed: &T,
+    ) -> Result<Response, (StatusCode, Json<Value>)> {
+        let changes = diff(current, proposed);
+        if changes.is_empty() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "No changes to submit"}))));
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let requested_by = authorized.user.as_ref().map(|user| user.id);
+
+        match changeRequestsTable::new(connection).create(resource, record_id, &changes, requested_by) {
+            Ok(change_request) => Ok((StatusCode::ACCEPTED, Json(change_request)).into_response()),
+            Err(err) => {
+                eprintln!("Error creating change request: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to submit changes for review"}))))
+            }
+        }
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/change-requests",
+        tag = "change-requests",
+        operation_id = "get_all_change_requests",
+        params(ChangeRequestParams),
+        responses(
+            (status = 200, body = [ChangeRequest])
+        )
+    )]
+    pub async fn get_all_change_requests_handler(
+        State(shared_state): State<ConnectionPool>,
+        Query(params): Query<ChangeRequestParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match changeRequestsTable::new(connection).get_all(params.status.as_deref()) {
+            Ok(change_requests) => Ok((StatusCode::OK, Json(change_requests))),
+            Err(err) => {
+                eprintln!("Error fetching change requests: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch change requests"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/change-requests/{change_request_id}",
+        tag = "change-requests",
+        operation_id = "read_change_request",
+        params(("change_request_id" = i32, Path, description = "ID of the change request")),
+        responses(
+            (status = 200, body = ChangeRequest),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn read_change_request_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (change_request_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match changeRequestsTable::new(connection).get(change_request_id) {
+            Ok(Some(change_request)) => Ok((StatusCode::OK, Json(change_request))),
+            Ok(None) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "Change request not found"})))),
+            Err(err) => {
+                eprintln!("Error reading change request: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read change request"}))))
+            }
+        }
+    }
+
+    // Applies the requested changes. Requests that have been reviewed already, or whose record has been edited
+    // or deleted since, are turned away with 409 - the latter may only be rejected.
+    #[utoipa::path(
+        post,
+        path = "/change-requests/{change_request_id}/approve",
+        tag = "change-requests",
+        operation_id = "approve_change_request",
+        params(("change_request_id" = i32, Path, description = "ID of the change request")),
+        responses(
+            (status = 200, body = ChangeRequest),
+            (status = 404, body = ApiError),
+            (status = 409, description = "Reviewed already, or the record was changed since", body = ApiError)
+        )
+    )]
+    pub async fn approve_change_request_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Extension(caches): Extension<HotCaches>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (change_request_id, ) = path.0;
+
+        // Reviewers may only approve changes to fields they could have changed themselves
+        match changeRequestsTable::new(shared_state.pool.get().expect("Failed to acquire connection from pool")).get(change_request_id) {
+            Ok(Some(change_request)) => {
+                let changed_fields = change_request.changed_fields();
+                forbid_fields(FieldPermissions::from_env().forbidden_fields(
+                    change_request.table(),
+                    &authorized.role,
+                    changed_fields.iter().map(String::as_str),
+                ))?;
+            },
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Change request not found"})))),
+            Err(err) => {
+                eprintln!("Error reading change request: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to approve change request"}))));
+            }
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let reviewed_by = authorized.user.as_ref().map(|user| user.id);
+
+        match changeRequestsTable::new(connection).approve(change_request_id, reviewed_by) {
+            Ok(Conditional::Applied((change_request, updated_record))) => {
+                let event_type = match change_request.resource.as_str() {
+                    "location" => {
+                        // Approved edits bypass the locations routes, which otherwise drop the cached list on every write
+                        caches.locations.invalidate();
+                        "location.updated"
+                    },
+                    _ => "empire.updated",
+                };
+                dispatch_event(shared_state.clone(), event_type, updated_record);
+                Ok((StatusCode::OK, Json(change_request)))
+            },
+            Ok(Conditional::PreconditionFailed((change_request, _))) if change_request.status != STATUS_PENDING => {
+                Err((StatusCode::CONFLICT, Json(json!({"error": format!("Change request has already been {}", change_request.status)}))))
+            },
+            Ok(Conditional::PreconditionFailed(_)) => {
+                Err((StatusCode::CONFLICT, Json(json!({"error": "The record has been changed or deleted since the change was requested"}))))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Change request not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error approving change request: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to approve change request"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/change-requests/{change_request_id}/reject",
+        tag = "change-requests",
+        operation_id = "reject_change_request",
+        params(("change_request_id" = i32, Path, description = "ID of the change request")),
+        responses(
+            (status = 200, body = ChangeRequest),
+            (status = 404, body = ApiError),
+            (status = 409, description = "Reviewed already", body = ApiError)
+        )
+    )]
+    pub async fn reject_change_request_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (change_request_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let reviewed_by = authorized.user.as_ref().map(|user| user.id);
+
+        match changeRequestsTable::new(connection).reject(change_request_id, reviewed_by) {
+            Ok(Conditional::Applied(change_request)) => Ok((StatusCode::OK, Json(change_request))),
+            Ok(Conditional::PreconditionFailed(change_request)) => {
+                Err((StatusCode::CONFLICT, Json(json!({"error": format!("Change request has already been {}", change_request.status)}))))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Change request not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error rejecting change request: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to reject change request"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use crate::{
+            change_requests::router::router::change_requests_route,
+            common::{
+                db::create_shared_connection_pool,
+                warmup::HotCaches,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            empires::{
+                model::UpsertEmpire,
+                router::router::empires_route,
+                service::service::EmpiresTable
+            },
+            locations::{
+                model::UpsertLocation,
+                service::service::LocationsTable
+            },
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn writer_edits_are_applied_once_approved_and_only_if_not_stale() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone()).merge(change_requests_route(connection_pool.clone(), HotCaches::new()));
+
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Genesis".to_string(), area: "Yulai".to_string() })
+                .unwrap();
+            let empire = EmpiresTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertEmpire {
+                    name: "CONCORD".to_string(),
+                    slogan: "Order".to_string(),
+                    location_id: location.id,
+                    description: "Keeps the peace".to_string(),
+                }, true)
+                .unwrap();
+
+            let writer_token = create_user_and_generate_token(connection_pool.clone(), "foreslag@concord.gov", UserRole::WRITER).unwrap();
+            let editor_token = create_user_and_generate_token(connection_pool, "godkjenner@concord.gov", UserRole::EDITOR).unwrap();
+
+            let send = |method: &str, uri: String, bearer_token: &str, body: Value| {
+                Request::builder()
+                    .uri(uri)
+                    .method(method)
+                    .header("Authorization", format!("Bearer {}", bearer_token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap()
+            };
+            let edit = |slogan: &str| json!({
+                "name": "CONCORD",
+                "slogan": slogan,
+                "location_id": location.id,
+                "description": "Keeps the peace"
+            });
+            let empire_uri = format!("/empires/{}", empire.id);
+
+            // Assert that the edits of a writer are held for review
+            let response = service.clone().oneshot(send("PUT", empire_uri.clone(), &writer_token, edit("Law and order"))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let first: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(first["status"], "pending");
+            assert_eq!(first["changes"], json!({"slogan": {"from": "Order", "to": "Law and order"}}));
+
+            let response = service.clone().oneshot(send("PUT", empire_uri.clone(), &writer_token, edit("Peace through order"))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let second: Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that approval applies the change
+            let approve_uri = |change_request: &Value| format!("/change-requests/{}/approve", change_request["id"]);
+            let response = service.clone().oneshot(send("POST", approve_uri(&first), &editor_token, Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = service.clone().oneshot(send("GET", empire_uri, &editor_token, Value::Null)).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let updated: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(updated["slogan"], "Law and order");
+
+            // Assert that a request cannot be approved twice, nor one based on a value changed since
+            let response = service.clone().oneshot(send("POST", approve_uri(&first), &editor_token, Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+            let response = service.clone().oneshot(send("POST", approve_uri(&second), &editor_token, Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+
+            let reject_uri = format!("/change-requests/{}/reject", second["id"]);
+            let response = service.oneshot(send("POST", reject_uri, &editor_token, Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+pub mod service {
+    use chrono::Utc;
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use serde_json::{json, Value};
+    use crate::{
+        change_requests::model::{apply, ChangeRequest, ChangeSet, STATUS_APPROVED, STATUS_PENDING, STATUS_REJECTED},
+        common::concurrency::Conditional,
+        empires::model::{Empire, UpsertEmpire},
+        locations::model::{Location, UpsertLocation},
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct ChangeRequestsTable {
+        connection: PooledPg,
+    }
+
+    impl ChangeRequestsTable {
+        pub fn new(connection: PooledPg) -> ChangeRequestsTable {
+            ChangeRequestsTable { connection }
+        }
+
+        pub fn create(&mut self, resource: &str, record_id: i32, changes: &ChangeSet, requested_by: Option<i32>,
+        ) -> Result<ChangeRequest, diesel::result::Error> {
+            use schema::change_requests;
+
+            diesel::insert_into(change_requests::table)
+                .values((
+                    change_requests::resource.eq(resource),
+                    change_requests::record_id.eq(record_id),
+                    change_requests::changes.eq(json!(changes).to_string()),
+                    change_requests::requested_by.eq(requested_by),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        // Change requests oldest first, optionally only those of the given status
+        pub fn get_all(&mut self, status: Option<&str>) -> Result<Vec<ChangeRequest>, diesel::result::Error> {
+            use schema::change_requests;
+
+            let mut query = change_requests::table
+                .order(change_requests::id)
+                .into_boxed();
+
+            if let Some(status) = status {
+                query = query.filter(change_requests::status.eq(status));
+            }
+
+            query.load::<ChangeRequest>(&mut self.connection)
+        }
+
+        pub fn get(&mut self, change_request_id: i32) -> Result<Option<ChangeRequest>, diesel::result::Error> {
+            use schema::change_requests;
+
+            change_requests::table
+                .find(change_request_id)
+                .get_result(&mut self.connection)
+                .optional()
+        }
+
+        // Applies the changes to the record and marks the request approved, in a single transaction. Fails the
+        // precondition if the request has already been reviewed, or if the record has been edited or deleted since.
+        // Returns the approved request along with the updated record.
+        pub fn approve(&mut self, change_request_id: i32, reviewed_by: Option<i32>,
+        ) -> Result<Conditional<(ChangeRequest, Value)>, diesel::result::Error> {
+            use schema::change_requests;
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                let change_request = change_requests::table
+                    .find(change_request_id)
+                    .for_update()
+                    .get_result::<ChangeRequest>(connection)?;
+
+                if change_request.status != STATUS_PENDING {
+                    return Ok(Conditional::PreconditionFailed((change_request, Value::Null)));
+                }
+
+                let changes: ChangeSet = serde_json::from_str(&change_request.changes)
+                    .map_err(|err| diesel::result::Error::DeserializationError(Box::new(err)))?;
+
+                let updated_record = match change_request.resource.as_str() {
+                    "empire" => apply_to_empire(connection, change_request.record_id, &changes)?.map(|empire| json!(empire)),
+                    "location" => apply_to_location(connection, change_request.record_id, &changes)?.map(|location| json!(location)),
+                    _ => None,
+                };
+
+                let Some(updated_record) = updated_record else {
+                    return Ok(Conditional::PreconditionFailed((change_request, Value::Null)));
+                };
+
+                let approved = review(connection, change_request_id, STATUS_APPROVED, reviewed_by)?;
+                Ok(Conditional::Applied((approved, updated_record)))
+            })
+        }
+
+        // Marks the request rejected, failing the precondition if it has already been reviewed
+        pub fn reject(&mut self, change_request_id: i32, reviewed_by: Option<i32>,
+        ) -> Result<Conditional<ChangeRequest>, diesel::result::Error> {
+            use schema::change_requests;
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                let change_request = change_requests::table
+                    .find(change_request_id)
+                    .for_update()
+                    .get_result::<ChangeRequest>(connection)?;
+
+                if change_request.status != STATUS_PENDING {
+                    return Ok(Conditional::PreconditionFailed(change_request));
+                }
+
+                review(connection, change_request_id, STATUS_REJECTED, reviewed_by).map(Conditional::Applied)
+            })
+        }
+    }
+
+    fn review(connection: &mut PgConnection, change_request_id: i32, status: &str, reviewed_by: Option<i32>,
+    ) -> Result<ChangeRequest, diesel::result::Error> {
+        use schema::change_requests;
+
+        diesel::update(change_requests::table.find(change_request_id))
+            .set((
+                change_requests::status.eq(status),
+                change_requests::reviewed_by.eq(reviewed_by),
+                change_requests::reviewed_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .get_result(connection)
+    }
+
+    // The updated empire, or None if it no longer exists or has been edited since the changes were requested
+    fn apply_to_empire(connection: &mut PgConnection, empire_id: i32, changes: &ChangeSet,
+    ) -> Result<Option<Empire>, diesel::result::Error> {
+        use schema::empires;
+
+        let Some(current) = empires::table.find(empire_id).for_update().get_result::<Empire>(connection).optional()? else {
+            return Ok(None);
+        };
+        let Some(upsert_empire) = apply(&UpsertEmpire::from(current), changes) else {
+            return Ok(None);
+        };
+
+        diesel::update(empires::table.find(empire_id))
+            .set((
+                empires::name.eq(&upsert_empire.name),
+                empires::slogan.eq(&upsert_empire.slogan),
+                empires::location_id.eq(upsert_empire.location_id),
+                empires::description.eq(&upsert_empire.description)
+            ))
+            .get_result(connection)
+            .map(Some)
+    }
+
+    // The updated location, or None if it no longer exists or has been edited since the changes were requested
+    fn apply_to_location(connection: &mut PgConnection, location_id: i32, changes: &ChangeSet,
+    ) -> Result<Option<Location>, diesel::result::Error> {
+        use schema::locations;
+
+        let Some(current) = locations::table.find(location_id).for_update().get_result::<Location>(connection).optional()? else {
+            return Ok(None);
+        };
+        let Some(upsert_location) = apply(&UpsertLocation::from(current), changes) else {
+            return Ok(None);
+        };
+
+        diesel::update(locations::table.find(location_id))
+            .set((
+                locations::star_system.eq(&upsert_location.star_system),
+                locations::area.eq(&upsert_location.area),
+            ))
+            .get_result(connection)
+            .map(Some)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::exports::model::EXPORTABLE_RESOURCES;
+
+// How command results are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    // A single JSON object, for deployment pipelines to parse
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Migrate,
+    Seed,
+    // Writes the resource to 'output', or to storage like scheduled exports when absent
+    Export { resource: String, output: Option<String> },
+    CheckConfig,
+    // Writes the OpenAPI document of the API to 'output', for the client generator
+    OpenApi { output: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub command: Command,
+    pub format: OutputFormat,
+}
+
+pub const USAGE: &str = "Usage: axum_api_with_auth [migrate | seed | export <users|locations|empires> [--output <file>] | check-config | openapi --output <file>] [--format text|json]";
+
+// Parses the arguments following the program name. Returns None when there are none, in which case the API is served.
+pub fn parse_args(args: &[String]) -> Result<Option<Invocation>, String> {
+    let mut format = OutputFormat::Text;
+    let mut output = None;
+    let mut positional = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().map(String::as_str) {
+                    Some("text") => OutputFormat::Text,
+                    Some("json") => OutputFormat::Json,
+                    other => return Err(format!("Invalid --format '{}', expected 'text' or 'json'", other.unwrap_or(""))),
+                };
+            }
+            "--output" => {
+                output = Some(args.next().ok_or("Missing file after --output")?.clone());
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            value => positional.push(value),
+        }
+    }
+
+    let command = match positional.as_slice() {
+        [] => return Ok(None),
+        ["migrate"] => Command::Migrate,
+        ["seed"] => Command::Seed,
+        ["export", resource] if EXPORTABLE_RESOURCES.contains(resource) => {
+            Command::Export { resource: resource.to_string(), output: output.take() }
+        }
+        ["export", resource] => return Err(format!("Unknown export resource '{}'", resource)),
+        ["export"] => return Err("Missing resource to export".to_string()),
+        ["check-config"] => Command::CheckConfig,
+        ["openapi"] => Command::OpenApi { output: output.take().ok_or("Missing --output for openapi")? },
+        [other, ..] => return Err(format!("Unknown command '{}'", other)),
+    };
+
+    if output.is_some() {
+        return Err("--output only applies to export and openapi".to_string());
+    }
+
+    Ok(Some(Invocation { command, format }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::args::{parse_args, Command, Invocation, OutputFormat};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_reads_command_and_options_in_any_order() {
+        assert_eq!(parse_args(&args(&[])), Ok(None));
+        assert_eq!(
+            parse_args(&args(&["--format", "json", "migrate"])),
+            Ok(Some(Invocation { command: Command::Migrate, format: OutputFormat::Json }))
+        );
+        assert_eq!(
+            parse_args(&args(&["export", "locations", "--output", "locations.json"])),
+            Ok(Some(Invocation {
+                command: Command::Export { resource: "locations".to_string(), output: Some("locations.json".to_string()) },
+                format: OutputFormat::Text,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args(&["openapi", "--output", "openapi.json"])),
+            Ok(Some(Invocation { command: Command::OpenApi { output: "openapi.json".to_string() }, format: OutputFormat::Text }))
+        );
+    }
+
+    #[test]
+    fn parse_args_rejects_unknown_commands_and_options() {
+        assert!(parse_args(&args(&["deploy"])).is_err());
+        assert!(parse_args(&args(&["migrate", "--verbose"])).is_err());
+        assert!(parse_args(&args(&["seed", "--format", "yaml"])).is_err());
+        assert!(parse_args(&args(&["export", "ships"])).is_err());
+        assert!(parse_args(&args(&["seed", "--output", "seed.json"])).is_err());
+        assert!(parse_args(&args(&["openapi"])).is_err());
+    }
+}
@@ -0,0 +1,315 @@
+use std::{fs, path::Path, sync::Arc};
+use diesel::{Connection, PgConnection};
+use serde_json::{json, Map, Value};
+use crate::{
+    cli::{
+        args::{Command, Invocation, OutputFormat, USAGE},
+        migrate::run_pending_migrations,
+    },
+    common::{
+        auth_provider::{check_ldap_transport, parse_ldap_role_mapping},
+        db::try_create_shared_connection_pool,
+        openapi::openapi_json,
+        permissions::FieldPermissions,
+        rate_limit::parse_trusted_proxies,
+        security::hash_password,
+        storage::{LocalStorage, StorageBackend},
+        util::load_optional_environment_variable,
+    },
+    exports::{model::now, scheduler::export_resource},
+    users::{
+        model::{UpsertUser, UserRole},
+        service::service::UsersTable,
+    },
+};
+
+// Exit codes, for pipelines to tell outcomes apart without parsing output
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_INVALID_CONFIG: i32 = 3;
+
+// Outcome of a command. Printed as a single JSON object with '--format json', with the details next to 'command' and 'status'.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub command: &'static str,
+    pub exit_code: i32,
+    pub details: Map<String, Value>,
+    pub error: Option<String>,
+}
+
+impl Report {
+    fn ok(command: &'static str, details: Value) -> Report {
+        Report { command, exit_code: EXIT_OK, details: into_map(details), error: None }
+    }
+
+    fn failed(command: &'static str, exit_code: i32, error: String) -> Report {
+        Report { command, exit_code, details: Map::new(), error: Some(error) }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut report = Map::new();
+        report.insert("command".to_string(), json!(self.command));
+        report.insert("status".to_string(), json!(if self.exit_code == EXIT_OK { "ok" } else { "failed" }));
+        report.insert("exit_code".to_string(), json!(self.exit_code));
+        report.extend(self.details.clone());
+        if let Some(error) = &self.error {
+            report.insert("error".to_string(), json!(error));
+        }
+        Value::Object(report)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![match &self.error {
+            None => format!("{}: ok", self.command),
+            Some(error) => format!("{}: failed - {}", self.command, error),
+        }];
+        lines.extend(self.details.iter().map(|(key, value)| format!("  {}: {}", key, value)));
+        lines.join("\n")
+    }
+}
+
+fn into_map(details: Value) -> Map<String, Value> {
+    match details {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+// Runs the command and prints its report to stdout - logs go to stderr, so that JSON output stays parseable.
+// Returns the exit code of the process.
+pub fn run_command(invocation: &Invocation) -> i32 {
+    let report = match &invocation.command {
+        Command::Migrate => migrate(),
+        Command::Seed => seed(),
+        Command::Export { resource, output } => export(resource, output.as_deref()),
+        Command::CheckConfig => check_config(),
+        Command::OpenApi { output } => openapi(output),
+    };
+
+    print_report(&report, invocation.format);
+    report.exit_code
+}
+
+// Reports malformed arguments in the requested format, as far as it could be made out
+pub fn report_usage_error(error: String, format: OutputFormat) -> i32 {
+    let report = Report { details: into_map(json!({"usage": USAGE})), ..Report::failed("usage", EXIT_USAGE, error) };
+    print_report(&report, format);
+    EXIT_USAGE
+}
+
+fn print_report(report: &Report, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => println!("{}", report.to_text()),
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
+}
+
+fn database_url(command: &'static str) -> Result<String, Report> {
+    load_optional_environment_variable("DEV_DB")
+        .ok_or_else(|| Report::failed(command, EXIT_INVALID_CONFIG, "DEV_DB must be set".to_string()))
+}
+
+// Applies pending migrations from MIGRATIONS_DIR (default 'migrations')
+fn migrate() -> Report {
+    let database_url = match database_url("migrate") {
+        Ok(database_url) => database_url,
+        Err(report) => return report,
+    };
+    let directory = load_optional_environment_variable("MIGRATIONS_DIR").unwrap_or_else(|| "migrations".to_string());
+
+    let result = PgConnection::establish(&database_url)
+        .map_err(|err| format!("Failed to connect to database: {}", err.to_string().trim_end()))
+        .and_then(|mut connection| run_pending_migrations(&mut connection, Path::new(&directory)));
+
+    match result {
+        Ok(applied) => Report::ok("migrate", json!({"applied": applied})),
+        Err(err) => Report::failed("migrate", EXIT_FAILED, err),
+    }
+}
+
+// Creates the first administrator from SEED_ADMIN_EMAIL and SEED_ADMIN_PASSWORD, unless a user with that email exists
+fn seed() -> Report {
+    let database_url = match database_url("seed") {
+        Ok(database_url) => database_url,
+        Err(report) => return report,
+    };
+    let (Some(email), Some(password)) = (
+        load_optional_environment_variable("SEED_ADMIN_EMAIL"),
+        load_optional_environment_variable("SEED_ADMIN_PASSWORD"),
+    ) else {
+        return Report::failed("seed", EXIT_INVALID_CONFIG, "SEED_ADMIN_EMAIL and SEED_ADMIN_PASSWORD must be set".to_string());
+    };
+
+    let pool = match try_create_shared_connection_pool(database_url, 1) {
+        Ok(pool) => pool,
+        Err(err) => return Report::failed("seed", EXIT_FAILED, err),
+    };
+    let mut users = UsersTable::new(pool.pool.get().expect("Failed to acquire connection from pool"));
+
+    match users.get_by_email(email.clone()) {
+        Ok(Some(_)) => return Report::ok("seed", json!({"email": email, "created": false})),
+        Ok(None) => {}
+        Err(err) => return Report::failed("seed", EXIT_FAILED, format!("Failed to look up user: {}", err)),
+    }
+
+    let mut admin = UpsertUser {
+        email: email.clone(),
+        password,
+        fullname: load_optional_environment_variable("SEED_ADMIN_FULLNAME").unwrap_or_else(|| "Administrator".to_string()),
+        role: UserRole::ADMIN.to_string(),
+    };
+    if hash_password(&mut admin).is_err() {
+        return Report::failed("seed", EXIT_FAILED, "Failed to hash password".to_string());
+    }
+
+    match users.create(admin) {
+        Ok(user) => Report::ok("seed", json!({"email": email, "created": true, "user_id": user.id})),
+        Err(err) => Report::failed("seed", EXIT_FAILED, format!("Failed to create user: {:?}", err)),
+    }
+}
+
+// Exports the resource like a scheduled export job would, to the given file or else to storage
+fn export(resource: &str, output: Option<&str>) -> Report {
+    let database_url = match database_url("export") {
+        Ok(database_url) => database_url,
+        Err(report) => return report,
+    };
+
+    let result = try_create_shared_connection_pool(database_url, 1)
+        .and_then(|pool| export_resource(&pool, resource))
+        .and_then(|payload| {
+            let records = payload.as_array().map_or(0, Vec::len);
+            let bytes = serde_json::to_vec_pretty(&payload).map_err(|err| format!("Failed to serialize export: {}", err))?;
+
+            let artifact = match output {
+                Some(path) => fs::write(path, &bytes)
+                    .map(|_| path.to_string())
+                    .map_err(|err| format!("Failed to write {}: {}", path, err))?,
+                None => {
+                    let storage: Arc<dyn StorageBackend> = Arc::new(LocalStorage::from_env());
+                    let key = format!("exports/cli/{}-{}.json", resource, now().format("%Y%m%dT%H%M%S"));
+                    storage.put(&key, &bytes)?
+                }
+            };
+
+            Ok((records, artifact))
+        });
+
+    match result {
+        Ok((records, artifact)) => Report::ok("export", json!({"resource": resource, "records": records, "artifact": artifact})),
+        Err(err) => Report::failed("export", EXIT_FAILED, err),
+    }
+}
+
+// Writes the OpenAPI document, which needs neither the database nor any configuration
+fn openapi(output: &str) -> Report {
+    let document = openapi_json() + "\n";
+    match fs::write(output, &document) {
+        Ok(_) => Report::ok("openapi", json!({"output": output, "bytes": document.len()})),
+        Err(err) => Report::failed("openapi", EXIT_FAILED, format!("Failed to write {}: {}", output, err)),
+    }
+}
+
+// Validates the configuration without starting the API, connecting to the database to verify DEV_DB
+fn check_config() -> Report {
+    let mut checks: Vec<Value> = Vec::new();
+    let mut check = |name: &str, result: Result<(), String>| {
+        checks.push(match result {
+            Ok(()) => json!({"name": name, "ok": true}),
+            Err(error) => json!({"name": name, "ok": false, "error": error}),
+        });
+    };
+
+    check("DEV_DB", match load_optional_environment_variable("DEV_DB") {
+        Some(database_url) => PgConnection::establish(&database_url)
+            .map(|_| ())
+            .map_err(|err| format!("Failed to connect to database: {}", err.to_string().trim_end())),
+        None => Err("Must be set".to_string()),
+    });
+    check("ENCRYPTION_KEY", match load_optional_environment_variable("ENCRYPTION_KEY") {
+        Some(key) if !key.is_empty() => Ok(()),
+        _ => Err("Must be set".to_string()),
+    });
+    check("FIELD_PERMISSIONS", optional_setting("FIELD_PERMISSIONS", |value| FieldPermissions::parse(value).map(|_| ())));
+    check("TOKEN_STRATEGY", optional_setting("TOKEN_STRATEGY", |value| one_of(value, &["jwt", "opaque"])));
+    check("AUTH_PROVIDER", optional_setting("AUTH_PROVIDER", |value| one_of(value, &["local", "oidc", "ldap"])));
+    if load_optional_environment_variable("AUTH_PROVIDER").as_deref() == Some("oidc") {
+        for name in ["OIDC_ISSUER", "OIDC_CLIENT_ID"] {
+            check(name, load_optional_environment_variable(name).map(|_| ()).ok_or_else(|| "Must be set with AUTH_PROVIDER 'oidc'".to_string()));
+        }
+    }
+    if load_optional_environment_variable("AUTH_PROVIDER").as_deref() == Some("ldap") {
+        check("LDAP_STARTTLS", optional_setting("LDAP_STARTTLS", |value| one_of(value, &["true", "false"])));
+        let starttls = load_optional_environment_variable("LDAP_STARTTLS").as_deref() == Some("true");
+        check("LDAP_URL", match load_optional_environment_variable("LDAP_URL") {
+            Some(url) => check_ldap_transport(&url, starttls),
+            None => Err("Must be set with AUTH_PROVIDER 'ldap'".to_string()),
+        });
+        check("LDAP_USER_DN_TEMPLATE", load_optional_environment_variable("LDAP_USER_DN_TEMPLATE")
+            .map(|_| ())
+            .ok_or_else(|| "Must be set with AUTH_PROVIDER 'ldap'".to_string()));
+        check("LDAP_ROLE_MAPPING", optional_setting("LDAP_ROLE_MAPPING", |value| parse_ldap_role_mapping(value).map(|_| ())));
+        check("LDAP_DEFAULT_ROLE", optional_setting("LDAP_DEFAULT_ROLE", |value| one_of(value, &["READER", "WRITER", "EDITOR", "ADMIN"])));
+    }
+    check("ROUTE_NORMALIZATION", optional_setting("ROUTE_NORMALIZATION", |value| one_of(value, &["off", "rewrite", "redirect"])));
+    for name in ["LOGIN_DELAY_MS", "LOGIN_DELAY_WINDOW_SECONDS"] {
+        check(name, optional_setting(name, |value| {
+            value.parse::<u64>().map(|_| ()).map_err(|_| "Must be a non-negative number".to_string())
+        }));
+    }
+    check("TRUSTED_PROXIES", optional_setting("TRUSTED_PROXIES", |value| parse_trusted_proxies(value).map(|_| ())));
+    for name in ["API_RATE_LIMIT", "TELEMETRY_RATE_LIMIT"] {
+        check(name, optional_setting(name, |value| {
+            value.parse::<u32>().map(|_| ()).map_err(|_| "Must be a non-negative number".to_string())
+        }));
+    }
+    check("TELEMETRY_RETENTION_DAYS", optional_setting("TELEMETRY_RETENTION_DAYS", |value| {
+        value.parse::<i64>().map(|_| ()).map_err(|_| "Must be a number".to_string())
+    }));
+
+    let failed: Vec<&str> = checks.iter()
+        .filter(|check| check["ok"] == false)
+        .filter_map(|check| check["name"].as_str())
+        .collect();
+
+    if failed.is_empty() {
+        Report::ok("check-config", json!({"checks": checks}))
+    } else {
+        let error = format!("Invalid configuration: {}", failed.join(", "));
+        Report { details: into_map(json!({"checks": checks})), ..Report::failed("check-config", EXIT_INVALID_CONFIG, error) }
+    }
+}
+
+// Settings that are optional pass when absent, and are validated otherwise
+fn optional_setting(name: &str, validate: impl FnOnce(&str) -> Result<(), String>) -> Result<(), String> {
+    load_optional_environment_variable(name).map_or(Ok(()), |value| validate(&value))
+}
+
+fn one_of(value: &str, accepted: &[&str]) -> Result<(), String> {
+    if accepted.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("Must be one of {}", accepted.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::cli::commands::{into_map, Report, EXIT_INVALID_CONFIG};
+
+    #[test]
+    fn report_json_carries_status_exit_code_and_details() {
+        let report = Report::ok("migrate", json!({"applied": ["20261016190500"]}));
+        assert_eq!(report.to_json(), json!({"command": "migrate", "status": "ok", "exit_code": 0, "applied": ["20261016190500"]}));
+
+        let report = Report {
+            details: into_map(json!({"checks": []})),
+            ..Report::failed("check-config", EXIT_INVALID_CONFIG, "Invalid configuration: DEV_DB".to_string())
+        };
+        assert_eq!(report.to_json()["status"], "failed");
+        assert_eq!(report.to_json()["exit_code"], 3);
+        assert_eq!(report.to_json()["error"], "Invalid configuration: DEV_DB");
+    }
+}
@@ -0,0 +1,99 @@
+use std::{fs, path::{Path, PathBuf}};
+use diesel::{connection::SimpleConnection, prelude::*, sql_types::Text};
+
+// Bookkeeping table of the Diesel CLI, so that this and 'diesel migration run' can be used interchangeably
+const CREATE_MIGRATIONS_TABLE: &str = "CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (
+    version VARCHAR(50) PRIMARY KEY NOT NULL,
+    run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+)";
+
+#[derive(QueryableByName)]
+struct AppliedVersion {
+    #[diesel(sql_type = Text)]
+    version: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    // Folder name without the description, dashes removed - e.g. '20261016190500'
+    pub version: String,
+    pub path: PathBuf,
+}
+
+// Migrations found in the directory, oldest first
+pub fn find_migrations(directory: &Path) -> Result<Vec<Migration>, String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|err| format!("Failed to read migrations directory {:?}: {}", directory, err))?;
+
+    let mut migrations: Vec<Migration> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("up.sql").is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let version = migration_version(name);
+            Some(Migration { version, path })
+        })
+        .collect();
+
+    migrations.sort_by(|a, b| a.version.cmp(&b.version));
+    Ok(migrations)
+}
+
+fn migration_version(folder_name: &str) -> String {
+    folder_name.split('_').next().unwrap_or(folder_name).replace('-', "")
+}
+
+// Runs the migrations not yet applied, each in its own transaction, and returns their versions.
+// Stops at the first failing migration, leaving those before it applied.
+pub fn run_pending_migrations(connection: &mut PgConnection, directory: &Path) -> Result<Vec<String>, String> {
+    let migrations = find_migrations(directory)?;
+
+    connection.batch_execute(CREATE_MIGRATIONS_TABLE)
+        .map_err(|err| format!("Failed to create migrations table: {}", err))?;
+
+    let applied: Vec<String> = diesel::sql_query("SELECT version FROM __diesel_schema_migrations")
+        .load::<AppliedVersion>(connection)
+        .map_err(|err| format!("Failed to read applied migrations: {}", err))?
+        .into_iter()
+        .map(|applied| applied.version)
+        .collect();
+
+    let mut newly_applied = Vec::new();
+    for migration in migrations.into_iter().filter(|migration| !applied.contains(&migration.version)) {
+        let up_sql = fs::read_to_string(migration.path.join("up.sql"))
+            .map_err(|err| format!("Failed to read {:?}: {}", migration.path, err))?;
+
+        connection.transaction::<_, diesel::result::Error, _>(|connection| {
+            connection.batch_execute(&up_sql)?;
+            diesel::sql_query("INSERT INTO __diesel_schema_migrations (version) VALUES ($1)")
+                .bind::<Text, _>(&migration.version)
+                .execute(connection)?;
+            Ok(())
+        }).map_err(|err| format!("Migration {} failed: {}", migration.version, err))?;
+
+        newly_applied.push(migration.version);
+    }
+
+    Ok(newly_applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::cli::migrate::{find_migrations, migration_version};
+
+    #[test]
+    fn migration_version_matches_diesel_cli() {
+        assert_eq!(migration_version("2026-10-16-190500_create_change_requests"), "20261016190500");
+        assert_eq!(migration_version("00000000000000_diesel_initial_setup"), "00000000000000");
+    }
+
+    #[test]
+    fn find_migrations_lists_repository_migrations_in_order() {
+        let migrations = find_migrations(Path::new("migrations")).unwrap();
+
+        assert_eq!(migrations.first().unwrap().version, "00000000000000");
+        assert!(migrations.windows(2).all(|pair| pair[0].version < pair[1].version));
+    }
+}
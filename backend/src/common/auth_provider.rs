@@ -0,0 +1,568 @@
+use std::{future::Future, pin::Pin, time::Duration};
+use axum::{http::StatusCode, Json};
+use bcrypt::verify;
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, DecodingKey, Validation};
+use ldap3::{
+    dn_escape, exop::{WhoAmI, WhoAmIResp}, ldap_escape, LdapConnAsync, LdapConnSettings, LdapError, Scope, SearchEntry,
+};
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use crate::{
+    common::{
+        db::ConnectionPool,
+        security::role_satisfies,
+        util::load_optional_environment_variable,
+    },
+    users::{
+        model::{string_to_user_role, LoginUser, UpsertUser, User, UserRole},
+        service::service::UsersTable,
+    },
+};
+
+pub type AuthResult = Result<User, (StatusCode, Json<Value>)>;
+
+// Boxed, as trait methods cannot be async
+pub type AuthFuture<'a> = Pin<Box<dyn Future<Output = AuthResult> + Send + 'a>>;
+
+// Verifies the credentials presented on login and resolves them to the local user they belong to. Second factors and
+// sessions are handled by the login endpoint regardless of the provider, as are roles, which are those of the local user - though
+// providers may keep them in line with a directory.
+pub trait AuthProvider: Send + Sync {
+    // Name of the provider, as selected through AUTH_PROVIDER
+    fn name(&self) -> &'static str;
+
+    fn authenticate<'a>(&'a self, shared_state: &'a ConnectionPool, credentials: &'a LoginUser) -> AuthFuture<'a>;
+}
+
+// Reads AUTH_PROVIDER (local | oidc | ldap), defaulting to local - which is also used should it name an unknown provider
+pub fn auth_provider() -> Box<dyn AuthProvider> {
+    match load_optional_environment_variable("AUTH_PROVIDER").as_deref() {
+        None | Some("local") => Box::new(LocalPasswordProvider),
+        Some("oidc") => Box::new(OidcProvider::from_env()),
+        Some("ldap") => Box::new(LdapProvider::from_env()),
+        Some(other) => {
+            eprintln!("Unknown AUTH_PROVIDER '{}' - falling back to 'local'", other);
+            Box::new(LocalPasswordProvider)
+        }
+    }
+}
+
+fn find_local_user(shared_state: &ConnectionPool, email: &str) -> AuthResult {
+    let connection = shared_state.pool.get()
+        .expect("Failed to acquire connection from pool");
+
+    match UsersTable::new(connection).get_by_email(email.to_string()) {
+        Ok(Some(user)) if user.email == email => Ok(user),
+        Ok(_) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "User not found"})))),
+        Err(err) => {
+            eprintln!("Error reading user: {:?}", err);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read user"}))))
+        }
+    }
+}
+
+// Email and password, checked against the bcrypt hash stored with the user
+#[derive(Debug, Clone, Default)]
+pub struct LocalPasswordProvider;
+
+impl AuthProvider for LocalPasswordProvider {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn authenticate<'a>(&'a self, shared_state: &'a ConnectionPool, credentials: &'a LoginUser) -> AuthFuture<'a> {
+        Box::pin(async move {
+            let user = find_local_user(shared_state, &credentials.email)?;
+
+            if verify(&credentials.password, &user.password).unwrap_or(false) {
+                Ok(user)
+            } else {
+                Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Wrong password"}))))
+            }
+        })
+    }
+}
+
+// Claims of an OpenID Connect ID token needed to resolve it to a local user
+#[derive(Debug, Clone, Deserialize)]
+struct IdTokenClaims {
+    email: String,
+    #[serde(default)]
+    email_verified: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OpenIdConfiguration {
+    jwks_uri: String,
+}
+
+// Exchanges an ID token issued by an OpenID Connect provider (sent as 'id_token') for a session of the local user with
+// the token's email. The token's signature is verified against the keys the issuer publishes, along with its audience.
+#[derive(Debug, Clone)]
+pub struct OidcProvider {
+    // e.g. 'https://login.example.com' - its '/.well-known/openid-configuration' locates the signing keys
+    pub issuer: Option<String>,
+    // Client id the ID tokens must be issued for
+    pub client_id: Option<String>,
+}
+
+impl OidcProvider {
+    // Reads OIDC_ISSUER and OIDC_CLIENT_ID
+    pub fn from_env() -> OidcProvider {
+        OidcProvider {
+            issuer: load_optional_environment_variable("OIDC_ISSUER"),
+            client_id: load_optional_environment_variable("OIDC_CLIENT_ID"),
+        }
+    }
+
+    async fn fetch_keys(issuer: &str) -> Result<JwkSet, String> {
+        let client = reqwest::Client::new();
+        let discovery_url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+
+        let configuration: OpenIdConfiguration = client.get(&discovery_url)
+            .timeout(Duration::from_secs(10))
+            .send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| format!("Failed to fetch {}: {}", discovery_url, err))?
+            .json().await
+            .map_err(|err| format!("Invalid OpenID configuration: {}", err))?;
+
+        client.get(&configuration.jwks_uri)
+            .timeout(Duration::from_secs(10))
+            .send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| format!("Failed to fetch {}: {}", configuration.jwks_uri, err))?
+            .json().await
+            .map_err(|err| format!("Invalid key set: {}", err))
+    }
+}
+
+impl AuthProvider for OidcProvider {
+    fn name(&self) -> &'static str {
+        "oidc"
+    }
+
+    fn authenticate<'a>(&'a self, shared_state: &'a ConnectionPool, credentials: &'a LoginUser) -> AuthFuture<'a> {
+        Box::pin(async move {
+            let invalid_token = || (StatusCode::UNAUTHORIZED, Json(json!({"error": "Invalid ID token"})));
+
+            let (Some(issuer), Some(client_id)) = (&self.issuer, &self.client_id) else {
+                eprintln!("OIDC_ISSUER and OIDC_CLIENT_ID must be set to sign in with OpenID Connect");
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Authentication provider is not configured"}))));
+            };
+            let id_token = credentials.id_token.as_deref()
+                .ok_or_else(|| (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Missing id_token"}))))?;
+
+            let header = decode_header(id_token).map_err(|_| invalid_token())?;
+            let keys = OidcProvider::fetch_keys(issuer).await.map_err(|err| {
+                eprintln!("{}", err);
+                (StatusCode::BAD_GATEWAY, Json(json!({"error": "Failed to reach authentication provider"})))
+            })?;
+            let key = header.kid.as_deref()
+                .and_then(|kid| keys.find(kid))
+                .and_then(|jwk| DecodingKey::from_jwk(jwk).ok())
+                .ok_or_else(invalid_token)?;
+
+            let mut validation = Validation::new(header.alg);
+            validation.set_issuer(&[issuer]);
+            validation.set_audience(&[client_id]);
+
+            let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+                .map_err(|err| {
+                    eprintln!("Rejected ID token: {:?}", err);
+                    invalid_token()
+                })?
+                .claims;
+
+            // Unverified addresses could be claimed by anyone registering with the provider
+            if claims.email_verified == Some(false) {
+                return Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Email address has not been verified"}))));
+            }
+
+            find_local_user(shared_state, &claims.email)
+        })
+    }
+}
+
+// Default attribute listing the groups of a directory entry, as used by Active Directory and OpenLDAP's memberof overlay
+pub const DEFAULT_LDAP_GROUP_ATTRIBUTE: &str = "memberOf";
+
+// Default attribute holding the email of a directory entry, which is also matched against the email when searching for users
+pub const DEFAULT_LDAP_LOGIN_ATTRIBUTE: &str = "mail";
+
+// Password stored with users provisioned by an external system. Not a bcrypt hash, so they cannot sign in with a local password.
+pub const PROVISIONED_PASSWORD: &str = "!external";
+
+// Password stored with users provisioned on LDAP login, marking them as those whose role follows the directory
+pub const LDAP_PROVISIONED_PASSWORD: &str = "!ldap";
+
+// Result code of a bind with wrong credentials
+const LDAP_INVALID_CREDENTIALS: u32 = 49;
+
+const LDAP_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Parses a role mapping such as 'cn=admins,ou=groups,dc=example,dc=com=ADMIN;cn=staff,ou=groups,dc=example,dc=com=WRITER'.
+// Group DNs contain '=' themselves, so each entry is split at its last one.
+pub fn parse_ldap_role_mapping(mapping: &str) -> Result<Vec<(String, UserRole)>, String> {
+    mapping.split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (group, role) = entry.rsplit_once('=')
+                .ok_or_else(|| format!("Expected 'group=ROLE', got '{}'", entry))?;
+            match string_to_user_role(role.trim().to_string()) {
+                UserRole::INVALID => Err(format!("Unknown role '{}'", role.trim())),
+                role => Ok((group.trim().to_string(), role)),
+            }
+        })
+        .collect()
+}
+
+// Passwords are sent with the bind, so the connection must be encrypted - either from the start ('ldaps://') or upgraded through StartTLS
+pub fn check_ldap_transport(url: &str, starttls: bool) -> Result<(), String> {
+    if url.starts_with("ldaps://") || (url.starts_with("ldap://") && starttls) {
+        Ok(())
+    } else if url.starts_with("ldap://") {
+        Err("Must be an 'ldaps://' URL, unless LDAP_STARTTLS is 'true'".to_string())
+    } else {
+        Err("Must be an 'ldaps://host[:port]' or 'ldap://host[:port]' URL".to_string())
+    }
+}
+
+// Values of an attribute of a directory entry, whose names are matched regardless of case as in LDAP itself
+fn ldap_values<'e>(entry: &'e SearchEntry, attribute: &str) -> &'e [String] {
+    entry.attrs.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(attribute))
+        .map_or(&[], |(_, values)| values.as_slice())
+}
+
+// Signs users in by binding to an LDAP directory (e.g. Active Directory) with their email and password. Their role follows
+// their groups through a configurable mapping, and a local user is provisioned on first login so that sessions, two-factor
+// authentication and ownership work as for everyone else.
+#[derive(Debug, Clone)]
+pub struct LdapProvider {
+    // 'ldaps://host[:port]', or 'ldap://host[:port]' along with 'starttls'
+    pub url: Option<String>,
+    pub starttls: bool,
+    // DN bound as, with '{email}' and '{username}' (the part before '@') filled in - e.g. 'uid={username},ou=people,dc=example,dc=com'
+    pub user_dn_template: Option<String>,
+    // Base to search for the user's entry under, matching 'login_attribute' against the email. The bound DN itself is read when unset.
+    pub search_base: Option<String>,
+    pub login_attribute: String,
+    pub group_attribute: String,
+    // Group DNs with the role they grant - the highest of a user's roles wins
+    pub role_mapping: Vec<(String, UserRole)>,
+    // Role of users in none of the mapped groups. They are turned away when unset.
+    pub default_role: Option<UserRole>,
+}
+
+impl LdapProvider {
+    // Reads LDAP_URL, LDAP_STARTTLS, LDAP_USER_DN_TEMPLATE, LDAP_SEARCH_BASE, LDAP_LOGIN_ATTRIBUTE, LDAP_GROUP_ATTRIBUTE, LDAP_ROLE_MAPPING
+    // and LDAP_DEFAULT_ROLE
+    pub fn from_env() -> LdapProvider {
+        let role_mapping = load_optional_environment_variable("LDAP_ROLE_MAPPING")
+            .map(|mapping| parse_ldap_role_mapping(&mapping).unwrap_or_else(|err| {
+                eprintln!("Invalid LDAP_ROLE_MAPPING: {} - no groups are mapped", err);
+                Vec::new()
+            }))
+            .unwrap_or_default();
+        let default_role = load_optional_environment_variable("LDAP_DEFAULT_ROLE")
+            .map(string_to_user_role)
+            .filter(|role| *role != UserRole::INVALID);
+
+        LdapProvider {
+            url: load_optional_environment_variable("LDAP_URL"),
+            starttls: load_optional_environment_variable("LDAP_STARTTLS").as_deref() == Some("true"),
+            user_dn_template: load_optional_environment_variable("LDAP_USER_DN_TEMPLATE"),
+            search_base: load_optional_environment_variable("LDAP_SEARCH_BASE"),
+            login_attribute: load_optional_environment_variable("LDAP_LOGIN_ATTRIBUTE").unwrap_or_else(|| DEFAULT_LDAP_LOGIN_ATTRIBUTE.to_string()),
+            group_attribute: load_optional_environment_variable("LDAP_GROUP_ATTRIBUTE").unwrap_or_else(|| DEFAULT_LDAP_GROUP_ATTRIBUTE.to_string()),
+            role_mapping,
+            default_role,
+        }
+    }
+
+    fn user_dn(template: &str, email: &str) -> String {
+        let username = email.split('@').next().unwrap_or(email);
+        template
+            .replace("{email}", &dn_escape(email))
+            .replace("{username}", &dn_escape(username))
+    }
+
+    // Highest role granted by the entry's groups, falling back to the default role
+    fn role_of(&self, entry: &SearchEntry) -> Option<UserRole> {
+        let groups = ldap_values(entry, &self.group_attribute);
+
+        self.role_mapping.iter()
+            .filter(|(group, _)| groups.iter().any(|member_of| member_of.eq_ignore_ascii_case(group)))
+            .map(|(_, role)| role.clone())
+            .reduce(|highest, role| if role_satisfies(&role, &highest) { role } else { highest })
+            .or_else(|| self.default_role.clone())
+    }
+
+    // Binds as the user and reads the entries matching their email, along with the DN the directory authenticated them as -
+    // which it reports through Who am I? (RFC 4532) when bound by another name, e.g. an Active Directory user principal name
+    async fn lookup(&self, url: &str, dn: &str, credentials: &LoginUser) -> Result<(String, Vec<SearchEntry>), LdapError> {
+        let settings = LdapConnSettings::new()
+            .set_conn_timeout(LDAP_TIMEOUT)
+            .set_starttls(self.starttls);
+        let (connection, mut ldap) = LdapConnAsync::with_settings(settings, url).await?;
+        ldap3::drive!(connection);
+
+        ldap.with_timeout(LDAP_TIMEOUT).simple_bind(dn, &credentials.password).await?.success()?;
+        let bound_dn = match ldap.with_timeout(LDAP_TIMEOUT).extended(WhoAmI).await.and_then(|result| result.success()) {
+            Ok((exop, _)) => exop.parse::<WhoAmIResp>().authzid.strip_prefix("dn:").unwrap_or(dn).to_string(),
+            Err(_) => dn.to_string(),
+        };
+
+        let attributes = [self.login_attribute.as_str(), self.group_attribute.as_str(), "displayName", "cn"];
+        let (entries, _) = match &self.search_base {
+            Some(base) => {
+                let filter = format!("({}={})", self.login_attribute, ldap_escape(credentials.email.as_str()));
+                ldap.with_timeout(LDAP_TIMEOUT).search(base, Scope::Subtree, &filter, attributes).await?.success()?
+            }
+            None => ldap.with_timeout(LDAP_TIMEOUT).search(&bound_dn, Scope::Base, "(objectClass=*)", attributes).await?.success()?,
+        };
+        let _ = ldap.unbind().await;
+
+        Ok((bound_dn, entries.into_iter().map(SearchEntry::construct).collect()))
+    }
+
+    // Local user of the entry the user bound as, identified by the email the directory holds for it rather than the one typed in
+    fn sign_in(&self, shared_state: &ConnectionPool, bound_dn: &str, entries: Vec<SearchEntry>) -> AuthResult {
+        // A search may turn up entries of others claiming the same email, so only the bound one is trusted
+        let entry = entries.into_iter()
+            .find(|entry| entry.dn.eq_ignore_ascii_case(bound_dn))
+            .ok_or_else(|| (StatusCode::FORBIDDEN, Json(json!({"error": "Directory entry not found"}))))?;
+        let email = ldap_values(&entry, &self.login_attribute).first()
+            .cloned()
+            .ok_or_else(|| (StatusCode::FORBIDDEN, Json(json!({"error": "Directory entry has no email"}))))?;
+        let role = self.role_of(&entry)
+            .ok_or_else(|| (StatusCode::FORBIDDEN, Json(json!({"error": "Not a member of any group granted access"}))))?;
+        let fullname = ldap_values(&entry, "displayName").first()
+            .or_else(|| ldap_values(&entry, "cn").first())
+            .cloned()
+            .unwrap_or_else(|| email.clone());
+
+        LdapProvider::provision(shared_state, &email, fullname, &role)
+    }
+
+    // Local user of the email, created on first login. The role of users created this way follows the directory on every login,
+    // whereas other users keep theirs.
+    fn provision(shared_state: &ConnectionPool, email: &str, fullname: String, role: &UserRole) -> AuthResult {
+        let failed = |err: &dyn std::fmt::Debug| {
+            eprintln!("Error provisioning user from LDAP: {:?}", err);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to provision user"})))
+        };
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut users_table = UsersTable::new(connection);
+
+        match users_table.get_by_email(email.to_string()).map_err(|err| failed(&err))? {
+            Some(user) if user.password != LDAP_PROVISIONED_PASSWORD || user.role == role.to_string() => Ok(user),
+            Some(user) => users_table.set_role(user.id, &role.to_string()).map_err(|err| failed(&err)),
+            None => {
+                let user = users_table.create(UpsertUser {
+                    email: email.to_string(),
+                    password: LDAP_PROVISIONED_PASSWORD.to_string(),
+                    fullname,
+                    role: role.to_string(),
+                }).map_err(|err| failed(&err))?;
+                eprintln!("Provisioned user {} from LDAP", user.id);
+                Ok(user)
+            }
+        }
+    }
+}
+
+impl AuthProvider for LdapProvider {
+    fn name(&self) -> &'static str {
+        "ldap"
+    }
+
+    fn authenticate<'a>(&'a self, shared_state: &'a ConnectionPool, credentials: &'a LoginUser) -> AuthFuture<'a> {
+        Box::pin(async move {
+            let not_configured = || (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Authentication provider is not configured"})));
+
+            let (Some(url), Some(user_dn_template)) = (&self.url, &self.user_dn_template) else {
+                eprintln!("LDAP_URL and LDAP_USER_DN_TEMPLATE must be set to sign in with LDAP");
+                return Err(not_configured());
+            };
+            if let Err(err) = check_ldap_transport(url, self.starttls) {
+                eprintln!("Invalid LDAP_URL: {}", err);
+                return Err(not_configured());
+            }
+            if credentials.email.is_empty() {
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Missing email"}))));
+            }
+            // Servers treat binds without a password as unauthenticated binds, which succeed
+            if credentials.password.is_empty() {
+                return Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Wrong password"}))));
+            }
+
+            let dn = LdapProvider::user_dn(user_dn_template, &credentials.email);
+            let (bound_dn, entries) = match self.lookup(url, &dn, credentials).await {
+                Ok(lookup) => lookup,
+                Err(LdapError::LdapResult { result }) if result.rc == LDAP_INVALID_CREDENTIALS => {
+                    return Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Wrong password"}))));
+                }
+                Err(err) => {
+                    eprintln!("LDAP lookup failed: {}", err);
+                    return Err((StatusCode::BAD_GATEWAY, Json(json!({"error": "Failed to reach authentication provider"}))));
+                }
+            };
+
+            self.sign_in(shared_state, &bound_dn, entries)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use axum::http::StatusCode;
+    use ldap3::SearchEntry;
+    use crate::{
+        common::{
+            auth_provider::{check_ldap_transport, parse_ldap_role_mapping, AuthProvider, LdapProvider, OidcProvider},
+            db::create_shared_connection_pool,
+            util::load_environment_variable,
+        },
+        users::{model::{LoginUser, UpsertUser, UserRole}, service::service::UsersTable},
+    };
+
+    fn directory_entry(dn: &str, attributes: &[(&str, &[&str])]) -> SearchEntry {
+        SearchEntry {
+            dn: dn.to_string(),
+            attrs: attributes.iter()
+                .map(|(name, values)| (name.to_string(), values.iter().map(|value| value.to_string()).collect()))
+                .collect(),
+            bin_attrs: HashMap::new(),
+        }
+    }
+
+    fn ldap_provider(url: &str, role_mapping: &str) -> LdapProvider {
+        LdapProvider {
+            url: Some(url.to_string()),
+            starttls: false,
+            user_dn_template: Some("uid={username},ou=people".to_string()),
+            search_base: Some("ou=people".to_string()),
+            login_attribute: "mail".to_string(),
+            group_attribute: "memberOf".to_string(),
+            role_mapping: parse_ldap_role_mapping(role_mapping).unwrap(),
+            default_role: None,
+        }
+    }
+
+    #[test]
+    fn parse_ldap_role_mapping_splits_group_dns_at_their_last_equals_sign() {
+        assert_eq!(
+            parse_ldap_role_mapping("cn=admins,dc=example=ADMIN; cn=staff,dc=example=WRITER;").unwrap(),
+            vec![("cn=admins,dc=example".to_string(), UserRole::ADMIN), ("cn=staff,dc=example".to_string(), UserRole::WRITER)]
+        );
+        assert!(parse_ldap_role_mapping("cn=admins=OWNER").is_err());
+        assert!(parse_ldap_role_mapping("admins").is_err());
+    }
+
+    #[test]
+    fn check_ldap_transport_requires_tls_or_starttls() {
+        assert!(check_ldap_transport("ldaps://directory.example.com", false).is_ok());
+        assert!(check_ldap_transport("ldap://directory.example.com", true).is_ok());
+        assert!(check_ldap_transport("ldap://directory.example.com", false).is_err());
+        assert!(check_ldap_transport("directory.example.com:636", true).is_err());
+    }
+
+    #[tokio::test]
+    async fn ldap_provider_refuses_plain_connections_and_empty_passwords_before_connecting() {
+        let connection_pool = create_shared_connection_pool(load_environment_variable("TEST_DB"), 1);
+        let mut credentials = LoginUser { email: "ldap.pilot@example.com".to_string(), password: "secret".to_string(), otp: None, id_token: None };
+
+        let plain = ldap_provider("ldap://127.0.0.1:1", "");
+        let (status, _) = plain.authenticate(&connection_pool, &credentials).await.unwrap_err();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+        // Assert that an empty password is refused rather than bound anonymously
+        credentials.password = String::new();
+        let encrypted = ldap_provider("ldaps://127.0.0.1:1", "");
+        let (status, _) = encrypted.authenticate(&connection_pool, &credentials).await.unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn ldap_provider_signs_in_the_bound_entry_by_its_email_with_its_highest_mapped_role() {
+        let connection_pool = create_shared_connection_pool(load_environment_variable("TEST_DB"), 1);
+        let email = "ldap.pilot@example.com";
+        let bound_dn = "uid=ldap.pilot,ou=people";
+        let provider = ldap_provider("ldaps://directory.example.com", "cn=pilots,dc=example=WRITER;cn=admirals,dc=example=EDITOR");
+        let pilot = || directory_entry("UID=ldap.pilot,ou=people", &[
+            ("mail", &[email]),
+            ("memberOf", &["CN=Pilots,DC=example", "cn=admirals,dc=example"]),
+            ("displayName", &["LDAP Pilot"]),
+        ]);
+
+        let user = provider.sign_in(&connection_pool, bound_dn, vec![pilot()]).unwrap();
+        assert_eq!((user.email.as_str(), user.fullname.as_str(), user.role.as_str()), (email, "LDAP Pilot", "EDITOR"));
+
+        // Assert that the provisioned user is reused, with its role following the mapping
+        let demoted = LdapProvider { role_mapping: parse_ldap_role_mapping("cn=pilots,dc=example=READER").unwrap(), ..provider.clone() };
+        let same_user = demoted.sign_in(&connection_pool, bound_dn, vec![pilot()]).unwrap();
+        assert_eq!((same_user.id, same_user.role.as_str()), (user.id, "READER"));
+
+        // Assert that entries other than the bound one are not trusted, nor entries without an email
+        let impostor = directory_entry("uid=impostor,ou=people", &[("mail", &[email]), ("memberOf", &["cn=admirals,dc=example"])]);
+        let (status, _) = provider.sign_in(&connection_pool, bound_dn, vec![impostor]).unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = provider.sign_in(&connection_pool, bound_dn, vec![directory_entry(bound_dn, &[("memberOf", &["cn=admirals,dc=example"])])]).unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Assert that users in no mapped group are turned away without a default role
+        let unmapped = LdapProvider { role_mapping: Vec::new(), ..provider.clone() };
+        let (status, _) = unmapped.sign_in(&connection_pool, bound_dn, vec![pilot()]).unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        UsersTable::new(connection_pool.pool.get().unwrap()).delete(user.id).unwrap();
+    }
+
+    #[test]
+    fn ldap_provider_leaves_the_role_of_local_users_alone() {
+        let connection_pool = create_shared_connection_pool(load_environment_variable("TEST_DB"), 1);
+        let email = "lokal.admiral@example.com";
+        let local_user = UsersTable::new(connection_pool.pool.get().unwrap())
+            .create(UpsertUser {
+                email: email.to_string(),
+                password: "not-a-directory-user".to_string(),
+                fullname: "Local Admiral".to_string(),
+                role: "ADMIN".to_string(),
+            })
+            .unwrap();
+
+        let provider = ldap_provider("ldaps://directory.example.com", "cn=pilots,dc=example=READER");
+        let entry = directory_entry("uid=lokal.admiral,ou=people", &[("mail", &[email]), ("memberOf", &["cn=pilots,dc=example"])]);
+        let user = provider.sign_in(&connection_pool, "uid=lokal.admiral,ou=people", vec![entry]).unwrap();
+        assert_eq!((user.id, user.role.as_str()), (local_user.id, "ADMIN"));
+
+        UsersTable::new(connection_pool.pool.get().unwrap()).delete(local_user.id).unwrap();
+    }
+
+    #[tokio::test]
+    async fn oidc_provider_rejects_missing_and_malformed_id_tokens_before_contacting_issuer() {
+        let connection_pool = create_shared_connection_pool(load_environment_variable("TEST_DB"), 1);
+        let provider = OidcProvider {
+            issuer: Some("https://login.invalid".to_string()),
+            client_id: Some("empires".to_string()),
+        };
+        let mut credentials = LoginUser { email: String::new(), password: String::new(), otp: None, id_token: None };
+
+        let (status, _) = provider.authenticate(&connection_pool, &credentials).await.unwrap_err();
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        credentials.id_token = Some("not-a-token".to_string());
+        let (status, _) = provider.authenticate(&connection_pool, &credentials).await.unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // Assert that an unconfigured provider fails rather than accepting anything
+        let unconfigured = OidcProvider { issuer: None, client_id: None };
+        let (status, _) = unconfigured.authenticate(&connection_pool, &credentials).await.unwrap_err();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,89 @@
+use axum::{http::StatusCode, Json, response::{IntoResponse, Response}};
+use serde_derive::Serialize;
+use utoipa::ToSchema;
+use crate::ships::model::ReassignmentReport;
+
+// Outcome of a single item of a batch request. 'code' and 'error' are only set for items that failed,
+// the former being stable for clients to act on and the latter meant for people.
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct BatchItem {
+    pub id: i32,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchItem {
+    pub fn succeeded(id: i32, status: StatusCode) -> BatchItem {
+        BatchItem { id, status: status.as_u16(), code: None, error: None }
+    }
+
+    pub fn failed(id: i32, status: StatusCode, code: &str, error: &str) -> BatchItem {
+        BatchItem { id, status: status.as_u16(), code: Some(code.to_string()), error: Some(error.to_string()) }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+// Envelope shared by every batch endpoint, listing items in the order they were given. Sent as 200 when every item
+// succeeded and as 207 Multi-Status otherwise, so that a partial failure is never mistaken for all or nothing.
+#[derive(Serialize, Debug, Clone, ToSchema)]
+#[aliases(ReassignmentOutcome = MultiStatus<ReassignmentReport>)]
+pub struct MultiStatus<T: serde::Serialize> {
+    // Fields describing the batch as a whole, e.g. the empire ships were moved to
+    #[serde(flatten)]
+    pub batch: T,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItem>,
+}
+
+impl<T: serde::Serialize> MultiStatus<T> {
+    pub fn new(batch: T, results: Vec<BatchItem>) -> MultiStatus<T> {
+        let succeeded = results.iter().filter(|item| item.is_success()).count();
+        MultiStatus { batch, succeeded, failed: results.len() - succeeded, results }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        if self.failed == 0 { StatusCode::OK } else { StatusCode::MULTI_STATUS }
+    }
+}
+
+impl<T: serde::Serialize> IntoResponse for MultiStatus<T> {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+    use crate::common::batch::{BatchItem, MultiStatus};
+
+    #[test]
+    fn multi_status_counts_items_and_is_207_on_partial_failure() {
+        let outcome = MultiStatus::new(json!({"target_empire_id": 2}), vec![
+            BatchItem::succeeded(1, StatusCode::OK),
+            BatchItem::failed(3, StatusCode::NOT_FOUND, "not_found", "Ship not found"),
+        ]);
+        assert_eq!((outcome.succeeded, outcome.failed, outcome.status()), (1, 1, StatusCode::MULTI_STATUS));
+
+        assert_eq!(serde_json::to_value(&outcome).unwrap(), json!({
+            "target_empire_id": 2,
+            "succeeded": 1,
+            "failed": 1,
+            "results": [
+                {"id": 1, "status": 200},
+                {"id": 3, "status": 404, "code": "not_found", "error": "Ship not found"}
+            ]
+        }));
+
+        let outcome = MultiStatus::new(json!({}), vec![BatchItem::succeeded(1, StatusCode::OK)]);
+        assert_eq!(outcome.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::{Duration, Instant};
+use serde_derive::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::{empires::model::Empire, locations::model::Location};
+
+// Time budget granted to a collection query when the client does not ask for one
+pub const DEFAULT_BUDGET_MS: u64 = 2000;
+const MIN_BUDGET_MS: u64 = 50;
+const MAX_BUDGET_MS: u64 = 10_000;
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+// Rows fetched per round trip - small enough that a single batch rarely eats the whole budget
+const BATCH_SIZE: usize = 100;
+
+// Query parameters shared by the search endpoints
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    pub q: Option<String>,
+    pub cursor: Option<i32>,
+    pub limit: Option<usize>,
+    pub budget_ms: Option<u64>,
+    pub include: Option<String>,
+}
+
+impl SearchParams {
+    pub fn terms(&self) -> Vec<String> {
+        self.q.as_deref().unwrap_or("").split_whitespace().map(|term| term.to_string()).collect()
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub fn budget(&self) -> QueryBudget {
+        QueryBudget::new(Duration::from_millis(self.budget_ms.unwrap_or(DEFAULT_BUDGET_MS).clamp(MIN_BUDGET_MS, MAX_BUDGET_MS)))
+    }
+
+    pub fn includes(&self, relation: &str) -> bool {
+        self.include.as_deref().unwrap_or("").split(',').any(|included| included.trim() == relation)
+    }
+}
+
+// Deadline for a single request's worth of database work
+#[derive(Debug, Clone, Copy)]
+pub struct QueryBudget {
+    deadline: Instant,
+}
+
+impl QueryBudget {
+    pub fn new(budget: Duration) -> QueryBudget {
+        QueryBudget { deadline: Instant::now() + budget }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+}
+
+// One page of a cursor-paginated collection. 'partial' is set when the time budget ran out before the
+// page could be filled - the client may resume from 'next_cursor' to fetch the remainder.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[aliases(LocationPage = Page<Location>, EmpirePage = Page<Empire>)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub partial: bool,
+    pub next_cursor: Option<i32>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U> {
+        Page { items: self.items.into_iter().map(f).collect(), partial: self.partial, next_cursor: self.next_cursor }
+    }
+}
+
+// Fills a page by repeatedly calling 'fetch_batch(after_id, batch_size, timeout_ms)' until the page is full,
+// the rows are exhausted or the budget runs out. Rows must be returned in ascending key order.
+pub fn collect_within_budget<T, F, K>(
+    budget: &QueryBudget,
+    cursor: Option<i32>,
+    limit: usize,
+    mut fetch_batch: F,
+    key: K,
+) -> Result<Page<T>, diesel::result::Error>
+where
+    F: FnMut(Option<i32>, i64, u64) -> Result<Vec<T>, diesel::result::Error>,
+    K: Fn(&T) -> i32,
+{
+    let mut items: Vec<T> = Vec::new();
+    let mut cursor = cursor;
+
+    loop {
+        let remaining = budget.remaining();
+        if remaining.is_zero() {
+            return Ok(Page { items, partial: true, next_cursor: cursor });
+        }
+
+        let wanted = (limit - items.len()).min(BATCH_SIZE);
+        let timeout_ms = (remaining.as_millis() as u64).max(1);
+
+        match fetch_batch(cursor, wanted as i64, timeout_ms) {
+            Ok(batch) => {
+                let fetched = batch.len();
+                if let Some(last) = batch.last() {
+                    cursor = Some(key(last));
+                }
+                items.extend(batch);
+
+                if fetched < wanted {
+                    return Ok(Page { items, partial: false, next_cursor: None });
+                }
+                if items.len() >= limit {
+                    return Ok(Page { items, partial: false, next_cursor: cursor });
+                }
+            }
+            Err(err) if is_statement_timeout(&err) => {
+                return Ok(Page { items, partial: true, next_cursor: cursor });
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+// Applies the remaining budget as the statement timeout of the current transaction
+pub fn set_statement_timeout(connection: &mut diesel::PgConnection, timeout_ms: u64) -> Result<(), diesel::result::Error> {
+    use diesel::RunQueryDsl;
+
+    diesel::sql_query(format!("SET LOCAL statement_timeout = {}", timeout_ms))
+        .execute(connection)
+        .map(|_| ())
+}
+
+fn is_statement_timeout(err: &diesel::result::Error) -> bool {
+    match err {
+        diesel::result::Error::DatabaseError(_, info) => info.message().contains("statement timeout"),
+        _ => false,
+    }
+}
+
+// Escapes LIKE wildcards so that the value is matched literally
+pub fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+// Pattern matching search terms literally anywhere in a value
+pub fn like_pattern(term: &str) -> String {
+    format!("%{}%", escape_like(term))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::common::budget::{collect_within_budget, like_pattern, QueryBudget};
+
+    // Fake table of ids 1..=total served in ascending order
+    fn fetch_from(total: i32) -> impl FnMut(Option<i32>, i64, u64) -> Result<Vec<i32>, diesel::result::Error> {
+        move |after, batch_size, _| {
+            let start = after.unwrap_or(0) + 1;
+            Ok((start..=total).take(batch_size as usize).collect())
+        }
+    }
+
+    #[test]
+    fn collect_returns_complete_page_without_cursor_when_rows_are_exhausted() {
+        let budget = QueryBudget::new(Duration::from_secs(5));
+
+        let page = collect_within_budget(&budget, None, 50, fetch_from(10), |id| *id).unwrap();
+
+        assert_eq!(page.items.len(), 10);
+        assert!(!page.partial);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn collect_returns_cursor_when_page_is_full() {
+        let budget = QueryBudget::new(Duration::from_secs(5));
+
+        let page = collect_within_budget(&budget, Some(100), 150, fetch_from(1000), |id| *id).unwrap();
+
+        assert_eq!(page.items.first(), Some(&101));
+        assert_eq!(page.items.len(), 150);
+        assert!(!page.partial);
+        assert_eq!(page.next_cursor, Some(250));
+    }
+
+    #[test]
+    fn collect_returns_partial_page_when_budget_is_exhausted() {
+        let budget = QueryBudget::new(Duration::from_millis(20));
+        let mut inner = fetch_from(1000);
+
+        // Every batch takes longer than the whole budget
+        let slow_fetch = move |after, batch_size, timeout_ms| {
+            std::thread::sleep(Duration::from_millis(30));
+            inner(after, batch_size, timeout_ms)
+        };
+
+        let page = collect_within_budget(&budget, None, 500, slow_fetch, |id| *id).unwrap();
+
+        assert!(page.partial);
+        assert_eq!(page.items.len(), 100);
+        assert_eq!(page.next_cursor, Some(100));
+    }
+
+    #[test]
+    fn like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("100%_done"), "%100\\%\\_done%");
+    }
+}
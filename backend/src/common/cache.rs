@@ -0,0 +1,128 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+
+// Holds a single value for a fixed time, after which the next read recomputes it. Clones share the same value.
+#[derive(Clone)]
+pub struct TtlCache<T: Clone> {
+    ttl: Duration,
+    entry: Arc<Mutex<Entry<T>>>,
+}
+
+struct Entry<T> {
+    value: Option<(Instant, T)>,
+    // Bumped on every invalidation, so that a load started before one is not cached after it
+    generation: u64,
+}
+
+impl<T: Clone> TtlCache<T> {
+    pub fn new(ttl: Duration) -> TtlCache<T> {
+        TtlCache { ttl, entry: Arc::new(Mutex::new(Entry { value: None, generation: 0 })) }
+    }
+
+    // Returns the cached value while it is fresh, otherwise loads and caches a new one. Failed loads are not cached.
+    pub fn get_or_load<E>(&self, load: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        self.get_or_load_at(Instant::now(), load)
+    }
+
+    // Caches the value as if it had just been loaded, e.g. when warming up
+    pub fn store(&self, value: T) {
+        self.entry.lock().expect("Cache lock poisoned").value = Some((Instant::now(), value));
+    }
+
+    // Drops the cached value, so that the next read loads a fresh one
+    pub fn invalidate(&self) {
+        let mut entry = self.entry.lock().expect("Cache lock poisoned");
+        entry.value = None;
+        entry.generation += 1;
+    }
+
+    fn get_or_load_at<E>(&self, now: Instant, load: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let generation = {
+            let entry = self.entry.lock().expect("Cache lock poisoned");
+            if let Some((loaded_at, value)) = entry.value.as_ref() {
+                if now.duration_since(*loaded_at) < self.ttl {
+                    return Ok(value.clone());
+                }
+            }
+            entry.generation
+        };
+
+        // The lock is released while loading, as loads wait on the connection pool and must not block other readers
+        // or writers invalidating the cache for as long. Concurrent readers of a stale entry may thus each load it.
+        let value = load()?;
+
+        let mut entry = self.entry.lock().expect("Cache lock poisoned");
+        if entry.generation == generation {
+            entry.value = Some((now, value.clone()));
+        }
+        Ok(value)
+    }
+}
+
+// Middleware invalidating the cache once a request has succeeded, for routes changing the data it holds
+pub async fn invalidate_on_success<T: Clone + Send + Sync + 'static>(
+    State(cache): State<TtlCache<T>>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let response = next.run(req).await;
+    if response.status().is_success() {
+        cache.invalidate();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use crate::common::cache::TtlCache;
+
+    #[test]
+    fn get_or_load_reuses_value_until_expired() {
+        let cache = TtlCache::new(Duration::from_secs(5));
+        let now = Instant::now();
+
+        assert_eq!(cache.get_or_load_at(now, || Ok::<_, ()>(1)), Ok(1));
+        assert_eq!(cache.get_or_load_at(now + Duration::from_secs(4), || Ok::<_, ()>(2)), Ok(1));
+        assert_eq!(cache.get_or_load_at(now + Duration::from_secs(5), || Ok::<_, ()>(3)), Ok(3));
+    }
+
+    #[test]
+    fn get_or_load_does_not_cache_failures() {
+        let cache = TtlCache::new(Duration::from_secs(5));
+        let now = Instant::now();
+
+        assert_eq!(cache.get_or_load_at(now, || Err::<i32, _>("unavailable")), Err("unavailable"));
+        assert_eq!(cache.get_or_load_at(now, || Ok::<_, &str>(1)), Ok(1));
+    }
+
+    #[test]
+    fn invalidate_drops_stored_value() {
+        let cache = TtlCache::new(Duration::from_secs(5));
+
+        cache.store(1);
+        assert_eq!(cache.get_or_load(|| Ok::<_, ()>(2)), Ok(1));
+
+        cache.invalidate();
+        assert_eq!(cache.get_or_load(|| Ok::<_, ()>(3)), Ok(3));
+    }
+
+    #[test]
+    fn load_does_not_hold_the_lock_nor_outlive_an_invalidation() {
+        let cache = TtlCache::new(Duration::from_secs(5));
+
+        // Invalidating from within the load would deadlock if the lock were held
+        assert_eq!(cache.get_or_load(|| { cache.invalidate(); Ok::<_, ()>(1) }), Ok(1));
+        assert_eq!(cache.get_or_load(|| Ok::<_, ()>(2)), Ok(2));
+        assert_eq!(cache.get_or_load(|| Ok::<_, ()>(3)), Ok(2));
+    }
+}
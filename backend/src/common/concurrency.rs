@@ -0,0 +1,58 @@
+use axum::http::{header, HeaderMap};
+use serde::Serialize;
+
+// Outcome of an update guarded by a precondition on the current state of the row
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    Applied(T),
+    // The row was changed by someone else - carries its current state
+    PreconditionFailed(T),
+}
+
+// Strong entity tag derived from the serialized representation of a record. FNV-1a is used rather than the
+// standard library hasher as the tag has to stay stable across restarts and between instances.
+pub fn etag_of<T: Serialize>(value: &T) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let bytes = serde_json::to_vec(value).unwrap_or_default();
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME));
+
+    format!("\"{:016x}\"", hash)
+}
+
+// Entity tags listed in the If-Match header, or None if the client did not send one
+pub fn if_match_tags(headers: &HeaderMap) -> Option<Vec<String>> {
+    let value = headers.get(header::IF_MATCH)?.to_str().ok()?;
+
+    Some(value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect())
+}
+
+// Whether the current entity tag satisfies the If-Match header, using strong comparison
+pub fn if_match_satisfied(tags: &[String], current_etag: &str) -> bool {
+    tags.iter().any(|tag| tag == "*" || tag == current_etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::common::concurrency::{etag_of, if_match_satisfied};
+
+    #[test]
+    fn etag_of_changes_with_content() {
+        let original = etag_of(&json!({"id": 1, "area": "The Serpent's Lair"}));
+
+        assert_eq!(original, etag_of(&json!({"id": 1, "area": "The Serpent's Lair"})));
+        assert_ne!(original, etag_of(&json!({"id": 1, "area": "The Crimson Expanse"})));
+        assert!(original.starts_with('"') && original.ends_with('"'));
+    }
+
+    #[test]
+    fn if_match_satisfied_compares_tags() {
+        let current = "\"00000000000000ff\"".to_string();
+
+        assert!(if_match_satisfied(&["*".to_string()], &current));
+        assert!(if_match_satisfied(&["\"0000000000000001\"".to_string(), current.clone()], &current));
+        assert!(!if_match_satisfied(&["\"0000000000000001\"".to_string()], &current));
+    }
+}
@@ -17,4 +17,15 @@ pub fn create_shared_connection_pool(database_url: String, max_size: u32) -> Con
     ConnectionPool {
         pool,
     }
+}
+
+// Variant of the above reporting an unreachable database instead of panicking, for commands run by automation
+pub fn try_create_shared_connection_pool(database_url: String, max_size: u32) -> Result<ConnectionPool, String> {
+    let manager = ConnectionManager::<PgConnection>::new(database_url);
+
+    Pool::builder()
+        .max_size(max_size)
+        .build(manager)
+        .map(|pool| ConnectionPool { pool })
+        .map_err(|err| format!("Failed to connect to database: {}", err.to_string().trim_end()))
 }
\ No newline at end of file
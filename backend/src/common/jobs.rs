@@ -0,0 +1,34 @@
+use std::{future::Future, time::Duration};
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+
+// Runs the given task every 'period' on the Tokio runtime for as long as the process lives.
+// Ticks that are missed while a slow run is in progress are skipped rather than bunched up.
+pub fn spawn_periodic<F, Fut>(name: &'static str, period: Duration, task: F) -> JoinHandle<()>
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        eprintln!("Started periodic job '{}' with period {:?}", name, period);
+
+        loop {
+            interval.tick().await;
+            task().await;
+        }
+    })
+}
+
+// Runs blocking work (such as Diesel queries) on the blocking thread pool and flattens join errors
+pub async fn run_blocking<T, F>(task: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(task).await {
+        Ok(result) => result,
+        Err(err) => Err(format!("Blocking task failed: {:?}", err)),
+    }
+}
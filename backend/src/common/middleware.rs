@@ -1,20 +1,38 @@
 use axum::{
     body::Body,
     extract::State,
-    http::Request,
+    http::{Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response}
     ,
+    Json,
 };
+use serde_json::{json, Value};
 
 use crate::{
-    common::{db::ConnectionPool, security::authorize_with_role},
+    common::{db::ConnectionPool, security::{authorize_with_role, role_satisfies}},
     users::model::{User, UserRole},
 };
 
 // Extension to store authorized user in request
+#[derive(Clone)]
 pub struct AuthorizedUser {
     pub user: Option<User>,
+    // Role of the user, or of the API key the request was made with
+    pub role: UserRole,
+}
+
+impl AuthorizedUser {
+    // Whether the request was authorized with the given role or higher
+    pub fn has_role(&self, role: &UserRole) -> bool {
+        role_satisfies(&self.role, role)
+    }
+
+    // The signed-in user, turning away requests authorized with an API key as these do not act on behalf of a user
+    pub fn require_user(self) -> Result<User, (StatusCode, Json<Value>)> {
+        self.user
+            .ok_or((StatusCode::FORBIDDEN, Json(json!({"error": "Only available to signed-in users"}))))
+    }
 }
 
 // Middleware function for requiring specific roles
@@ -61,9 +79,9 @@ async fn authorize_and_continue(
     
     // Authorize user
     match authorize_with_role(headers, &pool, required_role).await {
-        Ok(user) => {
+        Ok(authorized) => {
             // Add user to request extensions
-            req.extensions_mut().insert(AuthorizedUser { user });
+            req.extensions_mut().insert(authorized);
             
             // Continue to the handler
             next.run(req).await
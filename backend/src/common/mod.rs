@@ -3,3 +3,20 @@ pub mod security;
 pub mod util;
 pub mod error;
 pub mod middleware;
+pub mod normalize;
+pub mod versioning;
+pub mod jobs;
+pub mod storage;
+pub mod notify;
+pub mod budget;
+pub mod concurrency;
+pub mod rate_limit;
+pub mod cache;
+pub mod totp;
+pub mod permissions;
+pub mod warmup;
+pub mod auth_provider;
+pub mod batch;
+pub mod openapi;
+#[cfg(test)]
+pub mod test_util;
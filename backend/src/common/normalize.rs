@@ -0,0 +1,187 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, Method, Request, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::common::util::load_optional_environment_variable;
+
+// Singular route prefixes used by older clients mapped to their canonical plural form
+const LEGACY_PREFIXES: [(&str, &str); 3] = [
+    ("user", "users"),
+    ("location", "locations"),
+    ("empire", "empires"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NormalizationMode {
+    // Leave request paths untouched
+    Off,
+    // Rewrite non-canonical paths in place before routing
+    Rewrite,
+    // Answer non-canonical paths with a redirect to the canonical one
+    Redirect,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RouteNormalization {
+    pub mode: NormalizationMode,
+}
+
+impl RouteNormalization {
+    // Reads ROUTE_NORMALIZATION (off | rewrite | redirect), defaulting to rewrite
+    pub fn from_env() -> RouteNormalization {
+        let mode = match load_optional_environment_variable("ROUTE_NORMALIZATION").as_deref() {
+            Some("off") => NormalizationMode::Off,
+            Some("redirect") => NormalizationMode::Redirect,
+            _ => NormalizationMode::Rewrite,
+        };
+
+        RouteNormalization { mode }
+    }
+}
+
+// Returns the canonical form of the given path, or None if it already is canonical
+pub fn canonical_path(path: &str) -> Option<String> {
+    let trimmed = path.trim_end_matches('/');
+    let trimmed = if trimmed.is_empty() { "/" } else { trimmed };
+
+    let mut segments: Vec<&str> = trimmed.split('/').collect();
+    if let Some(first) = segments.get_mut(1) {
+        if let Some((_, plural)) = LEGACY_PREFIXES.iter().find(|(singular, _)| singular == first) {
+            *first = plural;
+        }
+    }
+
+    let canonical = segments.join("/");
+    if canonical == path {
+        None
+    } else {
+        Some(canonical)
+    }
+}
+
+// Middleware wrapping the entire router so that rewritten paths are taken into account when routing
+pub async fn normalize_route(
+    State(config): State<RouteNormalization>,
+    mut req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    if config.mode == NormalizationMode::Off {
+        return next.run(req).await;
+    }
+
+    let canonical = match canonical_path(req.uri().path()) {
+        Some(canonical) => canonical,
+        None => return next.run(req).await,
+    };
+
+    // Carry the query string over to the canonical location
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{}?{}", canonical, query),
+        None => canonical,
+    };
+
+    match config.mode {
+        NormalizationMode::Redirect => {
+            // Methods with bodies get a 307 so that clients replay the request unchanged
+            let status = if req.method() == Method::GET || req.method() == Method::HEAD {
+                StatusCode::MOVED_PERMANENTLY
+            } else {
+                StatusCode::TEMPORARY_REDIRECT
+            };
+
+            (status, [(header::LOCATION, path_and_query)]).into_response()
+        }
+        _ => {
+            let mut parts = req.uri().clone().into_parts();
+            match path_and_query.parse() {
+                Ok(parsed) => parts.path_and_query = Some(parsed),
+                Err(err) => {
+                    eprintln!("Failed to rewrite path '{}': {:?}", path_and_query, err);
+                    return next.run(req).await;
+                }
+            }
+
+            match Uri::from_parts(parts) {
+                Ok(uri) => *req.uri_mut() = uri,
+                Err(err) => eprintln!("Failed to rebuild URI: {:?}", err),
+            }
+
+            next.run(req).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        middleware, Router,
+    };
+    use tower::{Layer, ServiceExt};
+    use crate::common::normalize::{canonical_path, normalize_route, NormalizationMode, RouteNormalization};
+
+    fn service(mode: NormalizationMode) -> impl tower::Service<Request<Body>, Response = axum::response::Response, Error = std::convert::Infallible> {
+        let router = Router::new()
+            .route("/empires/:empire_id", axum::routing::get(|| async { "empire" }).put(|| async { "updated" }));
+
+        middleware::from_fn_with_state(RouteNormalization { mode }, normalize_route).layer(router)
+    }
+
+    #[test]
+    fn canonical_path_trims_trailing_slashes_and_pluralizes_legacy_prefixes() {
+        assert_eq!(canonical_path("/empires/"), Some("/empires".to_string()));
+        assert_eq!(canonical_path("/empire/5"), Some("/empires/5".to_string()));
+        assert_eq!(canonical_path("/user/login/"), Some("/users/login".to_string()));
+        assert_eq!(canonical_path("//"), Some("/".to_string()));
+    }
+
+    #[test]
+    fn canonical_path_returns_none_on_canonical_input() {
+        assert_eq!(canonical_path("/"), None);
+        assert_eq!(canonical_path("/users/login"), None);
+        assert_eq!(canonical_path("/locations/3"), None);
+    }
+
+    #[tokio::test]
+    async fn rewrite_mode_routes_trailing_slash_to_canonical_handler() {
+        let request = Request::builder()
+            .uri("/empire/1/")
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = service(NormalizationMode::Rewrite).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn redirect_mode_returns_301_for_get_and_307_for_put() {
+        let request = Request::builder()
+            .uri("/empires/1/?verbose=true")
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = service(NormalizationMode::Redirect).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers().get(header::LOCATION).unwrap(), "/empires/1?verbose=true");
+
+        let request = Request::builder()
+            .uri("/empire/1")
+            .method("PUT")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = service(NormalizationMode::Redirect).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+        assert_eq!(response.headers().get(header::LOCATION).unwrap(), "/empires/1");
+    }
+}
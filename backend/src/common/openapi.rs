@@ -0,0 +1,217 @@
+use axum::{Json, Router};
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
+use crate::common::security::API_KEY_HEADER;
+
+// Body of every error response. Some carry further fields, e.g. 'two_factor_required' on failed logins.
+// Handlers build it with json!, so it is only ever described.
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct ApiError {
+    pub error: String,
+}
+
+// Document of the JSON API, served at /openapi.json and written by the 'openapi' command for the client generator.
+// Payloads are described as of schema version 2. SCIM, telemetry, health and the .well-known files are left out,
+// as they follow specifications of their own or are meant for infrastructure rather than clients.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Empires API",
+        description = "Users, locations, empires and their ships. Payloads are those of schema version 2, requested through the X-Api-Schema-Version header."
+    ),
+    paths(
+        crate::users::router::router::create_user_handler,
+        crate::users::router::router::login_user_handler,
+        crate::users::router::router::list_users_handler,
+        crate::users::router::router::get_user_handler,
+        crate::users::router::router::update_user_handler,
+        crate::users::router::router::delete_user_handler,
+        crate::users::router::router::deactivate_user_handler,
+        crate::users::router::router::reactivate_user_handler,
+        crate::users::router::router::impersonate_user_handler,
+        crate::sessions::router::router::get_my_sessions_handler,
+        crate::sessions::router::router::revoke_all_my_sessions_handler,
+        crate::sessions::router::router::revoke_current_session_handler,
+        crate::sessions::router::router::revoke_my_session_handler,
+        crate::two_factor::router::router::get_two_factor_status_handler,
+        crate::two_factor::router::router::setup_two_factor_handler,
+        crate::two_factor::router::router::confirm_two_factor_handler,
+        crate::two_factor::router::router::disable_two_factor_handler,
+        crate::locations::router::router::get_all_locations_handler,
+        crate::locations::router::router::search_locations_handler,
+        crate::locations::router::router::create_location_handler,
+        crate::locations::router::router::clone_location_handler,
+        crate::locations::router::router::read_location_handler,
+        crate::locations::router::router::update_location_handler,
+        crate::locations::router::router::update_location_coordinates_handler,
+        crate::locations::router::router::delete_location_handler,
+        crate::empires::router::router::get_all_empires_handler,
+        crate::empires::router::router::search_empires_handler,
+        crate::empires::router::router::create_empire_handler,
+        crate::empires::router::router::clone_empire_handler,
+        crate::empires::router::router::read_empire_handler,
+        crate::empires::router::router::update_empire_handler,
+        crate::empires::router::router::archive_empire_handler,
+        crate::empires::router::router::unarchive_empire_handler,
+        crate::empires::router::router::publish_empire_handler,
+        crate::empires::router::router::delete_empire_handler,
+        crate::ships::router::router::get_empire_ships_handler,
+        crate::ships::router::router::create_ship_handler,
+        crate::ships::router::router::reassign_ships_handler,
+        crate::templates::router::router::get_all_templates_handler,
+        crate::templates::router::router::create_template_handler,
+        crate::templates::router::router::read_template_handler,
+        crate::templates::router::router::update_template_handler,
+        crate::templates::router::router::delete_template_handler,
+        crate::change_requests::router::router::get_all_change_requests_handler,
+        crate::change_requests::router::router::read_change_request_handler,
+        crate::change_requests::router::router::approve_change_request_handler,
+        crate::change_requests::router::router::reject_change_request_handler,
+        crate::map::router::router::get_map_handler,
+        crate::stats::router::router::get_stats_history_handler,
+        crate::api_keys::router::router::get_all_api_keys_handler,
+        crate::api_keys::router::router::create_api_key_handler,
+        crate::api_keys::router::router::revoke_api_key_handler,
+        crate::exports::router::router::get_all_export_jobs_handler,
+        crate::exports::router::router::create_export_job_handler,
+        crate::exports::router::router::read_export_job_handler,
+        crate::exports::router::router::update_export_job_handler,
+        crate::exports::router::router::delete_export_job_handler,
+        crate::exports::router::router::run_export_job_handler,
+        crate::webhooks::router::router::get_all_webhooks_handler,
+        crate::webhooks::router::router::create_webhook_handler,
+        crate::webhooks::router::router::get_webhook_events_handler,
+        crate::webhooks::router::router::read_webhook_handler,
+        crate::webhooks::router::router::update_webhook_handler,
+        crate::webhooks::router::router::delete_webhook_handler,
+        crate::webhooks::router::router::get_deliveries_handler,
+        crate::webhooks::router::router::test_webhook_handler,
+        crate::webhooks::router::router::redrive_delivery_handler,
+    ),
+    components(schemas(
+        ApiError,
+        crate::users::model::User,
+        crate::users::model::UpsertUser,
+        crate::users::model::LoginUser,
+        crate::users::model::LoginResponse,
+        crate::users::model::ImpersonationResponse,
+        crate::sessions::model::Session,
+        crate::sessions::model::SessionSummary,
+        crate::two_factor::model::TwoFactorCode,
+        crate::two_factor::model::TwoFactorStatus,
+        crate::two_factor::model::TwoFactorSetup,
+        crate::two_factor::model::RecoveryCodes,
+        crate::locations::model::Location,
+        crate::locations::model::UpsertLocation,
+        crate::locations::model::LocationCoordinates,
+        crate::common::budget::LocationPage,
+        crate::empires::model::Empire,
+        crate::empires::model::UpsertEmpire,
+        crate::empires::model::PublishEmpire,
+        crate::common::budget::EmpirePage,
+        crate::ships::model::Ship,
+        crate::ships::model::UpsertShip,
+        crate::ships::model::ReassignShips,
+        crate::ships::model::ReassignmentReport,
+        crate::common::batch::BatchItem,
+        crate::common::batch::ReassignmentOutcome,
+        crate::templates::model::Template,
+        crate::templates::model::UpsertTemplate,
+        crate::change_requests::model::ChangeRequest,
+        crate::map::model::StarMap,
+        crate::map::model::MapRegion,
+        crate::map::model::MapLocation,
+        crate::stats::model::StatsHistory,
+        crate::stats::model::DailyCounts,
+        crate::api_keys::model::ApiKey,
+        crate::api_keys::model::CreateApiKey,
+        crate::api_keys::model::CreatedApiKey,
+        crate::exports::model::ExportJob,
+        crate::exports::model::UpsertExportJob,
+        crate::webhooks::model::Webhook,
+        crate::webhooks::model::UpsertWebhook,
+        crate::webhooks::model::WebhookDelivery,
+    )),
+    modifiers(&SecuritySchemes, &Unlicensed),
+    security(("bearer" = []), ("api_key" = [])),
+)]
+pub struct ApiDoc;
+
+// Tokens issued on login and API keys are accepted alike, apart from the routes acting on the signed-in user.
+// Public routes opt out of both with an empty requirement.
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+        components.add_security_scheme("api_key", SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(API_KEY_HEADER))));
+    }
+}
+
+// The crate declares no license, which would otherwise be described as one without a name
+struct Unlicensed;
+
+impl Modify for Unlicensed {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None;
+    }
+}
+
+// Served without authentication, as it only describes what any client could find out by trying
+pub fn openapi_route() -> Router {
+    Router::new().route("/openapi.json", axum::routing::get(|| async { Json(ApiDoc::openapi()) }))
+}
+
+pub fn openapi_json() -> String {
+    ApiDoc::openapi().to_pretty_json().expect("Failed to serialize the OpenAPI document")
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode}
+    };
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use crate::common::openapi::openapi_route;
+
+    fn references(value: &Value, found: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    found.push(reference.trim_start_matches("#/components/schemas/").to_string());
+                }
+                map.values().for_each(|value| references(value, found));
+            }
+            Value::Array(values) => values.iter().for_each(|value| references(value, found)),
+            _ => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn openapi_json_is_served_and_every_schema_it_references_is_described() {
+        let request = Request::builder().uri("/openapi.json").body(Body::empty()).unwrap();
+        let response = openapi_route().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let document: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(document["paths"]["/users/login"]["post"]["security"], serde_json::json!([{}]));
+
+        let mut found = Vec::new();
+        references(&document["paths"], &mut found);
+        references(&document["components"], &mut found);
+        assert!(!found.is_empty());
+        for name in found {
+            assert!(document["components"]["schemas"].get(&name).is_some(), "Schema '{}' is referenced but not described", name);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use axum::{http::StatusCode, Json};
+use serde::Serialize;
+use serde_json::{json, Value};
+use crate::{
+    common::{middleware::AuthorizedUser, security::role_satisfies, util::load_optional_environment_variable},
+    users::model::{string_to_user_role, UserRole},
+};
+
+// Fields only the given role and those above may change, as comma-separated 'table.field=ROLE' entries
+pub const DEFAULT_FIELD_PERMISSIONS: &str = "users.role=ADMIN,empires.location_id=EDITOR";
+
+// Roles required to change individual fields, on top of the role required by the endpoint itself
+#[derive(Debug, Clone, Default)]
+pub struct FieldPermissions {
+    required_roles: HashMap<(String, String), UserRole>,
+}
+
+impl FieldPermissions {
+    pub fn parse(spec: &str) -> Result<FieldPermissions, String> {
+        let mut required_roles = HashMap::new();
+
+        for entry in spec.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let invalid = || format!("Invalid field permission '{}', expected 'table.field=ROLE'", entry);
+            let (field, role) = entry.split_once('=').ok_or_else(invalid)?;
+            let (table, field) = field.trim().split_once('.').ok_or_else(invalid)?;
+
+            let role = string_to_user_role(role.trim().to_uppercase());
+            if role == UserRole::INVALID || table.is_empty() || field.is_empty() {
+                return Err(invalid());
+            }
+            required_roles.insert((table.to_string(), field.to_string()), role);
+        }
+
+        Ok(FieldPermissions { required_roles })
+    }
+
+    // Reads FIELD_PERMISSIONS, defaulting to DEFAULT_FIELD_PERMISSIONS - which is also used should it be malformed
+    pub fn from_env() -> FieldPermissions {
+        let spec = load_optional_environment_variable("FIELD_PERMISSIONS")
+            .unwrap_or_else(|| DEFAULT_FIELD_PERMISSIONS.to_string());
+
+        FieldPermissions::parse(&spec).unwrap_or_else(|err| {
+            eprintln!("{} - falling back to '{}'", err, DEFAULT_FIELD_PERMISSIONS);
+            FieldPermissions::parse(DEFAULT_FIELD_PERMISSIONS).expect("Default field permissions are valid")
+        })
+    }
+
+    // Fields of the table among 'fields' the role may not change, in the order given
+    pub fn forbidden_fields<'a>(&self, table: &str, role: &UserRole, fields: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        fields.into_iter()
+            .filter(|field| {
+                self.required_roles
+                    .get(&(table.to_string(), field.to_string()))
+                    .is_some_and(|required_role| !role_satisfies(role, required_role))
+            })
+            .map(str::to_string)
+            .collect()
+    }
+
+    // Fields restricted in the table whose value differs between 'current' and 'proposed'
+    fn changed_restricted_fields<C: Serialize, P: Serialize>(&self, table: &str, current: &C, proposed: &P) -> Vec<String> {
+        let current = serde_json::to_value(current).unwrap_or(Value::Null);
+        let proposed = serde_json::to_value(proposed).unwrap_or(Value::Null);
+
+        let mut fields: Vec<String> = self.required_roles.keys()
+            .filter(|(restricted_table, _)| restricted_table == table)
+            .map(|(_, field)| field)
+            .filter(|field| proposed.get(field.as_str()).is_some_and(|value| current.get(field.as_str()) != Some(value)))
+            .cloned()
+            .collect();
+        fields.sort();
+        fields
+    }
+}
+
+// Turns away an update of a record in 'table' with 403 if it changes fields the role of the request may not change,
+// naming those fields. Fields left as they are never count, so clients may send the full record back unmodified.
+pub fn enforce_field_permissions<C: Serialize, P: Serialize>(
+    authorized: &AuthorizedUser,
+    table: &str,
+    current: &C,
+    proposed: &P,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let permissions = FieldPermissions::from_env();
+    let changed = permissions.changed_restricted_fields(table, current, proposed);
+
+    forbid_fields(permissions.forbidden_fields(table, &authorized.role, changed.iter().map(String::as_str)))
+}
+
+// 403 naming the given fields, if any
+pub fn forbid_fields(fields: Vec<String>) -> Result<(), (StatusCode, Json<Value>)> {
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({"error": format!("Not allowed to change: {}", fields.join(", ")), "fields": fields})),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::{common::permissions::FieldPermissions, users::model::UserRole};
+
+    #[test]
+    fn forbidden_fields_names_changed_fields_above_role() {
+        let permissions = FieldPermissions::parse("users.role=ADMIN, empires.location_id=editor").unwrap();
+
+        let current = json!({"name": "Caldari State", "location_id": 3});
+        let moved = json!({"name": "Caldari State", "location_id": 4});
+        let changed = permissions.changed_restricted_fields("empires", &current, &moved);
+        assert_eq!(changed, ["location_id"]);
+
+        assert_eq!(permissions.forbidden_fields("empires", &UserRole::WRITER, changed.iter().map(String::as_str)), ["location_id"]);
+        assert!(permissions.forbidden_fields("empires", &UserRole::EDITOR, changed.iter().map(String::as_str)).is_empty());
+
+        // Assert that sending a restricted field back unchanged is allowed
+        assert!(permissions.changed_restricted_fields("empires", &current, &current).is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_entries() {
+        assert!(FieldPermissions::parse("users.role").is_err());
+        assert!(FieldPermissions::parse("role=ADMIN").is_err());
+        assert!(FieldPermissions::parse("users.role=OWNER").is_err());
+        assert!(FieldPermissions::parse("").is_ok());
+    }
+}
@@ -0,0 +1,483 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+use axum::{
+    body::{Body, HttpBody},
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use crate::common::util::load_optional_environment_variable;
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "X-RateLimit-Limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "X-RateLimit-Remaining";
+
+// Requests per minute and client across the API when API_RATE_LIMIT is not set
+const DEFAULT_API_RATE_LIMIT_PER_MINUTE: u32 = 600;
+
+// Number of tracked clients above which expired windows are purged
+const PURGE_THRESHOLD: usize = 10_000;
+
+// Delay added to the next login attempt per failed one, and how long failures are remembered
+const DEFAULT_LOGIN_DELAY_MS: u64 = 250;
+const DEFAULT_LOGIN_DELAY_WINDOW_SECONDS: u64 = 900;
+
+// Upper bound of the delay, so that legitimate users locked in with an attacker still get through eventually
+const MAX_LOGIN_DELAY: Duration = Duration::from_secs(10);
+
+// Largest login body accepted - it is read in full for the email to key delays on
+const MAX_LOGIN_BODY_BYTES: usize = 16 * 1024;
+
+// Fixed-window request limiter keyed by client address. Clones share the same counters.
+#[derive(Clone)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Arc<Mutex<HashMap<String, (Instant, u32)>>>,
+}
+
+// Outcome of counting a request against the limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateDecision {
+    Allowed { remaining: u32 },
+    Limited { retry_after: Duration },
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> RateLimiter {
+        RateLimiter { limit, window, windows: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    // Limiter of the API as a whole, allowing API_RATE_LIMIT requests per minute and client
+    pub fn api_from_env() -> RateLimiter {
+        let limit = load_optional_environment_variable("API_RATE_LIMIT")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_API_RATE_LIMIT_PER_MINUTE);
+
+        RateLimiter::new(limit, Duration::from_secs(60))
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn check(&self, client: &str) -> RateDecision {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> RateDecision {
+        let mut windows = self.windows.lock().expect("Rate limiter lock poisoned");
+
+        if windows.len() > PURGE_THRESHOLD {
+            let window = self.window;
+            windows.retain(|_, (started_at, _)| now.duration_since(*started_at) < window);
+        }
+
+        let (started_at, count) = windows.entry(client.to_string()).or_insert((now, 0));
+        if now.duration_since(*started_at) >= self.window {
+            *started_at = now;
+            *count = 0;
+        }
+
+        if *count >= self.limit {
+            return RateDecision::Limited { retry_after: self.window - now.duration_since(*started_at) };
+        }
+
+        *count += 1;
+        RateDecision::Allowed { remaining: self.limit - *count }
+    }
+}
+
+// Failed logins per client and per account within a window, each one delaying the next attempt by another step. This slows
+// brute forcing down well before any limit is reached, while the occasional typo goes all but unnoticed. Clones share the same counters.
+#[derive(Clone)]
+pub struct LoginDelays {
+    step: Duration,
+    window: Duration,
+    failures: Arc<Mutex<HashMap<String, (Instant, u32)>>>,
+}
+
+impl LoginDelays {
+    pub fn new(step: Duration, window: Duration) -> LoginDelays {
+        LoginDelays { step, window, failures: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    // Reads LOGIN_DELAY_MS (per failure) and LOGIN_DELAY_WINDOW_SECONDS
+    pub fn from_env() -> LoginDelays {
+        let step = load_optional_environment_variable("LOGIN_DELAY_MS")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_LOGIN_DELAY_MS);
+        let window = load_optional_environment_variable("LOGIN_DELAY_WINDOW_SECONDS")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_LOGIN_DELAY_WINDOW_SECONDS);
+
+        LoginDelays::new(Duration::from_millis(step), Duration::from_secs(window))
+    }
+
+    // Counts an attempt as failed up front and returns its delay - that of whichever key has failed the most before it.
+    // Attempts in flight thus delay one another, rather than all going through undelayed before the first one fails.
+    pub fn reserve(&self, keys: &[String]) -> Duration {
+        self.reserve_at(keys, Instant::now())
+    }
+
+    fn reserve_at(&self, keys: &[String], now: Instant) -> Duration {
+        let mut failures = self.failures.lock().expect("Login delay lock poisoned");
+
+        let most_failures = keys.iter()
+            .filter_map(|key| failures.get(key))
+            .filter(|(started_at, _)| now.duration_since(*started_at) < self.window)
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0);
+
+        if failures.len() > PURGE_THRESHOLD {
+            let window = self.window;
+            failures.retain(|_, (started_at, _)| now.duration_since(*started_at) < window);
+        }
+
+        for key in keys {
+            let (started_at, count) = failures.entry(key.clone()).or_insert((now, 0));
+            if now.duration_since(*started_at) >= self.window {
+                *started_at = now;
+                *count = 0;
+            }
+            *count += 1;
+        }
+
+        (self.step * most_failures).min(MAX_LOGIN_DELAY)
+    }
+
+    // Takes back an attempt reserved as failed, once it turned out not to have failed
+    pub fn release(&self, keys: &[String]) {
+        let mut failures = self.failures.lock().expect("Login delay lock poisoned");
+
+        for key in keys {
+            if let Some((_, count)) = failures.get_mut(key) {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn reset(&self, key: &str) {
+        self.failures.lock().expect("Login delay lock poisoned").remove(key);
+    }
+}
+
+// Keys delays are tracked under - the client, and the account when the body names one
+fn login_delay_keys(client: String, body: &[u8]) -> (String, Option<String>) {
+    let account = serde_json::from_slice::<Value>(body).ok()
+        .and_then(|body| body.get("email").and_then(Value::as_str).map(str::to_lowercase))
+        .filter(|email| !email.is_empty())
+        .map(|email| format!("account:{}", email));
+
+    (format!("client:{}", client), account)
+}
+
+// Reads the whole body, however it is sent, turning it down once it exceeds the limit
+async fn read_limited_body(mut body: Body, limit: usize) -> Result<Vec<u8>, (StatusCode, Json<Value>)> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({"error": "Failed to read request body"}))))?;
+        if bytes.len() + chunk.len() > limit {
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, Json(json!({"error": "Request body too large"}))));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+// Middleware delaying login attempts by the failures of the client and the account before them. The delay is awaited before
+// the handler runs, so waiting attempts hold no pooled connection. Every attempt counts as failed until it turns out otherwise, and
+// only wrong credentials (401) and unknown users (404) keep counting. Success only clears the account's failures - otherwise
+// attackers could reset their own by signing in to an account of theirs.
+pub async fn progressive_login_delay(
+    State(delays): State<LoginDelays>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let client = client_key(&req);
+
+    // The email is read off the body, which is buffered whether or not its length was declared and handed on unchanged
+    let (parts, body) = req.into_parts();
+    let body = match read_limited_body(body, MAX_LOGIN_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let (client, account) = login_delay_keys(client, &body);
+    let req = Request::from_parts(parts, Body::from(body));
+    let keys: Vec<String> = std::iter::once(client.clone()).chain(account.clone()).collect();
+
+    let delay = delays.reserve(&keys);
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+
+    let response = next.run(req).await;
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => {}
+        status if status.is_success() => {
+            if let Some(account) = &account {
+                delays.reset(account);
+            }
+            delays.release(&[client]);
+        }
+        _ => delays.release(&keys),
+    }
+
+    response
+}
+
+// Address of the client. X-Forwarded-For is only taken into account when the connection comes from one of TRUSTED_PROXIES,
+// as anyone else can send whatever they like in it.
+pub fn client_address(headers: &HeaderMap, remote: Option<SocketAddr>) -> Option<String> {
+    static TRUSTED_PROXIES: OnceLock<Vec<IpAddr>> = OnceLock::new();
+    let trusted_proxies = TRUSTED_PROXIES.get_or_init(|| {
+        load_optional_environment_variable("TRUSTED_PROXIES").map_or_else(Vec::new, |value| {
+            parse_trusted_proxies(&value).unwrap_or_else(|err| {
+                eprintln!("Ignoring TRUSTED_PROXIES: {}", err);
+                Vec::new()
+            })
+        })
+    });
+
+    resolve_client_address(headers, remote, trusted_proxies).map(|address| address.to_string())
+}
+
+// Comma-separated IP addresses of the reverse proxies in front of the API
+pub fn parse_trusted_proxies(value: &str) -> Result<Vec<IpAddr>, String> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.parse::<IpAddr>().map_err(|_| format!("'{}' is not an IP address", entry)))
+        .collect()
+}
+
+// Proxies append the address they were connected from, so X-Forwarded-For is read from the right: entries are skipped
+// for as long as they are trusted proxies themselves, and the first one that is not is the client.
+fn resolve_client_address(headers: &HeaderMap, remote: Option<SocketAddr>, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let mut client = remote?.ip();
+    if !trusted_proxies.contains(&client) {
+        return Some(client);
+    }
+
+    let forwarded: Vec<&str> = headers.get_all("X-Forwarded-For").iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+
+    for entry in forwarded.into_iter().rev() {
+        // Whatever precedes a malformed entry cannot be relied upon
+        let Ok(address) = entry.parse::<IpAddr>() else {
+            break;
+        };
+        client = address;
+        if !trusted_proxies.contains(&address) {
+            break;
+        }
+    }
+
+    Some(client)
+}
+
+fn client_key(req: &Request<Body>) -> String {
+    let remote = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(address)| *address);
+
+    client_address(req.headers(), remote).unwrap_or_else(|| "unknown".to_string())
+}
+
+// Middleware rejecting clients exceeding the limit with 429 and a Retry-After header. Every response
+// carries X-RateLimit-Limit and X-RateLimit-Remaining so that well-behaved clients can slow down in time.
+// Where limiters are nested, the headers of the innermost one are kept, as it is the one closest to running out.
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    match limiter.check(&client_key(&req)) {
+        RateDecision::Allowed { remaining } => {
+            let mut response = next.run(req).await;
+            let headers = response.headers_mut();
+            if !headers.contains_key(RATE_LIMIT_LIMIT_HEADER) {
+                headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(limiter.limit()));
+                headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from(remaining));
+            }
+            response
+        }
+        RateDecision::Limited { retry_after } => {
+            // Round up so that clients never retry before the window has actually reset
+            let retry_after_seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [
+                    ("Retry-After", retry_after_seconds.to_string()),
+                    (RATE_LIMIT_LIMIT_HEADER, limiter.limit().to_string()),
+                    (RATE_LIMIT_REMAINING_HEADER, "0".to_string()),
+                ],
+                Json(json!({"error": "Too many requests"})),
+            ).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::{IpAddr, SocketAddr}, time::{Duration, Instant}};
+    use axum::{
+        body::Body,
+        http::{HeaderMap, HeaderValue, Request, StatusCode},
+        middleware, Router,
+    };
+    use hyper::body::Bytes;
+    use tower::ServiceExt;
+    use crate::common::rate_limit::{
+        login_delay_keys, parse_trusted_proxies, progressive_login_delay, rate_limit, resolve_client_address, LoginDelays, RateDecision, RateLimiter, MAX_LOGIN_DELAY,
+    };
+
+    #[test]
+    fn check_limits_clients_independently_within_window() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert_eq!(limiter.check_at("10.0.0.1", now), RateDecision::Allowed { remaining: 1 });
+        assert_eq!(limiter.check_at("10.0.0.1", now), RateDecision::Allowed { remaining: 0 });
+        assert_eq!(
+            limiter.check_at("10.0.0.1", now + Duration::from_secs(15)),
+            RateDecision::Limited { retry_after: Duration::from_secs(45) }
+        );
+
+        // Assert that other clients have their own window
+        assert_eq!(limiter.check_at("10.0.0.2", now), RateDecision::Allowed { remaining: 1 });
+    }
+
+    #[test]
+    fn check_resets_once_window_has_passed() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert_eq!(limiter.check_at("10.0.0.1", now), RateDecision::Allowed { remaining: 0 });
+        assert_eq!(limiter.check_at("10.0.0.1", now + Duration::from_secs(60)), RateDecision::Allowed { remaining: 0 });
+    }
+
+    #[tokio::test]
+    async fn rate_limit_reports_the_innermost_budget() {
+        let service = Router::new()
+            .route("/telemetry", axum::routing::get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(RateLimiter::new(5, Duration::from_secs(60)), rate_limit))
+            .route("/empires", axum::routing::get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(RateLimiter::new(100, Duration::from_secs(60)), rate_limit));
+
+        let response = service.clone().oneshot(Request::get("/empires").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.headers()["X-RateLimit-Remaining"], "99");
+
+        let response = service.oneshot(Request::get("/telemetry").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.headers()["X-RateLimit-Limit"], "5");
+        assert_eq!(response.headers()["X-RateLimit-Remaining"], "4");
+    }
+    #[test]
+    fn login_delays_grow_with_failures_of_either_key_up_to_the_cap() {
+        let delays = LoginDelays::new(Duration::from_millis(250), Duration::from_secs(900));
+        let now = Instant::now();
+        let keys = vec!["client:10.0.0.1".to_string(), "account:ada@example.com".to_string()];
+
+        assert_eq!(delays.reserve_at(&keys, now), Duration::ZERO);
+        assert_eq!(delays.reserve_at(&keys, now), Duration::from_millis(250));
+
+        // Assert that attempts on the same account from another client are delayed too
+        let elsewhere = vec!["client:10.0.0.2".to_string(), "account:ada@example.com".to_string()];
+        assert_eq!(delays.reserve_at(&elsewhere, now), Duration::from_millis(500));
+
+        for _ in 0..100 {
+            delays.reserve_at(&keys, now);
+        }
+        assert_eq!(delays.reserve_at(&keys, now), MAX_LOGIN_DELAY);
+
+        // Assert that failures are forgotten once the window has passed
+        assert_eq!(delays.reserve_at(&keys, now + Duration::from_secs(900)), Duration::ZERO);
+    }
+
+    #[test]
+    fn login_delays_release_attempts_that_did_not_fail() {
+        let delays = LoginDelays::new(Duration::from_millis(250), Duration::from_secs(900));
+        let now = Instant::now();
+        let keys = vec!["client:10.0.0.1".to_string()];
+
+        // Assert that attempts in flight delay one another, until they are released
+        assert_eq!(delays.reserve_at(&keys, now), Duration::ZERO);
+        assert_eq!(delays.reserve_at(&keys, now), Duration::from_millis(250));
+        delays.release(&keys);
+        delays.release(&keys);
+        assert_eq!(delays.reserve_at(&keys, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn login_delays_reset_per_account_on_success() {
+        let delays = LoginDelays::new(Duration::from_millis(250), Duration::from_secs(900));
+        let now = Instant::now();
+        let (client, account) = login_delay_keys("10.0.0.1".to_string(), br#"{"email": "Ada@Example.com", "password": "x"}"#);
+        let account = account.unwrap();
+        assert_eq!(account, "account:ada@example.com");
+
+        delays.reserve_at(&[client.clone(), account.clone()], now);
+        delays.reset(&account);
+
+        assert_eq!(delays.reserve_at(&[account], now), Duration::ZERO);
+        assert_eq!(delays.reserve_at(&[client], now), Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn progressive_login_delay_reads_the_account_of_bodies_without_a_length() {
+        let delays = LoginDelays::new(Duration::from_millis(1), Duration::from_secs(900));
+        let service = Router::new()
+            .route("/users/login", axum::routing::post(|| async { StatusCode::UNAUTHORIZED }))
+            .layer(middleware::from_fn_with_state(delays.clone(), progressive_login_delay));
+
+        // Streamed bodies are sent without a Content-Length, as chunked requests are
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            sender.send_data(Bytes::from_static(br#"{"email": "ada@"#)).await.unwrap();
+            sender.send_data(Bytes::from_static(br#"example.com"}"#)).await.unwrap();
+        });
+        let request = Request::builder()
+            .uri("/users/login")
+            .method("POST")
+            .body(body)
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(delays.reserve_at(&["account:ada@example.com".to_string()], Instant::now()), Duration::from_millis(1));
+
+        let request = Request::builder()
+            .uri("/users/login")
+            .method("POST")
+            .body(Body::from(vec![b' '; 32 * 1024]))
+            .unwrap();
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn client_address_only_trusts_forwarded_for_from_trusted_proxies() {
+        let trusted_proxies = parse_trusted_proxies("10.0.0.1, 10.0.0.2").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Forwarded-For", HeaderValue::from_static("6.6.6.6, 203.0.113.7, 10.0.0.2"));
+
+        // Assert that the header is ignored when sent straight to the API
+        let direct: SocketAddr = "198.51.100.4:5000".parse().unwrap();
+        assert_eq!(resolve_client_address(&headers, Some(direct), &trusted_proxies), Some("198.51.100.4".parse::<IpAddr>().unwrap()));
+
+        // Assert that, behind the proxies, the spoofed leftmost entry is passed over for the one the proxies appended
+        let proxy: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        assert_eq!(resolve_client_address(&headers, Some(proxy), &trusted_proxies), Some("203.0.113.7".parse::<IpAddr>().unwrap()));
+
+        assert!(parse_trusted_proxies("10.0.0.1, proxy.internal").is_err());
+    }
+}
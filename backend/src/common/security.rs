@@ -1,18 +1,28 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::OnceLock};
 use std::time::{Duration, SystemTime};
+use chrono::Utc;
 use axum::{http, Json};
 use bcrypt::hash;
 use http::{HeaderMap, StatusCode};
+use ring::{digest, rand::{SecureRandom, SystemRandom}};
 use jsonwebtoken::{Algorithm, decode, DecodingKey, TokenData, Validation, errors::ErrorKind as JwtErrorKind, encode, Header, EncodingKey};
 use serde_json::{json, Value};
 use crate::{
-    common::{db::ConnectionPool, util::load_environment_variable},
+    api_keys::service::service::ApiKeysTable,
+    common::{db::ConnectionPool, middleware::AuthorizedUser, util::{load_environment_variable, load_optional_environment_variable}},
+    sessions::service::service::SessionsTable,
     users::{
         model::{Claims, User, UpsertUser, UserRole, string_to_user_role},
         service::service::UsersTable as UsersDB,
     },
 };
 
+// Lifetime of issued tokens, also reported to clients in the versioned login response
+pub const TOKEN_LIFETIME_SECONDS: u64 = 3600;
+
+// Header carrying API keys, as an alternative to bearer tokens
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
 pub fn hash_password(body: &mut UpsertUser) -> Result<(), (StatusCode, Json<Value>)> {
     if let Ok(hashed_password) = hash(&body.password, 12) {
         body.password = hashed_password;
@@ -22,10 +32,87 @@ pub fn hash_password(body: &mut UpsertUser) -> Result<(), (StatusCode, Json<Valu
     }
 }
 
-pub fn generate_token(user: &User) -> Result<String, jsonwebtoken::errors::Error> {
+// Prefix of every API key, making keys easy to recognize in logs and secret scanners
+pub const API_KEY_PREFIX: &str = "ak_";
+
+// Generates a new API key - 32 random bytes, hex encoded behind API_KEY_PREFIX
+pub fn generate_api_key() -> Result<String, (StatusCode, Json<Value>)> {
+    random_hex().map(|random| format!("{}{}", API_KEY_PREFIX, random))
+}
+
+// Generates the id of a new session, which is embedded in the JSON Web Tokens issued for it
+pub fn generate_session_token_id() -> Result<String, (StatusCode, Json<Value>)> {
+    random_hex()
+}
+
+// Prefix of every opaque token, telling them apart from JSON Web Tokens at a glance
+pub const OPAQUE_TOKEN_PREFIX: &str = "ot_";
+
+// Generates a new opaque token - 32 random bytes, hex encoded behind OPAQUE_TOKEN_PREFIX
+pub fn generate_opaque_token() -> Result<String, (StatusCode, Json<Value>)> {
+    random_hex().map(|random| format!("{}{}", OPAQUE_TOKEN_PREFIX, random))
+}
+
+// 32 random bytes, hex encoded
+fn random_hex() -> Result<String, (StatusCode, Json<Value>)> {
+    random_bytes(32).map(|bytes| to_hex(&bytes))
+}
+
+// Bytes from the system's secure random number generator
+pub fn random_bytes(length: usize) -> Result<Vec<u8>, (StatusCode, Json<Value>)> {
+    let mut bytes = vec![0u8; length];
+    SystemRandom::new().fill(&mut bytes)
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to generate random value"}))))?;
+
+    Ok(bytes)
+}
+
+// API keys are high-entropy random values, so a fast digest suffices - unlike passwords, which are hashed with bcrypt
+pub fn hash_api_key(api_key: &str) -> String {
+    sha256_hex(api_key)
+}
+
+// Opaque tokens are random like API keys, and stored as the token id of their session in hashed form
+pub fn hash_opaque_token(token: &str) -> String {
+    sha256_hex(token)
+}
+
+// Recovery codes are hashed like API keys, ignoring case and the dashes they are displayed with
+pub fn hash_recovery_code(recovery_code: &str) -> String {
+    let normalized: String = recovery_code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    sha256_hex(&normalized)
+}
+
+fn sha256_hex(value: &str) -> String {
+    to_hex(digest::digest(&digest::SHA256, value.as_bytes()).as_ref())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Inverse of to_hex, returning None on malformed input
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+// Issues a token tied to the given session, which stops being accepted once the session is revoked
+pub fn generate_session_token(user: &User, session_token_id: Option<&str>) -> Result<String, jsonwebtoken::errors::Error> {
     let role = string_to_user_role(user.clone().role);
     let expiration = SystemTime::now()
-        .checked_add(Duration::from_secs(3600)) // Set the token to expire in 1 hour
+        .checked_add(Duration::from_secs(TOKEN_LIFETIME_SECONDS)) // Set the token to expire in 1 hour
         .expect("Failed to calculate token expiration")
         .duration_since(SystemTime::UNIX_EPOCH)
         .expect("SystemTime before UNIX EPOCH")
@@ -35,12 +122,14 @@ pub fn generate_token(user: &User) -> Result<String, jsonwebtoken::errors::Error
         sub: user.email.clone(),
         role: role.clone(),
         exp: expiration,
+        sid: session_token_id.map(str::to_string),
     };
 
     encode(&Header::default(), &claims, &EncodingKey::from_secret(load_environment_variable("ENCRYPTION_KEY").as_ref()))
 }
 
-pub fn decode_claims(headers: &HeaderMap) -> Result<Option<TokenData<Claims>>, (StatusCode, Json<Value>)> {
+// The token of the Authorization header, without its 'Bearer ' prefix
+pub fn bearer_token(headers: &HeaderMap) -> Result<&str, (StatusCode, Json<Value>)> {
 
     // Retrieve Authorization header from the map of request headers
     let token_header = headers.get("Authorization");
@@ -65,9 +154,13 @@ pub fn decode_claims(headers: &HeaderMap) -> Result<Option<TokenData<Claims>>, (
         ));
     }
 
+    Ok(&token[7..])
+}
+
+fn decode_token(token: &str) -> Result<TokenData<Claims>, (StatusCode, Json<Value>)> {
     // Attempt to decode token and match the results
     match decode::<Claims>(
-        &token[7..],
+        token,
         &DecodingKey::from_secret(load_environment_variable("ENCRYPTION_KEY").as_ref()),
         &Validation::new(Algorithm::HS256),
     ) {
@@ -91,7 +184,7 @@ pub fn decode_claims(headers: &HeaderMap) -> Result<Option<TokenData<Claims>>, (
                 }
             }
         }
-        Ok(decoded_claims) => Ok(Some(decoded_claims)),
+        Ok(decoded_claims) => Ok(decoded_claims),
     }
 }
 
@@ -99,51 +192,211 @@ pub async fn authorize_with_role(
     headers: &HeaderMap,
     shared_state: &ConnectionPool,
     required_role: UserRole,
-) -> Result<Option<User>, (StatusCode, Json<Value>)> {
-    // Decode claims from bearer token header
-    let claims = match decode_claims(headers) {
-        Ok(claims) => claims,
-        Err((status_code, json_value)) => return Err((status_code, json_value)),
-    };
+) -> Result<AuthorizedUser, (StatusCode, Json<Value>)> {
+    // Requests from integrations authenticate with an API key, which carries its own role rather than a user's
+    if let Some(api_key) = headers.get(API_KEY_HEADER) {
+        let api_key = api_key.to_str()
+            .map_err(|_| (StatusCode::UNAUTHORIZED, Json(json!({"error": "Invalid API key"}))))?;
+        return enforce_api_key_role(shared_state, api_key, required_role);
+    }
+
+    // Resolve the user the bearer token was issued to, in whichever way the configured strategy issues tokens
+    let user = token_strategy().authenticate(shared_state, bearer_token(headers)?)?;
+    enforce_active_account(&user)?;
+
+    // Ensure that the user has the required role or higher
+    let user = enforce_role_policy(user, required_role)?;
+    let role = string_to_user_role(user.role.clone());
+
+    Ok(AuthorizedUser { user: Some(user), role })
+}
+
+// Turns away deactivated users, whether signing in or presenting a token issued before they were deactivated
+pub fn enforce_active_account(user: &User) -> Result<(), (StatusCode, Json<Value>)> {
+    if user.deactivated {
+        eprintln!("Rejected deactivated user '{}'", user.email);
+        Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Account has been deactivated"}))))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn enforce_role_policy(user: User, required_role: UserRole) -> Result<User, (StatusCode, Json<Value>)> {
+    let user_role = string_to_user_role(user.role.clone());
+
+    if role_satisfies(&user_role, &required_role) {
+        eprintln!("Access granted: User role '{}' is a superset of or equal to required role '{}'", user_role, required_role);
+        Ok(user)
+    } else {
+        eprintln!("User role: {} does not match required role: {}", user_role, required_role);
+        Err((StatusCode::UNAUTHORIZED, Json(json!({"error": format!("Current role of {} does not have access to {}", user_role, required_role)}))))
+    }
+}
+
+// Bearer token issued for a new session, along with the token id the session is stored with
+#[derive(Debug, Clone)]
+pub struct SessionToken {
+    pub token: String,
+    pub token_id: String,
+}
+
+// How bearer tokens are issued on sign-in and resolved to their user on later requests
+pub trait TokenStrategy: Send + Sync {
+    // Issues a token for a new session of the user, which the caller stores under the returned token id
+    fn issue(&self, user: &User) -> Result<SessionToken, (StatusCode, Json<Value>)>;
 
-    // Ensure that the user derived from claims exists and has the required role or higher
-    enforce_role_policy(shared_state, &claims, required_role).await
+    // The user the token was issued to, provided the token is valid and its session has not been revoked
+    fn authenticate(&self, shared_state: &ConnectionPool, token: &str) -> Result<User, (StatusCode, Json<Value>)>;
+
+    // Token id of the session the token was issued for, if any - without checking the token's validity
+    fn session_token_id(&self, token: &str) -> Option<String>;
+}
+
+// Self-contained, signed tokens carrying the user's email and the id of their session
+#[derive(Debug, Clone, Default)]
+pub struct JwtTokens;
+
+impl TokenStrategy for JwtTokens {
+    fn issue(&self, user: &User) -> Result<SessionToken, (StatusCode, Json<Value>)> {
+        let token_id = generate_session_token_id()?;
+        let token = generate_session_token(user, Some(&token_id))
+            .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to generate token"}))))?;
+
+        Ok(SessionToken { token, token_id })
+    }
+
+    fn authenticate(&self, shared_state: &ConnectionPool, token: &str) -> Result<User, (StatusCode, Json<Value>)> {
+        let claims = decode_token(token)?.claims;
+
+        // Tokens issued for a session are only accepted for as long as the session has not been revoked
+        if let Some(session_token_id) = &claims.sid {
+            enforce_active_session(shared_state, session_token_id)?;
+        }
+
+        // Ensure that the user derived from claims exists
+        let connection = shared_state.pool.get().expect("Failed to acquire connection from pool");
+        match UsersDB::new(connection).get_by_email(claims.sub) {
+            Ok(Some(user)) => Ok(user),
+            Ok(None) => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "User in claims not found in DB"})))),
+            Err(err) => {
+                eprintln!("User in claims not found in DB {:?}", err);
+                Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "User in claims not found in DB"}))))
+            }
+        }
+    }
+
+    fn session_token_id(&self, token: &str) -> Option<String> {
+        decode_token(token).ok().and_then(|claims| claims.claims.sid)
+    }
 }
 
-pub async fn enforce_role_policy(
+// Random tokens meaning nothing on their own. Only their hash is stored, as the token id of their session, which
+// is joined to its user on every request - so revoking the session or changing the user's role takes effect at once.
+#[derive(Debug, Clone, Default)]
+pub struct OpaqueTokens;
+
+impl TokenStrategy for OpaqueTokens {
+    fn issue(&self, _user: &User) -> Result<SessionToken, (StatusCode, Json<Value>)> {
+        let token = generate_opaque_token()?;
+        let token_id = hash_opaque_token(&token);
+
+        Ok(SessionToken { token, token_id })
+    }
+
+    fn authenticate(&self, shared_state: &ConnectionPool, token: &str) -> Result<User, (StatusCode, Json<Value>)> {
+        // Expire tokens like their JSON Web Token counterparts, counting from when the session was opened
+        let opened_after = Utc::now().naive_utc() - chrono::Duration::seconds(TOKEN_LIFETIME_SECONDS as i64);
+        let connection = shared_state.pool.get().expect("Failed to acquire connection from pool");
+
+        match SessionsTable::new(connection).touch_active_user(&hash_opaque_token(token), opened_after) {
+            Ok(Some(user)) => Ok(user),
+            Ok(None) => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Invalid or expired token"})))),
+            Err(err) => {
+                eprintln!("Error looking up session: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to verify session"}))))
+            }
+        }
+    }
+
+    fn session_token_id(&self, token: &str) -> Option<String> {
+        Some(hash_opaque_token(token))
+    }
+}
+
+// Reads TOKEN_STRATEGY (jwt | opaque), defaulting to jwt. Tokens of the other strategy are not accepted.
+pub fn token_strategy() -> Box<dyn TokenStrategy> {
+    match load_optional_environment_variable("TOKEN_STRATEGY").as_deref() {
+        Some("opaque") => Box::new(OpaqueTokens),
+        _ => Box::new(JwtTokens),
+    }
+}
+
+// Token id of the session the request was made with, if any
+pub fn current_session_token_id(headers: &HeaderMap) -> Option<String> {
+    bearer_token(headers).ok().and_then(|token| token_strategy().session_token_id(token))
+}
+
+static ROLE_HIERARCHY: OnceLock<HashMap<UserRole, Vec<UserRole>>> = OnceLock::new();
+
+// Accessing this map under UserRole key will return a list of associated subset roles. Built once, on first use.
+pub fn role_hierarchy() -> &'static HashMap<UserRole, Vec<UserRole>> {
+    ROLE_HIERARCHY.get_or_init(|| {
+        let mut hierarchy = HashMap::new();
+        hierarchy.insert(UserRole::ADMIN, vec![UserRole::ADMIN, UserRole::EDITOR, UserRole::WRITER, UserRole::READER]);
+        hierarchy.insert(UserRole::EDITOR, vec![UserRole::EDITOR, UserRole::WRITER, UserRole::READER]);
+        hierarchy.insert(UserRole::WRITER, vec![UserRole::WRITER, UserRole::READER]);
+        hierarchy.insert(UserRole::READER, vec![UserRole::READER]);
+        hierarchy
+    })
+}
+
+// Whether the role is a superset of or equal to the required role
+pub fn role_satisfies(role: &UserRole, required_role: &UserRole) -> bool {
+    // Check if the list of UserRoles associated with HashMap retrieval under key 'role' contains the required role
+    role_hierarchy().get(role).map(|roles| roles.contains(required_role)).unwrap_or(false)
+}
+
+// Authorizes a request made with an API key. Revoked and unknown keys are rejected alike, and the time of use is recorded.
+pub fn enforce_api_key_role(
     shared_state: &ConnectionPool,
-    claims: &Option<TokenData<Claims>>,
+    api_key: &str,
     required_role: UserRole,
-) -> Result<Option<User>, (StatusCode, Json<Value>)> {
+) -> Result<AuthorizedUser, (StatusCode, Json<Value>)> {
     let connection = shared_state.pool.get().expect("Failed to acquire connection from pool");
-    let mut users = UsersDB::new(connection);
-
-    match users.get_by_email(claims.clone().unwrap().claims.sub) {
-        Ok(user) => {
-            let user_role = string_to_user_role(user.clone().unwrap().role);
-
-            // Accessing this map under UserRole key will return a list of associated subset roles
-            let role_hierarchy: HashMap<UserRole, Vec<UserRole>> = {
-                let mut hierarchy = HashMap::new();
-                hierarchy.insert(UserRole::ADMIN, vec![UserRole::ADMIN, UserRole::EDITOR, UserRole::WRITER, UserRole::READER]);
-                hierarchy.insert(UserRole::EDITOR, vec![UserRole::EDITOR, UserRole::WRITER, UserRole::READER]);
-                hierarchy.insert(UserRole::WRITER, vec![UserRole::WRITER, UserRole::READER]);
-                hierarchy.insert(UserRole::READER, vec![UserRole::READER]);
-                hierarchy
-            };
-
-            // Check if the list of UserRoles associated with HashMap retrieval under key '&user_role' contains the required role '&required_role'
-            if role_hierarchy.get(&user_role).map(|roles| roles.contains(&required_role)).unwrap_or(false) {
-                eprintln!("Access granted: User role '{}' is a superset of or equal to required role '{}'", user_role, required_role);
-                Ok(user)
-            } else {
-                eprintln!("User role: {} does not match required role: {}", user_role, required_role);
-                Err((StatusCode::UNAUTHORIZED, Json(json!({"error": format!("Current role of {} does not have access to {}", user_role, required_role)}))))
-            }
+    let mut api_keys = ApiKeysTable::new(connection);
+
+    let api_key = match api_keys.get_active_by_hash(&hash_api_key(api_key)) {
+        Ok(Some(api_key)) => api_key,
+        Ok(None) => return Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Invalid API key"})))),
+        Err(err) => {
+            eprintln!("Error looking up API key: {:?}", err);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to verify API key"}))));
         }
+    };
+
+    let key_role = string_to_user_role(api_key.role.clone());
+    if !role_satisfies(&key_role, &required_role) {
+        eprintln!("API key '{}' with role {} does not match required role: {}", api_key.prefix, key_role, required_role);
+        return Err((StatusCode::UNAUTHORIZED, Json(json!({"error": format!("API key role of {} does not have access to {}", key_role, required_role)}))));
+    }
+
+    if let Err(err) = api_keys.touch(api_key.id) {
+        eprintln!("Failed to record use of API key '{}': {:?}", api_key.prefix, err);
+    }
+
+    Ok(AuthorizedUser { user: None, role: key_role })
+}
+
+// Rejects tokens of revoked sessions and records when the session was last seen
+fn enforce_active_session(shared_state: &ConnectionPool, session_token_id: &str) -> Result<(), (StatusCode, Json<Value>)> {
+    let connection = shared_state.pool.get().expect("Failed to acquire connection from pool");
+
+    match SessionsTable::new(connection).touch_active(session_token_id) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Session has been revoked"})))),
         Err(err) => {
-            eprintln!("User in claims not found in DB {:?}", err);
-            Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "User in claims not found in DB"}))))
+            eprintln!("Error looking up session: {:?}", err);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to verify session"}))))
         }
     }
-}
\ No newline at end of file
+}
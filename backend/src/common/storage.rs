@@ -0,0 +1,80 @@
+use std::{fs, path::PathBuf};
+
+use crate::common::util::load_optional_environment_variable;
+
+// Destination for generated artifacts such as exports. Keys are relative, '/'-separated paths.
+pub trait StorageBackend: Send + Sync {
+    // Stores the bytes under the given key and returns a locator for the stored artifact
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<String, String>;
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, String>;
+}
+
+// Storage backend writing artifacts to a directory on the local file system
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    pub root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: PathBuf) -> LocalStorage {
+        LocalStorage { root }
+    }
+
+    // Reads STORAGE_DIR, defaulting to a 'storage' directory relative to the working directory
+    pub fn from_env() -> LocalStorage {
+        let root = load_optional_environment_variable("STORAGE_DIR").unwrap_or_else(|| "storage".to_string());
+        LocalStorage::new(PathBuf::from(root))
+    }
+
+    fn resolve(&self, key: &str) -> Result<PathBuf, String> {
+        if key.split('/').any(|segment| segment.is_empty() || segment == "..") {
+            return Err(format!("Invalid storage key '{}'", key));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+impl StorageBackend for LocalStorage {
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<String, String> {
+        let path = self.resolve(key)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| format!("Failed to create directory {:?}: {}", parent, err))?;
+        }
+
+        fs::write(&path, bytes).map_err(|err| format!("Failed to write {:?}: {}", path, err))?;
+
+        Ok(key.to_string())
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>, String> {
+        let path = self.resolve(key)?;
+        fs::read(&path).map_err(|err| format!("Failed to read {:?}: {}", path, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use crate::common::storage::{LocalStorage, StorageBackend};
+
+    #[test]
+    fn put_then_get_round_trips_bytes() {
+        let storage = LocalStorage::new(env::temp_dir().join("axum_api_storage_test"));
+
+        let locator = storage.put("exports/1/artifact.json", b"[]").expect("Put failed");
+
+        assert_eq!(locator, "exports/1/artifact.json");
+        assert_eq!(storage.get(&locator).expect("Get failed"), b"[]".to_vec());
+    }
+
+    #[test]
+    fn put_rejects_keys_escaping_the_root() {
+        let storage = LocalStorage::new(env::temp_dir().join("axum_api_storage_test"));
+
+        assert!(storage.put("../outside.json", b"[]").is_err());
+        assert!(storage.put("/absolute.json", b"[]").is_err());
+    }
+}
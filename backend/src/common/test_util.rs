@@ -0,0 +1,34 @@
+use crate::{
+    common::{
+        db::ConnectionPool,
+        security::{generate_session_token, hash_password},
+    },
+    users::{
+        model::{UpsertUser, UserRole},
+        service::service::UsersTable,
+    },
+};
+
+// Helper method utilized to create user with a specific role and return the associated bearer token in one line of code
+pub fn create_user_and_generate_token(connection_pool: ConnectionPool, email: &str, user_role: UserRole) -> Result<String, jsonwebtoken::errors::Error> {
+
+    // Only email and role are mutable as password and fullname has no constraints
+    let mut new_user = UpsertUser {
+        email: email.to_string(),
+        role: user_role.to_string(),
+        password: "StålGardinerFunkerFjell53".to_string(),
+        fullname: "Josef Stålhard".to_string()
+    };
+
+    // Hash the password
+    hash_password(&mut new_user).expect("Hash failed");
+
+    // Perform the user creation
+    let create_user_result = {
+        let connection = connection_pool.pool.get().expect("Failed to get connection");
+        UsersTable::new(connection).create(new_user.clone())
+    };
+
+    // Generate the bearer token, which is not tied to a session
+    generate_session_token(&create_user_result.unwrap(), None)
+}
@@ -0,0 +1,116 @@
+use ring::hmac;
+
+// Time-based one-time passwords as specified by RFC 6238, with the parameters authenticator apps assume by default
+pub const TOTP_STEP_SECONDS: u64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+
+// Number of steps a code may lag behind or run ahead of the server clock
+const ALLOWED_SKEW_STEPS: u64 = 1;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// Unpadded base32, the encoding authenticator apps expect secrets in
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    encoded
+}
+
+// HOTP value (RFC 4226) of the given step, zero-padded to TOTP_DIGITS
+pub fn code_at(secret: &[u8], step: u64) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &step.to_be_bytes());
+    let digest = tag.as_ref();
+
+    // Dynamic truncation - the low nibble of the last byte selects four bytes of the digest
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let truncated = u32::from_be_bytes([digest[offset] & 0x7f, digest[offset + 1], digest[offset + 2], digest[offset + 3]]);
+
+    format!("{:0width$}", truncated % 10u32.pow(TOTP_DIGITS), width = TOTP_DIGITS as usize)
+}
+
+// Step the code is valid for at the given time, tolerating a little clock skew. Callers should reject
+// steps at or below the last one accepted, so that a code cannot be replayed.
+pub fn matching_step(secret: &[u8], code: &str, unix_time: u64) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let current_step = unix_time / TOTP_STEP_SECONDS;
+    (current_step.saturating_sub(ALLOWED_SKEW_STEPS)..=current_step + ALLOWED_SKEW_STEPS)
+        .find(|step| code_at(secret, *step) == code)
+}
+
+// Key URI understood by authenticator apps, usually handed to them as a QR code
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &[u8]) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        base32_encode(secret),
+        percent_encode(issuer),
+        TOTP_DIGITS,
+        TOTP_STEP_SECONDS
+    )
+}
+
+fn percent_encode(value: &str) -> String {
+    value.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::totp::{base32_encode, code_at, matching_step, otpauth_uri};
+
+    // Secret of the SHA-1 test vectors in RFC 6238, appendix B
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn code_at_matches_rfc_test_vectors() {
+        // The RFC lists eight digit values - six digit codes are their last six digits
+        assert_eq!(code_at(RFC_SECRET, 59 / 30), "287082");
+        assert_eq!(code_at(RFC_SECRET, 1111111109 / 30), "081804");
+        assert_eq!(code_at(RFC_SECRET, 1234567890 / 30), "005924");
+        assert_eq!(code_at(RFC_SECRET, 20000000000 / 30), "353130");
+    }
+
+    #[test]
+    fn matching_step_tolerates_one_step_of_skew() {
+        let code = code_at(RFC_SECRET, 1000);
+
+        assert_eq!(matching_step(RFC_SECRET, &code, 1000 * 30), Some(1000));
+        assert_eq!(matching_step(RFC_SECRET, &code, 1001 * 30 + 29), Some(1000));
+        assert_eq!(matching_step(RFC_SECRET, &code, 1002 * 30), None);
+        assert_eq!(matching_step(RFC_SECRET, "12a456", 1000 * 30), None);
+    }
+
+    #[test]
+    fn otpauth_uri_encodes_secret_and_account() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+
+        let uri = otpauth_uri("Empires API", "ola nordmann@concord.gov", b"foobar");
+        assert_eq!(
+            uri,
+            "otpauth://totp/Empires%20API:ola%20nordmann%40concord.gov?secret=MZXW6YTBOI&issuer=Empires%20API&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
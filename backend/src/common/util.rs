@@ -1,5 +1,7 @@
 use std::env;
 use dotenvy::dotenv;
+use serde::Serializer;
+use serde_json::{Map, Value};
 
 pub fn load_environment_variable(variable_name: &str) -> String {
     dotenv().ok();
@@ -7,3 +9,38 @@ pub fn load_environment_variable(variable_name: &str) -> String {
         .expect(&format!("{} must be set", variable_name))
 }
 
+// Variant of the above for settings that fall back to a default when absent
+pub fn load_optional_environment_variable(variable_name: &str) -> Option<String> {
+    dotenv().ok();
+    env::var(variable_name).ok()
+}
+
+// Suffix marking a cloned record
+pub const COPY_SUFFIX: &str = " (copy)";
+
+// Name of a clone of the record named 'name', shortening the original as needed to stay within 'max_length' characters
+pub fn copy_name(name: &str, max_length: usize) -> String {
+    let kept = max_length.saturating_sub(COPY_SUFFIX.chars().count());
+    let original: String = name.chars().take(kept).collect();
+    format!("{}{}", original.trim_end(), COPY_SUFFIX)
+}
+
+// Serializes a column holding JSON text as the JSON it contains, falling back to an empty object should it be malformed
+pub fn serialize_json_text<S: Serializer>(json_text: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_some(&serde_json::from_str::<Value>(json_text).unwrap_or_else(|_| Value::Object(Map::new())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::util::copy_name;
+
+    #[test]
+    fn copy_name_appends_suffix_within_max_length() {
+        assert_eq!(copy_name("Amarr Empire", 100), "Amarr Empire (copy)");
+
+        let long_name = "Ø".repeat(100);
+        let copied = copy_name(&long_name, 100);
+        assert_eq!(copied.chars().count(), 100);
+        assert!(copied.ends_with(" (copy)"));
+    }
+}
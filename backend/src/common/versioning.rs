@@ -0,0 +1,93 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+    Json,
+};
+use serde_json::{json, Value};
+
+pub const SCHEMA_VERSION_HEADER: &str = "X-Api-Schema-Version";
+
+// Version assumed for clients that do not declare one, which keeps existing integrations working
+pub const DEFAULT_SCHEMA_VERSION: u32 = 1;
+
+// Versions the handlers know how to serialize - extend when a DTO changes shape
+pub const SUPPORTED_SCHEMA_VERSIONS: [u32; 2] = [1, 2];
+
+// Payload schema version declared by the client, either through the X-Api-Schema-Version header
+// or through a versioned media type such as 'application/vnd.empires.v2+json' in the Accept header
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchemaVersion(pub u32);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for SchemaVersion
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<Value>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let declared = match parts.headers.get(SCHEMA_VERSION_HEADER) {
+            Some(value) => {
+                let parsed = value.to_str().ok().and_then(|value| value.trim().parse::<u32>().ok());
+                match parsed {
+                    Some(version) => Some(version),
+                    None => {
+                        return Err((
+                            StatusCode::BAD_REQUEST,
+                            Json(json!({"error": format!("Invalid value for header '{}'", SCHEMA_VERSION_HEADER)})),
+                        ));
+                    }
+                }
+            }
+            None => parts.headers
+                .get(header::ACCEPT)
+                .and_then(|value| value.to_str().ok())
+                .and_then(version_from_media_type),
+        };
+
+        let version = declared.unwrap_or(DEFAULT_SCHEMA_VERSION);
+
+        if SUPPORTED_SCHEMA_VERSIONS.contains(&version) {
+            Ok(SchemaVersion(version))
+        } else {
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("Unsupported schema version {}", version),
+                    "supported_versions": SUPPORTED_SCHEMA_VERSIONS
+                })),
+            ))
+        }
+    }
+}
+
+// Extracts N from media types on the form 'application/vnd.<name>.vN+json'
+fn version_from_media_type(accept: &str) -> Option<u32> {
+    accept
+        .split(',')
+        .map(|media_type| media_type.split(';').next().unwrap_or("").trim())
+        .filter(|media_type| media_type.starts_with("application/vnd.") && media_type.ends_with("+json"))
+        .find_map(|media_type| {
+            let without_suffix = media_type.trim_end_matches("+json");
+            let (_, version) = without_suffix.rsplit_once(".v")?;
+            version.parse::<u32>().ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::versioning::version_from_media_type;
+
+    #[test]
+    fn version_from_media_type_parses_vendor_types() {
+        assert_eq!(version_from_media_type("application/vnd.empires.v2+json"), Some(2));
+        assert_eq!(version_from_media_type("text/html, application/vnd.empires.v1+json; q=0.9"), Some(1));
+    }
+
+    #[test]
+    fn version_from_media_type_ignores_plain_json() {
+        assert_eq!(version_from_media_type("application/json"), None);
+        assert_eq!(version_from_media_type("*/*"), None);
+    }
+}
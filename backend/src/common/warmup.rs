@@ -0,0 +1,108 @@
+use std::{
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::{Duration, Instant},
+};
+use serde_derive::Serialize;
+use tokio::task::JoinHandle;
+use crate::{
+    common::{cache::TtlCache, db::ConnectionPool, jobs::run_blocking, security::role_hierarchy},
+    locations::{model::Location, service::service::LocationsTable},
+    map::{model::StarMap, router::router::MAP_CACHE_SECONDS, service::service::MapTable},
+};
+
+// The location list is invalidated by writes through the locations routes, so this only bounds how long other changes go unseen
+pub const LOCATIONS_CACHE_SECONDS: u64 = 30;
+
+// Delay between attempts at warming up on startup, e.g. while the database is still unreachable
+const WARMUP_RETRY_PERIOD: Duration = Duration::from_secs(5);
+
+// Caches of data read on nearly every visit. Warming them up before reporting ready spares the database a burst of
+// identical queries from the first clients after a deploy. Clones share the same caches and readiness.
+#[derive(Clone)]
+pub struct HotCaches {
+    pub locations: TtlCache<Vec<Location>>,
+    pub star_map: TtlCache<StarMap>,
+    ready: Arc<AtomicBool>,
+}
+
+// What a warm-up loaded, as reported by 'POST /admin/warmup'
+#[derive(Debug, Clone, Serialize)]
+pub struct WarmupReport {
+    pub locations: usize,
+    pub map_regions: usize,
+    pub roles: usize,
+    pub duration_ms: u128,
+}
+
+impl HotCaches {
+    pub fn new() -> HotCaches {
+        HotCaches {
+            locations: TtlCache::new(Duration::from_secs(LOCATIONS_CACHE_SECONDS)),
+            star_map: TtlCache::new(Duration::from_secs(MAP_CACHE_SECONDS)),
+            ready: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    // Whether the caches have been warmed up at least once
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    // Loads the hot data into the caches, replacing what they hold, and marks them ready once every load has succeeded.
+    // Loads run one after another, so that a single pooled connection suffices.
+    pub fn warm_up(&self, pool: &ConnectionPool) -> Result<WarmupReport, String> {
+        let started_at = Instant::now();
+
+        let locations = {
+            let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+            LocationsTable::new(connection).get_all().map_err(|err| format!("Failed to load locations: {}", err))?
+        };
+        let star_map = {
+            let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+            MapTable::new(connection).locations().map(StarMap::from_locations).map_err(|err| format!("Failed to load map: {}", err))?
+        };
+
+        let report = WarmupReport {
+            locations: locations.len(),
+            map_regions: star_map.regions.len(),
+            roles: role_hierarchy().len(),
+            duration_ms: started_at.elapsed().as_millis(),
+        };
+
+        self.locations.store(locations);
+        self.star_map.store(star_map);
+        self.ready.store(true, Ordering::SeqCst);
+
+        Ok(report)
+    }
+}
+
+impl Default for HotCaches {
+    fn default() -> HotCaches {
+        HotCaches::new()
+    }
+}
+
+// Warms the caches up in the background on startup, retrying until it succeeds
+pub fn spawn_cache_warmup(pool: ConnectionPool, caches: HotCaches) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let result = run_blocking({
+                let pool = pool.clone();
+                let caches = caches.clone();
+                move || caches.warm_up(&pool)
+            }).await;
+
+            match result {
+                Ok(report) => {
+                    eprintln!("Warmed up caches: {:?}", report);
+                    break;
+                }
+                Err(err) => {
+                    eprintln!("Failed to warm up caches, retrying in {:?}: {}", WARMUP_RETRY_PERIOD, err);
+                    tokio::time::sleep(WARMUP_RETRY_PERIOD).await;
+                }
+            }
+        }
+    })
+}
@@ -1,22 +1,63 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::prelude::*;
 use serde_derive::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
 use crate::schema::empires;
 
-#[derive(Serialize, Debug, Clone, Queryable)]
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
 #[diesel(table_name = empires)]
 pub struct Empire {
     pub id: i32,
     pub name: String,
     pub slogan: String,
     pub location_id: i32,
-    pub description: String
+    pub description: String,
+    pub created_at: NaiveDateTime,
+    // Archived empires are left out of listings, but remain readable by id
+    pub archived: bool,
+    // Empires are hidden from readers until published - None while staged, or a future time when scheduled
+    pub published_at: Option<NaiveDateTime>
 }
 
-#[derive(Debug, Clone, Insertable, Deserialize, Serialize)]
+#[derive(Debug, Clone, Insertable, Deserialize, Serialize, ToSchema)]
 #[diesel(table_name = empires)]
 pub struct UpsertEmpire {
     pub name: String,
     pub slogan: String,
     pub location_id: i32,
     pub description: String
-}
\ No newline at end of file
+}
+
+// Body of 'POST /empires/:id/publish' - publishes at the given time, or right away if absent.
+// The time is RFC 3339 with an offset, e.g. '2026-12-01T00:00:00Z', and is stored as naive UTC like every other.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct PublishEmpire {
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl From<Empire> for UpsertEmpire {
+    fn from(empire: Empire) -> UpsertEmpire {
+        UpsertEmpire {
+            name: empire.name,
+            slogan: empire.slogan,
+            location_id: empire.location_id,
+            description: empire.description,
+        }
+    }
+}
+
+// Length of the name column
+pub const MAX_NAME_LENGTH: usize = 100;
+
+// Optional relations and records requested through 'include', e.g. 'include=archived' on listings or 'include=ships' when cloning
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncludeParams {
+    pub include: Option<String>,
+}
+
+impl IncludeParams {
+    pub fn includes(&self, relation: &str) -> bool {
+        self.include.as_deref().unwrap_or("").split(',').any(|included| included.trim() == relation)
+    }
+}
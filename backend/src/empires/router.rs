@@ -1,16 +1,25 @@
 pub mod router {
-    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use serde_json::{json, Map, Value};
+    use diesel::result::DatabaseErrorKind;
     use axum::{
-        Router, http::StatusCode, Json, response::IntoResponse, extract::State, extract, middleware,
+        Router, body::Bytes, http::{header, HeaderMap, StatusCode}, Json, response::{IntoResponse, Response}, extract::{Query, State}, extract, middleware, Extension,
     };
     use crate::{
         common::{
+            budget::{collect_within_budget, SearchParams},
+            concurrency::{etag_of, if_match_satisfied, if_match_tags, Conditional},
             db::ConnectionPool,
-            middleware::{require_writer, require_reader, require_editor, require_admin}
+            middleware::{AuthorizedUser, require_writer, require_reader, require_editor, require_admin},
+            permissions::enforce_field_permissions
         },
+        change_requests::router::router::submit_change_request,
+        users::model::UserRole,
+        webhooks::dispatch::dispatch_event,
+        templates::{model::TemplateParams, router::router::prefill_from_template},
         empires::{
             service::service::EmpiresTable as empiresTable,
-            model::UpsertEmpire
+            model::{IncludeParams, PublishEmpire, UpsertEmpire}
         }
     };
 
@@ -20,15 +29,21 @@ pub mod router {
         // Create route groups with appropriate middleware
         let create_routes = Router::new()
             .route("/empires", axum::routing::post(create_empire_handler))
+            .route("/empires/:empire_id/clone", axum::routing::post(clone_empire_handler))
+            // Updates by WRITERs are submitted for review rather than applied
+            .route("/empires/:empire_id", axum::routing::put(update_empire_handler))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_writer));
         
         let read_routes = Router::new()
             .route("/empires", axum::routing::get(get_all_empires_handler))
+            .route("/empires/search", axum::routing::get(search_empires_handler))
             .route("/empires/:empire_id", axum::routing::get(read_empire_handler))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
         
         let update_routes = Router::new()
-            .route("/empires/:empire_id", axum::routing::put(update_empire_handler))
+            .route("/empires/:empire_id/archive", axum::routing::post(archive_empire_handler))
+            .route("/empires/:empire_id/unarchive", axum::routing::post(unarchive_empire_handler))
+            .route("/empires/:empire_id/publish", axum::routing::post(publish_empire_handler))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_editor));
         
         let delete_routes = Router::new()
@@ -44,15 +59,37 @@ pub mod router {
             .with_state(shared_connection_pool)
     }
 
+    // Empires that have not been published yet are visible to WRITERs and above only
+    fn sees_unpublished(authorized: &AuthorizedUser) -> bool {
+        authorized.has_role(&UserRole::WRITER)
+    }
+
+    // Empires created by EDITORs and above are published right away, while those of WRITERs are staged until an EDITOR publishes them
+    fn publishes_on_create(authorized: &AuthorizedUser) -> bool {
+        authorized.has_role(&UserRole::EDITOR)
+    }
+
     // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
 
+    #[utoipa::path(
+        get,
+        path = "/empires",
+        tag = "empires",
+        operation_id = "get_all_empires",
+        params(IncludeParams),
+        responses(
+            (status = 200, body = [Empire])
+        )
+    )]
     pub async fn get_all_empires_handler(
         State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Query(params): Query<IncludeParams>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
-        match empiresTable::new(connection).get_all() {
+        match empiresTable::new(connection).get_all(params.includes("archived"), sees_unpublished(&authorized)) {
             Ok(empires) => Ok((StatusCode::OK, Json(empires))),
             Err(err) => {
                 eprintln!("Error fetching all empires: {:?}", err);
@@ -61,15 +98,105 @@ pub mod router {
         }
     }
 
+    // Searches empires within the requested time budget. Rather than timing out, the rows gathered so far are
+    // returned flagged as partial along with a cursor from which the client may continue.
+    #[utoipa::path(
+        get,
+        path = "/empires/search",
+        tag = "empires",
+        operation_id = "search_empires",
+        params(SearchParams),
+        responses(
+            (status = 200, description = "With 'include=location' every empire carries its location", body = EmpirePage)
+        )
+    )]
+    pub async fn search_empires_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Query(params): Query<SearchParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut empires_db = empiresTable::new(connection);
+        let terms = params.terms();
+        let include_archived = params.includes("archived");
+        let include_unpublished = sees_unpublished(&authorized);
+
+        let page = collect_within_budget(
+            &params.budget(),
+            params.cursor,
+            params.page_size(),
+            |after_id, batch_size, timeout_ms| empires_db.search_batch(&terms, include_archived, include_unpublished, after_id, batch_size, timeout_ms),
+            |empire| empire.id,
+        );
+
+        let page = match page {
+            Ok(page) => page,
+            Err(err) => {
+                eprintln!("Error searching empires: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to search empires"}))));
+            }
+        };
+
+        if !params.includes("location") {
+            return Ok((StatusCode::OK, Json(json!(page))));
+        }
+
+        match empires_db.locations_of(&page.items) {
+            Ok(locations) => {
+                let locations_by_id: HashMap<i32, _> = locations.into_iter()
+                    .map(|location| (location.id, location))
+                    .collect();
+
+                let page = page.map(|empire| {
+                    let mut item = json!(empire);
+                    item["location"] = json!(locations_by_id.get(&empire.location_id));
+                    item
+                });
+
+                Ok((StatusCode::OK, Json(json!(page))))
+            }
+            Err(err) => {
+                eprintln!("Error including locations in empire search: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to search empires"}))))
+            }
+        }
+    }
+
+    // Creates an empire. With '?template_id=' any fields absent from the body are taken from the template.
+    #[utoipa::path(
+        post,
+        path = "/empires",
+        tag = "empires",
+        operation_id = "create_empire",
+        params(TemplateParams),
+        request_body = UpsertEmpire,
+        responses(
+            (status = 201, body = Empire),
+            (status = 422, body = ApiError)
+        )
+    )]
     pub async fn create_empire_handler(
         State(shared_state): State<ConnectionPool>,
-        Json(upsert_empire): Json<UpsertEmpire>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Query(template): Query<TemplateParams>,
+        Json(body): Json<Map<String, Value>>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let body = prefill_from_template(&shared_state, &template, "empire", body)?;
+        let upsert_empire: UpsertEmpire = serde_json::from_value(Value::Object(body))
+            .map_err(|err| (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": format!("Invalid empire: {}", err)}))))?;
+
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
-        match empiresTable::new(connection).create(upsert_empire) {
-            Ok(new_empire) => Ok((StatusCode::CREATED, Json(new_empire))),
+        match empiresTable::new(connection).create(upsert_empire, publishes_on_create(&authorized)) {
+            Ok(new_empire) => {
+                dispatch_event(shared_state.clone(), "empire.created", json!(new_empire));
+                Ok((StatusCode::CREATED, Json(new_empire)))
+            },
+            Err(diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+                Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Location not found"}))))
+            },
             Err(err) => {
                 eprintln!("Error creating empire: {:?}", err);
                 Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create empire"}))))
@@ -78,18 +205,74 @@ pub mod router {
     }
 
 
+    #[utoipa::path(
+        post,
+        path = "/empires/{empire_id}/clone",
+        tag = "empires",
+        operation_id = "clone_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire"), IncludeParams),
+        responses(
+            (status = 201, body = Empire),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn clone_empire_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+        Query(params): Query<IncludeParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (empire_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match empiresTable::new(connection).clone_empire(empire_id, params.includes("ships"), publishes_on_create(&authorized)) {
+            Ok(new_empire) => {
+                dispatch_event(shared_state.clone(), "empire.created", json!(new_empire));
+                Ok((StatusCode::CREATED, Json(new_empire)))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error cloning empire: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to clone empire"}))))
+            }
+        }
+    }
+
+    // Empires that have not been published are reported as not found to those who may not see them
+    #[utoipa::path(
+        get,
+        path = "/empires/{empire_id}",
+        tag = "empires",
+        operation_id = "read_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        responses(
+            (status = 200, body = Empire),
+            (status = 404, body = ApiError)
+        )
+    )]
     pub async fn read_empire_handler(
         State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
         path: extract::Path<(i32, )>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
         let (empire_id, ) = path.0;
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
-        match empiresTable::new(connection).get(empire_id) {
+        let mut empires_db = empiresTable::new(connection);
+        let empire = if sees_unpublished(&authorized) {
+            empires_db.get(empire_id)
+        } else {
+            empires_db.get_published(empire_id)
+        };
+
+        match empire {
             Ok(empire) => {
                 if let Some(empire) = empire {
-                    Ok((StatusCode::OK, Json(empire)))
+                    Ok((StatusCode::OK, [(header::ETAG, etag_of(&empire))], Json(empire)))
                 } else {
                     Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
                 }
@@ -101,17 +284,69 @@ pub mod router {
         }
     }
 
+    // Updates the empire. When an If-Match header is present the update is only applied if it matches the current
+    // ETag - otherwise 412 is returned along with the current empire so that the client may resolve the conflict.
+    // Updates by WRITERs are not applied, but submitted for review as a change request instead.
+    #[utoipa::path(
+        put,
+        path = "/empires/{empire_id}",
+        tag = "empires",
+        operation_id = "update_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire"), ("If-Match" = Option<String>, Header, description = "ETag the update is conditional on")),
+        request_body = UpsertEmpire,
+        responses(
+            (status = 200, body = Empire),
+            (status = 202, description = "Submitted for review", body = ChangeRequest),
+            (status = 404, body = ApiError),
+            (status = 412, description = "The empire was changed since - carries the current one", body = ApiError)
+        )
+    )]
     pub async fn update_empire_handler(
         State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
         path: extract::Path<(i32, )>,
+        headers: HeaderMap,
         Json(upsert_empire): Json<UpsertEmpire>,
-    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    ) -> Result<Response, (StatusCode, Json<Value>)> {
         let (empire_id, ) = path.0;
+        let current = match empiresTable::new(shared_state.pool.get().expect("Failed to acquire connection from pool")).get(empire_id) {
+            Ok(Some(current)) => UpsertEmpire::from(current),
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"})))),
+            Err(err) => {
+                eprintln!("Error reading empire: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update empire"}))));
+            }
+        };
+        enforce_field_permissions(&authorized, "empires", &current, &upsert_empire)?;
+
+        if !authorized.has_role(&UserRole::EDITOR) {
+            return submit_change_request(&shared_state, &authorized, "empire", empire_id, &current, &upsert_empire);
+        }
+
+        let expected_tags = if_match_tags(&headers);
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
-        match empiresTable::new(connection).update(empire_id, upsert_empire) {
-            Ok(updated_empire) => Ok((StatusCode::OK, Json(updated_empire))),
+        let mut empires_db = empiresTable::new(connection);
+        let result = match expected_tags {
+            Some(expected_tags) => empires_db.update_where(empire_id, upsert_empire, |current| {
+                if_match_satisfied(&expected_tags, &etag_of(current))
+            }),
+            None => empires_db.update(empire_id, upsert_empire).map(Conditional::Applied),
+        };
+
+        match result {
+            Ok(Conditional::Applied(updated_empire)) => {
+                dispatch_event(shared_state.clone(), "empire.updated", json!(updated_empire));
+                Ok((StatusCode::OK, [(header::ETAG, etag_of(&updated_empire))], Json(updated_empire)).into_response())
+            },
+            Ok(Conditional::PreconditionFailed(current)) => {
+                Ok((
+                    StatusCode::PRECONDITION_FAILED,
+                    [(header::ETAG, etag_of(&current))],
+                    Json(json!({"error": "Empire has been modified by someone else", "current": current})),
+                ).into_response())
+            },
             Err(diesel::result::Error::NotFound) => {
                 Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
             },
@@ -122,6 +357,123 @@ pub mod router {
         }
     }
 
+    #[utoipa::path(
+        post,
+        path = "/empires/{empire_id}/archive",
+        tag = "empires",
+        operation_id = "archive_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        responses(
+            (status = 200, body = Empire),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn archive_empire_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        set_archived(shared_state, path.0.0, true)
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/empires/{empire_id}/unarchive",
+        tag = "empires",
+        operation_id = "unarchive_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        responses(
+            (status = 200, body = Empire),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn unarchive_empire_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        set_archived(shared_state, path.0.0, false)
+    }
+
+    fn set_archived(shared_state: ConnectionPool, empire_id: i32, archived: bool) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match empiresTable::new(connection).set_archived(empire_id, archived) {
+            Ok(updated_empire) => {
+                dispatch_event(shared_state.clone(), "empire.updated", json!(updated_empire));
+                Ok((StatusCode::OK, [(header::ETAG, etag_of(&updated_empire))], Json(updated_empire)))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
+            },
+            Err(err) => {
+                let (action, failure) = if archived {
+                    ("archiving", "Failed to archive empire")
+                } else {
+                    ("unarchiving", "Failed to unarchive empire")
+                };
+                eprintln!("Error {} empire: {:?}", action, err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": failure}))))
+            }
+        }
+    }
+
+    // Publishes the empire right away, or at the time given in the optional body. The body is read as raw bytes, as only
+    // an empty one means 'now' - the frontend sends none, but declares JSON all the same.
+    #[utoipa::path(
+        post,
+        path = "/empires/{empire_id}/publish",
+        tag = "empires",
+        operation_id = "publish_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        request_body = Option<PublishEmpire>,
+        responses(
+            (status = 200, body = Empire),
+            (status = 404, body = ApiError),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn publish_empire_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        body: Bytes,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (empire_id, ) = path.0;
+        let published_at = if body.iter().all(u8::is_ascii_whitespace) {
+            None
+        } else {
+            serde_json::from_slice::<PublishEmpire>(&body)
+                .map_err(|err| (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": format!("Invalid publication: {}", err)}))))?
+                .published_at
+                .map(|published_at| published_at.naive_utc())
+        };
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match empiresTable::new(connection).publish(empire_id, published_at) {
+            Ok(updated_empire) => {
+                dispatch_event(shared_state.clone(), "empire.updated", json!(updated_empire));
+                Ok((StatusCode::OK, [(header::ETAG, etag_of(&updated_empire))], Json(updated_empire)))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error publishing empire: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to publish empire"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/empires/{empire_id}",
+        tag = "empires",
+        operation_id = "delete_empire",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        responses(
+            (status = 204)
+        )
+    )]
     pub async fn delete_empire_handler(
         State(shared_state): State<ConnectionPool>,
         path: extract::Path<(i32, )>,
@@ -131,11 +483,283 @@ pub mod router {
             .expect("Failed to acquire connection from pool");
 
         match empiresTable::new(connection).delete(empire_id) {
-            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Ok(_) => {
+                dispatch_event(shared_state.clone(), "empire.deleted", json!({"id": empire_id}));
+                Ok((StatusCode::NO_CONTENT, ()))
+            },
             Err(err) => {
                 eprintln!("Error deleting empire: {:?}", err);
                 Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to delete empire"}))))
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use diesel::prelude::*;
+        use tower::ServiceExt;
+        use crate::{
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            empires::{
+                model::UpsertEmpire,
+                router::router::empires_route,
+                service::service::EmpiresTable
+            },
+            locations::{
+                model::UpsertLocation,
+                service::service::LocationsTable
+            },
+            schema::ships,
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn clone_empire_copies_empire_and_requested_ships() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Kor-Azor".to_string(), area: "Kor-Azor Prime".to_string() })
+                .unwrap();
+            let empire = EmpiresTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertEmpire {
+                    name: "Kor-Azor Family".to_string(),
+                    slogan: "Faith and fleet".to_string(),
+                    location_id: location.id,
+                    description: "One of the five heir families".to_string(),
+                }, true)
+                .unwrap();
+            diesel::insert_into(ships::table)
+                .values((ships::name.eq("Punisher"), ships::category.eq("Frigate"), ships::empire_id.eq(empire.id)))
+                .execute(&mut connection_pool.pool.get().unwrap())
+                .unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool.clone(), "kopist@concord.gov", UserRole::WRITER).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/empires/{}/clone?include=ships", empire.id))
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let copy: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_ne!(copy["id"], empire.id);
+            assert_eq!(copy["name"], "Kor-Azor Family (copy)");
+            assert_eq!(copy["slogan"], empire.slogan);
+            assert_eq!(copy["location_id"], empire.location_id);
+
+            // Assert that the ships were copied over to the clone
+            let copied_ships: Vec<(String, Option<String>)> = ships::table
+                .filter(ships::empire_id.eq(copy["id"].as_i64().unwrap() as i32))
+                .select((ships::name, ships::category))
+                .load(&mut connection_pool.pool.get().unwrap())
+                .unwrap();
+            assert_eq!(copied_ships, [("Punisher".to_string(), Some("Frigate".to_string()))]);
+        }
+
+        #[tokio::test]
+        async fn archived_empires_are_left_out_of_listings_but_remain_readable() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Cloud Ring".to_string(), area: "FD-MLJ".to_string() })
+                .unwrap();
+            let empire = EmpiresTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertEmpire {
+                    name: "Jovian Directorate".to_string(),
+                    slogan: "Long gone".to_string(),
+                    location_id: location.id,
+                    description: "Withdrew from the cluster".to_string(),
+                }, true)
+                .unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "arkivar@concord.gov", UserRole::EDITOR).unwrap();
+
+            let send = |method: &str, uri: String| {
+                Request::builder()
+                    .uri(uri)
+                    .method(method)
+                    .header("Authorization", format!("Bearer {}", bearer_token))
+                    .body(Body::empty())
+                    .unwrap()
+            };
+            let listed_ids = |body: &[u8]| -> Vec<i64> {
+                serde_json::from_slice::<serde_json::Value>(body).unwrap()
+                    .as_array().unwrap()
+                    .iter().map(|empire| empire["id"].as_i64().unwrap())
+                    .collect()
+            };
+
+            let response = service.clone().oneshot(send("POST", format!("/empires/{}/archive", empire.id))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let archived: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(archived["archived"], true);
+
+            // Assert that the archived empire is only listed on request
+            let response = service.clone().oneshot(send("GET", "/empires".to_string())).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            assert!(!listed_ids(&body).contains(&(empire.id as i64)));
+
+            let response = service.clone().oneshot(send("GET", "/empires?include=archived".to_string())).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            assert!(listed_ids(&body).contains(&(empire.id as i64)));
+
+            // Assert that the archived empire is still readable by id
+            let response = service.clone().oneshot(send("GET", format!("/empires/{}", empire.id))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Assert that unarchiving lists the empire again
+            let response = service.clone().oneshot(send("POST", format!("/empires/{}/unarchive", empire.id))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = service.oneshot(send("GET", "/empires".to_string())).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            assert!(listed_ids(&body).contains(&(empire.id as i64)));
+        }
+
+        #[tokio::test]
+        async fn staged_empires_are_hidden_from_readers_until_published() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Khanid".to_string(), area: "Khanid Prime".to_string() })
+                .unwrap();
+
+            let writer_token = create_user_and_generate_token(connection_pool.clone(), "forfatter@concord.gov", UserRole::WRITER).unwrap();
+            let reader_token = create_user_and_generate_token(connection_pool.clone(), "leser@concord.gov", UserRole::READER).unwrap();
+            let editor_token = create_user_and_generate_token(connection_pool, "redaktor@concord.gov", UserRole::EDITOR).unwrap();
+
+            let send = |method: &str, uri: String, bearer_token: &str, body: serde_json::Value| {
+                Request::builder()
+                    .uri(uri)
+                    .method(method)
+                    .header("Authorization", format!("Bearer {}", bearer_token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap()
+            };
+
+            let new_empire = serde_json::json!({
+                "name": "Khanid Kingdom",
+                "slogan": "Independence",
+                "location_id": location.id,
+                "description": "Seceded from the Empire"
+            });
+            let response = service.clone().oneshot(send("POST", "/empires".to_string(), &writer_token, new_empire)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let staged: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(staged["published_at"], serde_json::Value::Null);
+            let empire_uri = format!("/empires/{}", staged["id"]);
+
+            // Assert that the staged empire is visible to its writer, but not to readers
+            let response = service.clone().oneshot(send("GET", empire_uri.clone(), &writer_token, serde_json::Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let response = service.clone().oneshot(send("GET", empire_uri.clone(), &reader_token, serde_json::Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            // Assert that publishing at a future time keeps the empire hidden, whatever the offset of the time
+            let publish_uri = format!("{}/publish", empire_uri);
+            for published_at in ["2999-01-01T00:00:00Z", "2999-01-01T01:00:00+01:00"] {
+                let scheduled = serde_json::json!({"published_at": published_at});
+                let response = service.clone().oneshot(send("POST", publish_uri.clone(), &editor_token, scheduled)).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+                let scheduled: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(scheduled["published_at"], "2999-01-01T00:00:00");
+                let response = service.clone().oneshot(send("GET", empire_uri.clone(), &reader_token, serde_json::Value::Null)).await.unwrap();
+                assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            }
+
+            // Assert that a time which cannot be read is rejected, rather than publishing right away
+            let malformed = serde_json::json!({"published_at": "next tuesday"});
+            let response = service.clone().oneshot(send("POST", publish_uri.clone(), &editor_token, malformed)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+            let response = service.clone().oneshot(send("GET", empire_uri.clone(), &reader_token, serde_json::Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            // Assert that publishing without a body makes the empire visible right away
+            let publish_now = Request::builder()
+                .uri(publish_uri)
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", editor_token))
+                .header("Content-Type", "application/json")
+                .body(Body::empty())
+                .unwrap();
+            let response = service.clone().oneshot(publish_now).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let response = service.oneshot(send("GET", empire_uri, &reader_token, serde_json::Value::Null)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn moving_an_empire_requires_editor_and_names_the_forbidden_field() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let (origin, destination) = {
+                let mut locations = LocationsTable::new(connection_pool.pool.get().unwrap());
+                (
+                    locations.create(UpsertLocation { star_system: "Luminaire".to_string(), area: "Caldari Prime".to_string() }).unwrap(),
+                    locations.create(UpsertLocation { star_system: "New Caldari".to_string(), area: "New Caldari Prime".to_string() }).unwrap(),
+                )
+            };
+            let empire = EmpiresTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertEmpire {
+                    name: "Caldari State".to_string(),
+                    slogan: "Efficiency above all".to_string(),
+                    location_id: origin.id,
+                    description: "Corporate megastate".to_string(),
+                }, true)
+                .unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "flytter@concord.gov", UserRole::WRITER).unwrap();
+
+            let put = |location_id: i32, slogan: &str| {
+                let body = serde_json::json!({
+                    "name": empire.name,
+                    "slogan": slogan,
+                    "location_id": location_id,
+                    "description": empire.description,
+                });
+                Request::builder()
+                    .uri(format!("/empires/{}", empire.id))
+                    .method("PUT")
+                    .header("Authorization", format!("Bearer {}", bearer_token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap()
+            };
+
+            let response = service.clone().oneshot(put(destination.id, "Efficiency above all")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(error["fields"], serde_json::json!(["location_id"]));
+
+            // Assert that other fields may still be changed, as long as the location is sent back as it is
+            let response = service.oneshot(put(origin.id, "Profit through order")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+        }
+    }
 }
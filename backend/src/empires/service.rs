@@ -1,11 +1,18 @@
 pub mod service {
+    use chrono::{NaiveDateTime, Utc};
     use diesel::{
         prelude::*,
         PgConnection,
         r2d2::{ConnectionManager, PooledConnection},
     };
     use crate::{
-        empires::model::{Empire, UpsertEmpire},
+        common::{
+            budget::{like_pattern, set_statement_timeout},
+            concurrency::Conditional,
+            util::copy_name,
+        },
+        empires::model::{Empire, UpsertEmpire, MAX_NAME_LENGTH},
+        locations::model::Location,
         schema
     };
 
@@ -20,26 +27,79 @@ pub mod service {
             EmpiresTable { connection }
         }
 
-        pub fn create(&mut self, upsert_empire: UpsertEmpire) -> Result<Empire, diesel::result::Error> {
+        // Creates the empire, published right away if 'publish' is set and staged otherwise
+        pub fn create(&mut self, upsert_empire: UpsertEmpire, publish: bool) -> Result<Empire, diesel::result::Error> {
             use schema::empires;
 
-            let new_empire = diesel::insert_into(empires::table)
+            diesel::insert_into(empires::table)
                 .values((
                     empires::name.eq(&upsert_empire.name),
                     empires::slogan.eq(&upsert_empire.slogan),
                     empires::location_id.eq(&upsert_empire.location_id),
-                    empires::description.eq(&upsert_empire.description)
+                    empires::description.eq(&upsert_empire.description),
+                    empires::published_at.eq(publish.then(|| Utc::now().naive_utc()))
                 ))
                 .get_result(&mut self.connection)
-                .expect("Create empire failed");
+        }
+
+        // Creates a copy of the empire, its name marked with a "(copy)" suffix, optionally along with copies of its ships.
+        // The copy is never archived, even if the original is, and is published like a newly created empire.
+        pub fn clone_empire(&mut self, empire_id: i32, include_ships: bool, publish: bool) -> Result<Empire, diesel::result::Error> {
+            use schema::{empires, ships};
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                let original = empires::table
+                    .find(empire_id)
+                    .get_result::<Empire>(connection)?;
+
+                let copy = diesel::insert_into(empires::table)
+                    .values((
+                        empires::name.eq(copy_name(&original.name, MAX_NAME_LENGTH)),
+                        empires::slogan.eq(&original.slogan),
+                        empires::location_id.eq(original.location_id),
+                        empires::description.eq(&original.description),
+                        empires::published_at.eq(publish.then(|| Utc::now().naive_utc()))
+                    ))
+                    .get_result::<Empire>(connection)?;
+
+                if include_ships {
+                    let ships = ships::table
+                        .filter(ships::empire_id.eq(original.id))
+                        .order(ships::id)
+                        .select((ships::name, ships::category, ships::description))
+                        .load::<(String, Option<String>, Option<String>)>(connection)?;
+
+                    let copied_ships: Vec<_> = ships.into_iter()
+                        .map(|(name, category, description)| (
+                            ships::name.eq(name),
+                            ships::category.eq(category),
+                            ships::description.eq(description),
+                            ships::empire_id.eq(copy.id),
+                        ))
+                        .collect();
 
-            Ok(new_empire)
+                    diesel::insert_into(ships::table)
+                        .values(&copied_ships)
+                        .execute(connection)?;
+                }
+
+                Ok(copy)
+            })
         }
 
-        pub fn get_all(&mut self) -> Result<Vec<Empire>, diesel::result::Error> {
+        pub fn get_all(&mut self, include_archived: bool, include_unpublished: bool) -> Result<Vec<Empire>, diesel::result::Error> {
             use schema::empires;
 
-            let all_empires = empires::table
+            let mut query = empires::table.into_boxed();
+            if !include_archived {
+                query = query.filter(empires::archived.eq(false));
+            }
+            if !include_unpublished {
+                query = query.filter(empires::published_at.le(Utc::now().naive_utc()));
+            }
+
+            let all_empires = query
                 .load::<Empire>(&mut self.connection)?;
 
             Ok(all_empires)
@@ -56,6 +116,68 @@ pub mod service {
             Ok(empire)
         }
 
+        // Fetches the empire only if it has been published
+        pub fn get_published(&mut self, empire_id: i32) -> Result<Option<Empire>, diesel::result::Error> {
+            use schema::empires;
+
+            empires::table
+                .find(empire_id)
+                .filter(empires::published_at.le(Utc::now().naive_utc()))
+                .get_result(&mut self.connection)
+                .optional()
+        }
+
+        // Fetches the next batch of empires after 'after_id' where every term matches the name, slogan or description.
+        // The batch is aborted by the database once 'timeout_ms' has passed.
+        pub fn search_batch(&mut self, terms: &[String], include_archived: bool, include_unpublished: bool, after_id: Option<i32>, batch_size: i64, timeout_ms: u64,
+        ) -> Result<Vec<Empire>, diesel::result::Error> {
+            use schema::empires;
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                set_statement_timeout(connection, timeout_ms)?;
+
+                let mut query = empires::table
+                    .order(empires::id)
+                    .limit(batch_size)
+                    .into_boxed();
+
+                if let Some(after_id) = after_id {
+                    query = query.filter(empires::id.gt(after_id));
+                }
+
+                if !include_archived {
+                    query = query.filter(empires::archived.eq(false));
+                }
+
+                if !include_unpublished {
+                    query = query.filter(empires::published_at.le(Utc::now().naive_utc()));
+                }
+
+                for term in terms {
+                    let pattern = like_pattern(term);
+                    query = query.filter(
+                        empires::name.ilike(pattern.clone())
+                            .or(empires::slogan.ilike(pattern.clone()))
+                            .or(empires::description.ilike(pattern))
+                    );
+                }
+
+                query.load::<Empire>(connection)
+            })
+        }
+
+        // Loads the locations referenced by the given empires in a single query
+        pub fn locations_of(&mut self, empires: &[Empire]) -> Result<Vec<Location>, diesel::result::Error> {
+            use schema::locations;
+
+            let location_ids: Vec<i32> = empires.iter().map(|empire| empire.location_id).collect();
+
+            locations::table
+                .filter(locations::id.eq_any(location_ids))
+                .load::<Location>(&mut self.connection)
+        }
+
         pub fn update(&mut self, empire_id: i32, upsert_empire: UpsertEmpire,
         ) -> Result<Empire, diesel::result::Error> {
             use schema::empires;
@@ -83,6 +205,57 @@ pub mod service {
             }
         }
 
+        // Archives or restores the empire, leaving its other fields untouched
+        pub fn set_archived(&mut self, empire_id: i32, archived: bool) -> Result<Empire, diesel::result::Error> {
+            use schema::empires;
+
+            diesel::update(empires::table.find(empire_id))
+                .set(empires::archived.eq(archived))
+                .get_result(&mut self.connection)
+        }
+
+        // Publishes the empire at the given time, or right away. Publishing an empire again moves its publication time.
+        pub fn publish(&mut self, empire_id: i32, published_at: Option<NaiveDateTime>) -> Result<Empire, diesel::result::Error> {
+            use schema::empires;
+
+            diesel::update(empires::table.find(empire_id))
+                .set(empires::published_at.eq(published_at.unwrap_or_else(|| Utc::now().naive_utc())))
+                .get_result(&mut self.connection)
+        }
+
+        // Updates the empire only if its current state satisfies 'precondition', which is evaluated while holding
+        // a row lock so that concurrent writers cannot slip in between the check and the update
+        pub fn update_where<P>(&mut self, empire_id: i32, upsert_empire: UpsertEmpire, precondition: P,
+        ) -> Result<Conditional<Empire>, diesel::result::Error>
+        where
+            P: FnOnce(&Empire) -> bool,
+        {
+            use schema::empires;
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                let current = empires::table
+                    .find(empire_id)
+                    .for_update()
+                    .get_result::<Empire>(connection)?;
+
+                if !precondition(&current) {
+                    return Ok(Conditional::PreconditionFailed(current));
+                }
+
+                let updated_empire = diesel::update(empires::table.find(empire_id))
+                    .set((
+                        empires::name.eq(&upsert_empire.name),
+                        empires::slogan.eq(&upsert_empire.slogan),
+                        empires::location_id.eq(&upsert_empire.location_id),
+                        empires::description.eq(&upsert_empire.description)
+                    ))
+                    .get_result(connection)?;
+
+                Ok(Conditional::Applied(updated_empire))
+            })
+        }
+
         pub fn delete(&mut self, empire_id: i32) -> Result<(), diesel::result::Error> {
             use schema::empires;
 
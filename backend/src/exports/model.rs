@@ -0,0 +1,74 @@
+use std::str::FromStr;
+use chrono::{NaiveDateTime, Utc};
+use cron::Schedule;
+use diesel::prelude::*;
+use serde_derive::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+// Resources that may be exported
+pub const EXPORTABLE_RESOURCES: [&str; 3] = ["users", "locations", "empires"];
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+pub struct ExportJob {
+    pub id: i32,
+    // The administrator who created the job - only they may see, change, run or download it
+    pub created_by: i32,
+    pub name: String,
+    pub resource: String,
+    pub schedule: String,
+    pub notify_email: Option<String>,
+    pub notify_webhook: Option<String>,
+    pub enabled: bool,
+    pub next_run_at: NaiveDateTime,
+    pub last_run_at: Option<NaiveDateTime>,
+    pub last_artifact: Option<String>,
+    pub last_error: Option<String>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpsertExportJob {
+    pub name: String,
+    pub resource: String,
+    pub schedule: String,
+    pub notify_email: Option<String>,
+    pub notify_webhook: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl UpsertExportJob {
+    pub fn is_valid_resource(&self) -> bool {
+        EXPORTABLE_RESOURCES.contains(&self.resource.as_str())
+    }
+
+    // First point in time after 'after' matched by the schedule, or None if the expression is invalid
+    pub fn next_run_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        next_run_after(&self.schedule, after)
+    }
+}
+
+// Schedules are standard five-field cron expressions (minute hour day-of-month month day-of-week),
+// evaluated in UTC. Six- and seven-field expressions with seconds and years are accepted as well.
+pub fn parse_schedule(expression: &str) -> Result<Schedule, String> {
+    let expression = expression.trim();
+    let normalized = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+
+    Schedule::from_str(&normalized).map_err(|err| format!("Invalid schedule '{}': {}", expression, err))
+}
+
+pub fn next_run_after(expression: &str, after: NaiveDateTime) -> Option<NaiveDateTime> {
+    let schedule = parse_schedule(expression).ok()?;
+    schedule.after(&after.and_utc()).next().map(|next| next.naive_utc())
+}
+
+pub fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
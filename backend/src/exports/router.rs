@@ -0,0 +1,453 @@
+pub mod router {
+    use std::sync::Arc;
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::{header, StatusCode}, Json, response::IntoResponse, extract::State, extract, middleware, Extension,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::{require_admin, AuthorizedUser},
+            storage::{LocalStorage, StorageBackend}
+        },
+        exports::{
+            service::service::ExportJobsTable as exportJobsTable,
+            model::{UpsertExportJob, parse_schedule, now, EXPORTABLE_RESOURCES},
+            scheduler::run_export_job
+        }
+    };
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn exports_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Export jobs are managed by administrators only, each seeing just the jobs they created
+        let admin_routes = Router::new()
+            .route("/admin/export-jobs", axum::routing::get(get_all_export_jobs_handler).post(create_export_job_handler))
+            .route("/admin/export-jobs/:export_job_id", axum::routing::get(read_export_job_handler)
+                .put(update_export_job_handler)
+                .delete(delete_export_job_handler))
+            .route("/admin/export-jobs/:export_job_id/run", axum::routing::post(run_export_job_handler))
+            .route("/admin/export-jobs/:export_job_id/artifact", axum::routing::get(download_artifact_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(admin_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/admin/export-jobs",
+        tag = "export-jobs",
+        operation_id = "get_all_export_jobs",
+        responses(
+            (status = 200, body = [ExportJob])
+        )
+    )]
+    pub async fn get_all_export_jobs_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match exportJobsTable::new(connection).get_all_of(user.id) {
+            Ok(export_jobs) => Ok((StatusCode::OK, Json(export_jobs))),
+            Err(err) => {
+                eprintln!("Error fetching all export jobs: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch export jobs"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/admin/export-jobs",
+        tag = "export-jobs",
+        operation_id = "create_export_job",
+        request_body = UpsertExportJob,
+        responses(
+            (status = 201, body = ExportJob),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn create_export_job_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Json(upsert_export_job): Json<UpsertExportJob>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+        let next_run_at = validate_export_job(&upsert_export_job)?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match exportJobsTable::new(connection).create(user.id, upsert_export_job, next_run_at) {
+            Ok(new_export_job) => Ok((StatusCode::CREATED, Json(new_export_job))),
+            Err(err) => {
+                eprintln!("Error creating export job: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create export job"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/admin/export-jobs/{export_job_id}",
+        tag = "export-jobs",
+        operation_id = "read_export_job",
+        params(("export_job_id" = i32, Path, description = "ID of the export job")),
+        responses(
+            (status = 200, body = ExportJob),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn read_export_job_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (export_job_id, ) = path.0;
+        let user = authorized.require_user()?;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match exportJobsTable::new(connection).get_of(export_job_id, user.id) {
+            Ok(Some(export_job)) => Ok((StatusCode::OK, Json(export_job))),
+            Ok(None) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "Export job not found"})))),
+            Err(err) => {
+                eprintln!("Error reading export job: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read export job"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        put,
+        path = "/admin/export-jobs/{export_job_id}",
+        tag = "export-jobs",
+        operation_id = "update_export_job",
+        params(("export_job_id" = i32, Path, description = "ID of the export job")),
+        request_body = UpsertExportJob,
+        responses(
+            (status = 200, body = ExportJob),
+            (status = 404, body = ApiError),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn update_export_job_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+        Json(upsert_export_job): Json<UpsertExportJob>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (export_job_id, ) = path.0;
+        let user = authorized.require_user()?;
+        let next_run_at = validate_export_job(&upsert_export_job)?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match exportJobsTable::new(connection).update(export_job_id, user.id, upsert_export_job, next_run_at) {
+            Ok(updated_export_job) => Ok((StatusCode::OK, Json(updated_export_job))),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Export job not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error updating export job: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update export job"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/admin/export-jobs/{export_job_id}",
+        tag = "export-jobs",
+        operation_id = "delete_export_job",
+        params(("export_job_id" = i32, Path, description = "ID of the export job")),
+        responses(
+            (status = 204),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn delete_export_job_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (export_job_id, ) = path.0;
+        let user = authorized.require_user()?;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match exportJobsTable::new(connection).delete(export_job_id, user.id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Export job not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error deleting export job: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to delete export job"}))))
+            }
+        }
+    }
+
+    // Runs the export immediately, outside of its schedule, and returns the job with the recorded outcome
+    #[utoipa::path(
+        post,
+        path = "/admin/export-jobs/{export_job_id}/run",
+        tag = "export-jobs",
+        operation_id = "run_export_job",
+        params(("export_job_id" = i32, Path, description = "ID of the export job")),
+        responses(
+            (status = 200, description = "The job with the outcome of the run", body = ExportJob),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn run_export_job_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (export_job_id, ) = path.0;
+        let user = authorized.require_user()?;
+        let export_job = {
+            let connection = shared_state.pool.get()
+                .expect("Failed to acquire connection from pool");
+
+            match exportJobsTable::new(connection).get_of(export_job_id, user.id) {
+                Ok(Some(export_job)) => export_job,
+                Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Export job not found"})))),
+                Err(err) => {
+                    eprintln!("Error reading export job: {:?}", err);
+                    return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read export job"}))));
+                }
+            }
+        };
+
+        match run_export_job(shared_state, Arc::new(LocalStorage::from_env()), export_job).await {
+            Ok(finished_export_job) => Ok((StatusCode::OK, Json(finished_export_job))),
+            Err(err) => {
+                eprintln!("Error running export job: {}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to run export job"}))))
+            }
+        }
+    }
+
+    // Serves the artifact produced by the most recent run
+    pub async fn download_artifact_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (export_job_id, ) = path.0;
+        let user = authorized.require_user()?;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        let artifact = match exportJobsTable::new(connection).get_of(export_job_id, user.id) {
+            Ok(Some(export_job)) => export_job.last_artifact,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Export job not found"})))),
+            Err(err) => {
+                eprintln!("Error reading export job: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read export job"}))));
+            }
+        };
+
+        let artifact = artifact.ok_or((StatusCode::NOT_FOUND, Json(json!({"error": "Export job has no artifact yet"}))))?;
+
+        match LocalStorage::from_env().get(&artifact) {
+            Ok(bytes) => Ok((StatusCode::OK, [(header::CONTENT_TYPE, "application/json")], bytes)),
+            Err(err) => {
+                eprintln!("Error reading artifact: {}", err);
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Artifact no longer available"}))))
+            }
+        }
+    }
+
+    // Validates resource and schedule, returning the first scheduled run on success
+    fn validate_export_job(upsert_export_job: &UpsertExportJob) -> Result<chrono::NaiveDateTime, (StatusCode, Json<Value>)> {
+        if !upsert_export_job.is_valid_resource() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({
+                "error": "Invalid input for field 'resource'",
+                "allowed": EXPORTABLE_RESOURCES
+            }))));
+        }
+
+        if let Err(err) = parse_schedule(&upsert_export_job.schedule) {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        upsert_export_job.next_run_after(now())
+            .ok_or((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Schedule never fires"}))))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use tower::ServiceExt;
+        use crate::{
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            exports::{
+                model::UpsertExportJob,
+                router::router::exports_route
+            },
+            users::model::UserRole
+        };
+
+        fn export_job_request(schedule: &str) -> UpsertExportJob {
+            UpsertExportJob {
+                name: "Weekly empires".to_string(),
+                resource: "empires".to_string(),
+                schedule: schedule.to_string(),
+                notify_email: Some("archivist@concord.gov".to_string()),
+                notify_webhook: None,
+                enabled: true,
+            }
+        }
+
+        #[tokio::test]
+        async fn post_export_jobs_returns_201_for_admin() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = exports_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "eksport.admin@concord.gov", UserRole::ADMIN);
+
+            // Create a request with a valid weekly schedule as payload
+            let request = Request::builder()
+                .uri("/admin/export-jobs")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::from(serde_json::to_string(&export_job_request("30 2 * * MON")).unwrap()))
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 201
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            // Extract body from response
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that the first run has been scheduled
+            assert!(response_json["next_run_at"].is_string());
+        }
+
+        #[tokio::test]
+        async fn post_export_jobs_returns_422_on_invalid_schedule() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = exports_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "eksport.slurv@concord.gov", UserRole::ADMIN);
+
+            let request = Request::builder()
+                .uri("/admin/export-jobs")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::from(serde_json::to_string(&export_job_request("every other tuesday")).unwrap()))
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 422
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        #[tokio::test]
+        async fn export_jobs_are_hidden_from_other_admins() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = exports_route(connection_pool.clone());
+
+            let owner_token = create_user_and_generate_token(connection_pool.clone(), "eksport.eier@concord.gov", UserRole::ADMIN).unwrap();
+            let other_token = create_user_and_generate_token(connection_pool, "eksport.nabo@concord.gov", UserRole::ADMIN).unwrap();
+
+            let request = Request::builder()
+                .uri("/admin/export-jobs")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", owner_token))
+                .body(Body::from(serde_json::to_string(&export_job_request("0 4 * * *")).unwrap()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let export_job_id = created["id"].as_i64().unwrap();
+
+            // Assert that another admin can neither list nor download the job
+            let request = Request::builder()
+                .uri("/admin/export-jobs")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", other_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert!(listed.as_array().unwrap().iter().all(|export_job| export_job["id"].as_i64() != Some(export_job_id)));
+
+            let request = Request::builder()
+                .uri(format!("/admin/export-jobs/{}/artifact", export_job_id))
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", other_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(error["error"], "Export job not found");
+        }
+
+        #[tokio::test]
+        async fn get_export_jobs_returns_401_for_editor() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = exports_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "eksport.redaktor@concord.gov", UserRole::EDITOR);
+
+            let request = Request::builder()
+                .uri("/admin/export-jobs")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::empty())
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 401
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+}
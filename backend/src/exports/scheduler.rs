@@ -0,0 +1,201 @@
+use std::{sync::Arc, time::Duration};
+use serde_json::{json, Value};
+use crate::{
+    common::{
+        db::ConnectionPool,
+        jobs::{run_blocking, spawn_periodic},
+        notify::{post_webhook, send_email},
+        storage::{LocalStorage, StorageBackend},
+    },
+    empires::service::service::EmpiresTable,
+    exports::{
+        model::{next_run_after, now, ExportJob},
+        service::service::ExportJobsTable,
+    },
+    locations::service::service::LocationsTable,
+    users::service::service::UsersTable,
+};
+
+// How often the scheduler looks for due export jobs - schedules are therefore minute-granular at best
+const SCHEDULER_PERIOD: Duration = Duration::from_secs(30);
+
+pub fn spawn_export_scheduler(pool: ConnectionPool) {
+    let storage: Arc<dyn StorageBackend> = Arc::new(LocalStorage::from_env());
+
+    spawn_periodic("export-scheduler", SCHEDULER_PERIOD, move || {
+        let pool = pool.clone();
+        let storage = storage.clone();
+        async move { run_due_exports(pool, storage).await }
+    });
+}
+
+pub async fn run_due_exports(pool: ConnectionPool, storage: Arc<dyn StorageBackend>) {
+    let due_jobs = run_blocking({
+        let pool = pool.clone();
+        move || {
+            let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+            ExportJobsTable::new(connection).get_due(now()).map_err(|err| format!("Failed to fetch due export jobs: {}", err))
+        }
+    }).await;
+
+    match due_jobs {
+        Ok(jobs) => {
+            for job in jobs {
+                if let Err(err) = run_export_job(pool.clone(), storage.clone(), job).await {
+                    eprintln!("Export job failed: {}", err);
+                }
+            }
+        }
+        Err(err) => eprintln!("{}", err),
+    }
+}
+
+// Runs a single export job: reschedules it, writes the artifact to storage, records the outcome and notifies
+pub async fn run_export_job(pool: ConnectionPool, storage: Arc<dyn StorageBackend>, job: ExportJob) -> Result<ExportJob, String> {
+    let finished_job = run_blocking(move || {
+        let ran_at = now();
+
+        // Reschedule before running so that a slow export is not picked up again by the next tick
+        let next_run_at = next_run_after(&job.schedule, ran_at)
+            .unwrap_or(ran_at + chrono::Duration::days(1));
+        {
+            let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+            ExportJobsTable::new(connection).set_next_run(job.id, next_run_at)
+                .map_err(|err| format!("Failed to reschedule export job {}: {}", job.id, err))?;
+        }
+
+        let outcome = export_resource(&pool, &job.resource).and_then(|payload| {
+            let key = format!("exports/{}/{}-{}.json", job.id, job.resource, ran_at.format("%Y%m%dT%H%M%S"));
+            let bytes = serde_json::to_vec_pretty(&payload).map_err(|err| format!("Failed to serialize export: {}", err))?;
+            storage.put(&key, &bytes)
+        });
+
+        let (artifact, error) = match outcome {
+            Ok(artifact) => (Some(artifact), None),
+            Err(err) => (None, Some(err)),
+        };
+
+        let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+        ExportJobsTable::new(connection).record_run(job.id, ran_at, artifact, error)
+            .map_err(|err| format!("Failed to record run of export job {}: {}", job.id, err))
+    }).await?;
+
+    notify(&finished_job).await;
+
+    Ok(finished_job)
+}
+
+pub fn export_resource(pool: &ConnectionPool, resource: &str) -> Result<Value, String> {
+    let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+
+    let payload = match resource {
+        // Password hashes never leave the database
+        "users" => UsersTable::new(connection).list().map(|users| {
+            users.into_iter()
+                .map(|user| json!({"id": user.id, "email": user.email, "fullname": user.fullname, "role": user.role}))
+                .collect::<Vec<Value>>()
+                .into()
+        }),
+        "locations" => LocationsTable::new(connection).get_all().map(|locations| json!(locations)),
+        // Exports are a record of everything, so archived empires are included
+        "empires" => EmpiresTable::new(connection).get_all(true, true).map(|empires| json!(empires)),
+        other => return Err(format!("Unknown export resource '{}'", other)),
+    };
+
+    payload.map_err(|err| format!("Failed to export {}: {}", resource, err))
+}
+
+async fn notify(job: &ExportJob) {
+    let payload = json!({
+        "event": if job.last_error.is_none() { "export.ready" } else { "export.failed" },
+        "export_job_id": job.id,
+        "name": job.name,
+        "resource": job.resource,
+        "artifact": job.last_artifact,
+        "error": job.last_error,
+        "ran_at": job.last_run_at
+    });
+
+    if let Some(email) = &job.notify_email {
+        let subject = match &job.last_error {
+            None => format!("Export '{}' is ready", job.name),
+            Some(_) => format!("Export '{}' failed", job.name),
+        };
+        let body = serde_json::to_string_pretty(&payload).unwrap_or_default();
+
+        if let Err(err) = send_email(email, &subject, &body).await {
+            eprintln!("Failed to notify {} about export job {}: {}", email, job.id, err);
+        }
+    }
+
+    if let Some(url) = &job.notify_webhook {
+        match post_webhook(url, &payload).await {
+            Ok(status) if (200..300).contains(&status) => {}
+            Ok(status) => eprintln!("Webhook {} for export job {} responded with status {}", url, job.id, status),
+            Err(err) => eprintln!("Failed to notify webhook {} for export job {}: {}", url, job.id, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, sync::Arc};
+    use crate::{
+        common::{
+            db::create_shared_connection_pool,
+            storage::{LocalStorage, StorageBackend},
+            util::load_environment_variable,
+        },
+        exports::{
+            model::{now, UpsertExportJob},
+            scheduler::run_export_job,
+            service::service::ExportJobsTable,
+        },
+        users::{model::UpsertUser, service::service::UsersTable},
+    };
+
+    #[tokio::test]
+    async fn run_export_job_stores_artifact_and_reschedules() {
+        let database_url = load_environment_variable("TEST_DB");
+        let connection_pool = create_shared_connection_pool(database_url, 1);
+        let storage = Arc::new(LocalStorage::new(env::temp_dir().join("axum_api_export_test")));
+
+        let request = UpsertExportJob {
+            name: "Nightly locations".to_string(),
+            resource: "locations".to_string(),
+            schedule: "0 3 * * *".to_string(),
+            notify_email: None,
+            notify_webhook: None,
+            enabled: true,
+        };
+
+        let admin = {
+            let connection = connection_pool.pool.get().expect("Failed to get connection");
+            UsersTable::new(connection).create(UpsertUser {
+                email: "eksport.planlegger@concord.gov".to_string(),
+                password: "StålGardinerFunkerFjell53".to_string(),
+                fullname: "Josef Stålhard".to_string(),
+                role: "ADMIN".to_string(),
+            }).expect("Create user failed")
+        };
+
+        let created_job = {
+            let connection = connection_pool.pool.get().expect("Failed to get connection");
+            ExportJobsTable::new(connection).create(admin.id, request, now()).expect("Create export job failed")
+        };
+
+        let finished_job = run_export_job(connection_pool, storage.clone(), created_job.clone())
+            .await
+            .expect("Run export job failed");
+
+        // Assert that the run was recorded and the job was pushed to its next scheduled run
+        assert!(finished_job.last_error.is_none());
+        assert!(finished_job.last_run_at.is_some());
+        assert!(finished_job.next_run_at > created_job.next_run_at);
+
+        // Assert that the artifact is a JSON array of locations
+        let artifact = storage.get(&finished_job.last_artifact.unwrap()).expect("Artifact missing");
+        let exported: serde_json::Value = serde_json::from_slice(&artifact).unwrap();
+        assert!(exported.is_array());
+    }
+}
@@ -0,0 +1,124 @@
+pub mod service {
+    use chrono::NaiveDateTime;
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::{
+        exports::model::{ExportJob, UpsertExportJob},
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct ExportJobsTable {
+        connection: PooledPg,
+    }
+
+    impl ExportJobsTable {
+        pub fn new(connection: PooledPg) -> ExportJobsTable {
+            ExportJobsTable { connection }
+        }
+
+        pub fn create(&mut self, created_by: i32, upsert_export_job: UpsertExportJob, next_run_at: NaiveDateTime) -> Result<ExportJob, diesel::result::Error> {
+            use schema::export_jobs;
+
+            diesel::insert_into(export_jobs::table)
+                .values((
+                    export_jobs::created_by.eq(created_by),
+                    export_jobs::name.eq(&upsert_export_job.name),
+                    export_jobs::resource.eq(&upsert_export_job.resource),
+                    export_jobs::schedule.eq(&upsert_export_job.schedule),
+                    export_jobs::notify_email.eq(&upsert_export_job.notify_email),
+                    export_jobs::notify_webhook.eq(&upsert_export_job.notify_webhook),
+                    export_jobs::enabled.eq(upsert_export_job.enabled),
+                    export_jobs::next_run_at.eq(next_run_at),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        // Jobs created by the given user
+        pub fn get_all_of(&mut self, created_by: i32) -> Result<Vec<ExportJob>, diesel::result::Error> {
+            use schema::export_jobs;
+
+            export_jobs::table
+                .filter(export_jobs::created_by.eq(created_by))
+                .order(export_jobs::id)
+                .load::<ExportJob>(&mut self.connection)
+        }
+
+        // The job, provided it was created by the given user
+        pub fn get_of(&mut self, export_job_id: i32, created_by: i32) -> Result<Option<ExportJob>, diesel::result::Error> {
+            use schema::export_jobs;
+
+            export_jobs::table
+                .find(export_job_id)
+                .filter(export_jobs::created_by.eq(created_by))
+                .get_result(&mut self.connection)
+                .optional()
+        }
+
+        // Enabled jobs whose next run is due at the given point in time
+        pub fn get_due(&mut self, now: NaiveDateTime) -> Result<Vec<ExportJob>, diesel::result::Error> {
+            use schema::export_jobs;
+
+            export_jobs::table
+                .filter(export_jobs::enabled.eq(true))
+                .filter(export_jobs::next_run_at.le(now))
+                .order(export_jobs::next_run_at)
+                .load::<ExportJob>(&mut self.connection)
+        }
+
+        // Fails with NotFound unless the job was created by the given user
+        pub fn update(&mut self, export_job_id: i32, created_by: i32, upsert_export_job: UpsertExportJob, next_run_at: NaiveDateTime) -> Result<ExportJob, diesel::result::Error> {
+            use schema::export_jobs;
+
+            diesel::update(export_jobs::table.find(export_job_id).filter(export_jobs::created_by.eq(created_by)))
+                .set((
+                    export_jobs::name.eq(&upsert_export_job.name),
+                    export_jobs::resource.eq(&upsert_export_job.resource),
+                    export_jobs::schedule.eq(&upsert_export_job.schedule),
+                    export_jobs::notify_email.eq(&upsert_export_job.notify_email),
+                    export_jobs::notify_webhook.eq(&upsert_export_job.notify_webhook),
+                    export_jobs::enabled.eq(upsert_export_job.enabled),
+                    export_jobs::next_run_at.eq(next_run_at),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        pub fn set_next_run(&mut self, export_job_id: i32, next_run_at: NaiveDateTime) -> Result<ExportJob, diesel::result::Error> {
+            use schema::export_jobs;
+
+            diesel::update(export_jobs::table.find(export_job_id))
+                .set(export_jobs::next_run_at.eq(next_run_at))
+                .get_result(&mut self.connection)
+        }
+
+        pub fn record_run(&mut self, export_job_id: i32, ran_at: NaiveDateTime, artifact: Option<String>, error: Option<String>) -> Result<ExportJob, diesel::result::Error> {
+            use schema::export_jobs;
+
+            diesel::update(export_jobs::table.find(export_job_id))
+                .set((
+                    export_jobs::last_run_at.eq(Some(ran_at)),
+                    export_jobs::last_artifact.eq(artifact),
+                    export_jobs::last_error.eq(error),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        // Fails with NotFound unless the job was created by the given user
+        pub fn delete(&mut self, export_job_id: i32, created_by: i32) -> Result<(), diesel::result::Error> {
+            use schema::export_jobs;
+
+            let deleted_rows = diesel::delete(export_jobs::table.find(export_job_id).filter(export_jobs::created_by.eq(created_by)))
+                .execute(&mut self.connection)?;
+
+            if deleted_rows == 0 {
+                Err(diesel::result::Error::NotFound)
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
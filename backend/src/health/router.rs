@@ -0,0 +1,120 @@
+pub mod router {
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::State, middleware, Extension,
+    };
+    use crate::common::{
+        db::ConnectionPool,
+        jobs::run_blocking,
+        middleware::require_admin,
+        warmup::HotCaches
+    };
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn health_route(shared_connection_pool: ConnectionPool, caches: HotCaches) -> Router {
+        // Polled by load balancers and orchestrators, which hold no credentials
+        let public_routes = Router::new()
+            .route("/health/ready", axum::routing::get(readiness_handler));
+
+        let admin_routes = Router::new()
+            .route("/admin/warmup", axum::routing::post(warmup_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(public_routes)
+            .merge(admin_routes)
+            .layer(Extension(caches))
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    // Reports ready only once the caches have been warmed up, so that traffic is not routed here before then
+    pub async fn readiness_handler(
+        Extension(caches): Extension<HotCaches>,
+    ) -> impl IntoResponse {
+        if caches.is_ready() {
+            (StatusCode::OK, Json(json!({"status": "ready"})))
+        } else {
+            (StatusCode::SERVICE_UNAVAILABLE, Json(json!({"status": "warming_up"})))
+        }
+    }
+
+    // Reloads the caches on demand, e.g. after bulk changes made directly in the database
+    pub async fn warmup_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(caches): Extension<HotCaches>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        match run_blocking(move || caches.warm_up(&shared_state)).await {
+            Ok(report) => Ok((StatusCode::OK, Json(report))),
+            Err(err) => {
+                eprintln!("Error warming up caches: {}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to warm up caches"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use tower::ServiceExt;
+        use crate::{
+            health::router::router::health_route,
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token,
+                warmup::HotCaches
+            },
+            locations::{
+                model::UpsertLocation,
+                service::service::LocationsTable
+            },
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn readiness_reports_unavailable_until_caches_are_warmed_up() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let caches = HotCaches::new();
+            let service = health_route(connection_pool.clone(), caches.clone());
+
+            LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Genesis".to_string(), area: "Yulai".to_string() })
+                .unwrap();
+
+            let ready = || Request::builder().uri("/health/ready").method("GET").body(Body::empty()).unwrap();
+
+            let response = service.clone().oneshot(ready()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "oppvarmer@concord.gov", UserRole::ADMIN).unwrap();
+            let request = Request::builder()
+                .uri("/admin/warmup")
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert!(report["locations"].as_u64().unwrap() >= 1);
+            assert_eq!(report["roles"], 4);
+
+            // Assert that the location list is served from the cache from now on
+            let cached = caches.locations.get_or_load(|| Err::<_, ()>(())).unwrap();
+            assert!(cached.iter().any(|location| location.area == "Yulai"));
+
+            let response = service.oneshot(ready()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
@@ -1,18 +1,55 @@
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde_derive::{Serialize, Deserialize};
+use utoipa::ToSchema;
 use crate::schema::locations;
 
-#[derive(Serialize, Debug, Clone, Queryable)]
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
 #[diesel(table_name = locations)]
 pub struct Location {
     pub id: i32,
     pub star_system: String,
     pub area: String,
+    pub created_at: NaiveDateTime,
+    // Position on the starmap, omitted for locations that have not been placed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<f64>,
 }
 
-#[derive(Debug, Clone, Insertable, Deserialize, Serialize)]
+#[derive(Debug, Clone, Insertable, Deserialize, Serialize, ToSchema)]
 #[diesel(table_name = locations)]
 pub struct UpsertLocation {
     pub star_system: String,
     pub area: String,
-}
\ No newline at end of file
+}
+
+impl From<Location> for UpsertLocation {
+    fn from(location: Location) -> UpsertLocation {
+        UpsertLocation { star_system: location.star_system, area: location.area }
+    }
+}
+
+// Length of the area column
+pub const MAX_AREA_LENGTH: usize = 100;
+
+// Starmap coordinates span this distance from the origin in either direction
+pub const MAX_COORDINATE: f64 = 10_000.0;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct LocationCoordinates {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl LocationCoordinates {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [("x", self.x), ("y", self.y)] {
+            if !value.is_finite() || value.abs() > MAX_COORDINATE {
+                return Err(format!("Field '{}' must be between -{} and {}", name, MAX_COORDINATE, MAX_COORDINATE));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,38 +1,54 @@
 pub mod router {
     use serde_json::{json, Value};
     use axum::{
-        Router, http::StatusCode, Json, response::IntoResponse, extract::State, extract, middleware,
+        Router, http::{header, HeaderMap, StatusCode}, Json, response::{IntoResponse, Response}, extract::{Query, State}, extract, middleware, Extension,
     };
     use crate::{
         common::{
+            budget::{collect_within_budget, SearchParams},
+            concurrency::{etag_of, if_match_satisfied, if_match_tags, Conditional},
+            cache::{invalidate_on_success, TtlCache},
             db::ConnectionPool,
-            middleware::{require_writer, require_reader, require_editor, require_admin}
+            middleware::{AuthorizedUser, require_writer, require_reader, require_editor, require_admin},
+            permissions::enforce_field_permissions,
+            warmup::HotCaches
         },
+        change_requests::router::router::submit_change_request,
+        users::model::UserRole,
+        webhooks::dispatch::dispatch_event,
         locations::{
             service::service::LocationsTable as locationsDB,
-            model::UpsertLocation
+            model::{Location, LocationCoordinates, UpsertLocation}
         },
     };
 
     // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
 
-    pub fn locations_route(shared_connection_pool: ConnectionPool) -> Router {
-        // Create route groups with appropriate middleware
+    pub fn locations_route(shared_connection_pool: ConnectionPool, caches: HotCaches) -> Router {
+        // Create route groups with appropriate middleware. Successful writes drop the cached location list.
         let create_routes = Router::new()
             .route("/locations", axum::routing::post(create_location_handler))
+            .route("/locations/:location_id/clone", axum::routing::post(clone_location_handler))
+            // Updates by WRITERs are submitted for review rather than applied
+            .route("/locations/:location_id", axum::routing::put(update_location_handler))
+            .layer(middleware::from_fn_with_state(caches.locations.clone(), invalidate_on_success::<Vec<Location>>))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_writer));
         
         let read_routes = Router::new()
             .route("/locations", axum::routing::get(get_all_locations_handler))
+            .route("/locations/search", axum::routing::get(search_locations_handler))
             .route("/locations/:location_id", axum::routing::get(read_location_handler))
+            .layer(Extension(caches.locations.clone()))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
         
         let update_routes = Router::new()
-            .route("/locations/:location_id", axum::routing::put(update_location_handler))
+            .route("/locations/:location_id/coordinates", axum::routing::put(update_location_coordinates_handler))
+            .layer(middleware::from_fn_with_state(caches.locations.clone(), invalidate_on_success::<Vec<Location>>))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_editor));
         
         let delete_routes = Router::new()
             .route("/locations/:location_id", axum::routing::delete(delete_location_handler))
+            .layer(middleware::from_fn_with_state(caches.locations, invalidate_on_success::<Vec<Location>>))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
 
         // Merge all route groups
@@ -46,13 +62,27 @@ pub mod router {
 
     // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
 
+    #[utoipa::path(
+        get,
+        path = "/locations",
+        tag = "locations",
+        operation_id = "get_all_locations",
+        responses(
+            (status = 200, body = [Location])
+        )
+    )]
     pub async fn get_all_locations_handler(
         State(shared_state): State<ConnectionPool>,
+        Extension(cache): Extension<TtlCache<Vec<Location>>>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-        let connection = shared_state.pool.get()
-            .expect("Failed to acquire connection from pool");
+        let locations = cache.get_or_load(|| {
+            let connection = shared_state.pool.get()
+                .expect("Failed to acquire connection from pool");
+
+            locationsDB::new(connection).get_all()
+        });
 
-        match locationsDB::new(connection).get_all() {
+        match locations {
             Ok(locations) => Ok((StatusCode::OK, Json(locations))),
             Err(err) => {
                 eprintln!("Error fetching all locations: {:?}", err);
@@ -61,6 +91,53 @@ pub mod router {
         }
     }
 
+    // Searches locations within the requested time budget, returning a partial page instead of timing out
+    #[utoipa::path(
+        get,
+        path = "/locations/search",
+        tag = "locations",
+        operation_id = "search_locations",
+        params(SearchParams),
+        responses(
+            (status = 200, body = LocationPage)
+        )
+    )]
+    pub async fn search_locations_handler(
+        State(shared_state): State<ConnectionPool>,
+        Query(params): Query<SearchParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut locations_db = locationsDB::new(connection);
+        let terms = params.terms();
+
+        let page = collect_within_budget(
+            &params.budget(),
+            params.cursor,
+            params.page_size(),
+            |after_id, batch_size, timeout_ms| locations_db.search_batch(&terms, after_id, batch_size, timeout_ms),
+            |location| location.id,
+        );
+
+        match page {
+            Ok(page) => Ok((StatusCode::OK, Json(page))),
+            Err(err) => {
+                eprintln!("Error searching locations: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to search locations"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/locations",
+        tag = "locations",
+        operation_id = "create_location",
+        request_body = UpsertLocation,
+        responses(
+            (status = 201, body = Location)
+        )
+    )]
     pub async fn create_location_handler(
         State(shared_state): State<ConnectionPool>,
         Json(upsert_location): Json<UpsertLocation>,
@@ -69,7 +146,10 @@ pub mod router {
             .expect("Failed to acquire connection from pool");
 
         match locationsDB::new(connection).create(upsert_location) {
-            Ok(new_location) => Ok((StatusCode::CREATED, Json(new_location))),
+            Ok(new_location) => {
+                dispatch_event(shared_state.clone(), "location.created", json!(new_location));
+                Ok((StatusCode::CREATED, Json(new_location)))
+            },
             Err(err) => {
                 eprintln!("Error creating location: {:?}", err);
                 Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create location"}))))
@@ -77,6 +157,51 @@ pub mod router {
         }
     }
 
+    #[utoipa::path(
+        post,
+        path = "/locations/{location_id}/clone",
+        tag = "locations",
+        operation_id = "clone_location",
+        params(("location_id" = i32, Path, description = "ID of the location")),
+        responses(
+            (status = 201, body = Location),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn clone_location_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (location_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match locationsDB::new(connection).clone_location(location_id) {
+            Ok(new_location) => {
+                dispatch_event(shared_state.clone(), "location.created", json!(new_location));
+                Ok((StatusCode::CREATED, Json(new_location)))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Location not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error cloning location: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to clone location"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/locations/{location_id}",
+        tag = "locations",
+        operation_id = "read_location",
+        params(("location_id" = i32, Path, description = "ID of the location")),
+        responses(
+            (status = 200, body = Location),
+            (status = 404, body = ApiError)
+        )
+    )]
     pub async fn read_location_handler(
         State(shared_state): State<ConnectionPool>,
         path: extract::Path<(i32, )>,
@@ -88,7 +213,7 @@ pub mod router {
         match locationsDB::new(connection).get(location_id) {
             Ok(location) => {
                 if let Some(location) = location {
-                    Ok((StatusCode::OK, Json(location)))
+                    Ok((StatusCode::OK, [(header::ETAG, etag_of(&location))], Json(location)))
                 } else {
                     Err((StatusCode::NOT_FOUND, Json(json!({"error": "Location not found"}))))
                 }
@@ -100,17 +225,69 @@ pub mod router {
         }
     }
 
+    // Updates the location. When an If-Match header is present the update is only applied if it matches the current
+    // ETag - otherwise 412 is returned along with the current location so that the client may resolve the conflict.
+    // Updates by WRITERs are not applied, but submitted for review as a change request instead.
+    #[utoipa::path(
+        put,
+        path = "/locations/{location_id}",
+        tag = "locations",
+        operation_id = "update_location",
+        params(("location_id" = i32, Path, description = "ID of the location"), ("If-Match" = Option<String>, Header, description = "ETag the update is conditional on")),
+        request_body = UpsertLocation,
+        responses(
+            (status = 200, body = Location),
+            (status = 202, description = "Submitted for review", body = ChangeRequest),
+            (status = 404, body = ApiError),
+            (status = 412, description = "The location was changed since - carries the current one", body = ApiError)
+        )
+    )]
     pub async fn update_location_handler(
         State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
         path: extract::Path<(i32, )>,
+        headers: HeaderMap,
         Json(upsert_location): Json<UpsertLocation>,
-    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    ) -> Result<Response, (StatusCode, Json<Value>)> {
         let (location_id, ) = path.0;
+        let current = match locationsDB::new(shared_state.pool.get().expect("Failed to acquire connection from pool")).get(location_id) {
+            Ok(Some(current)) => UpsertLocation::from(current),
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Location not found"})))),
+            Err(err) => {
+                eprintln!("Error reading location: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update location"}))));
+            }
+        };
+        enforce_field_permissions(&authorized, "locations", &current, &upsert_location)?;
+
+        if !authorized.has_role(&UserRole::EDITOR) {
+            return submit_change_request(&shared_state, &authorized, "location", location_id, &current, &upsert_location);
+        }
+
+        let expected_tags = if_match_tags(&headers);
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
-        match locationsDB::new(connection).update(location_id, upsert_location) {
-            Ok(updated_location) => Ok((StatusCode::OK, Json(updated_location))),
+        let mut locations_db = locationsDB::new(connection);
+        let result = match expected_tags {
+            Some(expected_tags) => locations_db.update_where(location_id, upsert_location, |current| {
+                if_match_satisfied(&expected_tags, &etag_of(current))
+            }),
+            None => locations_db.update(location_id, upsert_location).map(Conditional::Applied),
+        };
+
+        match result {
+            Ok(Conditional::Applied(updated_location)) => {
+                dispatch_event(shared_state.clone(), "location.updated", json!(updated_location));
+                Ok((StatusCode::OK, [(header::ETAG, etag_of(&updated_location))], Json(updated_location)).into_response())
+            },
+            Ok(Conditional::PreconditionFailed(current)) => {
+                Ok((
+                    StatusCode::PRECONDITION_FAILED,
+                    [(header::ETAG, etag_of(&current))],
+                    Json(json!({"error": "Location has been modified by someone else", "current": current})),
+                ).into_response())
+            },
             Err(diesel::result::Error::NotFound) => {
                 Err((StatusCode::NOT_FOUND, Json(json!({"error": "Location not found"}))))
             },
@@ -121,6 +298,57 @@ pub mod router {
         }
     }
 
+    #[utoipa::path(
+        put,
+        path = "/locations/{location_id}/coordinates",
+        tag = "locations",
+        operation_id = "update_location_coordinates",
+        params(("location_id" = i32, Path, description = "ID of the location")),
+        request_body = LocationCoordinates,
+        responses(
+            (status = 200, body = Location),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn update_location_coordinates_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Json(coordinates): Json<LocationCoordinates>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (location_id, ) = path.0;
+
+        if let Err(err) = coordinates.validate() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match locationsDB::new(connection).set_coordinates(location_id, coordinates) {
+            Ok(updated_location) => {
+                dispatch_event(shared_state.clone(), "location.updated", json!(updated_location));
+                Ok((StatusCode::OK, [(header::ETAG, etag_of(&updated_location))], Json(updated_location)))
+            },
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Location not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error updating location coordinates: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update location coordinates"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/locations/{location_id}",
+        tag = "locations",
+        operation_id = "delete_location",
+        params(("location_id" = i32, Path, description = "ID of the location")),
+        responses(
+            (status = 204)
+        )
+    )]
     pub async fn delete_location_handler(
         State(shared_state): State<ConnectionPool>,
         path: extract::Path<(i32, )>,
@@ -130,7 +358,10 @@ pub mod router {
             .expect("Failed to acquire connection from pool");
 
         match locationsDB::new(connection).delete(location_id) {
-            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Ok(_) => {
+                dispatch_event(shared_state.clone(), "location.deleted", json!({"id": location_id}));
+                Ok((StatusCode::NO_CONTENT, ()))
+            },
             Err(err) => {
                 eprintln!("Error deleting location: {:?}", err);
                 Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to delete location"}))))
@@ -149,8 +380,10 @@ pub mod router {
         use crate::{
             common::{
                 db::create_shared_connection_pool,
+                concurrency::etag_of,
                 util::load_environment_variable,
-                security::hash_password
+                security::hash_password,
+                warmup::HotCaches
             },
             locations::{
                 model::UpsertLocation,
@@ -163,7 +396,7 @@ pub mod router {
             locations_route
         };
         use crate::common::db::ConnectionPool;
-        use crate::common::security::generate_token;
+        use crate::common::security::generate_session_token;
         use crate::users::model::UserRole;
 
         // Helper method utilized to create user with a specific role and return the associated bearer token in one line of code
@@ -187,14 +420,14 @@ pub mod router {
             };
 
             // Generate the bearer token
-            generate_token(&create_user_result.unwrap())
+            generate_session_token(&create_user_result.unwrap(), None)
         }
 
         #[tokio::test]
         async fn post_locations_returns_201_for_authorized_user_with_write_access() {
             let database_url = load_environment_variable("TEST_DB");
             let connection_pool = create_shared_connection_pool(database_url, 1);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             // Create user with role WRITER and generate associated bearer token
             let bearer_token = create_user_and_generate_token(connection_pool, "stål.hard.russer@ugreit.ru", UserRole::WRITER);
@@ -227,7 +460,7 @@ pub mod router {
         async fn post_locations_returns_401_for_unauthorized_user_without_write_access() {
             let database_url = load_environment_variable("TEST_DB");
             let connection_pool = create_shared_connection_pool(database_url, 1);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             // Create user with role READER and generate associated bearer token
             let bearer_token = create_user_and_generate_token(connection_pool, "myk.og.ekkel.russer@put.in", UserRole::READER);
@@ -262,7 +495,7 @@ pub mod router {
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             // Create user with role WRITER and generate associated bearer token
             let bearer_token = create_user_and_generate_token(connection_pool, "dagfinnkuk@blåfjelletsvenner.no", UserRole::EDITOR);
@@ -310,23 +543,86 @@ pub mod router {
             let expected_response = json!({
                 "id": created_location.id,
                 "area": updated_request_body.area,
-                "star_system": updated_request_body.star_system
+                "star_system": updated_request_body.star_system,
+                "created_at": created_location.created_at
             });
 
             // Assert equality
             assert_eq!(response_json, expected_response);
         }
 
+        #[tokio::test]
+        async fn put_locations_returns_412_with_current_location_on_stale_etag() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 2);
+            let connection = connection_pool.pool.get().expect("Failed to get connection");
+            let mut location_db = LocationsTable::new(connection);
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "samtidig@redigering.no", UserRole::EDITOR);
+
+            let request_body = UpsertLocation {
+                star_system: "Fountain".to_string(),
+                area: "The Serpent's Lair".to_string(),
+            };
+
+            // Create a new location and record its ETag before someone else changes it
+            let created_location = location_db.create(request_body.clone()).expect("Create location failed");
+            let stale_etag = etag_of(&created_location);
+
+            let concurrent_update = UpsertLocation {
+                star_system: "Fountain".to_string(),
+                area: "The Crimson Expanse".to_string(),
+            };
+            location_db.update(created_location.id, concurrent_update.clone()).expect("Update location failed");
+
+            let updated_request_body = UpsertLocation {
+                star_system: "Kador".to_string(),
+                area: "The Serpent's Lair".to_string(),
+            };
+
+            // Create a request conditioned on the stale ETag
+            let request = Request::builder()
+                .uri(format!("/locations/{}", created_location.id))
+                .method("PUT")
+                .header("content-type", "application/json")
+                .header("If-Match", stale_etag)
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap())) // Add the bearer token
+                .body(Body::from(serde_json::to_string(&updated_request_body).unwrap()))
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 412
+            assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+            // Extract body from response
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that the concurrent update is returned as the current state
+            assert_eq!(response_json["current"], json!({
+                "id": created_location.id,
+                "area": concurrent_update.area,
+                "star_system": concurrent_update.star_system,
+                "created_at": created_location.created_at
+            }));
+        }
+
         #[tokio::test]
         async fn put_locations_returns_401_for_unauthorized_user_without_edit_access() {
             let database_url = load_environment_variable("TEST_DB");
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
-            // Create user with role WRITER and generate associated bearer token
-            let bearer_token = create_user_and_generate_token(connection_pool, "necromancer@gpf.no", UserRole::WRITER);
+            // Create user with role READER and generate associated bearer token - edits of WRITERs are submitted for review instead
+            let bearer_token = create_user_and_generate_token(connection_pool, "necromancer@gpf.no", UserRole::READER);
 
             let request_body = UpsertLocation {
                 star_system: "Fountain".to_string(),
@@ -364,13 +660,96 @@ pub mod router {
             assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         }
 
+        #[tokio::test]
+        async fn put_location_coordinates_places_location_within_bounds() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
+
+            let created_location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Syndicate".to_string(), area: "Poitot".to_string() })
+                .expect("Create location failed");
+
+            // Create user with role EDITOR and generate associated bearer token
+            let bearer_token = create_user_and_generate_token(connection_pool, "kartograf@concord.gov", UserRole::EDITOR).unwrap();
+
+            // Assert that coordinates outside of the starmap are rejected
+            let request = Request::builder()
+                .uri(format!("/locations/{}/coordinates", created_location.id))
+                .method("PUT")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(json!({"x": 10001.0, "y": 0.0}).to_string()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let request = Request::builder()
+                .uri(format!("/locations/{}/coordinates", created_location.id))
+                .method("PUT")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(json!({"x": -120.5, "y": 64.0}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let location: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(location["x"], -120.5);
+            assert_eq!(location["y"], 64.0);
+        }
+
+        #[tokio::test]
+        async fn clone_location_returns_201_with_copy_of_location() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
+
+            let created_location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Sinq Laison".to_string(), area: "Dodixie".to_string() })
+                .expect("Create location failed");
+
+            // Create user with role WRITER and generate associated bearer token
+            let bearer_token = create_user_and_generate_token(connection_pool, "kloning@concord.gov", UserRole::WRITER).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/locations/{}/clone", created_location.id))
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let copy: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_ne!(copy["id"], created_location.id);
+            assert_eq!(copy["star_system"], "Sinq Laison");
+            assert_eq!(copy["area"], "Dodixie (copy)");
+
+            // Assert that cloning a non-existing location yields 404
+            let request = Request::builder()
+                .uri("/locations/-1/clone")
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
         #[tokio::test]
         async fn get_locations_returns_200_for_authorized_user_with_read_access() {
             let database_url = load_environment_variable("TEST_DB");
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             let bearer_token = create_user_and_generate_token(connection_pool, "duvetdet@gjerrigknark.no", UserRole::READER);
 
@@ -407,7 +786,8 @@ pub mod router {
             let expected_response = json!({
                 "id": created_location.id,
                 "area": request_body.area,
-                "star_system": request_body.star_system
+                "star_system": request_body.star_system,
+                "created_at": created_location.created_at
             });
 
             // Assert equality
@@ -420,7 +800,7 @@ pub mod router {
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             let bearer_token = create_user_and_generate_token(connection_pool, "kokefaktura@woodworm.org", UserRole::WRITER);
 
@@ -457,7 +837,8 @@ pub mod router {
             let expected_response = json!({
                 "id": created_location.id,
                 "area": request_body.area,
-                "star_system": request_body.star_system
+                "star_system": request_body.star_system,
+                "created_at": created_location.created_at
             });
 
             // Assert equality
@@ -470,7 +851,7 @@ pub mod router {
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             let bearer_token = create_user_and_generate_token(connection_pool, "igor.invalidus@bogdanov.fr", UserRole::INVALID);
 
@@ -500,11 +881,56 @@ pub mod router {
             assert_eq!(response.status(), StatusCode::UNAUTHORIZED)
         }
 
+        #[tokio::test]
+        async fn search_locations_returns_page_with_cursor_when_more_rows_match() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 2);
+            let connection = connection_pool.pool.get().expect("Failed to get connection");
+            let mut location_db = LocationsTable::new(connection);
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "sokemotor@kvasir.no", UserRole::READER);
+
+            // Create two locations sharing a distinctive area name
+            for star_system in ["Vega", "Deneb"] {
+                location_db.create(UpsertLocation {
+                    star_system: star_system.to_string(),
+                    area: "The Whispering Nebula".to_string(),
+                }).expect("Create location failed");
+            }
+
+            // Ask for one result at a time
+            let request = Request::builder()
+                .uri("/locations/search?q=whispering%20nebula&limit=1")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap())) // Add the bearer token
+                .body(Body::empty())
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 200
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Extract body from response
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that a complete page was returned along with a cursor to the next one
+            assert_eq!(response_json["items"].as_array().unwrap().len(), 1);
+            assert_eq!(response_json["partial"], json!(false));
+            assert_eq!(response_json["next_cursor"], response_json["items"][0]["id"]);
+        }
+
         #[tokio::test]
         async fn get_locations_returns_404_on_non_existing_id() {
             let database_url = load_environment_variable("TEST_DB");
             let connection_pool = create_shared_connection_pool(database_url, 2);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             let bearer_token = create_user_and_generate_token(connection_pool, "birdman@ifi.uio.no", UserRole::READER);
 
@@ -532,7 +958,7 @@ pub mod router {
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             let bearer_token = create_user_and_generate_token(connection_pool,"you.know.your.judo.well@succulentmail.gb", UserRole::ADMIN);
 
@@ -580,7 +1006,7 @@ pub mod router {
             let connection_pool = create_shared_connection_pool(database_url, 2);
             let connection = connection_pool.pool.get().expect("Failed to get connection");
             let mut location_db = LocationsTable::new(connection);
-            let service = locations_route(connection_pool.clone());
+            let service = locations_route(connection_pool.clone(), HotCaches::new());
 
             let bearer_token = create_user_and_generate_token(connection_pool,"donttouchmys@p.succulentor.gb", UserRole::EDITOR);
 
@@ -5,7 +5,12 @@ pub mod service {
         r2d2::{ConnectionManager, PooledConnection},
     };
     use crate::{
-        locations::model::{Location, UpsertLocation},
+        common::{
+            budget::{like_pattern, set_statement_timeout},
+            concurrency::Conditional,
+            util::copy_name,
+        },
+        locations::model::{Location, LocationCoordinates, UpsertLocation, MAX_AREA_LENGTH},
         schema
     };
 
@@ -34,6 +39,24 @@ pub mod service {
             Ok(new_location)
         }
 
+        // Creates a copy of the location, its area marked with a "(copy)" suffix
+        pub fn clone_location(&mut self, location_id: i32) -> Result<Location, diesel::result::Error> {
+            use schema::locations;
+
+            let original = locations::table
+                .find(location_id)
+                .get_result::<Location>(&mut self.connection)?;
+
+            diesel::insert_into(locations::table)
+                .values((
+                    locations::star_system.eq(&original.star_system),
+                    locations::area.eq(copy_name(&original.area, MAX_AREA_LENGTH)),
+                    locations::x.eq(original.x),
+                    locations::y.eq(original.y),
+                ))
+                .get_result(&mut self.connection)
+        }
+
         pub fn get_all(&mut self) -> Result<Vec<Location>, diesel::result::Error> {
             use schema::locations;
 
@@ -53,6 +76,33 @@ pub mod service {
             Ok(location)
         }
 
+        // Fetches the next batch of locations after 'after_id' where every term matches the star system or area.
+        // The batch is aborted by the database once 'timeout_ms' has passed.
+        pub fn search_batch(&mut self, terms: &[String], after_id: Option<i32>, batch_size: i64, timeout_ms: u64) -> Result<Vec<Location>, diesel::result::Error> {
+            use schema::locations;
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                set_statement_timeout(connection, timeout_ms)?;
+
+                let mut query = locations::table
+                    .order(locations::id)
+                    .limit(batch_size)
+                    .into_boxed();
+
+                if let Some(after_id) = after_id {
+                    query = query.filter(locations::id.gt(after_id));
+                }
+
+                for term in terms {
+                    let pattern = like_pattern(term);
+                    query = query.filter(locations::star_system.ilike(pattern.clone()).or(locations::area.ilike(pattern)));
+                }
+
+                query.load::<Location>(connection)
+            })
+        }
+
         pub fn update(&mut self, location_id: i32, upsert_location: UpsertLocation) -> Result<Location, diesel::result::Error> {
             use schema::locations;
 
@@ -76,6 +126,49 @@ pub mod service {
             }
         }
 
+        // Places the location on the starmap
+        pub fn set_coordinates(&mut self, location_id: i32, coordinates: LocationCoordinates) -> Result<Location, diesel::result::Error> {
+            use schema::locations;
+
+            diesel::update(locations::table.find(location_id))
+                .set((
+                    locations::x.eq(Some(coordinates.x)),
+                    locations::y.eq(Some(coordinates.y)),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        // Updates the location only if its current state satisfies 'precondition', which is evaluated while holding
+        // a row lock so that concurrent writers cannot slip in between the check and the update
+        pub fn update_where<P>(&mut self, location_id: i32, upsert_location: UpsertLocation, precondition: P,
+        ) -> Result<Conditional<Location>, diesel::result::Error>
+        where
+            P: FnOnce(&Location) -> bool,
+        {
+            use schema::locations;
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                let current = locations::table
+                    .find(location_id)
+                    .for_update()
+                    .get_result::<Location>(connection)?;
+
+                if !precondition(&current) {
+                    return Ok(Conditional::PreconditionFailed(current));
+                }
+
+                let updated_location = diesel::update(locations::table.find(location_id))
+                    .set((
+                        locations::star_system.eq(&upsert_location.star_system),
+                        locations::area.eq(&upsert_location.area)
+                    ))
+                    .get_result(connection)?;
+
+                Ok(Conditional::Applied(updated_location))
+            })
+        }
+
         pub fn delete(&mut self, location_id: i32) -> Result<(), diesel::result::Error> {
             use schema::locations;
 
@@ -3,30 +3,111 @@ use crate:: {
     locations::router::router::locations_route,
     empires::router::router::empires_route,
     users::router::router::users_route,
+    exports::{router::router::exports_route, scheduler::spawn_export_scheduler},
+    telemetry::{router::router::telemetry_route, retention::spawn_telemetry_retention},
+    webhooks::router::router::webhooks_route,
+    api_keys::router::router::api_keys_route,
+    sessions::router::router::sessions_route,
+    two_factor::router::router::two_factor_route,
+    stats::router::router::stats_route,
+    map::router::router::map_route,
+    ships::router::router::ships_route,
+    templates::router::router::templates_route,
+    change_requests::router::router::change_requests_route,
+    health::router::router::health_route,
+    scim::router::router::scim_route,
+    well_known::router::router::well_known_route,
     common::util::load_environment_variable,
+    common::warmup::{spawn_cache_warmup, HotCaches},
+    common::normalize::{normalize_route, RouteNormalization},
+    common::openapi::openapi_route,
+    common::rate_limit::{rate_limit, RateLimiter},
+    cli::{args::{parse_args, OutputFormat}, commands::{report_usage_error, run_command}},
 };
+use axum::{http::{header, HeaderName}, middleware, ServiceExt};
+use std::net::SocketAddr;
+use tower::Layer;
 use tower_http::cors::{CorsLayer, Any};
 
 mod locations;mod users;mod schema;mod common;
 mod empires;
+mod exports;
+mod telemetry;
+mod webhooks;
+mod api_keys;
+mod sessions;
+mod two_factor;
+mod stats;
+mod map;
+mod ships;
+mod templates;
+mod change_requests;
+mod health;
+mod scim;
+mod well_known;
+mod cli;
 
 #[tokio::main]
 async fn main() {
+    // Subcommands such as 'migrate' run and exit, while no arguments at all serve the API
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match parse_args(&args) {
+        Ok(Some(invocation)) => std::process::exit(run_command(&invocation)),
+        Ok(None) => {}
+        Err(err) => {
+            let format = if args.windows(2).any(|pair| pair == ["--format", "json"]) { OutputFormat::Json } else { OutputFormat::Text };
+            std::process::exit(report_usage_error(err, format));
+        }
+    }
+
     let database_url = load_environment_variable("DEV_DB");
     let shared_connection_pool = create_shared_connection_pool(database_url, 1);
+    let caches = HotCaches::new();
 
     // Configure CORS
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([
+            header::ETAG,
+            header::RETRY_AFTER,
+            HeaderName::from_static("x-ratelimit-limit"),
+            HeaderName::from_static("x-ratelimit-remaining"),
+        ]);
+
+    let app = users_route(shared_connection_pool.clone())
+        .nest("/", locations_route(shared_connection_pool.clone(), caches.clone()))
+        .nest("/", empires_route(shared_connection_pool.clone()))
+        .nest("/", exports_route(shared_connection_pool.clone()))
+        .nest("/", telemetry_route(shared_connection_pool.clone()))
+        .nest("/", webhooks_route(shared_connection_pool.clone()))
+        .nest("/", api_keys_route(shared_connection_pool.clone()))
+        .nest("/", sessions_route(shared_connection_pool.clone()))
+        .nest("/", two_factor_route(shared_connection_pool.clone()))
+        .nest("/", stats_route(shared_connection_pool.clone()))
+        .nest("/", map_route(shared_connection_pool.clone(), caches.clone()))
+        .nest("/", ships_route(shared_connection_pool.clone()))
+        .nest("/", templates_route(shared_connection_pool.clone()))
+        .nest("/", change_requests_route(shared_connection_pool.clone(), caches.clone()))
+        .nest("/", health_route(shared_connection_pool.clone(), caches.clone()))
+        .nest("/", scim_route(shared_connection_pool.clone()))
+        .nest("/", well_known_route())
+        .nest("/", openapi_route())
+        // Every route reports the remaining budget, which the frontend paces its requests by
+        .layer(middleware::from_fn_with_state(RateLimiter::api_from_env(), rate_limit))
+        .layer(cors);
+
+    // Background jobs. The readiness endpoint reports unavailable until the caches are warmed up.
+    spawn_cache_warmup(shared_connection_pool.clone(), caches);
+    spawn_export_scheduler(shared_connection_pool.clone());
+    spawn_telemetry_retention(shared_connection_pool.clone());
+
+    // Path normalization has to wrap the router, as middleware added via Router::layer runs after routing
+    let app = middleware::from_fn_with_state(RouteNormalization::from_env(), normalize_route).layer(app);
 
     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
-        .serve(users_route(shared_connection_pool.clone())
-            .nest("/", locations_route(shared_connection_pool.clone()))
-            .nest("/", empires_route(shared_connection_pool.clone()))
-            .layer(cors)
-                .into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 }
@@ -0,0 +1,61 @@
+use diesel::{prelude::*, sql_types::{BigInt, Double, Integer, Varchar}};
+use serde_derive::Serialize;
+use utoipa::ToSchema;
+
+// A location placed on the starmap, with the empires and players currently found there
+#[derive(Serialize, Debug, Clone, QueryableByName, ToSchema)]
+pub struct MapLocation {
+    #[diesel(sql_type = Integer)]
+    pub id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub star_system: String,
+    #[diesel(sql_type = Varchar)]
+    pub area: String,
+    #[diesel(sql_type = Double)]
+    pub x: f64,
+    #[diesel(sql_type = Double)]
+    pub y: f64,
+    #[diesel(sql_type = BigInt)]
+    pub empire_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub player_count: i64,
+}
+
+// Locations of a region - the star system they belong to - with the counts summed over all of them
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct MapRegion {
+    pub name: String,
+    pub empire_count: i64,
+    pub player_count: i64,
+    pub locations: Vec<MapLocation>,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct StarMap {
+    pub regions: Vec<MapRegion>,
+}
+
+impl StarMap {
+    // Groups locations ordered by region into one entry per region
+    pub fn from_locations(locations: Vec<MapLocation>) -> StarMap {
+        let mut regions: Vec<MapRegion> = Vec::new();
+
+        for location in locations {
+            match regions.last_mut() {
+                Some(region) if region.name == location.star_system => {
+                    region.empire_count += location.empire_count;
+                    region.player_count += location.player_count;
+                    region.locations.push(location);
+                }
+                _ => regions.push(MapRegion {
+                    name: location.star_system.clone(),
+                    empire_count: location.empire_count,
+                    player_count: location.player_count,
+                    locations: vec![location],
+                }),
+            }
+        }
+
+        StarMap { regions }
+    }
+}
@@ -0,0 +1,143 @@
+pub mod router {
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::{header, StatusCode}, Json, response::IntoResponse, extract::State, middleware, Extension,
+    };
+    use crate::{
+        common::{cache::TtlCache, db::ConnectionPool, middleware::require_reader, warmup::HotCaches},
+        map::{
+            service::service::MapTable as mapTable,
+            model::StarMap
+        }
+    };
+
+    // The map is read far more often than locations, empires and players change, so it may lag behind by this much
+    pub const MAP_CACHE_SECONDS: u64 = 10;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn map_route(shared_connection_pool: ConnectionPool, caches: HotCaches) -> Router {
+        let reader_routes = Router::new()
+            .route("/map", axum::routing::get(get_map_handler))
+            .layer(Extension(caches.star_map))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
+
+        Router::new()
+            .merge(reader_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/map",
+        tag = "map",
+        operation_id = "get_map",
+        responses(
+            (status = 200, body = StarMap)
+        )
+    )]
+    pub async fn get_map_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(cache): Extension<TtlCache<StarMap>>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let star_map = cache.get_or_load(|| {
+            let connection = shared_state.pool.get()
+                .expect("Failed to acquire connection from pool");
+
+            mapTable::new(connection).locations().map(StarMap::from_locations)
+        });
+
+        match star_map {
+            Ok(star_map) => Ok((
+                StatusCode::OK,
+                [(header::CACHE_CONTROL, format!("private, max-age={}", MAP_CACHE_SECONDS))],
+                Json(star_map),
+            )),
+            Err(err) => {
+                eprintln!("Error fetching map: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch map"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use tower::ServiceExt;
+        use crate::{
+            map::router::router::map_route,
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token,
+                warmup::HotCaches
+            },
+            empires::{
+                model::UpsertEmpire,
+                service::service::EmpiresTable
+            },
+            locations::{
+                model::{LocationCoordinates, UpsertLocation},
+                service::service::LocationsTable
+            },
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn get_map_groups_placed_locations_by_region() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = map_route(connection_pool.clone(), HotCaches::new());
+
+            let (placed, unplaced) = {
+                let mut locations = LocationsTable::new(connection_pool.pool.get().unwrap());
+                let placed = locations.create(UpsertLocation { star_system: "Placid".to_string(), area: "Stacmon".to_string() }).unwrap();
+                let placed = locations.set_coordinates(placed.id, LocationCoordinates { x: 42.0, y: -17.5 }).unwrap();
+                let unplaced = locations.create(UpsertLocation { star_system: "Placid".to_string(), area: "Ostingele".to_string() }).unwrap();
+                (placed, unplaced)
+            };
+
+            EmpiresTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertEmpire {
+                    name: "Federal Navy Academy".to_string(),
+                    slogan: "Per aspera".to_string(),
+                    location_id: placed.id,
+                    description: "Trains the pilots of the Federation".to_string(),
+                }, true)
+                .unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "kart.leser@concord.gov", UserRole::READER).unwrap();
+
+            let request = Request::builder()
+                .uri("/map")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let map: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let region = map["regions"].as_array().unwrap().iter().find(|region| region["name"] == "Placid").unwrap();
+            let locations = region["locations"].as_array().unwrap();
+
+            // Assert that the placed location is included with its coordinates and counts
+            let entry = locations.iter().find(|entry| entry["id"] == placed.id).unwrap();
+            assert_eq!(entry["x"], 42.0);
+            assert_eq!(entry["y"], -17.5);
+            assert_eq!(entry["empire_count"], 1);
+            assert_eq!(entry["player_count"], 0);
+            assert!(region["empire_count"].as_i64().unwrap() >= 1);
+
+            // Assert that locations without coordinates are left off the map
+            assert!(locations.iter().all(|entry| entry["id"] != unplaced.id));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+pub mod service {
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::map::model::MapLocation;
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct MapTable {
+        connection: PooledPg,
+    }
+
+    impl MapTable {
+        pub fn new(connection: PooledPg) -> MapTable {
+            MapTable { connection }
+        }
+
+        // Every location with coordinates along with the number of published, unarchived empires and players located there, ordered by region
+        pub fn locations(&mut self) -> Result<Vec<MapLocation>, diesel::result::Error> {
+            diesel::sql_query(
+                "SELECT locations.id, locations.star_system, locations.area, locations.x, locations.y, \
+                        COALESCE(empire_counts.count, 0) AS empire_count, \
+                        COALESCE(player_counts.count, 0) AS player_count \
+                 FROM locations \
+                 LEFT JOIN (SELECT location_id, COUNT(*) AS count FROM empires WHERE NOT archived AND published_at <= now() AT TIME ZONE 'UTC' GROUP BY location_id) AS empire_counts \
+                     ON empire_counts.location_id = locations.id \
+                 LEFT JOIN (SELECT location_id, COUNT(*) AS count FROM players GROUP BY location_id) AS player_counts \
+                     ON player_counts.location_id = locations.id \
+                 WHERE locations.x IS NOT NULL AND locations.y IS NOT NULL \
+                 ORDER BY locations.star_system, locations.id"
+            )
+                .load::<MapLocation>(&mut self.connection)
+        }
+    }
+}
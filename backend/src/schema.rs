@@ -1,5 +1,38 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        #[max_length = 10]
+        role -> Varchar,
+        created_at -> Timestamp,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    change_requests (id) {
+        id -> Int4,
+        #[max_length = 20]
+        resource -> Varchar,
+        record_id -> Int4,
+        changes -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        requested_by -> Nullable<Int4>,
+        reviewed_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+        reviewed_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     empires (id) {
         id -> Int4,
@@ -9,6 +42,32 @@ diesel::table! {
         slogan -> Varchar,
         location_id -> Int4,
         description -> Text,
+        created_at -> Timestamp,
+        archived -> Bool,
+        published_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    export_jobs (id) {
+        id -> Int4,
+        created_by -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 20]
+        resource -> Varchar,
+        #[max_length = 100]
+        schedule -> Varchar,
+        #[max_length = 100]
+        notify_email -> Nullable<Varchar>,
+        #[max_length = 255]
+        notify_webhook -> Nullable<Varchar>,
+        enabled -> Bool,
+        next_run_at -> Timestamp,
+        last_run_at -> Nullable<Timestamp>,
+        #[max_length = 255]
+        last_artifact -> Nullable<Varchar>,
+        last_error -> Nullable<Text>,
     }
 }
 
@@ -19,6 +78,9 @@ diesel::table! {
         star_system -> Varchar,
         #[max_length = 100]
         area -> Varchar,
+        created_at -> Timestamp,
+        x -> Nullable<Float8>,
+        y -> Nullable<Float8>,
     }
 }
 
@@ -31,6 +93,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    recovery_codes (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        code_hash -> Varchar,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 64]
+        token_id -> Varchar,
+        #[max_length = 255]
+        user_agent -> Nullable<Varchar>,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        last_seen_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        impersonated_by -> Nullable<Int4>,
+    }
+}
+
 diesel::table! {
     ships (id) {
         id -> Int4,
@@ -43,6 +132,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    telemetry_events (id) {
+        id -> Int4,
+        #[max_length = 20]
+        kind -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 10]
+        method -> Nullable<Varchar>,
+        status -> Nullable<Int4>,
+        duration_ms -> Nullable<Float8>,
+        message -> Nullable<Text>,
+        received_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    templates (id) {
+        id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 20]
+        resource -> Varchar,
+        fields -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    totp_credentials (user_id) {
+        user_id -> Int4,
+        #[max_length = 40]
+        secret -> Varchar,
+        confirmed_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Int8>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Int4,
@@ -54,19 +182,61 @@ diesel::table! {
         fullname -> Varchar,
         #[max_length = 10]
         role -> Varchar,
+        created_at -> Timestamp,
+        deactivated -> Bool,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        #[max_length = 50]
+        event_type -> Varchar,
+        payload -> Text,
+        status_code -> Nullable<Int4>,
+        error -> Nullable<Text>,
+        redrive_of -> Nullable<Int4>,
+        delivered_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Int4,
+        #[max_length = 255]
+        url -> Varchar,
+        event_types -> Array<Text>,
+        enabled -> Bool,
+        created_at -> Timestamp,
     }
 }
 
 diesel::joinable!(empires -> locations (location_id));
+diesel::joinable!(export_jobs -> users (created_by));
 diesel::joinable!(players -> locations (location_id));
 diesel::joinable!(players -> ships (active_ship_id));
 diesel::joinable!(players -> users (user_id));
+diesel::joinable!(recovery_codes -> users (user_id));
+diesel::joinable!(sessions -> users (user_id));
 diesel::joinable!(ships -> empires (empire_id));
+diesel::joinable!(totp_credentials -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    change_requests,
     empires,
+    export_jobs,
     locations,
     players,
+    recovery_codes,
+    sessions,
     ships,
+    telemetry_events,
+    templates,
+    totp_credentials,
     users,
+    webhook_deliveries,
+    webhooks,
 );
@@ -0,0 +1,277 @@
+use serde_derive::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::users::model::{string_to_user_role, User, UserRole};
+
+// Schema URNs of RFC 7643 and RFC 7644
+pub const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const LIST_RESPONSE_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+
+// Page size of listings when 'count' is not given, and the largest one accepted
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimName {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScimEmail {
+    pub value: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScimRole {
+    pub value: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMeta {
+    pub resource_type: String,
+    pub created: String,
+    pub location: String,
+}
+
+// A user as represented to identity systems. 'userName' is the email, which is also the only email listed.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    pub schemas: Vec<String>,
+    pub id: String,
+    pub user_name: String,
+    pub name: ScimName,
+    pub display_name: String,
+    pub emails: Vec<ScimEmail>,
+    pub roles: Vec<ScimRole>,
+    pub active: bool,
+    pub meta: ScimMeta,
+}
+
+impl ScimUser {
+    pub fn from_user(user: &User) -> ScimUser {
+        ScimUser {
+            schemas: vec![USER_SCHEMA.to_string()],
+            id: user.id.to_string(),
+            user_name: user.email.clone(),
+            name: ScimName { formatted: Some(user.fullname.clone()), ..ScimName::default() },
+            display_name: user.fullname.clone(),
+            emails: vec![ScimEmail { value: user.email.clone(), primary: true }],
+            roles: vec![ScimRole { value: user.role.clone() }],
+            active: !user.deactivated,
+            meta: ScimMeta {
+                resource_type: "User".to_string(),
+                created: format!("{}Z", user.created_at.format("%Y-%m-%dT%H:%M:%S")),
+                location: format!("/scim/v2/Users/{}", user.id),
+            },
+        }
+    }
+}
+
+fn default_active() -> bool {
+    true
+}
+
+// Body of creating or replacing a user
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserRequest {
+    pub user_name: String,
+    #[serde(default)]
+    pub name: Option<ScimName>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+    // Only the first role is used. Users are created as READER without one, and keep their role when replaced without one.
+    #[serde(default)]
+    pub roles: Vec<ScimRole>,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    // Users created without a password can only sign in through an external authentication provider
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl ScimUserRequest {
+    // The primary email, falling back to the first one listed and then to 'userName'
+    pub fn email(&self) -> String {
+        self.emails.iter().find(|email| email.primary)
+            .or_else(|| self.emails.first())
+            .map(|email| email.value.clone())
+            .unwrap_or_else(|| self.user_name.clone())
+    }
+
+    pub fn fullname(&self) -> String {
+        let name = self.name.clone().unwrap_or_default();
+        let given_and_family = [name.given_name, name.family_name].into_iter().flatten().collect::<Vec<_>>().join(" ");
+
+        name.formatted
+            .or_else(|| self.display_name.clone())
+            .or_else(|| Some(given_and_family).filter(|name| !name.is_empty()))
+            .unwrap_or_else(|| self.user_name.clone())
+    }
+
+    pub fn role(&self) -> Result<Option<UserRole>, String> {
+        match self.roles.first() {
+            None => Ok(None),
+            Some(role) => match string_to_user_role(role.value.to_uppercase()) {
+                UserRole::INVALID => Err(format!("Unknown role '{}'", role.value)),
+                role => Ok(Some(role)),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PatchOperation {
+    pub op: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub value: Value,
+}
+
+// Body of a PATCH. Only 'replace' (and 'add', which amounts to the same for single-valued attributes) is supported.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PatchRequest {
+    #[serde(rename = "Operations")]
+    pub operations: Vec<PatchOperation>,
+}
+
+// Changes to a user collected from patch operations
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserPatch {
+    pub email: Option<String>,
+    pub fullname: Option<String>,
+    pub active: Option<bool>,
+}
+
+impl PatchRequest {
+    pub fn user_patch(&self) -> Result<UserPatch, String> {
+        let mut patch = UserPatch::default();
+
+        for operation in &self.operations {
+            if !["replace", "add"].contains(&operation.op.to_lowercase().as_str()) {
+                return Err(format!("Unsupported operation '{}'", operation.op));
+            }
+
+            // Without a path, the value is an object of attributes to replace
+            let attributes = match (&operation.path, &operation.value) {
+                (Some(path), value) => vec![(path.clone(), value.clone())],
+                (None, Value::Object(attributes)) => attributes.iter().map(|(path, value)| (path.clone(), value.clone())).collect(),
+                (None, _) => return Err("Operations without a path must have an object value".to_string()),
+            };
+
+            for (path, value) in attributes {
+                match (path.as_str(), &value) {
+                    ("active", Value::Bool(active)) => patch.active = Some(*active),
+                    // Some identity systems send booleans as strings
+                    ("active", Value::String(active)) => patch.active = Some(active.eq_ignore_ascii_case("true")),
+                    ("userName", Value::String(email)) => patch.email = Some(email.clone()),
+                    ("displayName" | "name.formatted", Value::String(fullname)) => patch.fullname = Some(fullname.clone()),
+                    _ => return Err(format!("Unsupported attribute '{}'", path)),
+                }
+            }
+        }
+
+        Ok(patch)
+    }
+}
+
+// Criteria of a listing filter. Supports 'eq' on userName (or emails.value) and active, joined by 'and'.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserFilter {
+    pub email: Option<String>,
+    pub active: Option<bool>,
+}
+
+impl UserFilter {
+    pub fn parse(filter: &str) -> Result<UserFilter, String> {
+        let mut parsed = UserFilter::default();
+
+        for expression in filter.split(" and ").map(str::trim).filter(|expression| !expression.is_empty()) {
+            let mut parts = expression.splitn(3, ' ');
+            let (Some(attribute), Some(operator), Some(value)) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(format!("Expected 'attribute eq value', got '{}'", expression));
+            };
+            if !operator.eq_ignore_ascii_case("eq") {
+                return Err(format!("Unsupported operator '{}'", operator));
+            }
+
+            let value = value.trim().trim_matches('"');
+            match attribute {
+                "userName" | "emails.value" | "emails" => parsed.email = Some(value.to_string()),
+                "active" => parsed.active = Some(value.parse().map_err(|_| format!("Expected true or false, got '{}'", value))?),
+                _ => return Err(format!("Unsupported attribute '{}'", attribute)),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQuery {
+    #[serde(default)]
+    pub filter: Option<String>,
+    // 1-based, as laid out in RFC 7644
+    #[serde(default)]
+    pub start_index: Option<i64>,
+    #[serde(default)]
+    pub count: Option<i64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResponse {
+    pub schemas: Vec<String>,
+    pub total_results: i64,
+    pub start_index: i64,
+    pub items_per_page: i64,
+    #[serde(rename = "Resources")]
+    pub resources: Vec<ScimUser>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::scim::model::{PatchRequest, UserFilter, UserPatch};
+
+    #[test]
+    fn parse_filter_supports_equality_on_user_name_and_active() {
+        assert_eq!(
+            UserFilter::parse("userName eq \"ada@example.com\" and active eq false").unwrap(),
+            UserFilter { email: Some("ada@example.com".to_string()), active: Some(false) }
+        );
+        assert_eq!(UserFilter::parse("").unwrap(), UserFilter::default());
+        assert!(UserFilter::parse("userName co \"ada\"").is_err());
+        assert!(UserFilter::parse("title eq \"Admiral\"").is_err());
+    }
+
+    #[test]
+    fn user_patch_accepts_operations_with_and_without_path() {
+        let patch: PatchRequest = serde_json::from_value(json!({
+            "schemas": ["urn:ietf:params:scim:api:messages:2.0:PatchOp"],
+            "Operations": [
+                {"op": "Replace", "path": "active", "value": "False"},
+                {"op": "replace", "value": {"displayName": "Ada Lovelace"}}
+            ]
+        })).unwrap();
+
+        assert_eq!(
+            patch.user_patch().unwrap(),
+            UserPatch { email: None, fullname: Some("Ada Lovelace".to_string()), active: Some(false) }
+        );
+    }
+}
@@ -0,0 +1,449 @@
+pub mod router {
+    use serde_json::{json, Value};
+    use axum::{
+        Router, body::Body, http::{header, HeaderMap, Request, StatusCode}, Json, response::{IntoResponse, Response},
+        extract::{Query, State}, extract, middleware::{self, Next},
+    };
+    use crate::{
+        common::{
+            auth_provider::PROVISIONED_PASSWORD,
+            db::ConnectionPool,
+            security::{enforce_api_key_role, hash_password, API_KEY_HEADER, API_KEY_PREFIX},
+        },
+        scim::model::{
+            ListQuery, ListResponse, PatchRequest, ScimUser, ScimUserRequest, UserFilter, UserPatch,
+            DEFAULT_PAGE_SIZE, ERROR_SCHEMA, LIST_RESPONSE_SCHEMA, MAX_PAGE_SIZE,
+        },
+        users::{
+            model::{UpsertUser, User, UserRole},
+            router::router::set_user_active,
+            service::service::UsersTable,
+        },
+    };
+
+    const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn scim_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Provisioning is done by identity systems holding an ADMIN API key, never by signed-in users
+        let provisioning_routes = Router::new()
+            .route("/scim/v2/Users", axum::routing::get(list_users_handler).post(create_user_handler))
+            .route(
+                "/scim/v2/Users/:user_id",
+                axum::routing::get(get_user_handler)
+                    .put(replace_user_handler)
+                    .patch(patch_user_handler)
+                    .delete(deactivate_user_handler),
+            )
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_provisioning_key));
+
+        Router::new()
+            .merge(provisioning_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [MIDDLEWARE] - - - - - - - - - - -
+
+    // The API key, sent through X-Api-Key or - as most identity systems do - as a bearer token
+    fn provisioning_key(headers: &HeaderMap) -> Option<String> {
+        let bearer = headers.get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|token| token.starts_with(API_KEY_PREFIX));
+
+        headers.get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .or(bearer)
+            .map(str::to_string)
+    }
+
+    async fn require_provisioning_key(
+        State(pool): State<ConnectionPool>,
+        mut req: Request<Body>,
+        next: Next<Body>,
+    ) -> Response {
+        let Some(api_key) = provisioning_key(req.headers()) else {
+            return scim_error(StatusCode::UNAUTHORIZED, "An ADMIN API key is required").into_response();
+        };
+
+        match enforce_api_key_role(&pool, &api_key, UserRole::ADMIN) {
+            Ok(authorized) => {
+                req.extensions_mut().insert(authorized);
+                next.run(req).await
+            }
+            Err((status, Json(error))) => scim_error(status, error["error"].as_str().unwrap_or("Unauthorized")).into_response(),
+        }
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    pub async fn list_users_handler(
+        State(shared_state): State<ConnectionPool>,
+        Query(query): Query<ListQuery>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let filter = UserFilter::parse(query.filter.as_deref().unwrap_or_default())
+            .map_err(|err| scim_error_of_type(StatusCode::BAD_REQUEST, "invalidFilter", &err))?;
+        let start_index = query.start_index.unwrap_or(1).max(1);
+        let count = query.count.unwrap_or(DEFAULT_PAGE_SIZE).clamp(0, MAX_PAGE_SIZE);
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match UsersTable::new(connection).search(filter.email.as_deref(), filter.active.map(|active| !active), start_index - 1, count) {
+            Ok((users, total_results)) => Ok(scim_response(StatusCode::OK, ListResponse {
+                schemas: vec![LIST_RESPONSE_SCHEMA.to_string()],
+                total_results,
+                start_index,
+                items_per_page: users.len() as i64,
+                resources: users.iter().map(ScimUser::from_user).collect(),
+            })),
+            Err(err) => {
+                eprintln!("Error listing users for SCIM: {:?}", err);
+                Err(scim_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list users"))
+            }
+        }
+    }
+
+    pub async fn get_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (user_id, ) = path.0;
+        let user = find_user(&shared_state, user_id)?;
+
+        Ok(scim_response(StatusCode::OK, ScimUser::from_user(&user)))
+    }
+
+    pub async fn create_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        Json(body): Json<ScimUserRequest>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let role = body.role().map_err(|err| scim_error_of_type(StatusCode::BAD_REQUEST, "invalidValue", &err))?;
+        let mut new_user = UpsertUser {
+            email: body.email(),
+            password: body.password.clone().unwrap_or_default(),
+            fullname: body.fullname(),
+            role: role.unwrap_or(UserRole::READER).to_string(),
+        };
+        validate(&new_user)?;
+        ensure_email_available(&shared_state, &new_user.email, None)?;
+        set_password(&mut new_user)?;
+
+        let user = {
+            let connection = shared_state.pool.get()
+                .expect("Failed to acquire connection from pool");
+
+            UsersTable::new(connection).create_with_status(new_user, !body.active).map_err(|err| {
+                eprintln!("Error creating user for SCIM: {:?}", err);
+                scim_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to create user")
+            })?
+        };
+        eprintln!("Provisioned user {} through SCIM", user.id);
+
+        let scim_user = ScimUser::from_user(&user);
+        let location = scim_user.meta.location.clone();
+        Ok((
+            StatusCode::CREATED,
+            [(header::CONTENT_TYPE, SCIM_CONTENT_TYPE.to_string()), (header::LOCATION, location)],
+            Json(scim_user),
+        ))
+    }
+
+    // Replaces the user's email, name and active state - along with their role and password, if given
+    pub async fn replace_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Json(body): Json<ScimUserRequest>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (user_id, ) = path.0;
+        let user = find_user(&shared_state, user_id)?;
+        let role = body.role().map_err(|err| scim_error_of_type(StatusCode::BAD_REQUEST, "invalidValue", &err))?;
+
+        let mut replacement = UpsertUser {
+            email: body.email(),
+            password: user.password.clone(),
+            fullname: body.fullname(),
+            role: role.map_or(user.role.clone(), |role| role.to_string()),
+        };
+        validate(&replacement)?;
+        ensure_email_available(&shared_state, &replacement.email, Some(user_id))?;
+        if let Some(password) = &body.password {
+            replacement.password = password.clone();
+            set_password(&mut replacement)?;
+        }
+
+        let user = update_user(&shared_state, user_id, replacement)?;
+        let user = set_active(&shared_state, user, body.active)?;
+
+        Ok(scim_response(StatusCode::OK, ScimUser::from_user(&user)))
+    }
+
+    // Applies 'replace' operations to the user's email, name or active state - the latter being how most identity systems deprovision
+    pub async fn patch_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Json(body): Json<PatchRequest>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (user_id, ) = path.0;
+        let user = find_user(&shared_state, user_id)?;
+        let UserPatch { email, fullname, active } = body.user_patch()
+            .map_err(|err| scim_error_of_type(StatusCode::BAD_REQUEST, "invalidPath", &err))?;
+
+        let user = if email.is_some() || fullname.is_some() {
+            let patched = UpsertUser {
+                email: email.unwrap_or(user.email),
+                password: user.password,
+                fullname: fullname.unwrap_or(user.fullname),
+                role: user.role,
+            };
+            validate(&patched)?;
+            ensure_email_available(&shared_state, &patched.email, Some(user_id))?;
+            update_user(&shared_state, user_id, patched)?
+        } else {
+            user
+        };
+
+        let user = match active {
+            Some(active) => set_active(&shared_state, user, active)?,
+            None => user,
+        };
+
+        Ok(scim_response(StatusCode::OK, ScimUser::from_user(&user)))
+    }
+
+    // Deactivates rather than deletes, so that whatever the user owns is kept for the record
+    pub async fn deactivate_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (user_id, ) = path.0;
+        let user = find_user(&shared_state, user_id)?;
+        set_active(&shared_state, user, false)?;
+
+        Ok((StatusCode::NO_CONTENT, ()))
+    }
+
+    // - - - - - - - - - - - [HELPERS] - - - - - - - - - - -
+
+    // Error body as laid out in RFC 7644
+    fn scim_error(status: StatusCode, detail: &str) -> (StatusCode, Json<Value>) {
+        (status, Json(json!({"schemas": [ERROR_SCHEMA], "status": status.as_u16().to_string(), "detail": detail})))
+    }
+
+    fn scim_error_of_type(status: StatusCode, scim_type: &str, detail: &str) -> (StatusCode, Json<Value>) {
+        let (status, Json(mut error)) = scim_error(status, detail);
+        error["scimType"] = json!(scim_type);
+        (status, Json(error))
+    }
+
+    fn scim_response<T: serde::Serialize>(status: StatusCode, body: T) -> Response {
+        (status, [(header::CONTENT_TYPE, SCIM_CONTENT_TYPE)], Json(body)).into_response()
+    }
+
+    fn validate(user: &UpsertUser) -> Result<(), (StatusCode, Json<Value>)> {
+        if !user.is_valid_email() {
+            return Err(scim_error_of_type(StatusCode::BAD_REQUEST, "invalidValue", "Invalid input for field 'userName'"));
+        }
+        if user.fullname.trim().is_empty() || user.fullname.len() > 100 {
+            return Err(scim_error_of_type(StatusCode::BAD_REQUEST, "invalidValue", "Invalid input for field 'name'"));
+        }
+        Ok(())
+    }
+
+    // Hashes the password given by the identity system, or locks local sign-in when none was given
+    fn set_password(user: &mut UpsertUser) -> Result<(), (StatusCode, Json<Value>)> {
+        if user.password.is_empty() {
+            user.password = PROVISIONED_PASSWORD.to_string();
+            Ok(())
+        } else {
+            hash_password(user)
+        }
+    }
+
+    fn find_user(shared_state: &ConnectionPool, user_id: i32) -> Result<User, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match UsersTable::new(connection).get(user_id) {
+            Ok(Some(user)) => Ok(user),
+            Ok(None) => Err(scim_error(StatusCode::NOT_FOUND, "User not found")),
+            Err(err) => {
+                eprintln!("Error reading user for SCIM: {:?}", err);
+                Err(scim_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read user"))
+            }
+        }
+    }
+
+    // Rejects emails already taken by a user other than the one given
+    fn ensure_email_available(shared_state: &ConnectionPool, email: &str, user_id: Option<i32>) -> Result<(), (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match UsersTable::new(connection).get_by_email(email.to_string()) {
+            Ok(Some(existing)) if Some(existing.id) != user_id => {
+                Err(scim_error_of_type(StatusCode::CONFLICT, "uniqueness", "A user with this userName already exists"))
+            }
+            Ok(_) => Ok(()),
+            Err(err) => {
+                eprintln!("Error reading user for SCIM: {:?}", err);
+                Err(scim_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read user"))
+            }
+        }
+    }
+
+    fn update_user(shared_state: &ConnectionPool, user_id: i32, user: UpsertUser) -> Result<User, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        UsersTable::new(connection).update(user_id, user).map_err(|err| {
+            eprintln!("Error updating user for SCIM: {:?}", err);
+            scim_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to update user")
+        })
+    }
+
+    fn set_active(shared_state: &ConnectionPool, user: User, active: bool) -> Result<User, (StatusCode, Json<Value>)> {
+        set_user_active(shared_state, user, active).map_err(|err| {
+            eprintln!("Error updating user for SCIM: {:?}", err);
+            scim_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to update user")
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use crate::{
+            api_keys::{model::CreateApiKey, service::service::ApiKeysTable},
+            common::{
+                db::create_shared_connection_pool,
+                security::{generate_api_key, hash_api_key},
+                test_util::create_user_and_generate_token,
+                util::load_environment_variable,
+            },
+            scim::router::router::scim_route,
+            users::{model::UserRole, router::router::users_route},
+        };
+
+        #[tokio::test]
+        async fn scim_provisions_and_deactivates_users_with_an_admin_api_key() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = scim_route(connection_pool.clone());
+
+            let secret = generate_api_key().unwrap();
+            {
+                let connection = connection_pool.pool.get().unwrap();
+                let create_api_key = CreateApiKey { name: "Identity sync".to_string(), role: "ADMIN".to_string() };
+                ApiKeysTable::new(connection).create(create_api_key, &secret[..11], &hash_api_key(&secret)).unwrap();
+            }
+
+            // Assert that bearer tokens of signed-in admins are not accepted
+            let admin_token = create_user_and_generate_token(connection_pool.clone(), "scim.admin@concord.gov", UserRole::ADMIN).unwrap();
+            let request = Request::builder()
+                .uri("/scim/v2/Users")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", admin_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            // Provision a user, sending the API key as a bearer token the way identity systems do
+            let request = Request::builder()
+                .uri("/scim/v2/Users")
+                .method("POST")
+                .header("content-type", "application/scim+json")
+                .header("Authorization", format!("Bearer {}", secret))
+                .body(Body::from(json!({
+                    "schemas": ["urn:ietf:params:scim:schemas:core:2.0:User"],
+                    "userName": "provisjonert@concord.gov",
+                    "name": {"givenName": "Pia", "familyName": "Provisjon"},
+                    "roles": [{"value": "writer"}],
+                    "password": "SkyVakt99"
+                }).to_string()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let created: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!((created["displayName"].as_str(), created["active"].as_bool()), (Some("Pia Provisjon"), Some(true)));
+            assert_eq!(created["roles"][0]["value"], "WRITER");
+            let user_id = created["id"].as_str().unwrap().to_string();
+
+            // Deactivate the user as identity systems do, through PATCH
+            let request = Request::builder()
+                .uri(format!("/scim/v2/Users/{}", user_id))
+                .method("PATCH")
+                .header("content-type", "application/scim+json")
+                .header("X-Api-Key", &secret)
+                .body(Body::from(json!({
+                    "schemas": ["urn:ietf:params:scim:api:messages:2.0:PatchOp"],
+                    "Operations": [{"op": "replace", "value": {"active": false}}]
+                }).to_string()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Assert that the user is found by filter regardless of case, and listed as inactive
+            let request = Request::builder()
+                .uri("/scim/v2/Users?filter=userName%20eq%20%22Provisjonert%40Concord.gov%22")
+                .method("GET")
+                .header("X-Api-Key", &secret)
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let listed: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(listed["totalResults"], 1);
+            assert_eq!(listed["Resources"][0]["active"], false);
+
+            // Assert that the deactivated user can no longer sign in
+            let request = Request::builder()
+                .uri("/users/login")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"email": "provisjonert@concord.gov", "password": "SkyVakt99"}).to_string()))
+                .unwrap();
+
+            let response = users_route(connection_pool.clone()).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            // Assert that duplicate userNames are rejected as such
+            let request = Request::builder()
+                .uri("/scim/v2/Users")
+                .method("POST")
+                .header("content-type", "application/scim+json")
+                .header("X-Api-Key", &secret)
+                .body(Body::from(json!({"userName": "provisjonert@concord.gov"}).to_string()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+
+            // Assert that users may be provisioned inactive
+            let request = Request::builder()
+                .uri("/scim/v2/Users")
+                .method("POST")
+                .header("content-type", "application/scim+json")
+                .header("X-Api-Key", &secret)
+                .body(Body::from(json!({"userName": "reserve@concord.gov", "active": false}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let created: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(created["active"], false);
+        }
+    }
+}
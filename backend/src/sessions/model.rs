@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_derive::Serialize;
+use utoipa::ToSchema;
+use crate::schema::sessions;
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+#[diesel(table_name = sessions)]
+pub struct Session {
+    pub id: i32,
+    pub user_id: i32,
+    // Embedded in the session's tokens - never handed out on its own
+    #[serde(skip_serializing)]
+    pub token_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    // Admin who opened the session by impersonating the user, if any
+    pub impersonated_by: Option<i32>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = sessions)]
+pub struct NewSession {
+    pub user_id: i32,
+    pub token_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub impersonated_by: Option<i32>,
+}
+
+// Session as listed to its owner, flagging the one the request was made with
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct SessionSummary {
+    #[serde(flatten)]
+    pub session: Session,
+    pub current: bool,
+}
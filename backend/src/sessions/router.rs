@@ -0,0 +1,386 @@
+pub mod router {
+    use std::net::SocketAddr;
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::{HeaderMap, StatusCode}, Json, response::IntoResponse, extract::State, extract, middleware, Extension,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::{require_reader, AuthorizedUser},
+            rate_limit::client_address,
+            security::{current_session_token_id, token_strategy}
+        },
+        sessions::{
+            service::service::SessionsTable as sessionsTable,
+            model::{NewSession, SessionSummary}
+        },
+        users::model::User
+    };
+
+    // Longest user agent stored, matching the column width
+    const MAX_USER_AGENT_LENGTH: usize = 255;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn sessions_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Every signed-in user manages their own sessions
+        let read_routes = Router::new()
+            .route("/users/me/sessions", axum::routing::get(get_my_sessions_handler))
+            .route("/users/me/sessions/revoke-all", axum::routing::post(revoke_all_my_sessions_handler))
+            .route("/users/me/sessions/revoke-current", axum::routing::post(revoke_current_session_handler))
+            .route("/users/me/sessions/:session_id", axum::routing::delete(revoke_my_session_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
+
+        Router::new()
+            .merge(read_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // Records a new session for the user and issues a token tied to it, of the configured token strategy.
+    // 'impersonated_by' is the admin opening the session on the user's behalf, if any.
+    pub fn open_session(
+        shared_state: &ConnectionPool,
+        user: &User,
+        headers: &HeaderMap,
+        remote: Option<SocketAddr>,
+        impersonated_by: Option<i32>,
+    ) -> Result<String, (StatusCode, Json<Value>)> {
+        let session_token = token_strategy().issue(user)?;
+        let user_agent = headers
+            .get("User-Agent")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        let new_session = NewSession {
+            user_id: user.id,
+            token_id: session_token.token_id,
+            user_agent,
+            ip_address: client_address(headers, remote),
+            impersonated_by,
+        };
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        if let Err(err) = sessionsTable::new(connection).create(new_session) {
+            eprintln!("Error creating session: {:?}", err);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create session"}))));
+        }
+
+        Ok(session_token.token)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/users/me/sessions",
+        tag = "sessions",
+        operation_id = "get_my_sessions",
+        responses(
+            (status = 200, body = [SessionSummary])
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn get_my_sessions_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        headers: HeaderMap,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+        let current_token_id = current_session_token_id(&headers);
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match sessionsTable::new(connection).active_for_user(user.id) {
+            Ok(sessions) => {
+                let summaries: Vec<SessionSummary> = sessions
+                    .into_iter()
+                    .map(|session| {
+                        let current = current_token_id.as_deref() == Some(session.token_id.as_str());
+                        SessionSummary { session, current }
+                    })
+                    .collect();
+                Ok((StatusCode::OK, Json(summaries)))
+            }
+            Err(err) => {
+                eprintln!("Error fetching sessions: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch sessions"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/users/me/sessions/{session_id}",
+        tag = "sessions",
+        operation_id = "revoke_my_session",
+        params(("session_id" = i32, Path, description = "ID of the session")),
+        responses(
+            (status = 204),
+            (status = 404, body = ApiError)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn revoke_my_session_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (session_id, ) = path.0;
+        let user = authorized.require_user()?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        // Sessions of other users are reported as missing rather than forbidden
+        match sessionsTable::new(connection).revoke(user.id, session_id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Session not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error revoking session: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to revoke session"}))))
+            }
+        }
+    }
+
+    // Signs out of the session the request was made with, e.g. when an admin stops impersonating a user
+    #[utoipa::path(
+        post,
+        path = "/users/me/sessions/revoke-current",
+        tag = "sessions",
+        operation_id = "revoke_current_session",
+        responses(
+            (status = 204),
+            (status = 404, body = ApiError)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn revoke_current_session_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        headers: HeaderMap,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+        let Some(token_id) = current_session_token_id(&headers) else {
+            return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Session not found"}))));
+        };
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match sessionsTable::new(connection).revoke_by_token_id(user.id, &token_id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Session not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error revoking session: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to revoke session"}))))
+            }
+        }
+    }
+
+    // Signs the user out everywhere, including the session the request was made with
+    #[utoipa::path(
+        post,
+        path = "/users/me/sessions/revoke-all",
+        tag = "sessions",
+        operation_id = "revoke_all_my_sessions",
+        responses(
+            (status = 204)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn revoke_all_my_sessions_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match sessionsTable::new(connection).revoke_all(user.id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(err) => {
+                eprintln!("Error revoking sessions: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to revoke sessions"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::net::SocketAddr;
+        use axum::{
+            body::Body,
+            extract::ConnectInfo,
+            http::{Request, StatusCode},
+            Router
+        };
+        use serde_json::json;
+        use tower::ServiceExt;
+        use crate::{
+            sessions::router::router::sessions_route,
+            users::{model::UserRole, router::router::users_route, service::service::UsersTable},
+            sessions::{model::NewSession, service::service::SessionsTable},
+            common::{
+                db::{create_shared_connection_pool, ConnectionPool},
+                security::{OpaqueTokens, TokenStrategy, OPAQUE_TOKEN_PREFIX},
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            }
+        };
+
+        fn app(connection_pool: ConnectionPool) -> Router {
+            users_route(connection_pool.clone()).merge(sessions_route(connection_pool))
+        }
+
+        async fn login(service: Router, email: &str, user_agent: &str) -> String {
+            let request = Request::builder()
+                .uri("/users/login")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("User-Agent", user_agent)
+                .extension(ConnectInfo("203.0.113.7:50412".parse::<SocketAddr>().unwrap()))
+                .body(Body::from(json!({"email": email, "password": "StålGardinerFunkerFjell53"}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            serde_json::from_slice(&body).unwrap()
+        }
+
+        fn authorized(uri: String, method: &str, token: &str) -> Request<Body> {
+            Request::builder()
+                .uri(uri)
+                .method(method)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn revoked_session_token_is_rejected() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = app(connection_pool.clone());
+
+            let email = "økt.leser@concord.gov";
+            create_user_and_generate_token(connection_pool, email, UserRole::READER).unwrap();
+            let laptop_token = login(service.clone(), email, "Laptop").await;
+            let phone_token = login(service.clone(), email, "Phone").await;
+
+            // Assert that both sessions are listed, with the one of the request flagged as current
+            let response = service.clone().oneshot(authorized("/users/me/sessions".to_string(), "GET", &laptop_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let sessions: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let sessions = sessions.as_array().unwrap();
+            assert_eq!(sessions.len(), 2);
+
+            let phone_session = sessions.iter().find(|session| session["user_agent"] == "Phone").unwrap();
+            assert_eq!(phone_session["current"], false);
+            assert_eq!(phone_session["ip_address"], "203.0.113.7");
+            assert!(phone_session["token_id"].is_null());
+
+            // Revoke the phone session from the laptop
+            let uri = format!("/users/me/sessions/{}", phone_session["id"]);
+            let response = service.clone().oneshot(authorized(uri, "DELETE", &laptop_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            // Assert that the phone is signed out while the laptop is not
+            let response = service.clone().oneshot(authorized("/users/me/sessions".to_string(), "GET", &phone_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let response = service.oneshot(authorized("/users/me/sessions".to_string(), "GET", &laptop_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn revoke_all_signs_out_every_session() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = app(connection_pool.clone());
+
+            let email = "økt.alle@concord.gov";
+            create_user_and_generate_token(connection_pool, email, UserRole::READER).unwrap();
+            let first_token = login(service.clone(), email, "Laptop").await;
+            let second_token = login(service.clone(), email, "Phone").await;
+
+            let response = service.clone().oneshot(authorized("/users/me/sessions/revoke-all".to_string(), "POST", &first_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            // Assert that neither token is accepted anymore
+            for token in [first_token, second_token] {
+                let response = service.clone().oneshot(authorized("/users/me/sessions".to_string(), "GET", &token)).await.unwrap();
+                assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            }
+        }
+
+        #[tokio::test]
+        async fn revoking_another_users_session_returns_404() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = app(connection_pool.clone());
+
+            create_user_and_generate_token(connection_pool.clone(), "økt.eier@concord.gov", UserRole::READER).unwrap();
+            let owner_token = login(service.clone(), "økt.eier@concord.gov", "Laptop").await;
+            let other_token = create_user_and_generate_token(connection_pool, "økt.annen@concord.gov", UserRole::READER).unwrap();
+
+            let response = service.clone().oneshot(authorized("/users/me/sessions".to_string(), "GET", &owner_token)).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let sessions: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            let uri = format!("/users/me/sessions/{}", sessions[0]["id"]);
+            let response = service.oneshot(authorized(uri, "DELETE", &other_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn opaque_tokens_are_stored_hashed_and_resolve_to_their_user_until_revoked() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+
+            create_user_and_generate_token(connection_pool.clone(), "ugjennomsiktig@concord.gov", UserRole::WRITER).unwrap();
+            let user = UsersTable::new(connection_pool.pool.get().unwrap())
+                .get_by_email("ugjennomsiktig@concord.gov".to_string())
+                .unwrap()
+                .unwrap();
+
+            let session_token = OpaqueTokens.issue(&user).unwrap();
+            assert!(session_token.token.starts_with(OPAQUE_TOKEN_PREFIX));
+            assert!(!session_token.token_id.contains(&session_token.token));
+
+            let session = SessionsTable::new(connection_pool.pool.get().unwrap())
+                .create(NewSession {
+                    user_id: user.id,
+                    token_id: session_token.token_id.clone(),
+                    user_agent: None,
+                    ip_address: None,
+                    impersonated_by: None,
+                })
+                .unwrap();
+
+            // Assert that the token resolves to its user, while made-up tokens do not
+            let authenticated = OpaqueTokens.authenticate(&connection_pool, &session_token.token).unwrap();
+            assert_eq!(authenticated.id, user.id);
+            assert_eq!(OpaqueTokens.session_token_id(&session_token.token), Some(session_token.token_id));
+            let (status, _) = OpaqueTokens.authenticate(&connection_pool, "ot_0000").unwrap_err();
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+            // Assert that the token stops working once its session is revoked
+            SessionsTable::new(connection_pool.pool.get().unwrap()).revoke(user.id, session.id).unwrap();
+            let (status, _) = OpaqueTokens.authenticate(&connection_pool, &session_token.token).unwrap_err();
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+pub mod service {
+    use chrono::{NaiveDateTime, Utc};
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::{
+        sessions::model::{NewSession, Session},
+        users::model::User,
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct SessionsTable {
+        connection: PooledPg,
+    }
+
+    impl SessionsTable {
+        pub fn new(connection: PooledPg) -> SessionsTable {
+            SessionsTable { connection }
+        }
+
+        pub fn create(&mut self, new_session: NewSession) -> Result<Session, diesel::result::Error> {
+            use schema::sessions;
+
+            diesel::insert_into(sessions::table)
+                .values(&new_session)
+                .get_result(&mut self.connection)
+        }
+
+        // Sessions of the user that have not been revoked, most recently seen first
+        pub fn active_for_user(&mut self, user_id: i32) -> Result<Vec<Session>, diesel::result::Error> {
+            use schema::sessions;
+
+            sessions::table
+                .filter(sessions::user_id.eq(user_id))
+                .filter(sessions::revoked_at.is_null())
+                .order(sessions::last_seen_at.desc())
+                .load::<Session>(&mut self.connection)
+        }
+
+        // Marks the session as seen now, returning false if it does not exist or has been revoked
+        pub fn touch_active(&mut self, token_id: &str) -> Result<bool, diesel::result::Error> {
+            use schema::sessions;
+
+            let updated_rows = diesel::update(sessions::table
+                .filter(sessions::token_id.eq(token_id))
+                .filter(sessions::revoked_at.is_null()))
+                .set(sessions::last_seen_at.eq(Utc::now().naive_utc()))
+                .execute(&mut self.connection)?;
+
+            Ok(updated_rows > 0)
+        }
+
+        // User of the active session with the token id, provided the session was opened after 'opened_after'.
+        // Marks the session as seen now, like touch_active.
+        pub fn touch_active_user(&mut self, token_id: &str, opened_after: NaiveDateTime) -> Result<Option<User>, diesel::result::Error> {
+            use schema::{sessions, users};
+
+            let user = sessions::table
+                .inner_join(users::table)
+                .filter(sessions::token_id.eq(token_id))
+                .filter(sessions::revoked_at.is_null())
+                .filter(sessions::created_at.gt(opened_after))
+                .select(users::all_columns)
+                .first::<User>(&mut self.connection)
+                .optional()?;
+
+            if user.is_some() {
+                self.touch_active(token_id)?;
+            }
+
+            Ok(user)
+        }
+
+        // Revokes one of the user's active sessions
+        pub fn revoke(&mut self, user_id: i32, session_id: i32) -> Result<(), diesel::result::Error> {
+            use schema::sessions;
+
+            let revoked_rows = diesel::update(sessions::table
+                .filter(sessions::id.eq(session_id))
+                .filter(sessions::user_id.eq(user_id))
+                .filter(sessions::revoked_at.is_null()))
+                .set(sessions::revoked_at.eq(Some(Utc::now().naive_utc())))
+                .execute(&mut self.connection)?;
+
+            if revoked_rows == 0 {
+                Err(diesel::result::Error::NotFound)
+            } else {
+                Ok(())
+            }
+        }
+
+        // Revokes the user's active session with the token id, e.g. the one a request was made with
+        pub fn revoke_by_token_id(&mut self, user_id: i32, token_id: &str) -> Result<(), diesel::result::Error> {
+            use schema::sessions;
+
+            let revoked_rows = diesel::update(sessions::table
+                .filter(sessions::token_id.eq(token_id))
+                .filter(sessions::user_id.eq(user_id))
+                .filter(sessions::revoked_at.is_null()))
+                .set(sessions::revoked_at.eq(Some(Utc::now().naive_utc())))
+                .execute(&mut self.connection)?;
+
+            if revoked_rows == 0 {
+                Err(diesel::result::Error::NotFound)
+            } else {
+                Ok(())
+            }
+        }
+
+        // Revokes every active session of the user, returning the number of revoked sessions
+        pub fn revoke_all(&mut self, user_id: i32) -> Result<usize, diesel::result::Error> {
+            use schema::sessions;
+
+            diesel::update(sessions::table
+                .filter(sessions::user_id.eq(user_id))
+                .filter(sessions::revoked_at.is_null()))
+                .set(sessions::revoked_at.eq(Some(Utc::now().naive_utc())))
+                .execute(&mut self.connection)
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use diesel::prelude::*;
+use axum::http::StatusCode;
+use serde_derive::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::common::batch::BatchItem;
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+pub struct Ship {
+    pub id: i32,
+    pub name: String,
+    pub category: Option<String>,
+    pub description: Option<String>,
+    pub empire_id: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpsertShip {
+    pub name: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl UpsertShip {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.chars().count() > 100 {
+            return Err("Invalid input for field 'name'".to_string());
+        }
+        if self.category.as_ref().is_some_and(|category| category.chars().count() > 50) {
+            return Err("Invalid input for field 'category'".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Ship ids accepted in a single reassignment, keeping the transaction and its row locks short
+pub const MAX_REASSIGNED_SHIPS: usize = 500;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ReassignShips {
+    pub target_empire_id: i32,
+    pub ship_ids: Vec<i32>,
+}
+
+impl ReassignShips {
+    pub fn validate(&self, source_empire_id: i32) -> Result<(), String> {
+        if self.target_empire_id == source_empire_id {
+            return Err("Field 'target_empire_id' must differ from the empire the ships are reassigned from".to_string());
+        }
+        if self.ship_ids.is_empty() {
+            return Err("Field 'ship_ids' must not be empty".to_string());
+        }
+        if self.ship_ids.len() > MAX_REASSIGNED_SHIPS {
+            return Err(format!("Field 'ship_ids' must not contain more than {} ids", MAX_REASSIGNED_SHIPS));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReassignmentStatus {
+    Reassigned,
+    // No ship with the id exists
+    NotFound,
+    // The ship belongs to an empire other than the one it is reassigned from
+    NotInEmpire,
+    // The id was listed more than once - only its first occurrence is processed
+    Duplicate,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ShipReassignment {
+    pub ship_id: i32,
+    pub status: ReassignmentStatus,
+}
+
+impl ShipReassignment {
+    pub fn to_batch_item(&self) -> BatchItem {
+        match self.status {
+            ReassignmentStatus::Reassigned => BatchItem::succeeded(self.ship_id, StatusCode::OK),
+            ReassignmentStatus::NotFound => BatchItem::failed(self.ship_id, StatusCode::NOT_FOUND, "not_found", "Ship not found"),
+            ReassignmentStatus::NotInEmpire => BatchItem::failed(
+                self.ship_id, StatusCode::CONFLICT, "not_in_empire", "Ship belongs to another empire",
+            ),
+            ReassignmentStatus::Duplicate => BatchItem::failed(
+                self.ship_id, StatusCode::CONFLICT, "duplicate", "Ship was listed more than once",
+            ),
+        }
+    }
+}
+
+// The empires of a reassignment, reported alongside the outcome of each ship
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ReassignmentReport {
+    pub source_empire_id: i32,
+    pub target_empire_id: i32,
+}
@@ -0,0 +1,276 @@
+pub mod router {
+    use serde_json::{json, Map, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::{Query, State}, extract, middleware,
+    };
+    use crate::{
+        common::{batch::MultiStatus, db::ConnectionPool, middleware::{require_editor, require_reader, require_writer}},
+        templates::{model::TemplateParams, router::router::prefill_from_template},
+        ships::{
+            service::service::ShipsTable as shipsTable,
+            model::{ReassignShips, ReassignmentReport, ShipReassignment, UpsertShip}
+        }
+    };
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn ships_route(shared_connection_pool: ConnectionPool) -> Router {
+        let read_routes = Router::new()
+            .route("/empires/:empire_id/ships", axum::routing::get(get_empire_ships_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
+
+        let create_routes = Router::new()
+            .route("/empires/:empire_id/ships", axum::routing::post(create_ship_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_writer));
+
+        let editor_routes = Router::new()
+            .route("/empires/:empire_id/ships/reassign", axum::routing::post(reassign_ships_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_editor));
+
+        Router::new()
+            .merge(read_routes)
+            .merge(create_routes)
+            .merge(editor_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/empires/{empire_id}/ships",
+        tag = "ships",
+        operation_id = "get_empire_ships",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        responses(
+            (status = 200, body = [Ship])
+        )
+    )]
+    pub async fn get_empire_ships_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (empire_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match shipsTable::new(connection).of_empire(empire_id) {
+            Ok(ships) => Ok((StatusCode::OK, Json(ships))),
+            Err(err) => {
+                eprintln!("Error fetching ships: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch ships"}))))
+            }
+        }
+    }
+
+    // Adds a ship to the empire. With '?template_id=' any fields absent from the body are taken from the template.
+    #[utoipa::path(
+        post,
+        path = "/empires/{empire_id}/ships",
+        tag = "ships",
+        operation_id = "create_ship",
+        params(("empire_id" = i32, Path, description = "ID of the empire"), TemplateParams),
+        request_body = UpsertShip,
+        responses(
+            (status = 201, body = Ship),
+            (status = 404, body = ApiError),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn create_ship_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Query(template): Query<TemplateParams>,
+        Json(body): Json<Map<String, Value>>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (empire_id, ) = path.0;
+        let body = prefill_from_template(&shared_state, &template, "ship", body)?;
+        let upsert_ship: UpsertShip = serde_json::from_value(Value::Object(body))
+            .map_err(|err| (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": format!("Invalid ship: {}", err)}))))?;
+
+        if let Err(err) = upsert_ship.validate() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match shipsTable::new(connection).create(empire_id, upsert_ship) {
+            Ok(new_ship) => Ok((StatusCode::CREATED, Json(new_ship))),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error creating ship: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create ship"}))))
+            }
+        }
+    }
+
+    // Ships that cannot be moved are reported rather than failing the request, so that the others are moved regardless.
+    // Responds with 207 Multi-Status as soon as a single ship could not be moved.
+    #[utoipa::path(
+        post,
+        path = "/empires/{empire_id}/ships/reassign",
+        tag = "ships",
+        operation_id = "reassign_ships",
+        params(("empire_id" = i32, Path, description = "ID of the empire")),
+        request_body = ReassignShips,
+        responses(
+            (status = 200, description = "Every ship was moved", body = ReassignmentOutcome),
+            (status = 207, description = "Some ships could not be moved", body = ReassignmentOutcome),
+            (status = 404, body = ApiError),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn reassign_ships_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Json(reassign_ships): Json<ReassignShips>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (empire_id, ) = path.0;
+
+        if let Err(err) = reassign_ships.validate(empire_id) {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match shipsTable::new(connection).reassign(empire_id, reassign_ships.target_empire_id, &reassign_ships.ship_ids) {
+            Ok(results) => Ok(MultiStatus::new(
+                ReassignmentReport { source_empire_id: empire_id, target_empire_id: reassign_ships.target_empire_id },
+                results.iter().map(ShipReassignment::to_batch_item).collect(),
+            )),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Empire not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error reassigning ships: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to reassign ships"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use diesel::prelude::*;
+        use serde_json::json;
+        use tower::ServiceExt;
+        use crate::{
+            ships::router::router::ships_route,
+            common::{
+                db::{create_shared_connection_pool, ConnectionPool},
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            empires::{
+                model::UpsertEmpire,
+                service::service::EmpiresTable
+            },
+            locations::{
+                model::UpsertLocation,
+                service::service::LocationsTable
+            },
+            schema::ships,
+            users::model::UserRole
+        };
+
+        // Creates an empire along with a ship for each of the given names, returning the ids of both
+        fn create_empire_with_ships(connection_pool: &ConnectionPool, name: &str, ship_names: &[&str]) -> (i32, Vec<i32>) {
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Essence".to_string(), area: "Luminaire".to_string() })
+                .unwrap();
+            let empire = EmpiresTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertEmpire {
+                    name: name.to_string(),
+                    slogan: "Ad astra".to_string(),
+                    location_id: location.id,
+                    description: "Fleet reorganization test".to_string(),
+                }, true)
+                .unwrap();
+
+            let mut connection = connection_pool.pool.get().unwrap();
+            let ship_ids = ship_names.iter().map(|ship_name| {
+                diesel::insert_into(ships::table)
+                    .values((ships::name.eq(*ship_name), ships::empire_id.eq(empire.id)))
+                    .returning(ships::id)
+                    .get_result::<i32>(&mut connection)
+                    .unwrap()
+            }).collect();
+
+            (empire.id, ship_ids)
+        }
+
+        #[tokio::test]
+        async fn reassign_ships_moves_owned_ships_and_reports_the_rest() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = ships_route(connection_pool.clone());
+
+            let (source_id, source_ships) = create_empire_with_ships(&connection_pool, "Gallente Federation", &["Thorax", "Vexor"]);
+            let (target_id, target_ships) = create_empire_with_ships(&connection_pool, "Intaki Syndicate", &["Catalyst"]);
+
+            let bearer_token = create_user_and_generate_token(connection_pool.clone(), "flåteadmiral@concord.gov", UserRole::EDITOR).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/empires/{}/ships/reassign", source_id))
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(json!({
+                    "target_empire_id": target_id,
+                    "ship_ids": [source_ships[0], source_ships[1], source_ships[0], target_ships[0], -1]
+                }).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::MULTI_STATUS);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!((report["succeeded"].as_u64(), report["failed"].as_u64()), (Some(2), Some(3)));
+
+            let results = report["results"].as_array().unwrap();
+            let statuses: Vec<u64> = results.iter().map(|result| result["status"].as_u64().unwrap()).collect();
+            assert_eq!(statuses, [200, 200, 409, 409, 404]);
+
+            let codes: Vec<Option<&str>> = results.iter().map(|result| result["code"].as_str()).collect();
+            assert_eq!(codes, [None, None, Some("duplicate"), Some("not_in_empire"), Some("not_found")]);
+
+            // Assert that the ships now belong to the target empire
+            let owners: Vec<i32> = ships::table
+                .filter(ships::id.eq_any(&source_ships))
+                .select(ships::empire_id)
+                .load(&mut connection_pool.pool.get().unwrap())
+                .unwrap();
+            assert_eq!(owners, [target_id, target_id]);
+        }
+
+        #[tokio::test]
+        async fn reassign_ships_returns_404_on_non_existing_target_empire() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = ships_route(connection_pool.clone());
+
+            let (source_id, source_ships) = create_empire_with_ships(&connection_pool, "Caldari State", &["Merlin"]);
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "flåtesjef@concord.gov", UserRole::EDITOR).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/empires/{}/ships/reassign", source_id))
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(json!({"target_empire_id": -1, "ship_ids": source_ships}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+}
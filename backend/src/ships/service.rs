@@ -0,0 +1,108 @@
+pub mod service {
+    use std::collections::{HashMap, HashSet};
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::{
+        schema,
+        ships::model::{ReassignmentStatus, Ship, ShipReassignment, UpsertShip},
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct ShipsTable {
+        connection: PooledPg,
+    }
+
+    impl ShipsTable {
+        pub fn new(connection: PooledPg) -> ShipsTable {
+            ShipsTable { connection }
+        }
+
+        // Fails with NotFound if the empire does not exist
+        pub fn create(&mut self, empire_id: i32, upsert_ship: UpsertShip) -> Result<Ship, diesel::result::Error> {
+            use schema::{empires, ships};
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                empires::table
+                    .find(empire_id)
+                    .select(empires::id)
+                    .for_share()
+                    .get_result::<i32>(connection)?;
+
+                diesel::insert_into(ships::table)
+                    .values((
+                        ships::name.eq(&upsert_ship.name),
+                        ships::category.eq(&upsert_ship.category),
+                        ships::description.eq(&upsert_ship.description),
+                        ships::empire_id.eq(empire_id),
+                    ))
+                    .get_result(connection)
+            })
+        }
+
+        pub fn of_empire(&mut self, empire_id: i32) -> Result<Vec<Ship>, diesel::result::Error> {
+            use schema::ships;
+
+            ships::table
+                .filter(ships::empire_id.eq(empire_id))
+                .order(ships::id)
+                .load::<Ship>(&mut self.connection)
+        }
+
+        // Moves the ships belonging to the source empire over to the target empire in a single transaction, reporting the
+        // outcome for every id in the order given. Fails with NotFound if either empire does not exist.
+        pub fn reassign(&mut self, source_empire_id: i32, target_empire_id: i32, ship_ids: &[i32],
+        ) -> Result<Vec<ShipReassignment>, diesel::result::Error> {
+            use schema::{empires, ships};
+
+            let connection: &mut PgConnection = &mut self.connection;
+            connection.transaction(|connection| {
+                // Both empires are share-locked so that neither can be deleted while ships are moved between them
+                let empire_ids: Vec<i32> = empires::table
+                    .filter(empires::id.eq_any([source_empire_id, target_empire_id]))
+                    .select(empires::id)
+                    .for_share()
+                    .load(connection)?;
+                if empire_ids.len() < 2 {
+                    return Err(diesel::result::Error::NotFound);
+                }
+
+                let owners: HashMap<i32, i32> = ships::table
+                    .filter(ships::id.eq_any(ship_ids))
+                    .select((ships::id, ships::empire_id))
+                    .for_update()
+                    .load::<(i32, i32)>(connection)?
+                    .into_iter()
+                    .collect();
+
+                let mut seen = HashSet::new();
+                let mut movable = Vec::new();
+                let results = ship_ids.iter().map(|&ship_id| {
+                    let status = if !seen.insert(ship_id) {
+                        ReassignmentStatus::Duplicate
+                    } else {
+                        match owners.get(&ship_id) {
+                            None => ReassignmentStatus::NotFound,
+                            Some(&empire_id) if empire_id != source_empire_id => ReassignmentStatus::NotInEmpire,
+                            Some(_) => {
+                                movable.push(ship_id);
+                                ReassignmentStatus::Reassigned
+                            }
+                        }
+                    };
+                    ShipReassignment { ship_id, status }
+                }).collect();
+
+                diesel::update(ships::table.filter(ships::id.eq_any(&movable)))
+                    .set(ships::empire_id.eq(target_empire_id))
+                    .execute(connection)?;
+
+                Ok(results)
+            })
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::NaiveDate;
+use diesel::{prelude::*, sql_types::{BigInt, Date}};
+use serde_derive::Serialize;
+use utoipa::ToSchema;
+
+// Entities created on a single day, along with the number of users at its end
+#[derive(Serialize, Debug, Clone, QueryableByName, ToSchema)]
+pub struct DailyCounts {
+    #[diesel(sql_type = Date)]
+    pub day: NaiveDate,
+    #[diesel(sql_type = BigInt)]
+    pub new_users: i64,
+    #[diesel(sql_type = BigInt)]
+    pub total_users: i64,
+    #[diesel(sql_type = BigInt)]
+    pub new_locations: i64,
+    #[diesel(sql_type = BigInt)]
+    pub new_empires: i64,
+}
+
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct StatsHistory {
+    pub days: i64,
+    pub history: Vec<DailyCounts>,
+}
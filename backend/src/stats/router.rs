@@ -0,0 +1,112 @@
+pub mod router {
+    use serde_derive::Deserialize;
+    use utoipa::IntoParams;
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::{Query, State}, middleware,
+    };
+    use crate::{
+        common::{db::ConnectionPool, middleware::require_admin},
+        stats::{
+            service::service::StatsTable as statsTable,
+            model::StatsHistory
+        }
+    };
+
+    const DEFAULT_HISTORY_DAYS: i64 = 30;
+    const MAX_HISTORY_DAYS: i64 = 365;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn stats_route(shared_connection_pool: ConnectionPool) -> Router {
+        let admin_routes = Router::new()
+            .route("/admin/stats/history", axum::routing::get(get_stats_history_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(admin_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    #[derive(Debug, Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub struct HistoryParams {
+        pub days: Option<i64>,
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/admin/stats/history",
+        tag = "stats",
+        operation_id = "get_stats_history",
+        params(HistoryParams),
+        responses(
+            (status = 200, body = StatsHistory)
+        )
+    )]
+    pub async fn get_stats_history_handler(
+        State(shared_state): State<ConnectionPool>,
+        Query(params): Query<HistoryParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let days = params.days.unwrap_or(DEFAULT_HISTORY_DAYS).clamp(1, MAX_HISTORY_DAYS);
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match statsTable::new(connection).daily_counts(days) {
+            Ok(history) => Ok((StatusCode::OK, Json(StatsHistory { days, history }))),
+            Err(err) => {
+                eprintln!("Error aggregating stats history: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to aggregate stats history"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use tower::ServiceExt;
+        use crate::{
+            stats::router::router::stats_route,
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn get_stats_history_returns_one_row_per_day() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = stats_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "statistikk.admin@concord.gov", UserRole::ADMIN).unwrap();
+
+            let request = Request::builder()
+                .uri("/admin/stats/history?days=7")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let history = stats["history"].as_array().unwrap();
+            assert_eq!(history.len(), 7);
+
+            // Assert that the user created above is counted today, and in the running total
+            let today = &history[6];
+            assert!(today["new_users"].as_i64().unwrap() >= 1);
+            assert!(today["total_users"].as_i64().unwrap() >= today["new_users"].as_i64().unwrap());
+        }
+    }
+}
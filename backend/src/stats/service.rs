@@ -0,0 +1,45 @@
+pub mod service {
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+        sql_types::BigInt,
+    };
+    use crate::stats::model::DailyCounts;
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct StatsTable {
+        connection: PooledPg,
+    }
+
+    impl StatsTable {
+        pub fn new(connection: PooledPg) -> StatsTable {
+            StatsTable { connection }
+        }
+
+        // One row per day for the last 'days' days up to and including today, days without activity included
+        pub fn daily_counts(&mut self, days: i64) -> Result<Vec<DailyCounts>, diesel::result::Error> {
+            diesel::sql_query(
+                "WITH days AS ( \
+                     SELECT generate_series(CURRENT_DATE - ($1 - 1)::int, CURRENT_DATE, INTERVAL '1 day')::date AS day \
+                 ), \
+                 new_users AS (SELECT created_at::date AS day, COUNT(*) AS count FROM users GROUP BY 1), \
+                 new_locations AS (SELECT created_at::date AS day, COUNT(*) AS count FROM locations GROUP BY 1), \
+                 new_empires AS (SELECT created_at::date AS day, COUNT(*) AS count FROM empires GROUP BY 1) \
+                 SELECT days.day, \
+                        COALESCE(new_users.count, 0) AS new_users, \
+                        (SELECT COUNT(*) FROM users WHERE created_at < days.day + 1) AS total_users, \
+                        COALESCE(new_locations.count, 0) AS new_locations, \
+                        COALESCE(new_empires.count, 0) AS new_empires \
+                 FROM days \
+                 LEFT JOIN new_users ON new_users.day = days.day \
+                 LEFT JOIN new_locations ON new_locations.day = days.day \
+                 LEFT JOIN new_empires ON new_empires.day = days.day \
+                 ORDER BY days.day"
+            )
+                .bind::<BigInt, _>(days)
+                .load::<DailyCounts>(&mut self.connection)
+        }
+    }
+}
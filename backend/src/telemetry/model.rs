@@ -0,0 +1,256 @@
+use chrono::NaiveDateTime;
+use diesel::{prelude::*, sql_types::{BigInt, Double, Nullable, Varchar}};
+use serde_derive::{Serialize, Deserialize};
+use crate::schema::telemetry_events;
+
+// Limits applied to incoming batches - anything beyond them is rejected rather than truncated
+pub const MAX_EVENTS_PER_BATCH: usize = 100;
+pub const MAX_NAME_LENGTH: usize = 255;
+pub const MAX_MESSAGE_LENGTH: usize = 2000;
+pub const MAX_DURATION_MS: f64 = 600_000.0;
+pub const HTTP_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+// Kinds of stored events
+pub const KIND_NAVIGATION: &str = "navigation";
+pub const KIND_API_LATENCY: &str = "api_latency";
+pub const KIND_ERROR: &str = "error";
+pub const KIND_CSP_VIOLATION: &str = "csp_violation";
+
+#[derive(Serialize, Debug, Clone, Queryable)]
+#[diesel(table_name = telemetry_events)]
+pub struct TelemetryEvent {
+    pub id: i32,
+    pub kind: String,
+    pub name: String,
+    pub method: Option<String>,
+    pub status: Option<i32>,
+    pub duration_ms: Option<f64>,
+    pub message: Option<String>,
+    pub received_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Insertable)]
+#[diesel(table_name = telemetry_events)]
+pub struct NewTelemetryEvent {
+    pub kind: String,
+    pub name: String,
+    pub method: Option<String>,
+    pub status: Option<i32>,
+    pub duration_ms: Option<f64>,
+    pub message: Option<String>,
+}
+
+// Batch sent by the frontend. Unknown fields are rejected so that identifiers cannot sneak in unnoticed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryBatch {
+    pub events: Vec<TelemetryEventPayload>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TelemetryEventPayload {
+    Navigation { path: String, duration_ms: f64 },
+    ApiLatency { method: String, endpoint: String, status: u16, duration_ms: f64 },
+    Error { source: String, message: String },
+}
+
+impl TelemetryBatch {
+    // Checks the batch against the limits above, returning the events to store on success
+    pub fn validate(self) -> Result<Vec<NewTelemetryEvent>, String> {
+        if self.events.len() > MAX_EVENTS_PER_BATCH {
+            return Err(format!("A batch may hold at most {} events", MAX_EVENTS_PER_BATCH));
+        }
+
+        self.events.into_iter()
+            .enumerate()
+            .map(|(index, event)| event.validate().map_err(|err| format!("Invalid event at index {}: {}", index, err)))
+            .collect()
+    }
+}
+
+impl TelemetryEventPayload {
+    fn validate(self) -> Result<NewTelemetryEvent, String> {
+        match self {
+            TelemetryEventPayload::Navigation { path, duration_ms } => {
+                validate_path("path", &path)?;
+                validate_duration(duration_ms)?;
+                Ok(NewTelemetryEvent {
+                    kind: KIND_NAVIGATION.to_string(),
+                    name: path,
+                    method: None,
+                    status: None,
+                    duration_ms: Some(duration_ms),
+                    message: None,
+                })
+            }
+            TelemetryEventPayload::ApiLatency { method, endpoint, status, duration_ms } => {
+                let method = method.to_uppercase();
+                if !HTTP_METHODS.contains(&method.as_str()) {
+                    return Err(format!("Unknown method '{}'", method));
+                }
+                // Status 0 is how the frontend reports requests that never got a response
+                if status != 0 && !(100..=599).contains(&status) {
+                    return Err(format!("Invalid status {}", status));
+                }
+                validate_path("endpoint", &endpoint)?;
+                validate_duration(duration_ms)?;
+                Ok(NewTelemetryEvent {
+                    kind: KIND_API_LATENCY.to_string(),
+                    name: endpoint,
+                    method: Some(method),
+                    status: Some(status as i32),
+                    duration_ms: Some(duration_ms),
+                    message: None,
+                })
+            }
+            TelemetryEventPayload::Error { source, message } => {
+                if source.is_empty() || source.len() > MAX_NAME_LENGTH {
+                    return Err("Field 'source' must be between 1 and 255 characters".to_string());
+                }
+                if message.chars().count() > MAX_MESSAGE_LENGTH {
+                    return Err(format!("Field 'message' may hold at most {} characters", MAX_MESSAGE_LENGTH));
+                }
+                Ok(NewTelemetryEvent {
+                    kind: KIND_ERROR.to_string(),
+                    name: source,
+                    method: None,
+                    status: None,
+                    duration_ms: None,
+                    message: Some(message),
+                })
+            }
+        }
+    }
+}
+
+fn validate_path(field: &str, path: &str) -> Result<(), String> {
+    if !path.starts_with('/') || path.len() > MAX_NAME_LENGTH {
+        return Err(format!("Field '{}' must be an absolute path of at most {} characters", field, MAX_NAME_LENGTH));
+    }
+    // Paths are expected in their route shape, without query strings that could carry personal data
+    if path.contains(['?', '#']) {
+        return Err(format!("Field '{}' may not carry a query string or fragment", field));
+    }
+    Ok(())
+}
+
+fn validate_duration(duration_ms: f64) -> Result<(), String> {
+    if !duration_ms.is_finite() || !(0.0..=MAX_DURATION_MS).contains(&duration_ms) {
+        return Err(format!("Field 'duration_ms' must be between 0 and {}", MAX_DURATION_MS));
+    }
+    Ok(())
+}
+
+// Legacy CSP report, sent with content type application/csp-report
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyCspReport {
+    #[serde(rename = "csp-report")]
+    pub csp_report: LegacyCspViolation,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LegacyCspViolation {
+    pub document_uri: Option<String>,
+    pub violated_directive: Option<String>,
+    pub effective_directive: Option<String>,
+    pub blocked_uri: Option<String>,
+}
+
+// Report sent through the Reporting API, with content type application/reports+json
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportingApiReport {
+    #[serde(rename = "type")]
+    pub report_type: String,
+    pub body: ReportingApiViolation,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingApiViolation {
+    #[serde(rename = "documentURL")]
+    pub document_url: Option<String>,
+    pub effective_directive: Option<String>,
+    #[serde(rename = "blockedURL")]
+    pub blocked_url: Option<String>,
+}
+
+// Parses either report format, returning one event per CSP violation
+pub fn parse_csp_report(body: &[u8]) -> Result<Vec<NewTelemetryEvent>, String> {
+    if let Ok(report) = serde_json::from_slice::<LegacyCspReport>(body) {
+        let violation = report.csp_report;
+        let directive = violation.effective_directive.or(violation.violated_directive);
+        return csp_violation(directive, violation.blocked_uri, violation.document_uri).map(|event| vec![event]);
+    }
+
+    let reports = serde_json::from_slice::<Vec<ReportingApiReport>>(body)
+        .map_err(|_| "Body is neither a CSP report nor a list of Reporting API reports".to_string())?;
+    if reports.len() > MAX_EVENTS_PER_BATCH {
+        return Err(format!("A batch may hold at most {} reports", MAX_EVENTS_PER_BATCH));
+    }
+
+    // Other report types (deprecations, interventions) may share the endpoint and are ignored
+    reports.into_iter()
+        .filter(|report| report.report_type == "csp-violation")
+        .map(|report| csp_violation(report.body.effective_directive, report.body.blocked_url, report.body.document_url))
+        .collect()
+}
+
+fn csp_violation(directive: Option<String>, blocked: Option<String>, document: Option<String>) -> Result<NewTelemetryEvent, String> {
+    let directive = directive
+        .filter(|directive| !directive.is_empty() && directive.len() <= MAX_NAME_LENGTH)
+        .ok_or("CSP report is missing a valid directive")?;
+
+    let message = format!(
+        "Blocked '{}' on '{}'",
+        strip_query(blocked.as_deref().unwrap_or("unknown")),
+        strip_query(document.as_deref().unwrap_or("unknown"))
+    );
+
+    Ok(NewTelemetryEvent {
+        kind: KIND_CSP_VIOLATION.to_string(),
+        name: directive,
+        method: None,
+        status: None,
+        duration_ms: None,
+        message: Some(message.chars().take(MAX_MESSAGE_LENGTH).collect()),
+    })
+}
+
+// Query strings and fragments are dropped from reported URIs, as they may carry tokens or personal data
+fn strip_query(uri: &str) -> &str {
+    uri.split(['?', '#']).next().unwrap_or("")
+}
+
+#[derive(Serialize, Debug, Clone, QueryableByName)]
+pub struct TimingSummary {
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub method: Option<String>,
+    #[diesel(sql_type = Varchar)]
+    pub name: String,
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+    #[diesel(sql_type = Double)]
+    pub avg_ms: f64,
+    #[diesel(sql_type = Double)]
+    pub p95_ms: f64,
+    #[diesel(sql_type = BigInt)]
+    pub failures: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NamedCount {
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TelemetryStats {
+    pub since: NaiveDateTime,
+    pub counts: Vec<NamedCount>,
+    pub api_latency: Vec<TimingSummary>,
+    pub navigation: Vec<TimingSummary>,
+    pub recent_errors: Vec<TelemetryEvent>,
+    pub csp_violations: Vec<NamedCount>,
+}
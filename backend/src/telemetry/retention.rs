@@ -0,0 +1,35 @@
+use std::time::Duration;
+use crate::{
+    common::{
+        db::ConnectionPool,
+        jobs::{run_blocking, spawn_periodic},
+        util::load_optional_environment_variable,
+    },
+    telemetry::service::service::TelemetryEventsTable,
+};
+
+const RETENTION_PERIOD: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+// Periodically removes telemetry older than TELEMETRY_RETENTION_DAYS (default 30)
+pub fn spawn_telemetry_retention(pool: ConnectionPool) {
+    let retention_days = load_optional_environment_variable("TELEMETRY_RETENTION_DAYS")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+
+    spawn_periodic("telemetry-retention", RETENTION_PERIOD, move || {
+        let pool = pool.clone();
+        async move {
+            let outcome = run_blocking(move || {
+                let cutoff = chrono::Utc::now().naive_utc() - chrono::Duration::days(retention_days);
+                let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+                TelemetryEventsTable::new(connection).delete_older_than(cutoff)
+                    .map_err(|err| format!("Failed to prune telemetry: {}", err))
+            }).await;
+
+            if let Err(err) = outcome {
+                eprintln!("{}", err);
+            }
+        }
+    });
+}
@@ -0,0 +1,275 @@
+pub mod router {
+    use std::time::Duration;
+    use serde_derive::Deserialize;
+    use serde_json::{json, Value};
+    use axum::{
+        Router, body::Bytes, http::StatusCode, Json, response::IntoResponse, extract::{DefaultBodyLimit, Query, State}, middleware,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::require_admin,
+            rate_limit::{rate_limit, RateLimiter},
+            util::load_optional_environment_variable
+        },
+        telemetry::{
+            service::service::TelemetryEventsTable as telemetryEventsTable,
+            model::{parse_csp_report, NewTelemetryEvent, TelemetryBatch}
+        }
+    };
+
+    // Reports are small - anything larger is most likely not coming from our frontend or a browser
+    const MAX_BODY_BYTES: usize = 64 * 1024;
+
+    const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 60;
+    const DEFAULT_STATS_HOURS: i64 = 24;
+    const MAX_STATS_HOURS: i64 = 24 * 30;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn telemetry_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Reports are sent by anonymous browsers (navigator.sendBeacon cannot carry a bearer token), so these are
+        // public and rate limited per client instead
+        let public_routes = Router::new()
+            .route("/telemetry", axum::routing::post(post_telemetry_handler))
+            .route("/csp-report", axum::routing::post(post_csp_report_handler))
+            .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+            .layer(middleware::from_fn_with_state(telemetry_rate_limiter(), rate_limit));
+
+        let admin_routes = Router::new()
+            .route("/admin/stats", axum::routing::get(get_stats_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(public_routes)
+            .merge(admin_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // Requests per minute and client, configured through TELEMETRY_RATE_LIMIT
+    fn telemetry_rate_limiter() -> RateLimiter {
+        let limit = load_optional_environment_variable("TELEMETRY_RATE_LIMIT")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_RATE_LIMIT_PER_MINUTE);
+
+        RateLimiter::new(limit, Duration::from_secs(60))
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct StatsParams {
+        pub hours: Option<i64>,
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    // The body is read as raw bytes, as sendBeacon posts JSON with a text/plain content type
+    pub async fn post_telemetry_handler(
+        State(shared_state): State<ConnectionPool>,
+        body: Bytes,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let batch = serde_json::from_slice::<TelemetryBatch>(&body)
+            .map_err(|err| (StatusCode::BAD_REQUEST, Json(json!({"error": format!("Malformed telemetry batch: {}", err)}))))?;
+
+        let new_events = batch.validate()
+            .map_err(|err| (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))))?;
+
+        store_events(shared_state, new_events)
+    }
+
+    // Accepts both the legacy application/csp-report format and Reporting API batches
+    pub async fn post_csp_report_handler(
+        State(shared_state): State<ConnectionPool>,
+        body: Bytes,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let new_events = parse_csp_report(&body)
+            .map_err(|err| (StatusCode::BAD_REQUEST, Json(json!({"error": err}))))?;
+
+        store_events(shared_state, new_events)
+    }
+
+    pub async fn get_stats_handler(
+        State(shared_state): State<ConnectionPool>,
+        Query(params): Query<StatsParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let hours = params.hours.unwrap_or(DEFAULT_STATS_HOURS).clamp(1, MAX_STATS_HOURS);
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match telemetryEventsTable::new(connection).stats(chrono::Utc::now().naive_utc() - chrono::Duration::hours(hours)) {
+            Ok(stats) => Ok((StatusCode::OK, Json(json!({"window_hours": hours, "telemetry": stats})))),
+            Err(err) => {
+                eprintln!("Error aggregating telemetry: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to aggregate telemetry"}))))
+            }
+        }
+    }
+
+    fn store_events(shared_state: ConnectionPool, new_events: Vec<NewTelemetryEvent>) -> Result<StatusCode, (StatusCode, Json<Value>)> {
+        if new_events.is_empty() {
+            return Ok(StatusCode::NO_CONTENT);
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match telemetryEventsTable::new(connection).create_all(&new_events) {
+            Ok(_) => Ok(StatusCode::NO_CONTENT),
+            Err(err) => {
+                eprintln!("Error storing telemetry: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to store telemetry"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use serde_json::json;
+        use tower::ServiceExt;
+        use crate::{
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            telemetry::router::router::telemetry_route,
+            users::model::UserRole
+        };
+
+        #[tokio::test]
+        async fn post_telemetry_returns_204_and_rate_limit_headers() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = telemetry_route(connection_pool);
+
+            let batch = json!({"events": [
+                {"type": "navigation", "path": "/empires", "duration_ms": 42.5},
+                {"type": "api_latency", "method": "GET", "endpoint": "/empires/:id", "status": 200, "duration_ms": 12.0},
+                {"type": "error", "source": "panic", "message": "index out of bounds"}
+            ]});
+
+            // sendBeacon posts the batch as text/plain
+            let request = Request::builder()
+                .uri("/telemetry")
+                .method("POST")
+                .header("content-type", "text/plain;charset=UTF-8")
+                .body(Body::from(batch.to_string()))
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 204 and that the client is told how many requests remain
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+            assert!(response.headers().contains_key("X-RateLimit-Remaining"));
+        }
+
+        #[tokio::test]
+        async fn post_telemetry_returns_422_on_path_with_query_string() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = telemetry_route(connection_pool);
+
+            let batch = json!({"events": [{"type": "navigation", "path": "/users?email=someone@concord.gov", "duration_ms": 10.0}]});
+
+            let request = Request::builder()
+                .uri("/telemetry")
+                .method("POST")
+                .body(Body::from(batch.to_string()))
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 422
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        #[tokio::test]
+        async fn post_csp_report_is_counted_in_admin_stats() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = telemetry_route(connection_pool.clone());
+
+            let report = json!({"csp-report": {
+                "document-uri": "https://empires.example/locations?token=secret",
+                "violated-directive": "script-src-elem",
+                "effective-directive": "script-src-elem",
+                "blocked-uri": "https://evil.example/miner.js"
+            }});
+
+            let request = Request::builder()
+                .uri("/csp-report")
+                .method("POST")
+                .header("content-type", "application/csp-report")
+                .body(Body::from(report.to_string()))
+                .unwrap();
+
+            // Send the report through the service and assert that it was accepted
+            let response = service.clone()
+                .oneshot(request)
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "telemetri.admin@concord.gov", UserRole::ADMIN);
+
+            let request = Request::builder()
+                .uri("/admin/stats?hours=1")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 200
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Extract body from response
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that the violation is counted under its directive
+            let violations = response_json["telemetry"]["csp_violations"].as_array().unwrap();
+            assert!(violations.iter().any(|violation| violation["name"] == "script-src-elem"));
+        }
+
+        #[tokio::test]
+        async fn get_stats_returns_401_for_editor() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = telemetry_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "telemetri.redaktor@concord.gov", UserRole::EDITOR);
+
+            let request = Request::builder()
+                .uri("/admin/stats")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::empty())
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 401
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+pub mod service {
+    use chrono::NaiveDateTime;
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+        sql_types::{BigInt, Timestamp, Varchar},
+    };
+    use crate::{
+        telemetry::model::{
+            NamedCount, NewTelemetryEvent, TelemetryEvent, TelemetryStats, TimingSummary,
+            KIND_API_LATENCY, KIND_CSP_VIOLATION, KIND_ERROR, KIND_NAVIGATION,
+        },
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    // Number of rows returned per section of the stats
+    const STATS_LIMIT: i64 = 20;
+
+    pub struct TelemetryEventsTable {
+        connection: PooledPg,
+    }
+
+    impl TelemetryEventsTable {
+        pub fn new(connection: PooledPg) -> TelemetryEventsTable {
+            TelemetryEventsTable { connection }
+        }
+
+        pub fn create_all(&mut self, new_events: &[NewTelemetryEvent]) -> Result<usize, diesel::result::Error> {
+            use schema::telemetry_events;
+
+            diesel::insert_into(telemetry_events::table)
+                .values(new_events)
+                .execute(&mut self.connection)
+        }
+
+        // Aggregates the events received since the given point in time
+        pub fn stats(&mut self, since: NaiveDateTime) -> Result<TelemetryStats, diesel::result::Error> {
+            Ok(TelemetryStats {
+                since,
+                counts: self.count_by_name(None, since)?,
+                api_latency: self.timings(KIND_API_LATENCY, since)?,
+                navigation: self.timings(KIND_NAVIGATION, since)?,
+                recent_errors: self.recent(KIND_ERROR, since)?,
+                csp_violations: self.count_by_name(Some(KIND_CSP_VIOLATION), since)?,
+            })
+        }
+
+        // Removes events received before the given point in time, returning the number of removed rows
+        pub fn delete_older_than(&mut self, cutoff: NaiveDateTime) -> Result<usize, diesel::result::Error> {
+            use schema::telemetry_events;
+
+            diesel::delete(telemetry_events::table.filter(telemetry_events::received_at.lt(cutoff)))
+                .execute(&mut self.connection)
+        }
+
+        // Counts per kind, or per name within a kind when one is given
+        fn count_by_name(&mut self, kind: Option<&str>, since: NaiveDateTime) -> Result<Vec<NamedCount>, diesel::result::Error> {
+            use schema::telemetry_events;
+
+            let rows = match kind {
+                None => telemetry_events::table
+                    .filter(telemetry_events::received_at.ge(since))
+                    .group_by(telemetry_events::kind)
+                    .select((telemetry_events::kind, diesel::dsl::count_star()))
+                    .order(diesel::dsl::count_star().desc())
+                    .load::<(String, i64)>(&mut self.connection)?,
+                Some(kind) => telemetry_events::table
+                    .filter(telemetry_events::kind.eq(kind))
+                    .filter(telemetry_events::received_at.ge(since))
+                    .group_by(telemetry_events::name)
+                    .select((telemetry_events::name, diesel::dsl::count_star()))
+                    .order(diesel::dsl::count_star().desc())
+                    .limit(STATS_LIMIT)
+                    .load::<(String, i64)>(&mut self.connection)?,
+            };
+
+            Ok(rows.into_iter().map(|(name, count)| NamedCount { name, count }).collect())
+        }
+
+        // Count, mean and 95th percentile of the durations per method and name, busiest first.
+        // Requests without a response (status 0) and server errors are counted as failures.
+        fn timings(&mut self, kind: &str, since: NaiveDateTime) -> Result<Vec<TimingSummary>, diesel::result::Error> {
+            diesel::sql_query(
+                "SELECT method, name, COUNT(*) AS count, AVG(duration_ms) AS avg_ms, \
+                        percentile_cont(0.95) WITHIN GROUP (ORDER BY duration_ms) AS p95_ms, \
+                        COUNT(*) FILTER (WHERE status = 0 OR status >= 500) AS failures \
+                 FROM telemetry_events \
+                 WHERE kind = $1 AND received_at >= $2 AND duration_ms IS NOT NULL \
+                 GROUP BY method, name \
+                 ORDER BY count DESC, name \
+                 LIMIT $3"
+            )
+                .bind::<Varchar, _>(kind)
+                .bind::<Timestamp, _>(since)
+                .bind::<BigInt, _>(STATS_LIMIT)
+                .load::<TimingSummary>(&mut self.connection)
+        }
+
+        fn recent(&mut self, kind: &str, since: NaiveDateTime) -> Result<Vec<TelemetryEvent>, diesel::result::Error> {
+            use schema::telemetry_events;
+
+            telemetry_events::table
+                .filter(telemetry_events::kind.eq(kind))
+                .filter(telemetry_events::received_at.ge(since))
+                .order(telemetry_events::id.desc())
+                .limit(STATS_LIMIT)
+                .load::<TelemetryEvent>(&mut self.connection)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_derive::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+use serde_json::{Map, Value};
+use crate::common::util::serialize_json_text;
+
+// Resources templates may be defined for
+pub const TEMPLATE_RESOURCES: [&str; 2] = ["empire", "ship"];
+
+// Fields of a resource a template may prefill
+fn template_fields(resource: &str) -> &'static [&'static str] {
+    match resource {
+        "empire" => &["name", "slogan", "location_id", "description"],
+        "ship" => &["name", "category", "description"],
+        _ => &[],
+    }
+}
+
+// Length of the column a string field is stored in, for those that are limited
+fn max_length(resource: &str, field: &str) -> Option<usize> {
+    match (resource, field) {
+        ("empire", "name" | "slogan") | ("ship", "name") => Some(100),
+        ("ship", "category") => Some(50),
+        _ => None,
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+pub struct Template {
+    pub id: i32,
+    pub name: String,
+    pub resource: String,
+    // Stored as JSON text, but served as an object
+    #[serde(serialize_with = "serialize_json_text")]
+    #[schema(value_type = Object)]
+    pub fields: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl Template {
+    // Fills in the fields absent from 'body' with the values of the template
+    pub fn prefill(&self, body: Map<String, Value>) -> Map<String, Value> {
+        let mut prefilled: Map<String, Value> = serde_json::from_str(&self.fields).unwrap_or_default();
+        prefilled.extend(body);
+        prefilled
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpsertTemplate {
+    pub name: String,
+    pub resource: String,
+    pub fields: Map<String, Value>,
+}
+
+impl UpsertTemplate {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.chars().count() > 100 {
+            return Err("Invalid input for field 'name'".to_string());
+        }
+        if !TEMPLATE_RESOURCES.contains(&self.resource.as_str()) {
+            return Err(format!("Unknown resource '{}'", self.resource));
+        }
+
+        let allowed = template_fields(&self.resource);
+        for (field, value) in &self.fields {
+            if !allowed.contains(&field.as_str()) {
+                return Err(format!("Field '{}' cannot be prefilled for {}s", field, self.resource));
+            }
+            let valid = match field.as_str() {
+                "location_id" => value.as_i64().is_some_and(|id| i32::try_from(id).is_ok()),
+                _ => value.as_str().is_some_and(|value| {
+                    max_length(&self.resource, field).is_none_or(|max| value.chars().count() <= max)
+                }),
+            };
+            if !valid {
+                return Err(format!("Invalid value for field '{}'", field));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Query parameter of the create endpoints selecting the template to prefill the record with
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TemplateParams {
+    pub template_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TemplateListParams {
+    pub resource: Option<String>,
+}
@@ -0,0 +1,369 @@
+pub mod router {
+    use serde_json::{json, Map, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::{Query, State}, extract, middleware,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::{require_admin, require_reader}
+        },
+        templates::{
+            service::service::TemplatesTable as templatesTable,
+            model::{TemplateListParams, TemplateParams, UpsertTemplate}
+        }
+    };
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn templates_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Anyone signed in may pick a template when creating a record
+        let read_routes = Router::new()
+            .route("/templates", axum::routing::get(get_all_templates_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
+
+        // Templates are defined by administrators only
+        let admin_routes = Router::new()
+            .route("/admin/templates", axum::routing::get(get_all_templates_handler).post(create_template_handler))
+            .route("/admin/templates/:template_id", axum::routing::get(read_template_handler)
+                .put(update_template_handler)
+                .delete(delete_template_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(read_routes)
+            .merge(admin_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // Fills in the fields absent from the body of a create request with those of the template selected through
+    // '?template_id=', if any. The connection is released again before the record itself is created.
+    pub fn prefill_from_template(
+        shared_state: &ConnectionPool,
+        params: &TemplateParams,
+        resource: &str,
+        body: Map<String, Value>,
+    ) -> Result<Map<String, Value>, (StatusCode, Json<Value>)> {
+        let Some(template_id) = params.template_id else {
+            return Ok(body);
+        };
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match templatesTable::new(connection).get(template_id) {
+            Ok(Some(template)) if template.resource == resource => Ok(template.prefill(body)),
+            Ok(Some(template)) => Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({"error": format!("Template {} is for {}s, not {}s", template_id, template.resource, resource)})),
+            )),
+            Ok(None) => Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Template not found"})))),
+            Err(err) => {
+                eprintln!("Error reading template: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read template"}))))
+            }
+        }
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/templates",
+        tag = "templates",
+        operation_id = "get_all_templates",
+        params(TemplateListParams),
+        responses(
+            (status = 200, body = [Template])
+        )
+    )]
+    pub async fn get_all_templates_handler(
+        State(shared_state): State<ConnectionPool>,
+        Query(params): Query<TemplateListParams>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match templatesTable::new(connection).get_all(params.resource.as_deref()) {
+            Ok(templates) => Ok((StatusCode::OK, Json(templates))),
+            Err(err) => {
+                eprintln!("Error fetching all templates: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch templates"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/admin/templates",
+        tag = "templates",
+        operation_id = "create_template",
+        request_body = UpsertTemplate,
+        responses(
+            (status = 201, body = Template),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn create_template_handler(
+        State(shared_state): State<ConnectionPool>,
+        Json(upsert_template): Json<UpsertTemplate>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        if let Err(err) = upsert_template.validate() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match templatesTable::new(connection).create(upsert_template) {
+            Ok(new_template) => Ok((StatusCode::CREATED, Json(new_template))),
+            Err(err) => {
+                eprintln!("Error creating template: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create template"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/admin/templates/{template_id}",
+        tag = "templates",
+        operation_id = "read_template",
+        params(("template_id" = i32, Path, description = "ID of the template")),
+        responses(
+            (status = 200, body = Template),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn read_template_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (template_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match templatesTable::new(connection).get(template_id) {
+            Ok(Some(template)) => Ok((StatusCode::OK, Json(template))),
+            Ok(None) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "Template not found"})))),
+            Err(err) => {
+                eprintln!("Error reading template: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read template"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        put,
+        path = "/admin/templates/{template_id}",
+        tag = "templates",
+        operation_id = "update_template",
+        params(("template_id" = i32, Path, description = "ID of the template")),
+        request_body = UpsertTemplate,
+        responses(
+            (status = 200, body = Template),
+            (status = 404, body = ApiError),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn update_template_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Json(upsert_template): Json<UpsertTemplate>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (template_id, ) = path.0;
+        if let Err(err) = upsert_template.validate() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err}))));
+        }
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match templatesTable::new(connection).update(template_id, upsert_template) {
+            Ok(updated_template) => Ok((StatusCode::OK, Json(updated_template))),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Template not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error updating template: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update template"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/admin/templates/{template_id}",
+        tag = "templates",
+        operation_id = "delete_template",
+        params(("template_id" = i32, Path, description = "ID of the template")),
+        responses(
+            (status = 204),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn delete_template_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (template_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match templatesTable::new(connection).delete(template_id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Template not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error deleting template: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to delete template"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use serde_json::{json, Map, Value};
+        use tower::ServiceExt;
+        use crate::{
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            empires::router::router::empires_route,
+            locations::{
+                model::UpsertLocation,
+                service::service::LocationsTable
+            },
+            templates::{
+                model::UpsertTemplate,
+                service::service::TemplatesTable
+            },
+            users::model::UserRole
+        };
+
+        fn fields(value: Value) -> Map<String, Value> {
+            value.as_object().unwrap().clone()
+        }
+
+        #[tokio::test]
+        async fn create_empire_prefills_fields_absent_from_body_with_template() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Sarum".to_string(), area: "Sarum Prime".to_string() })
+                .unwrap();
+            let template = TemplatesTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertTemplate {
+                    name: "Heir family".to_string(),
+                    resource: "empire".to_string(),
+                    fields: fields(json!({
+                        "name": "Unnamed family",
+                        "slogan": "Amarr victor",
+                        "location_id": location.id,
+                        "description": "One of the heir families"
+                    })),
+                })
+                .unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool.clone(), "mal@concord.gov", UserRole::WRITER).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/empires?template_id={}", template.id))
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"name": "Sarum Family"}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            // Assert that the body takes precedence over the template
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let empire: Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(empire["name"], "Sarum Family");
+            assert_eq!(empire["slogan"], "Amarr victor");
+            assert_eq!(empire["location_id"], location.id);
+            assert_eq!(empire["description"], "One of the heir families");
+        }
+
+        #[tokio::test]
+        async fn create_empire_rejects_template_of_other_resource() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let template = TemplatesTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertTemplate {
+                    name: "Frigate".to_string(),
+                    resource: "ship".to_string(),
+                    fields: fields(json!({"category": "Frigate"})),
+                })
+                .unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool.clone(), "ardishapur@concord.gov", UserRole::WRITER).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/empires?template_id={}", template.id))
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"name": "Ardishapur Family"}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        #[tokio::test]
+        async fn create_empire_rejects_template_of_deleted_location() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = empires_route(connection_pool.clone());
+
+            let location = LocationsTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertLocation { star_system: "Kador".to_string(), area: "Kador Prime".to_string() })
+                .unwrap();
+            let template = TemplatesTable::new(connection_pool.pool.get().unwrap())
+                .create(UpsertTemplate {
+                    name: "Kador outpost".to_string(),
+                    resource: "empire".to_string(),
+                    fields: fields(json!({"slogan": "Amarr victor", "location_id": location.id, "description": "An outpost"})),
+                })
+                .unwrap();
+            LocationsTable::new(connection_pool.pool.get().unwrap()).delete(location.id).unwrap();
+
+            let bearer_token = create_user_and_generate_token(connection_pool.clone(), "kador@concord.gov", UserRole::WRITER).unwrap();
+
+            let request = Request::builder()
+                .uri(format!("/empires?template_id={}", template.id))
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({"name": "Kador Family"}).to_string()))
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        #[test]
+        fn templates_with_values_longer_than_their_columns_are_rejected() {
+            let template = |values: Value| UpsertTemplate { name: "Long".to_string(), resource: "ship".to_string(), fields: fields(values) };
+
+            assert!(template(json!({"name": "N".repeat(100), "category": "C".repeat(50)})).validate().is_ok());
+            assert!(template(json!({"name": "N".repeat(101)})).validate().is_err());
+            assert!(template(json!({"category": "C".repeat(51)})).validate().is_err());
+            assert!(template(json!({"description": "D".repeat(1000)})).validate().is_ok());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+pub mod service {
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::{
+        templates::model::{Template, UpsertTemplate},
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct TemplatesTable {
+        connection: PooledPg,
+    }
+
+    impl TemplatesTable {
+        pub fn new(connection: PooledPg) -> TemplatesTable {
+            TemplatesTable { connection }
+        }
+
+        pub fn create(&mut self, upsert_template: UpsertTemplate) -> Result<Template, diesel::result::Error> {
+            use schema::templates;
+
+            diesel::insert_into(templates::table)
+                .values((
+                    templates::name.eq(&upsert_template.name),
+                    templates::resource.eq(&upsert_template.resource),
+                    templates::fields.eq(serde_json::Value::Object(upsert_template.fields).to_string()),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        // Templates ordered by name, optionally only those of the given resource
+        pub fn get_all(&mut self, resource: Option<&str>) -> Result<Vec<Template>, diesel::result::Error> {
+            use schema::templates;
+
+            let mut query = templates::table
+                .order((templates::name, templates::id))
+                .into_boxed();
+
+            if let Some(resource) = resource {
+                query = query.filter(templates::resource.eq(resource));
+            }
+
+            query.load::<Template>(&mut self.connection)
+        }
+
+        pub fn get(&mut self, template_id: i32) -> Result<Option<Template>, diesel::result::Error> {
+            use schema::templates;
+
+            templates::table
+                .find(template_id)
+                .get_result(&mut self.connection)
+                .optional()
+        }
+
+        pub fn update(&mut self, template_id: i32, upsert_template: UpsertTemplate) -> Result<Template, diesel::result::Error> {
+            use schema::templates;
+
+            diesel::update(templates::table.find(template_id))
+                .set((
+                    templates::name.eq(&upsert_template.name),
+                    templates::resource.eq(&upsert_template.resource),
+                    templates::fields.eq(serde_json::Value::Object(upsert_template.fields).to_string()),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        pub fn delete(&mut self, template_id: i32) -> Result<(), diesel::result::Error> {
+            use schema::templates;
+
+            let deleted = diesel::delete(templates::table.find(template_id))
+                .execute(&mut self.connection)?;
+
+            if deleted == 0 {
+                return Err(diesel::result::Error::NotFound);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::{
+    common::security::from_hex,
+    schema::{recovery_codes, totp_credentials}
+};
+
+// Authenticator secret of a user. Enrollment is pending until a first code has been confirmed.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = totp_credentials)]
+pub struct TotpCredential {
+    pub user_id: i32,
+    // Hex encoded
+    pub secret: String,
+    pub confirmed_at: Option<NaiveDateTime>,
+}
+
+impl TotpCredential {
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+
+    pub fn secret_bytes(&self) -> Vec<u8> {
+        from_hex(&self.secret).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = totp_credentials)]
+pub struct NewTotpCredential {
+    pub user_id: i32,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = recovery_codes)]
+pub struct NewRecoveryCode {
+    pub user_id: i32,
+    pub code_hash: String,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TwoFactorCode {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+// Secret of a pending enrollment, both as base32 for manual entry and as a key URI for QR codes
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TwoFactorSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
@@ -0,0 +1,430 @@
+pub mod router {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::State, middleware, Extension,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::{require_reader, AuthorizedUser},
+            security::{hash_recovery_code, random_bytes, to_hex},
+            totp::{base32_encode, matching_step, otpauth_uri}
+        },
+        two_factor::{
+            service::service::TwoFactorTable as twoFactorTable,
+            model::{NewTotpCredential, RecoveryCodes, TwoFactorCode, TwoFactorSetup, TwoFactorStatus}
+        },
+        users::model::User
+    };
+
+    // Name authenticator apps list the account under
+    const TOTP_ISSUER: &str = "Empires API";
+
+    // Length of generated secrets - 160 bits, as recommended by RFC 4226
+    const SECRET_LENGTH: usize = 20;
+
+    const RECOVERY_CODE_COUNT: usize = 10;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn two_factor_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Every signed-in user manages their own second factor
+        let read_routes = Router::new()
+            .route("/users/me/two-factor", axum::routing::get(get_two_factor_status_handler))
+            .route("/users/me/two-factor/setup", axum::routing::post(setup_two_factor_handler))
+            .route("/users/me/two-factor/confirm", axum::routing::post(confirm_two_factor_handler))
+            .route("/users/me/two-factor/disable", axum::routing::post(disable_two_factor_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_reader));
+
+        Router::new()
+            .merge(read_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    fn unix_time() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("SystemTime before UNIX EPOCH")
+            .as_secs()
+    }
+
+    // Formats five random bytes as two groups of four base32 characters, e.g. 'K3QF-7ZP2'
+    fn generate_recovery_code() -> Result<String, (StatusCode, Json<Value>)> {
+        let encoded = base32_encode(&random_bytes(5)?);
+        Ok(format!("{}-{}", &encoded[..4], &encoded[4..]))
+    }
+
+    // Rejects logins of users with two-factor authentication enabled unless a valid code is presented.
+    // Both errors carry 'two_factor_required' so that clients know to prompt for a code.
+    pub fn enforce_second_factor(
+        shared_state: &ConnectionPool,
+        user: &User,
+        code: Option<&str>,
+    ) -> Result<(), (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut two_factor = twoFactorTable::new(connection);
+
+        let credential = match two_factor.get(user.id) {
+            Ok(Some(credential)) if credential.is_confirmed() => credential,
+            Ok(_) => return Ok(()),
+            Err(err) => {
+                eprintln!("Error reading two-factor credential: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to verify two-factor code"}))));
+            }
+        };
+
+        let Some(code) = code.filter(|code| !code.trim().is_empty()) else {
+            return Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Two-factor code required", "two_factor_required": true}))));
+        };
+
+        match two_factor.verify_code(&credential, code, unix_time()) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Invalid two-factor code", "two_factor_required": true})))),
+            Err(err) => {
+                eprintln!("Error verifying two-factor code: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to verify two-factor code"}))))
+            }
+        }
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/users/me/two-factor",
+        tag = "two-factor",
+        operation_id = "get_two_factor_status",
+        responses(
+            (status = 200, body = TwoFactorStatus)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn get_two_factor_status_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut two_factor = twoFactorTable::new(connection);
+
+        let status = two_factor.get(user.id).and_then(|credential| {
+            let enabled = credential.map(|credential| credential.is_confirmed()).unwrap_or(false);
+            let recovery_codes_remaining = if enabled { two_factor.remaining_recovery_codes(user.id)? } else { 0 };
+            Ok(TwoFactorStatus { enabled, recovery_codes_remaining })
+        });
+
+        match status {
+            Ok(status) => Ok((StatusCode::OK, Json(status))),
+            Err(err) => {
+                eprintln!("Error reading two-factor status: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read two-factor status"}))))
+            }
+        }
+    }
+
+    // Generates a new secret. Two-factor authentication is not enforced until a code for it has been confirmed.
+    #[utoipa::path(
+        post,
+        path = "/users/me/two-factor/setup",
+        tag = "two-factor",
+        operation_id = "setup_two_factor",
+        responses(
+            (status = 200, body = TwoFactorSetup),
+            (status = 409, description = "Two-factor authentication is enabled already", body = ApiError)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn setup_two_factor_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+        let secret = random_bytes(SECRET_LENGTH)?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut two_factor = twoFactorTable::new(connection);
+
+        match two_factor.get(user.id) {
+            Ok(Some(credential)) if credential.is_confirmed() => {
+                return Err((StatusCode::CONFLICT, Json(json!({"error": "Two-factor authentication is already enabled"}))));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("Error reading two-factor credential: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to start two-factor setup"}))));
+            }
+        }
+
+        match two_factor.start_enrollment(NewTotpCredential { user_id: user.id, secret: to_hex(&secret) }) {
+            Ok(_) => Ok((StatusCode::OK, Json(TwoFactorSetup {
+                secret: base32_encode(&secret),
+                otpauth_uri: otpauth_uri(TOTP_ISSUER, &user.email, &secret),
+            }))),
+            Err(err) => {
+                eprintln!("Error starting two-factor setup: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to start two-factor setup"}))))
+            }
+        }
+    }
+
+    // Enables two-factor authentication once the user proves their authenticator produces valid codes,
+    // returning recovery codes. Only their hashes are stored, so they cannot be retrieved again.
+    #[utoipa::path(
+        post,
+        path = "/users/me/two-factor/confirm",
+        tag = "two-factor",
+        operation_id = "confirm_two_factor",
+        request_body = TwoFactorCode,
+        responses(
+            (status = 200, body = RecoveryCodes),
+            (status = 409, body = ApiError),
+            (status = 422, description = "Invalid code", body = ApiError)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn confirm_two_factor_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Json(body): Json<TwoFactorCode>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+
+        let recovery_codes = (0..RECOVERY_CODE_COUNT)
+            .map(|_| generate_recovery_code())
+            .collect::<Result<Vec<String>, _>>()?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut two_factor = twoFactorTable::new(connection);
+
+        let credential = match two_factor.get(user.id) {
+            Ok(Some(credential)) if credential.is_confirmed() => {
+                return Err((StatusCode::CONFLICT, Json(json!({"error": "Two-factor authentication is already enabled"}))));
+            }
+            Ok(Some(credential)) => credential,
+            Ok(None) => return Err((StatusCode::CONFLICT, Json(json!({"error": "No two-factor setup in progress"})))),
+            Err(err) => {
+                eprintln!("Error reading two-factor credential: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to confirm two-factor setup"}))));
+            }
+        };
+
+        let Some(step) = matching_step(&credential.secret_bytes(), &body.code, unix_time()) else {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Invalid code"}))));
+        };
+
+        let recovery_code_hashes = recovery_codes.iter().map(|code| hash_recovery_code(code)).collect();
+
+        match two_factor.confirm(user.id, step as i64, recovery_code_hashes) {
+            Ok(_) => Ok((StatusCode::OK, Json(RecoveryCodes { recovery_codes }))),
+            Err(err) => {
+                eprintln!("Error confirming two-factor setup: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to confirm two-factor setup"}))))
+            }
+        }
+    }
+
+    // Turning two-factor authentication off requires a current code, so that a stolen token alone does not suffice
+    #[utoipa::path(
+        post,
+        path = "/users/me/two-factor/disable",
+        tag = "two-factor",
+        operation_id = "disable_two_factor",
+        request_body = TwoFactorCode,
+        responses(
+            (status = 204),
+            (status = 409, body = ApiError),
+            (status = 422, description = "Invalid code", body = ApiError)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn disable_two_factor_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        Json(body): Json<TwoFactorCode>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = authorized.require_user()?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+        let mut two_factor = twoFactorTable::new(connection);
+
+        let credential = match two_factor.get(user.id) {
+            Ok(Some(credential)) if credential.is_confirmed() => credential,
+            Ok(_) => return Err((StatusCode::CONFLICT, Json(json!({"error": "Two-factor authentication is not enabled"})))),
+            Err(err) => {
+                eprintln!("Error reading two-factor credential: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to disable two-factor authentication"}))));
+            }
+        };
+
+        let result = two_factor.verify_code(&credential, &body.code, unix_time()).and_then(|valid| {
+            if valid { two_factor.disable(user.id).map(|_| true) } else { Ok(false) }
+        });
+
+        match result {
+            Ok(true) => Ok((StatusCode::NO_CONTENT, ())),
+            Ok(false) => Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Invalid code"})))),
+            Err(err) => {
+                eprintln!("Error disabling two-factor authentication: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to disable two-factor authentication"}))))
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode},
+            Router
+        };
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use crate::{
+            two_factor::{router::router::two_factor_route, service::service::TwoFactorTable},
+            users::{model::UserRole, router::router::users_route, service::service::UsersTable},
+            common::{
+                db::{create_shared_connection_pool, ConnectionPool},
+                totp::{code_at, TOTP_STEP_SECONDS},
+                security::from_hex,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            }
+        };
+
+        const PASSWORD: &str = "StålGardinerFunkerFjell53";
+
+        fn app(connection_pool: ConnectionPool) -> Router {
+            users_route(connection_pool.clone()).merge(two_factor_route(connection_pool))
+        }
+
+        fn current_step() -> u64 {
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() / TOTP_STEP_SECONDS
+        }
+
+        // Code the user's authenticator would show at the given step
+        fn authenticator_code(connection_pool: &ConnectionPool, email: &str, step: u64) -> String {
+            let user_id = {
+                let connection = connection_pool.pool.get().unwrap();
+                UsersTable::new(connection).get_by_email(email.to_string()).unwrap().unwrap().id
+            };
+            let connection = connection_pool.pool.get().unwrap();
+            let credential = TwoFactorTable::new(connection).get(user_id).unwrap().unwrap();
+            code_at(&from_hex(&credential.secret).unwrap(), step)
+        }
+
+        fn post(uri: &str, token: &str, body: Value) -> Request<Body> {
+            Request::builder()
+                .uri(uri)
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        }
+
+        fn login(email: &str, otp: Option<&str>) -> Request<Body> {
+            Request::builder()
+                .uri("/users/login")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"email": email, "password": PASSWORD, "otp": otp}).to_string()))
+                .unwrap()
+        }
+
+        async fn json_body(response: axum::response::Response) -> Value {
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            serde_json::from_slice(&body).unwrap()
+        }
+
+        #[tokio::test]
+        async fn login_requires_second_factor_once_enabled() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = app(connection_pool.clone());
+
+            let email = "tofaktor.leser@concord.gov";
+            let token = create_user_and_generate_token(connection_pool.clone(), email, UserRole::READER).unwrap();
+
+            // Start the setup and assert that the key URI names the account
+            let response = service.clone().oneshot(post("/users/me/two-factor/setup", &token, json!({}))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let setup = json_body(response).await;
+            assert!(setup["otpauth_uri"].as_str().unwrap().contains(setup["secret"].as_str().unwrap()));
+
+            // Assert that login does not ask for a code while the setup is unconfirmed
+            let response = service.clone().oneshot(login(email, None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Assert that a wrong code does not confirm the setup
+            let step = current_step();
+            let wrong_code = if authenticator_code(&connection_pool, email, step) == "000000" { "111111" } else { "000000" };
+            let response = service.clone().oneshot(post("/users/me/two-factor/confirm", &token, json!({"code": wrong_code}))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            // Confirm with the current code
+            let code = authenticator_code(&connection_pool, email, step);
+            let response = service.clone().oneshot(post("/users/me/two-factor/confirm", &token, json!({"code": code}))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let recovery_codes = json_body(response).await["recovery_codes"].as_array().unwrap().clone();
+            assert_eq!(recovery_codes.len(), 10);
+
+            // Assert that login now asks for a code, and that the code used for confirmation cannot be replayed
+            let response = service.clone().oneshot(login(email, None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(json_body(response).await["two_factor_required"], true);
+
+            let response = service.clone().oneshot(login(email, Some(&code))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            // Assert that the next code is accepted, being within the allowed clock skew
+            let next_code = authenticator_code(&connection_pool, email, step + 1);
+            let response = service.clone().oneshot(login(email, Some(&next_code))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Assert that a recovery code works exactly once, regardless of case
+            let recovery_code = recovery_codes[0].as_str().unwrap().to_lowercase();
+            let response = service.clone().oneshot(login(email, Some(&recovery_code))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = service.oneshot(login(email, Some(&recovery_code))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        #[tokio::test]
+        async fn disable_requires_valid_code() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = app(connection_pool.clone());
+
+            let email = "tofaktor.av@concord.gov";
+            let token = create_user_and_generate_token(connection_pool.clone(), email, UserRole::READER).unwrap();
+
+            service.clone().oneshot(post("/users/me/two-factor/setup", &token, json!({}))).await.unwrap();
+            let code = authenticator_code(&connection_pool, email, current_step());
+            let response = service.clone().oneshot(post("/users/me/two-factor/confirm", &token, json!({"code": code}))).await.unwrap();
+            let recovery_codes = json_body(response).await["recovery_codes"].clone();
+
+            // Assert that the setup cannot be restarted while enabled
+            let response = service.clone().oneshot(post("/users/me/two-factor/setup", &token, json!({}))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+
+            let response = service.clone().oneshot(post("/users/me/two-factor/disable", &token, json!({"code": "ZZZZ-ZZZZ"}))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let response = service.clone().oneshot(post("/users/me/two-factor/disable", &token, json!({"code": recovery_codes[0]}))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+            // Assert that login no longer asks for a code
+            let response = service.oneshot(login(email, None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+pub mod service {
+    use chrono::Utc;
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+        upsert::excluded,
+    };
+    use crate::{
+        common::{security::hash_recovery_code, totp::matching_step},
+        two_factor::model::{NewRecoveryCode, NewTotpCredential, TotpCredential},
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct TwoFactorTable {
+        connection: PooledPg,
+    }
+
+    impl TwoFactorTable {
+        pub fn new(connection: PooledPg) -> TwoFactorTable {
+            TwoFactorTable { connection }
+        }
+
+        pub fn get(&mut self, user_id: i32) -> Result<Option<TotpCredential>, diesel::result::Error> {
+            use schema::totp_credentials;
+
+            totp_credentials::table
+                .find(user_id)
+                .select(TotpCredential::as_select())
+                .first(&mut self.connection)
+                .optional()
+        }
+
+        // Starts enrollment with a new secret, replacing any enrollment left unfinished
+        pub fn start_enrollment(&mut self, new_credential: NewTotpCredential) -> Result<TotpCredential, diesel::result::Error> {
+            use schema::totp_credentials;
+
+            diesel::insert_into(totp_credentials::table)
+                .values(&new_credential)
+                .on_conflict(totp_credentials::user_id)
+                .do_update()
+                .set((
+                    totp_credentials::secret.eq(excluded(totp_credentials::secret)),
+                    totp_credentials::confirmed_at.eq(None::<chrono::NaiveDateTime>),
+                    totp_credentials::last_used_step.eq(None::<i64>),
+                    totp_credentials::created_at.eq(Utc::now().naive_utc()),
+                ))
+                .returning(TotpCredential::as_returning())
+                .get_result(&mut self.connection)
+        }
+
+        // Enables two-factor authentication, replacing any previous recovery codes
+        pub fn confirm(&mut self, user_id: i32, step: i64, recovery_code_hashes: Vec<String>) -> Result<(), diesel::result::Error> {
+            use schema::{recovery_codes, totp_credentials};
+
+            self.connection.transaction(|connection| {
+                diesel::update(totp_credentials::table.find(user_id))
+                    .set((
+                        totp_credentials::confirmed_at.eq(Some(Utc::now().naive_utc())),
+                        totp_credentials::last_used_step.eq(Some(step)),
+                    ))
+                    .execute(connection)?;
+
+                diesel::delete(recovery_codes::table.filter(recovery_codes::user_id.eq(user_id)))
+                    .execute(connection)?;
+
+                let new_codes: Vec<NewRecoveryCode> = recovery_code_hashes
+                    .into_iter()
+                    .map(|code_hash| NewRecoveryCode { user_id, code_hash })
+                    .collect();
+
+                diesel::insert_into(recovery_codes::table)
+                    .values(&new_codes)
+                    .execute(connection)?;
+
+                Ok(())
+            })
+        }
+
+        // Checks an authenticator code or, failing that, a recovery code. Accepted codes are used up - authenticator
+        // codes by advancing the last used step, so that neither they nor older ones are accepted again, and
+        // recovery codes by marking them as used.
+        pub fn verify_code(&mut self, credential: &TotpCredential, code: &str, unix_time: u64) -> Result<bool, diesel::result::Error> {
+            use schema::{recovery_codes, totp_credentials};
+
+            if let Some(step) = matching_step(&credential.secret_bytes(), code, unix_time) {
+                let step = step as i64;
+                let updated_rows = diesel::update(totp_credentials::table
+                    .find(credential.user_id)
+                    .filter(totp_credentials::last_used_step.is_null().or(totp_credentials::last_used_step.lt(step))))
+                    .set(totp_credentials::last_used_step.eq(Some(step)))
+                    .execute(&mut self.connection)?;
+
+                return Ok(updated_rows > 0);
+            }
+
+            let updated_rows = diesel::update(recovery_codes::table
+                .filter(recovery_codes::user_id.eq(credential.user_id))
+                .filter(recovery_codes::code_hash.eq(hash_recovery_code(code)))
+                .filter(recovery_codes::used_at.is_null()))
+                .set(recovery_codes::used_at.eq(Some(Utc::now().naive_utc())))
+                .execute(&mut self.connection)?;
+
+            Ok(updated_rows > 0)
+        }
+
+        pub fn remaining_recovery_codes(&mut self, user_id: i32) -> Result<i64, diesel::result::Error> {
+            use schema::recovery_codes;
+
+            recovery_codes::table
+                .filter(recovery_codes::user_id.eq(user_id))
+                .filter(recovery_codes::used_at.is_null())
+                .count()
+                .get_result(&mut self.connection)
+        }
+
+        pub fn disable(&mut self, user_id: i32) -> Result<(), diesel::result::Error> {
+            use schema::{recovery_codes, totp_credentials};
+
+            self.connection.transaction(|connection| {
+                diesel::delete(recovery_codes::table.filter(recovery_codes::user_id.eq(user_id)))
+                    .execute(connection)?;
+                diesel::delete(totp_credentials::table.find(user_id))
+                    .execute(connection)?;
+                Ok(())
+            })
+        }
+    }
+}
@@ -1,17 +1,22 @@
 use std::fmt;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use regex::Regex;
 use serde_derive::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
 use crate::schema::users;
 
-#[derive(Debug, Clone, Serialize, Queryable)]
+#[derive(Debug, Clone, Serialize, Queryable, ToSchema)]
 #[diesel(table_name = users)]
 pub struct User {
     pub id: i32,
     pub email: String,
     pub password: String,
     pub fullname: String,
-    pub role: String
+    pub role: String,
+    pub created_at: NaiveDateTime,
+    // Deactivated users keep their records but can neither sign in nor use tokens issued before
+    pub deactivated: bool
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -46,7 +51,7 @@ pub fn string_to_user_role(role: String) -> UserRole {
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable, ToSchema)]
 #[diesel(table_name = users)]
 pub struct UpsertUser {
     pub email: String,
@@ -62,15 +67,53 @@ impl UpsertUser {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Query of the user listing. 'active=true' leaves out deactivated users, e.g. for picking whom to assign something to.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersParams {
+    #[serde(default)]
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LoginUser {
+    // Email and password are left out when signing in with an ID token
+    #[serde(default)]
     pub email: String,
-    pub password: String
+    #[serde(default)]
+    pub password: String,
+    // Current authenticator code or an unused recovery code - required once two-factor authentication is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp: Option<String>,
+    // ID token of an OpenID Connect provider, exchanged for a session when AUTH_PROVIDER is 'oidc'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>
+}
+
+// Login response body for schema version 2 and up - version 1 clients receive the bare token string
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LoginResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: u64
+}
+
+// Token of a session opened by an admin on behalf of another user, along with whom it acts as
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImpersonationResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub user_id: i32,
+    pub email: String
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
     pub exp: i64,
-    pub role: UserRole
+    pub role: UserRole,
+    // Token id of the session the token was issued for - absent on tokens not tied to a session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>
 }
\ No newline at end of file
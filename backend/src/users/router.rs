@@ -1,18 +1,30 @@
 pub mod router {
     use serde_json::{json, Value};
-    use bcrypt::verify;
-    use axum::{extract, extract::State, http::StatusCode, Json, response::IntoResponse, Router, middleware};
+    use std::net::SocketAddr;
+    use axum::{extract, extract::{ConnectInfo, Query, State}, http::{HeaderMap, StatusCode}, Json, response::IntoResponse, Router, middleware, Extension};
     use crate::{
         common::{
+            auth_provider::auth_provider,
             db::ConnectionPool,
-            security::{hash_password, generate_token},
-            middleware::{require_reader, require_editor, require_admin}
+            security::{enforce_active_account, hash_password, TOKEN_LIFETIME_SECONDS},
+            middleware::{AuthorizedUser, require_reader, require_editor, require_admin},
+            permissions::enforce_field_permissions,
+            rate_limit::{client_address, progressive_login_delay, LoginDelays},
+            versioning::{SchemaVersion, SCHEMA_VERSION_HEADER}
         },
+        sessions::{router::router::open_session, service::service::SessionsTable},
+        two_factor::router::router::enforce_second_factor,
         users::{
             service::service::UsersTable,
             model::{
+                User,
                 UpsertUser,
                 LoginUser,
+                LoginResponse,
+                ListUsersParams,
+                ImpersonationResponse,
+                string_to_user_role,
+                UserRole,
             },
         },
     };
@@ -22,8 +34,12 @@ pub mod router {
     pub fn users_route(shared_connection_pool: ConnectionPool) -> Router {
         // Public routes (no authentication required)
         let public_routes = Router::new()
-            .route("/users", axum::routing::post(create_user_handler))  // Registration
-            .route("/users/login", axum::routing::post(login_user_handler));  // Login
+            .route("/users", axum::routing::post(create_user_handler));  // Registration
+
+        // Login, with every failed attempt delaying the next one of the same client or account
+        let login_routes = Router::new()
+            .route("/users/login", axum::routing::post(login_user_handler))
+            .layer(middleware::from_fn_with_state(LoginDelays::from_env(), progressive_login_delay));
         
         // Protected routes requiring authentication
         let read_routes = Router::new()
@@ -39,27 +55,47 @@ pub mod router {
             .route("/users/:user_id", axum::routing::delete(delete_user_handler))
             .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
 
+        // Deactivation keeps the user and their records, but locks them out
+        let activation_routes = Router::new()
+            .route("/users/:user_id/deactivate", axum::routing::post(deactivate_user_handler))
+            .route("/users/:user_id/reactivate", axum::routing::post(reactivate_user_handler))
+            .route("/admin/users/:user_id/impersonate", axum::routing::post(impersonate_user_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
         // Merge all route groups
         Router::new()
             .merge(public_routes)
+            .merge(login_routes)
             .merge(read_routes)
             .merge(update_routes)
             .merge(delete_routes)
+            .merge(activation_routes)
             .with_state(shared_connection_pool)
     }
 
 
     // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
 
+    #[utoipa::path(
+        get,
+        path = "/users",
+        tag = "users",
+        operation_id = "list_users",
+        params(ListUsersParams),
+        responses(
+            (status = 200, body = [User])
+        )
+    )]
     pub async fn list_users_handler(
         State(shared_state): State<ConnectionPool>,
+        Query(params): Query<ListUsersParams>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
         let mut users = UsersTable::new(connection);
 
-        match users.list() {
+        match users.list_by_status(params.active.map(|active| !active)) {
             Ok(users_list) => Ok((StatusCode::OK, Json(users_list))),
             Err(err) => {
                 eprintln!("Error listing users: {:?}", err);
@@ -68,6 +104,18 @@ pub mod router {
         }
     }
 
+    #[utoipa::path(
+        post,
+        path = "/users",
+        tag = "users",
+        operation_id = "create_user",
+        request_body = UpsertUser,
+        responses(
+            (status = 201, description = "The registered user", body = User),
+            (status = 422, description = "Invalid email, or the email is taken", body = ApiError)
+        ),
+        security(())
+    )]
     pub async fn create_user_handler(
         State(shared_state): State<ConnectionPool>,
         Json(mut body): Json<UpsertUser>,
@@ -94,6 +142,17 @@ pub mod router {
         body.is_valid_email()
     }
 
+    #[utoipa::path(
+        get,
+        path = "/users/{user_id}",
+        tag = "users",
+        operation_id = "get_user",
+        params(("user_id" = i32, Path, description = "ID of the user")),
+        responses(
+            (status = 200, body = User),
+            (status = 404, body = ApiError)
+        )
+    )]
     pub async fn get_user_handler(
         State(shared_state): State<ConnectionPool>,
         path: extract::Path<(i32,)>,
@@ -120,13 +179,36 @@ pub mod router {
         }
     }
 
+    #[utoipa::path(
+        put,
+        path = "/users/{user_id}",
+        tag = "users",
+        operation_id = "update_user",
+        params(("user_id" = i32, Path, description = "ID of the user")),
+        request_body = UpsertUser,
+        responses(
+            (status = 200, body = User),
+            (status = 403, description = "A field was changed that the role may not change", body = ApiError),
+            (status = 404, body = ApiError)
+        )
+    )]
     pub async fn update_user_handler(
         State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
         path: extract::Path<(i32,)>,
         Json(update_user): Json<UpsertUser>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
         let (user_id,) = path.0;
 
+        match UsersTable::new(shared_state.pool.get().expect("Failed to acquire connection from pool")).get(user_id) {
+            Ok(Some(current)) => enforce_field_permissions(&authorized, "users", &current, &update_user)?,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "User not found"})))),
+            Err(err) => {
+                eprintln!("Error reading user: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update user"}))));
+            }
+        }
+
         let connection = shared_state.pool.get()
             .expect("Failed to acquire connection from pool");
 
@@ -144,6 +226,16 @@ pub mod router {
         }
     }
 
+    #[utoipa::path(
+        delete,
+        path = "/users/{user_id}",
+        tag = "users",
+        operation_id = "delete_user",
+        params(("user_id" = i32, Path, description = "ID of the user")),
+        responses(
+            (status = 204)
+        )
+    )]
     pub async fn delete_user_handler(
         State(shared_state): State<ConnectionPool>,
         path: extract::Path<(i32,)>,
@@ -164,30 +256,204 @@ pub mod router {
         }
     }
 
-    pub async fn login_user_handler(
+    #[utoipa::path(
+        post,
+        path = "/users/{user_id}/deactivate",
+        tag = "users",
+        operation_id = "deactivate_user",
+        params(("user_id" = i32, Path, description = "ID of the user")),
+        responses(
+            (status = 200, body = User),
+            (status = 404, body = ApiError),
+            (status = 422, description = "Admins cannot deactivate themselves", body = ApiError)
+        )
+    )]
+    pub async fn deactivate_user_handler(
         State(shared_state): State<ConnectionPool>,
-        Json(body): Json<LoginUser>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32,)>,
     ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-        let connection = shared_state.pool.get()
-            .expect("Failed to acquire connection from pool");
+        let (user_id,) = path.0;
 
-        match UsersTable::new(connection).get_by_email(body.email.clone()) {
-            Ok(Some(user)) if body.email == user.email => {
-                return if verify(&body.password, &user.password).unwrap_or(false) {
-                    if let Some(token) = generate_token(&user).ok() {
-                        Ok((StatusCode::OK, Json(token)))
-                    } else {
-                        Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to generate token"}))))
-                    }
-                } else {
-                    Err((StatusCode::UNAUTHORIZED, Json(json!({"error": "Wrong password"}))))
-                }
+        // Admins locking themselves out would leave no one to undo it
+        if authorized.user.as_ref().is_some_and(|user| user.id == user_id) {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "You cannot deactivate your own account"}))));
+        }
+
+        change_activation(&shared_state, user_id, false)
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/users/{user_id}/reactivate",
+        tag = "users",
+        operation_id = "reactivate_user",
+        params(("user_id" = i32, Path, description = "ID of the user")),
+        responses(
+            (status = 200, body = User),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn reactivate_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32,)>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (user_id,) = path.0;
+
+        change_activation(&shared_state, user_id, true)
+    }
+
+    fn change_activation(shared_state: &ConnectionPool, user_id: i32, active: bool) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let user = match UsersTable::new(shared_state.pool.get().expect("Failed to acquire connection from pool")).get(user_id) {
+            Ok(Some(user)) => user,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "User not found"})))),
+            Err(err) => {
+                eprintln!("Error reading user: {:?}", err);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read user"}))));
             }
-            Ok(Some(_)) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "User not found"})))),
-            Ok(None) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "User not found"})))),
+        };
+
+        match set_user_active(shared_state, user, active) {
+            Ok(user) => Ok((StatusCode::OK, Json(user))),
+            Err(err) => {
+                eprintln!("Error updating user: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update user"}))))
+            }
+        }
+    }
+
+    // Activates or deactivates the user. Deactivation also revokes their sessions, so that reactivated users sign in afresh.
+    pub fn set_user_active(shared_state: &ConnectionPool, user: User, active: bool) -> Result<User, diesel::result::Error> {
+        if user.deactivated != active {
+            return Ok(user);
+        }
+
+        let user = UsersTable::new(shared_state.pool.get().expect("Failed to acquire connection from pool"))
+            .set_deactivated(user.id, !active)?;
+
+        if !active {
+            let connection = shared_state.pool.get()
+                .expect("Failed to acquire connection from pool");
+
+            if let Err(err) = SessionsTable::new(connection).revoke_all(user.id) {
+                eprintln!("Failed to revoke sessions of deactivated user {}: {:?}", user.id, err);
+            }
+        }
+        eprintln!("User {} {}", user.id, if active { "reactivated" } else { "deactivated" });
+
+        Ok(user)
+    }
+
+    // Opens a session as another user, for seeing the application the way they do. The session is theirs - it is listed
+    // among their sessions and they may revoke it - and every impersonation is logged along with the admin behind it.
+    #[utoipa::path(
+        post,
+        path = "/admin/users/{user_id}/impersonate",
+        tag = "users",
+        operation_id = "impersonate_user",
+        params(("user_id" = i32, Path, description = "ID of the user")),
+        responses(
+            (status = 200, description = "Token of a session opened as the user", body = ImpersonationResponse),
+            (status = 403, description = "Admins cannot be impersonated", body = ApiError),
+            (status = 404, body = ApiError),
+            (status = 422, description = "The user is the admin themselves, or deactivated", body = ApiError)
+        ),
+        security(("bearer" = []))
+    )]
+    pub async fn impersonate_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        Extension(authorized): Extension<AuthorizedUser>,
+        path: extract::Path<(i32,)>,
+        headers: HeaderMap,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (user_id,) = path.0;
+        let admin = authorized.require_user()?;
+
+        let user = match UsersTable::new(shared_state.pool.get().expect("Failed to acquire connection from pool")).get(user_id) {
+            Ok(Some(user)) => user,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "User not found"})))),
             Err(err) => {
                 eprintln!("Error reading user: {:?}", err);
-                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read user"}))))
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read user"}))));
+            }
+        };
+        if user.id == admin.id {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "You cannot impersonate yourself"}))));
+        }
+        if user.deactivated {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "Deactivated users cannot be impersonated"}))));
+        }
+        // Acting as another admin would let one admin's actions pass for those of another
+        if string_to_user_role(user.role.clone()) == UserRole::ADMIN {
+            return Err((StatusCode::FORBIDDEN, Json(json!({"error": "Admins cannot be impersonated"}))));
+        }
+
+        let remote = connect_info.map(|ConnectInfo(address)| address);
+        let token = open_session(&shared_state, &user, &headers, remote, Some(admin.id))?;
+        eprintln!(
+            "Audit: admin {} ('{}') opened a session impersonating user {} ('{}') from {}",
+            admin.id,
+            admin.email,
+            user.id,
+            user.email,
+            client_address(&headers, remote).unwrap_or_else(|| "an unknown address".to_string())
+        );
+
+        Ok((StatusCode::OK, Json(ImpersonationResponse {
+            token,
+            token_type: "Bearer".to_string(),
+            expires_in: TOKEN_LIFETIME_SECONDS,
+            user_id: user.id,
+            email: user.email,
+        })))
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/users/login",
+        tag = "users",
+        operation_id = "login",
+        request_body = LoginUser,
+        responses(
+            (status = 200, description = "Token of the new session", body = LoginResponse),
+            (status = 401, description = "Wrong credentials, or a two-factor code is required", body = ApiError)
+        ),
+        security(())
+    )]
+    pub async fn login_user_handler(
+        State(shared_state): State<ConnectionPool>,
+        schema_version: SchemaVersion,
+        headers: HeaderMap,
+        connect_info: Option<ConnectInfo<SocketAddr>>,
+        Json(body): Json<LoginUser>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        // The provider releases its connection before the session is opened, as the pool may only hold a single one
+        let provider = auth_provider();
+        let user = provider.authenticate(&shared_state, &body).await?;
+        eprintln!("User '{}' authenticated by the {} provider", user.email, provider.name());
+        enforce_active_account(&user)?;
+
+        enforce_second_factor(&shared_state, &user, body.otp.as_deref())?;
+        let remote = connect_info.map(|ConnectInfo(address)| address);
+        let token = open_session(&shared_state, &user, &headers, remote, None)?;
+        Ok(login_response(schema_version, token))
+    }
+
+    // Serializes the login result according to the schema version declared by the client
+    fn login_response(schema_version: SchemaVersion, token: String) -> axum::response::Response {
+        let SchemaVersion(version) = schema_version;
+        let version_header = [(SCHEMA_VERSION_HEADER, version.to_string())];
+
+        match version {
+            1 => (StatusCode::OK, version_header, Json(token)).into_response(),
+            _ => {
+                let response = LoginResponse {
+                    token,
+                    token_type: "Bearer".to_string(),
+                    expires_in: TOKEN_LIFETIME_SECONDS,
+                };
+                (StatusCode::OK, version_header, Json(response)).into_response()
             }
         }
     }
@@ -201,6 +467,39 @@ pub mod router {
         use crate::{create_shared_connection_pool, load_environment_variable, users_route};
         use crate::users::model::UpsertUser;
         use crate::users::service::service::UsersTable;
+        use crate::common::security::hash_password;
+        use crate::common::db::ConnectionPool;
+        use crate::common::test_util::create_user_and_generate_token;
+        use crate::users::model::UserRole;
+        use crate::sessions::router::router::sessions_route;
+
+        // Helper method utilized to register a user with a hashed password and build a login request for it
+        fn create_user_and_build_login_request(connection_pool: ConnectionPool, email: &str, schema_version: Option<&str>) -> Request<Body> {
+            let password = "SnøskredVarsel42";
+            let mut new_user = UpsertUser {
+                email: email.to_string(),
+                password: password.to_string(),
+                fullname: "Versjon Versjonsen".to_string(),
+                role: "READER".to_string()
+            };
+            hash_password(&mut new_user).expect("Hash failed");
+
+            let connection = connection_pool.pool.get().expect("Failed to get connection");
+            UsersTable::new(connection).create(new_user).expect("Create user failed");
+
+            let mut request = Request::builder()
+                .uri("/users/login")
+                .method("POST")
+                .header("content-type", "application/json");
+
+            if let Some(version) = schema_version {
+                request = request.header("X-Api-Schema-Version", version);
+            }
+
+            request
+                .body(Body::from(json!({"email": email, "password": password}).to_string()))
+                .unwrap()
+        }
 
         #[tokio::test]
         async fn post_users_returns_201_on_valid_data() {
@@ -481,5 +780,183 @@ pub mod router {
             // Assert that the response is an array
             assert!(response_json.is_array());
         }
+
+        #[tokio::test]
+        async fn login_returns_token_string_for_schema_version_1() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 2);
+            let service = users_route(connection_pool.clone());
+
+            // Clients that do not declare a version are served schema version 1
+            let request = create_user_and_build_login_request(connection_pool, "versjon.en@skjema.no", None);
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 200 and that the declared version is echoed back
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers().get("X-Api-Schema-Version").unwrap(), "1");
+
+            // Extract body from response
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that the body is the bare token string
+            assert!(response_json.is_string());
+        }
+
+        #[tokio::test]
+        async fn login_returns_token_object_for_schema_version_2() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 2);
+            let service = users_route(connection_pool.clone());
+
+            let request = create_user_and_build_login_request(connection_pool, "versjon.to@skjema.no", Some("2"));
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 200 and that the declared version is echoed back
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers().get("X-Api-Schema-Version").unwrap(), "2");
+
+            // Extract body from response
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+            // Assert that the body is the token object
+            assert!(response_json["token"].is_string());
+            assert_eq!(response_json["token_type"], "Bearer");
+            assert_eq!(response_json["expires_in"], 3600);
+        }
+
+        #[tokio::test]
+        async fn login_returns_400_on_unsupported_schema_version() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 2);
+            let service = users_route(connection_pool.clone());
+
+            let request = create_user_and_build_login_request(connection_pool, "versjon.tusen@skjema.no", Some("1000"));
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 400
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn deactivated_users_are_locked_out_and_left_out_of_active_listing() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = users_route(connection_pool.clone());
+
+            let admin_token = create_user_and_generate_token(connection_pool.clone(), "deaktivering.admin@concord.gov", UserRole::ADMIN).unwrap();
+            let reader_token = create_user_and_generate_token(connection_pool.clone(), "deaktivert.leser@concord.gov", UserRole::READER).unwrap();
+            let reader_id = {
+                let connection = connection_pool.pool.get().expect("Failed to get connection");
+                UsersTable::new(connection).get_by_email("deaktivert.leser@concord.gov".to_string()).unwrap().unwrap().id
+            };
+
+            let request = |uri: String, method: &str, token: &str| Request::builder()
+                .uri(uri)
+                .method(method)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request(format!("/users/{}/deactivate", reader_id), "POST", &admin_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Assert that tokens issued before deactivation are rejected
+            let response = service.clone().oneshot(request("/users".to_string(), "GET", &reader_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            // Assert that the user is still listed, but not among active users
+            let response = service.clone().oneshot(request("/users?active=true".to_string(), "GET", &admin_token)).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let active_users: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+            assert!(active_users.iter().all(|user| user["id"] != reader_id));
+
+            let response = service.clone().oneshot(request(format!("/users/{}", reader_id), "GET", &admin_token)).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let user: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(user["deactivated"], true);
+
+            let response = service.clone().oneshot(request(format!("/users/{}/reactivate", reader_id), "POST", &admin_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = service.oneshot(request("/users".to_string(), "GET", &reader_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        #[tokio::test]
+        async fn impersonation_opens_a_session_with_the_role_of_the_impersonated_user() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = users_route(connection_pool.clone()).merge(sessions_route(connection_pool.clone()));
+
+            let admin_token = create_user_and_generate_token(connection_pool.clone(), "imitasjon.admin@concord.gov", UserRole::ADMIN).unwrap();
+            let reader_token = create_user_and_generate_token(connection_pool.clone(), "imitert.leser@concord.gov", UserRole::READER).unwrap();
+            create_user_and_generate_token(connection_pool.clone(), "imitert.admin@concord.gov", UserRole::ADMIN).unwrap();
+            let user_id = |email: &str| {
+                let connection = connection_pool.pool.get().expect("Failed to get connection");
+                UsersTable::new(connection).get_by_email(email.to_string()).unwrap().unwrap().id
+            };
+            let (admin_id, reader_id, other_admin_id) =
+                (user_id("imitasjon.admin@concord.gov"), user_id("imitert.leser@concord.gov"), user_id("imitert.admin@concord.gov"));
+
+            let request = |uri: String, method: &str, token: &str| Request::builder()
+                .uri(uri)
+                .method(method)
+                .header("Authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap();
+
+            // Assert that only admins may impersonate
+            let response = service.clone().oneshot(request(format!("/admin/users/{}/impersonate", reader_id), "POST", &reader_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let response = service.clone().oneshot(request(format!("/admin/users/{}/impersonate", reader_id), "POST", &admin_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let impersonation: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(impersonation["email"], "imitert.leser@concord.gov");
+            let impersonation_token = impersonation["token"].as_str().unwrap();
+
+            // Assert that the token acts with the role of the impersonated user rather than that of the admin
+            let response = service.clone().oneshot(request(format!("/users/{}", reader_id), "GET", impersonation_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = service.clone().oneshot(request(format!("/users/{}/deactivate", reader_id), "POST", impersonation_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            // Assert that the session records the admin behind it
+            let response = service.clone().oneshot(request("/users/me/sessions".to_string(), "GET", impersonation_token)).await.unwrap();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let sessions: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let current = sessions.as_array().unwrap().iter().find(|session| session["current"] == true).unwrap();
+            assert_eq!(current["impersonated_by"], admin_id);
+
+            // Assert that switching back revokes the impersonation session
+            let response = service.clone().oneshot(request("/users/me/sessions/revoke-current".to_string(), "POST", impersonation_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+            let response = service.clone().oneshot(request(format!("/users/{}", reader_id), "GET", impersonation_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            // Assert that admins cannot be impersonated
+            let response = service.oneshot(request(format!("/admin/users/{}/impersonate", other_admin_id), "POST", &admin_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
     }
 }
@@ -10,7 +10,7 @@ pub mod service {
     use crate::{
         users::model::{User, UpsertUser},
         schema,
-        common::error::CustomError
+        common::{budget::escape_like, error::CustomError}
     };
 
     type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
@@ -25,6 +25,11 @@ pub mod service {
         }
 
         pub fn create(&mut self, create_user: UpsertUser) -> Result<User, CustomError> {
+            self.create_with_status(create_user, false)
+        }
+
+        // Creates the user already activated or deactivated, as identity systems may provision users of either state
+        pub fn create_with_status(&mut self, create_user: UpsertUser, deactivated: bool) -> Result<User, CustomError> {
             use schema::users;
 
             diesel::insert_into(users::table)
@@ -33,6 +38,7 @@ pub mod service {
                     users::password.eq(&create_user.password),
                     users::fullname.eq(&create_user.fullname),
                     users::role.eq(&create_user.role),
+                    users::deactivated.eq(deactivated),
                 ))
                 .get_result::<User>(&mut self.connection)
                 .map_err(|err| {
@@ -70,6 +76,18 @@ pub mod service {
             Ok(all_users)
         }
 
+        // Users who are deactivated or not, or all of them when 'deactivated' is not given
+        pub fn list_by_status(&mut self, deactivated: Option<bool>) -> Result<Vec<User>, Error> {
+            use schema::users;
+
+            let mut query = users::table.into_boxed();
+            if let Some(deactivated) = deactivated {
+                query = query.filter(users::deactivated.eq(deactivated));
+            }
+
+            query.load::<User>(&mut self.connection)
+        }
+
         pub fn update(&mut self, user_id: i32, update_user: UpsertUser) -> Result<User, Error> {
             use schema::users;
 
@@ -95,6 +113,48 @@ pub mod service {
             }
         }
 
+        // Users with the given email and deactivation state, by id, along with how many there are in total
+        pub fn search(&mut self, email: Option<&str>, deactivated: Option<bool>, offset: i64, limit: i64) -> Result<(Vec<User>, i64), Error> {
+            use schema::users;
+
+            let matching = || {
+                let mut query = users::table.into_boxed();
+                // Emails are matched regardless of case, as identity systems may not keep the case they were provisioned with
+                if let Some(email) = email {
+                    query = query.filter(users::email.ilike(escape_like(email)));
+                }
+                if let Some(deactivated) = deactivated {
+                    query = query.filter(users::deactivated.eq(deactivated));
+                }
+                query
+            };
+
+            let total = matching().count().get_result(&mut self.connection)?;
+            let users = matching()
+                .order(users::id)
+                .offset(offset)
+                .limit(limit)
+                .load::<User>(&mut self.connection)?;
+
+            Ok((users, total))
+        }
+
+        pub fn set_deactivated(&mut self, user_id: i32, deactivated: bool) -> Result<User, Error> {
+            use schema::users;
+
+            diesel::update(users::table.find(user_id))
+                .set(users::deactivated.eq(deactivated))
+                .get_result(&mut self.connection)
+        }
+
+        pub fn set_role(&mut self, user_id: i32, role: &str) -> Result<User, Error> {
+            use schema::users;
+
+            diesel::update(users::table.find(user_id))
+                .set(users::role.eq(role))
+                .get_result(&mut self.connection)
+        }
+
 
         pub fn delete(&mut self, user_id: i32) -> Result<(), diesel::result::Error> {
             use schema::users;
@@ -0,0 +1,71 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use crate::{
+    common::{
+        db::ConnectionPool,
+        jobs::run_blocking,
+        notify::post_webhook,
+    },
+    webhooks::{
+        model::{NewWebhookDelivery, Webhook, WebhookDelivery},
+        service::service::WebhooksTable,
+    },
+};
+
+// Notifies every webhook subscribed to the event in the background, so that slow endpoints never hold up the request
+pub fn dispatch_event(pool: ConnectionPool, event_type: &'static str, data: Value) {
+    tokio::spawn(async move {
+        let subscribed = run_blocking({
+            let pool = pool.clone();
+            move || {
+                let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+                WebhooksTable::new(connection).subscribed_to(event_type)
+                    .map_err(|err| format!("Failed to fetch webhooks for '{}': {}", event_type, err))
+            }
+        }).await;
+
+        let webhooks = match subscribed {
+            Ok(webhooks) => webhooks,
+            Err(err) => return eprintln!("{}", err),
+        };
+
+        let payload = json!({"event": event_type, "data": data, "sent_at": Utc::now().naive_utc()});
+        for webhook in webhooks {
+            if let Err(err) = deliver(pool.clone(), &webhook, event_type, &payload, None).await {
+                eprintln!("{}", err);
+            }
+        }
+    });
+}
+
+// POSTs the payload to the webhook and records the outcome. Failing to reach the endpoint is a recorded
+// delivery like any other - only failing to record it is an error.
+pub async fn deliver(
+    pool: ConnectionPool,
+    webhook: &Webhook,
+    event_type: &str,
+    payload: &Value,
+    redrive_of: Option<i32>,
+) -> Result<WebhookDelivery, String> {
+    let (status_code, error) = match post_webhook(&webhook.url, payload).await {
+        Ok(status) if (200..300).contains(&status) => (Some(status as i32), None),
+        Ok(status) => (Some(status as i32), Some(format!("Endpoint responded with status {}", status))),
+        Err(err) => (None, Some(err)),
+    };
+
+    let new_delivery = NewWebhookDelivery {
+        webhook_id: webhook.id,
+        event_type: event_type.to_string(),
+        payload: payload.to_string(),
+        status_code,
+        error,
+        redrive_of,
+        delivered_at: Utc::now().naive_utc(),
+    };
+
+    run_blocking(move || {
+        let connection = pool.pool.get().map_err(|err| format!("Failed to acquire connection: {}", err))?;
+        WebhooksTable::new(connection).record_delivery(new_delivery)
+            .map_err(|err| format!("Failed to record webhook delivery: {}", err))
+    }).await
+}
@@ -0,0 +1,81 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_derive::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::schema::webhook_deliveries;
+
+// Events a webhook may subscribe to
+pub const WEBHOOK_EVENTS: [&str; 6] = [
+    "empire.created",
+    "empire.updated",
+    "empire.deleted",
+    "location.created",
+    "location.updated",
+    "location.deleted",
+];
+
+// Sent by 'test delivery' regardless of the subscribed events
+pub const TEST_EVENT: &str = "webhook.test";
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+pub struct Webhook {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpsertWebhook {
+    pub url: String,
+    pub event_types: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl UpsertWebhook {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.url.starts_with("http://") || self.url.starts_with("https://")) || self.url.len() > 255 {
+            return Err("Invalid input for field 'url'".to_string());
+        }
+        if self.event_types.is_empty() {
+            return Err("At least one event type must be selected".to_string());
+        }
+        if let Some(unknown) = self.event_types.iter().find(|event| !WEBHOOK_EVENTS.contains(&event.as_str())) {
+            return Err(format!("Unknown event type '{}'", unknown));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Queryable, ToSchema)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+    // None when the endpoint could not be reached at all - see 'error'
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    // Delivery this one was a redrive of
+    pub redrive_of: Option<i32>,
+    pub delivered_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub redrive_of: Option<i32>,
+    pub delivered_at: NaiveDateTime,
+}
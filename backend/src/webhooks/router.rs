@@ -0,0 +1,460 @@
+pub mod router {
+    use chrono::Utc;
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::StatusCode, Json, response::IntoResponse, extract::State, extract, middleware,
+    };
+    use crate::{
+        common::{
+            db::ConnectionPool,
+            middleware::require_admin
+        },
+        webhooks::{
+            service::service::WebhooksTable as webhooksTable,
+            model::{UpsertWebhook, Webhook, TEST_EVENT, WEBHOOK_EVENTS},
+            dispatch::deliver
+        }
+    };
+
+    // Number of deliveries listed in the history of a webhook
+    const DELIVERY_HISTORY_LIMIT: i64 = 50;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn webhooks_route(shared_connection_pool: ConnectionPool) -> Router {
+        // Webhooks are managed by administrators only
+        let admin_routes = Router::new()
+            .route("/admin/webhooks", axum::routing::get(get_all_webhooks_handler).post(create_webhook_handler))
+            .route("/admin/webhooks/events", axum::routing::get(get_webhook_events_handler))
+            .route("/admin/webhooks/:webhook_id", axum::routing::get(read_webhook_handler)
+                .put(update_webhook_handler)
+                .delete(delete_webhook_handler))
+            .route("/admin/webhooks/:webhook_id/deliveries", axum::routing::get(get_deliveries_handler))
+            .route("/admin/webhooks/:webhook_id/test", axum::routing::post(test_webhook_handler))
+            .route("/admin/webhooks/:webhook_id/deliveries/:delivery_id/redrive", axum::routing::post(redrive_delivery_handler))
+            .layer(middleware::from_fn_with_state(shared_connection_pool.clone(), require_admin));
+
+        Router::new()
+            .merge(admin_routes)
+            .with_state(shared_connection_pool)
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    #[utoipa::path(
+        get,
+        path = "/admin/webhooks/events",
+        tag = "webhooks",
+        operation_id = "get_webhook_events",
+        responses(
+            (status = 200, description = "Event types webhooks may subscribe to", body = [String])
+        )
+    )]
+    pub async fn get_webhook_events_handler() -> impl IntoResponse {
+        (StatusCode::OK, Json(WEBHOOK_EVENTS))
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/admin/webhooks",
+        tag = "webhooks",
+        operation_id = "get_all_webhooks",
+        responses(
+            (status = 200, body = [Webhook])
+        )
+    )]
+    pub async fn get_all_webhooks_handler(
+        State(shared_state): State<ConnectionPool>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match webhooksTable::new(connection).get_all() {
+            Ok(webhooks) => Ok((StatusCode::OK, Json(webhooks))),
+            Err(err) => {
+                eprintln!("Error fetching all webhooks: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch webhooks"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/admin/webhooks",
+        tag = "webhooks",
+        operation_id = "create_webhook",
+        request_body = UpsertWebhook,
+        responses(
+            (status = 201, body = Webhook),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn create_webhook_handler(
+        State(shared_state): State<ConnectionPool>,
+        Json(upsert_webhook): Json<UpsertWebhook>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        validate_webhook(&upsert_webhook)?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match webhooksTable::new(connection).create(upsert_webhook) {
+            Ok(new_webhook) => Ok((StatusCode::CREATED, Json(new_webhook))),
+            Err(err) => {
+                eprintln!("Error creating webhook: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to create webhook"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/admin/webhooks/{webhook_id}",
+        tag = "webhooks",
+        operation_id = "read_webhook",
+        params(("webhook_id" = i32, Path, description = "ID of the webhook")),
+        responses(
+            (status = 200, body = Webhook),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn read_webhook_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (webhook_id, ) = path.0;
+
+        find_webhook(&shared_state, webhook_id).map(|webhook| (StatusCode::OK, Json(webhook)))
+    }
+
+    #[utoipa::path(
+        put,
+        path = "/admin/webhooks/{webhook_id}",
+        tag = "webhooks",
+        operation_id = "update_webhook",
+        params(("webhook_id" = i32, Path, description = "ID of the webhook")),
+        request_body = UpsertWebhook,
+        responses(
+            (status = 200, body = Webhook),
+            (status = 404, body = ApiError),
+            (status = 422, body = ApiError)
+        )
+    )]
+    pub async fn update_webhook_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+        Json(upsert_webhook): Json<UpsertWebhook>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (webhook_id, ) = path.0;
+        validate_webhook(&upsert_webhook)?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match webhooksTable::new(connection).update(webhook_id, upsert_webhook) {
+            Ok(updated_webhook) => Ok((StatusCode::OK, Json(updated_webhook))),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Webhook not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error updating webhook: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to update webhook"}))))
+            }
+        }
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/admin/webhooks/{webhook_id}",
+        tag = "webhooks",
+        operation_id = "delete_webhook",
+        params(("webhook_id" = i32, Path, description = "ID of the webhook")),
+        responses(
+            (status = 204),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn delete_webhook_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (webhook_id, ) = path.0;
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match webhooksTable::new(connection).delete(webhook_id) {
+            Ok(_) => Ok((StatusCode::NO_CONTENT, ())),
+            Err(diesel::result::Error::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json(json!({"error": "Webhook not found"}))))
+            },
+            Err(err) => {
+                eprintln!("Error deleting webhook: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to delete webhook"}))))
+            }
+        }
+    }
+
+    // Delivery history of the webhook, newest first
+    #[utoipa::path(
+        get,
+        path = "/admin/webhooks/{webhook_id}/deliveries",
+        tag = "webhooks",
+        operation_id = "get_deliveries",
+        params(("webhook_id" = i32, Path, description = "ID of the webhook")),
+        responses(
+            (status = 200, description = "Newest first", body = [WebhookDelivery]),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn get_deliveries_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (webhook_id, ) = path.0;
+        find_webhook(&shared_state, webhook_id)?;
+
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match webhooksTable::new(connection).deliveries(webhook_id, DELIVERY_HISTORY_LIMIT) {
+            Ok(deliveries) => Ok((StatusCode::OK, Json(deliveries))),
+            Err(err) => {
+                eprintln!("Error fetching webhook deliveries: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch webhook deliveries"}))))
+            }
+        }
+    }
+
+    // Sends a test event right away and returns the recorded delivery, whether or not the endpoint accepted it
+    #[utoipa::path(
+        post,
+        path = "/admin/webhooks/{webhook_id}/test",
+        tag = "webhooks",
+        operation_id = "test_webhook",
+        params(("webhook_id" = i32, Path, description = "ID of the webhook")),
+        responses(
+            (status = 200, description = "The recorded delivery, whether or not the endpoint accepted it", body = WebhookDelivery),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn test_webhook_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, )>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (webhook_id, ) = path.0;
+        let webhook = find_webhook(&shared_state, webhook_id)?;
+
+        let payload = json!({
+            "event": TEST_EVENT,
+            "data": {"webhook_id": webhook.id},
+            "sent_at": Utc::now().naive_utc()
+        });
+
+        match deliver(shared_state, &webhook, TEST_EVENT, &payload, None).await {
+            Ok(delivery) => Ok((StatusCode::OK, Json(delivery))),
+            Err(err) => {
+                eprintln!("Error delivering test event: {}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to deliver test event"}))))
+            }
+        }
+    }
+
+    // Sends the payload of an earlier delivery again, recording the outcome as a new delivery
+    #[utoipa::path(
+        post,
+        path = "/admin/webhooks/{webhook_id}/deliveries/{delivery_id}/redrive",
+        tag = "webhooks",
+        operation_id = "redrive_delivery",
+        params(("webhook_id" = i32, Path, description = "ID of the webhook"), ("delivery_id" = i32, Path, description = "ID of the delivery")),
+        responses(
+            (status = 200, body = WebhookDelivery),
+            (status = 404, body = ApiError)
+        )
+    )]
+    pub async fn redrive_delivery_handler(
+        State(shared_state): State<ConnectionPool>,
+        path: extract::Path<(i32, i32)>,
+    ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let (webhook_id, delivery_id) = path.0;
+        let webhook = find_webhook(&shared_state, webhook_id)?;
+
+        let delivery = {
+            let connection = shared_state.pool.get()
+                .expect("Failed to acquire connection from pool");
+
+            match webhooksTable::new(connection).get_delivery(webhook_id, delivery_id) {
+                Ok(Some(delivery)) => delivery,
+                Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Delivery not found"})))),
+                Err(err) => {
+                    eprintln!("Error reading webhook delivery: {:?}", err);
+                    return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read webhook delivery"}))));
+                }
+            }
+        };
+
+        let payload: Value = serde_json::from_str(&delivery.payload).unwrap_or(Value::Null);
+
+        match deliver(shared_state, &webhook, &delivery.event_type, &payload, Some(delivery.id)).await {
+            Ok(redelivery) => Ok((StatusCode::OK, Json(redelivery))),
+            Err(err) => {
+                eprintln!("Error redriving webhook delivery: {}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to redrive webhook delivery"}))))
+            }
+        }
+    }
+
+    // Looks up the webhook, releasing the connection before returning so that deliveries can record their outcome
+    fn find_webhook(shared_state: &ConnectionPool, webhook_id: i32) -> Result<Webhook, (StatusCode, Json<Value>)> {
+        let connection = shared_state.pool.get()
+            .expect("Failed to acquire connection from pool");
+
+        match webhooksTable::new(connection).get(webhook_id) {
+            Ok(Some(webhook)) => Ok(webhook),
+            Ok(None) => Err((StatusCode::NOT_FOUND, Json(json!({"error": "Webhook not found"})))),
+            Err(err) => {
+                eprintln!("Error reading webhook: {:?}", err);
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read webhook"}))))
+            }
+        }
+    }
+
+    fn validate_webhook(upsert_webhook: &UpsertWebhook) -> Result<(), (StatusCode, Json<Value>)> {
+        upsert_webhook.validate().map_err(|err| {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": err, "allowed": WEBHOOK_EVENTS})))
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use tower::ServiceExt;
+        use crate::{
+            common::{
+                db::create_shared_connection_pool,
+                util::load_environment_variable,
+                test_util::create_user_and_generate_token
+            },
+            webhooks::{
+                model::UpsertWebhook,
+                router::router::webhooks_route
+            },
+            users::model::UserRole
+        };
+
+        fn webhook_request(event_types: &[&str]) -> UpsertWebhook {
+            UpsertWebhook {
+                // Nothing listens on the discard port, so deliveries fail fast
+                url: "http://127.0.0.1:9/hooks/empires".to_string(),
+                event_types: event_types.iter().map(|event| event.to_string()).collect(),
+                enabled: true,
+            }
+        }
+
+        #[tokio::test]
+        async fn post_webhooks_returns_422_on_unknown_event_type() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = webhooks_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "krok.slurv@concord.gov", UserRole::ADMIN);
+
+            let request = Request::builder()
+                .uri("/admin/webhooks")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::from(serde_json::to_string(&webhook_request(&["empire.conquered"])).unwrap()))
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 422
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        #[tokio::test]
+        async fn post_webhook_test_records_failed_delivery_in_history() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = webhooks_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "krok.admin@concord.gov", UserRole::ADMIN).unwrap();
+
+            // Register a webhook
+            let request = Request::builder()
+                .uri("/admin/webhooks")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::from(serde_json::to_string(&webhook_request(&["empire.created"])).unwrap()))
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let webhook: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let webhook_id = webhook["id"].as_i64().unwrap();
+
+            // Trigger a test delivery to the unreachable endpoint
+            let request = Request::builder()
+                .uri(format!("/admin/webhooks/{}/test", webhook_id))
+                .method("POST")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // Assert that the delivery shows up in the history with its error
+            let request = Request::builder()
+                .uri(format!("/admin/webhooks/{}/deliveries", webhook_id))
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = service.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let deliveries: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(deliveries[0]["event_type"], "webhook.test");
+            assert!(deliveries[0]["status_code"].is_null());
+            assert!(deliveries[0]["error"].is_string());
+        }
+
+        #[tokio::test]
+        async fn get_webhooks_returns_401_for_editor() {
+            let database_url = load_environment_variable("TEST_DB");
+            let connection_pool = create_shared_connection_pool(database_url, 1);
+            let service = webhooks_route(connection_pool.clone());
+
+            let bearer_token = create_user_and_generate_token(connection_pool, "krok.redaktor@concord.gov", UserRole::EDITOR);
+
+            let request = Request::builder()
+                .uri("/admin/webhooks")
+                .method("GET")
+                .header("Authorization", format!("Bearer {}", bearer_token.unwrap()))
+                .body(Body::empty())
+                .unwrap();
+
+            // Send the request through the service
+            let response = service
+                .oneshot(request)
+                .await
+                .unwrap();
+
+            // Assert that the response status is 401
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+pub mod service {
+    use diesel::{
+        prelude::*,
+        PgConnection,
+        r2d2::{ConnectionManager, PooledConnection},
+    };
+    use crate::{
+        webhooks::model::{NewWebhookDelivery, UpsertWebhook, Webhook, WebhookDelivery},
+        schema
+    };
+
+    type PooledPg = PooledConnection<ConnectionManager<PgConnection>>;
+
+    pub struct WebhooksTable {
+        connection: PooledPg,
+    }
+
+    impl WebhooksTable {
+        pub fn new(connection: PooledPg) -> WebhooksTable {
+            WebhooksTable { connection }
+        }
+
+        pub fn create(&mut self, upsert_webhook: UpsertWebhook) -> Result<Webhook, diesel::result::Error> {
+            use schema::webhooks;
+
+            diesel::insert_into(webhooks::table)
+                .values((
+                    webhooks::url.eq(&upsert_webhook.url),
+                    webhooks::event_types.eq(&upsert_webhook.event_types),
+                    webhooks::enabled.eq(upsert_webhook.enabled),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        pub fn get_all(&mut self) -> Result<Vec<Webhook>, diesel::result::Error> {
+            use schema::webhooks;
+
+            webhooks::table
+                .order(webhooks::id)
+                .load::<Webhook>(&mut self.connection)
+        }
+
+        pub fn get(&mut self, webhook_id: i32) -> Result<Option<Webhook>, diesel::result::Error> {
+            use schema::webhooks;
+
+            webhooks::table
+                .find(webhook_id)
+                .get_result(&mut self.connection)
+                .optional()
+        }
+
+        // Enabled webhooks subscribed to the given event
+        pub fn subscribed_to(&mut self, event_type: &str) -> Result<Vec<Webhook>, diesel::result::Error> {
+            use schema::webhooks;
+
+            webhooks::table
+                .filter(webhooks::enabled.eq(true))
+                .filter(webhooks::event_types.contains(vec![event_type]))
+                .order(webhooks::id)
+                .load::<Webhook>(&mut self.connection)
+        }
+
+        pub fn update(&mut self, webhook_id: i32, upsert_webhook: UpsertWebhook) -> Result<Webhook, diesel::result::Error> {
+            use schema::webhooks;
+
+            diesel::update(webhooks::table.find(webhook_id))
+                .set((
+                    webhooks::url.eq(&upsert_webhook.url),
+                    webhooks::event_types.eq(&upsert_webhook.event_types),
+                    webhooks::enabled.eq(upsert_webhook.enabled),
+                ))
+                .get_result(&mut self.connection)
+        }
+
+        // Deliveries are removed along with the webhook
+        pub fn delete(&mut self, webhook_id: i32) -> Result<(), diesel::result::Error> {
+            use schema::webhooks;
+
+            let deleted_rows = diesel::delete(webhooks::table.find(webhook_id))
+                .execute(&mut self.connection)?;
+
+            if deleted_rows == 0 {
+                Err(diesel::result::Error::NotFound)
+            } else {
+                Ok(())
+            }
+        }
+
+        pub fn record_delivery(&mut self, new_delivery: NewWebhookDelivery) -> Result<WebhookDelivery, diesel::result::Error> {
+            use schema::webhook_deliveries;
+
+            diesel::insert_into(webhook_deliveries::table)
+                .values(&new_delivery)
+                .get_result(&mut self.connection)
+        }
+
+        // Most recent deliveries of the webhook, newest first
+        pub fn deliveries(&mut self, webhook_id: i32, limit: i64) -> Result<Vec<WebhookDelivery>, diesel::result::Error> {
+            use schema::webhook_deliveries;
+
+            webhook_deliveries::table
+                .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+                .order(webhook_deliveries::id.desc())
+                .limit(limit)
+                .load::<WebhookDelivery>(&mut self.connection)
+        }
+
+        pub fn get_delivery(&mut self, webhook_id: i32, delivery_id: i32) -> Result<Option<WebhookDelivery>, diesel::result::Error> {
+            use schema::webhook_deliveries;
+
+            webhook_deliveries::table
+                .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+                .filter(webhook_deliveries::id.eq(delivery_id))
+                .get_result(&mut self.connection)
+                .optional()
+        }
+    }
+}
@@ -0,0 +1,193 @@
+pub mod router {
+    use chrono::{NaiveDateTime, Utc};
+    use serde_json::{json, Value};
+    use axum::{
+        Router, http::{header, StatusCode}, Json, response::{IntoResponse, Redirect},
+    };
+    use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet};
+    use crate::common::util::load_optional_environment_variable;
+
+    // Where the frontend is served from when FRONTEND_URL is not set, as by serve_frontend.sh
+    const DEFAULT_FRONTEND_URL: &str = "http://localhost:8000";
+
+    // How long a generated security.txt is valid for when SECURITY_TXT_EXPIRES is not set
+    const SECURITY_TXT_VALIDITY_DAYS: i64 = 365;
+
+    // - - - - - - - - - - - [ROUTES] - - - - - - - - - - -
+
+    pub fn well_known_route() -> Router {
+        // Read by security researchers, password managers and token verifiers, none of which hold credentials
+        Router::new()
+            .route("/.well-known/security.txt", axum::routing::get(security_txt_handler))
+            .route("/.well-known/change-password", axum::routing::get(change_password_handler))
+            .route("/.well-known/jwks.json", axum::routing::get(jwks_handler))
+    }
+
+    // - - - - - - - - - - - [HANDLERS] - - - - - - - - - - -
+
+    // Served only once SECURITY_CONTACT is set, as a security.txt without contacts is of no use to anyone
+    pub async fn security_txt_handler() -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let config = SecurityTxt::from_env()
+            .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "No security contact has been configured"}))))?;
+
+        Ok((
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            config.render(Utc::now().naive_utc()),
+        ))
+    }
+
+    // Lets password managers take users straight to where they change their password
+    pub async fn change_password_handler() -> impl IntoResponse {
+        let url = load_optional_environment_variable("CHANGE_PASSWORD_URL").unwrap_or_else(|| {
+            let frontend_url = load_optional_environment_variable("FRONTEND_URL").unwrap_or_else(|| DEFAULT_FRONTEND_URL.to_string());
+            format!("{}/profile", frontend_url.trim_end_matches('/'))
+        });
+
+        Redirect::to(&url)
+    }
+
+    // Keys for verifying tokens, read from the JWK set at JWKS_FILE. Session tokens are signed with the shared ENCRYPTION_KEY,
+    // which must never be published, so the set is empty unless keys are configured - and symmetric keys are always left out.
+    pub async fn jwks_handler() -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+        let Some(path) = load_optional_environment_variable("JWKS_FILE") else {
+            return Ok((StatusCode::OK, Json(JwkSet { keys: Vec::new() })));
+        };
+
+        let keys = std::fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| serde_json::from_str::<JwkSet>(&contents).map_err(|err| err.to_string()))
+            .map_err(|err| {
+                eprintln!("Failed to read JWK set from '{}': {}", path, err);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to read key set"})))
+            })?;
+
+        Ok((StatusCode::OK, Json(public_keys(keys))))
+    }
+
+    // - - - - - - - - - - - [HELPERS] - - - - - - - - - - -
+
+    // Fields of security.txt as laid out in RFC 9116
+    #[derive(Debug, Clone, Default)]
+    pub struct SecurityTxt {
+        pub contacts: Vec<String>,
+        pub expires: Option<String>,
+        pub encryption: Option<String>,
+        pub policy: Option<String>,
+        pub preferred_languages: Option<String>,
+    }
+
+    impl SecurityTxt {
+        // Reads SECURITY_CONTACT (comma-separated), SECURITY_TXT_EXPIRES, SECURITY_ENCRYPTION, SECURITY_POLICY and SECURITY_PREFERRED_LANGUAGES
+        pub fn from_env() -> Option<SecurityTxt> {
+            let contacts: Vec<String> = load_optional_environment_variable("SECURITY_CONTACT")?
+                .split(',')
+                .map(str::trim)
+                .filter(|contact| !contact.is_empty())
+                .map(str::to_string)
+                .collect();
+
+            if contacts.is_empty() {
+                return None;
+            }
+
+            Some(SecurityTxt {
+                contacts,
+                expires: load_optional_environment_variable("SECURITY_TXT_EXPIRES"),
+                encryption: load_optional_environment_variable("SECURITY_ENCRYPTION"),
+                policy: load_optional_environment_variable("SECURITY_POLICY"),
+                preferred_languages: load_optional_environment_variable("SECURITY_PREFERRED_LANGUAGES"),
+            })
+        }
+
+        // Bare email addresses are turned into mailto URIs, as every contact must be a URI
+        pub fn render(&self, now: NaiveDateTime) -> String {
+            let expires = self.expires.clone().unwrap_or_else(|| {
+                (now + chrono::Duration::days(SECURITY_TXT_VALIDITY_DAYS)).format("%Y-%m-%dT%H:%M:%SZ").to_string()
+            });
+
+            let mut lines: Vec<String> = self.contacts.iter()
+                .map(|contact| if contact.contains('@') && !contact.contains(':') {
+                    format!("Contact: mailto:{}", contact)
+                } else {
+                    format!("Contact: {}", contact)
+                })
+                .collect();
+            lines.push(format!("Expires: {}", expires));
+
+            let optional_fields = [("Encryption", &self.encryption), ("Policy", &self.policy), ("Preferred-Languages", &self.preferred_languages)];
+            for (field, value) in optional_fields {
+                if let Some(value) = value {
+                    lines.push(format!("{}: {}", field, value));
+                }
+            }
+
+            lines.join("\n") + "\n"
+        }
+    }
+
+    // The keys of the set that are safe to publish, leaving out symmetric ones
+    fn public_keys(keys: JwkSet) -> JwkSet {
+        let (symmetric, public): (Vec<_>, Vec<_>) = keys.keys.into_iter()
+            .partition(|key| matches!(key.algorithm, AlgorithmParameters::OctetKey(_)));
+
+        if !symmetric.is_empty() {
+            eprintln!("Left {} symmetric key(s) out of the published JWK set", symmetric.len());
+        }
+
+        JwkSet { keys: public }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use axum::{
+            body::Body,
+            http::{Request, StatusCode}
+        };
+        use chrono::NaiveDate;
+        use jsonwebtoken::jwk::JwkSet;
+        use serde_json::json;
+        use tower::ServiceExt;
+        use crate::well_known::router::router::{public_keys, well_known_route, SecurityTxt};
+
+        #[test]
+        fn security_txt_renders_contacts_as_uris_and_expires_a_year_ahead() {
+            let security_txt = SecurityTxt {
+                contacts: vec!["security@example.com".to_string(), "https://example.com/report".to_string()],
+                policy: Some("https://example.com/disclosure".to_string()),
+                ..SecurityTxt::default()
+            };
+            let now = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap().and_hms_opt(12, 0, 0).unwrap();
+
+            assert_eq!(
+                security_txt.render(now),
+                "Contact: mailto:security@example.com\nContact: https://example.com/report\nExpires: 2027-10-16T12:00:00Z\nPolicy: https://example.com/disclosure\n"
+            );
+        }
+
+        #[test]
+        fn public_keys_leaves_out_symmetric_keys() {
+            let keys: JwkSet = serde_json::from_value(json!({"keys": [
+                {"kty": "oct", "kid": "shared", "k": "c2VjcmV0"},
+                {"kty": "EC", "kid": "signing", "crv": "P-256", "x": "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU", "y": "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"}
+            ]})).unwrap();
+
+            let public = public_keys(keys);
+            assert_eq!(public.keys.len(), 1);
+            assert_eq!(public.keys[0].common.key_id.as_deref(), Some("signing"));
+        }
+
+        #[tokio::test]
+        async fn change_password_redirects_to_the_frontend() {
+            let request = Request::builder()
+                .uri("/.well-known/change-password")
+                .method("GET")
+                .body(Body::empty())
+                .unwrap();
+
+            let response = well_known_route().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::SEE_OTHER);
+            assert!(response.headers()["Location"].to_str().unwrap().ends_with("/profile"));
+        }
+    }
+}
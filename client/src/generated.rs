@@ -0,0 +1,1012 @@
+// Generated by 'cargo xtask codegen' from openapi.json - do not edit by hand, regenerate instead.
+
+use serde::{Deserialize, Serialize};
+use crate::{decode, encode, unexpected, ApiClient, ApiRequest, ClientError, Method, Transport};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub role: String,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchItem {
+    pub id: i32,
+    pub status: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeRequest {
+    pub id: i32,
+    pub resource: String,
+    pub record_id: i32,
+    pub changes: serde_json::Map<String, serde_json::Value>,
+    pub status: String,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateApiKey {
+    pub name: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatedApiKey {
+    pub api_key: ApiKey,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyCounts {
+    pub day: String,
+    pub new_users: i64,
+    pub total_users: i64,
+    pub new_locations: i64,
+    pub new_empires: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Empire {
+    pub id: i32,
+    pub name: String,
+    pub slogan: String,
+    pub location_id: i32,
+    pub description: String,
+    pub created_at: String,
+    pub archived: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmpirePage {
+    pub items: Vec<Empire>,
+    pub partial: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportJob {
+    pub id: i32,
+    pub created_by: i32,
+    pub name: String,
+    pub resource: String,
+    pub schedule: String,
+    pub enabled: bool,
+    pub next_run_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_artifact: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_webhook: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImpersonationResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub user_id: i32,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Location {
+    pub id: i32,
+    pub star_system: String,
+    pub area: String,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationCoordinates {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationPage {
+    pub items: Vec<Location>,
+    pub partial: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoginResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoginUser {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapLocation {
+    pub id: i32,
+    pub star_system: String,
+    pub area: String,
+    pub x: f64,
+    pub y: f64,
+    pub empire_count: i64,
+    pub player_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapRegion {
+    pub name: String,
+    pub empire_count: i64,
+    pub player_count: i64,
+    pub locations: Vec<MapLocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublishEmpire {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReassignShips {
+    pub target_empire_id: i32,
+    pub ship_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReassignmentOutcome {
+    #[serde(flatten)]
+    pub reassignment_report: ReassignmentReport,
+    pub succeeded: u64,
+    pub failed: u64,
+    pub results: Vec<BatchItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReassignmentReport {
+    pub source_empire_id: i32,
+    pub target_empire_id: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub id: i32,
+    pub user_id: i32,
+    pub created_at: String,
+    pub last_seen_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    #[serde(flatten)]
+    pub session: Session,
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ship {
+    pub id: i32,
+    pub name: String,
+    pub empire_id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StarMap {
+    pub regions: Vec<MapRegion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsHistory {
+    pub days: i64,
+    pub history: Vec<DailyCounts>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Template {
+    pub id: i32,
+    pub name: String,
+    pub resource: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwoFactorCode {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwoFactorSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertEmpire {
+    pub name: String,
+    pub slogan: String,
+    pub location_id: i32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertExportJob {
+    pub name: String,
+    pub resource: String,
+    pub schedule: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_webhook: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertLocation {
+    pub star_system: String,
+    pub area: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertShip {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertTemplate {
+    pub name: String,
+    pub resource: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertUser {
+    pub email: String,
+    pub password: String,
+    pub fullname: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpsertWebhook {
+    pub url: String,
+    pub event_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    pub password: String,
+    pub fullname: String,
+    pub role: String,
+    pub created_at: String,
+    pub deactivated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+    pub delivered_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redrive_of: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetStatsHistoryQuery {
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetAllChangeRequestsQuery {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetAllEmpiresQuery {
+    pub include: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateEmpireQuery {
+    pub template_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchEmpiresQuery {
+    pub q: Option<String>,
+    pub cursor: Option<i32>,
+    pub limit: Option<u64>,
+    pub budget_ms: Option<u64>,
+    pub include: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateEmpireResponse {
+    Ok(Empire),
+    Accepted(ChangeRequest),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CloneEmpireQuery {
+    pub include: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateShipQuery {
+    pub template_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchLocationsQuery {
+    pub q: Option<String>,
+    pub cursor: Option<i32>,
+    pub limit: Option<u64>,
+    pub budget_ms: Option<u64>,
+    pub include: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateLocationResponse {
+    Ok(Location),
+    Accepted(ChangeRequest),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetAllTemplatesQuery {
+    pub resource: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListUsersQuery {
+    pub active: Option<bool>,
+}
+
+impl<T: Transport> ApiClient<T> {
+    // GET /admin/api-keys
+    pub async fn get_all_api_keys(&self) -> Result<Vec<ApiKey>, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/admin/api-keys".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /admin/api-keys
+    pub async fn create_api_key(&self, body: &CreateApiKey) -> Result<CreatedApiKey, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/admin/api-keys".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // DELETE /admin/api-keys/{api_key_id}
+    pub async fn revoke_api_key(&self, api_key_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/admin/api-keys/{}", api_key_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // GET /admin/export-jobs
+    pub async fn get_all_export_jobs(&self) -> Result<Vec<ExportJob>, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/admin/export-jobs".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /admin/export-jobs
+    pub async fn create_export_job(&self, body: &UpsertExportJob) -> Result<ExportJob, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/admin/export-jobs".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /admin/export-jobs/{export_job_id}
+    pub async fn read_export_job(&self, export_job_id: i32) -> Result<ExportJob, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/admin/export-jobs/{}", export_job_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /admin/export-jobs/{export_job_id}
+    pub async fn update_export_job(&self, export_job_id: i32, body: &UpsertExportJob) -> Result<ExportJob, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/admin/export-jobs/{}", export_job_id));
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // DELETE /admin/export-jobs/{export_job_id}
+    pub async fn delete_export_job(&self, export_job_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/admin/export-jobs/{}", export_job_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /admin/export-jobs/{export_job_id}/run
+    pub async fn run_export_job(&self, export_job_id: i32) -> Result<ExportJob, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/admin/export-jobs/{}/run", export_job_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /admin/stats/history
+    pub async fn get_stats_history(&self, query: &GetStatsHistoryQuery) -> Result<StatsHistory, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/admin/stats/history".to_string());
+        if let Some(value) = &query.days {
+            request.query.push(("days".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /admin/templates
+    pub async fn create_template(&self, body: &UpsertTemplate) -> Result<Template, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/admin/templates".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /admin/templates/{template_id}
+    pub async fn read_template(&self, template_id: i32) -> Result<Template, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/admin/templates/{}", template_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /admin/templates/{template_id}
+    pub async fn update_template(&self, template_id: i32, body: &UpsertTemplate) -> Result<Template, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/admin/templates/{}", template_id));
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // DELETE /admin/templates/{template_id}
+    pub async fn delete_template(&self, template_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/admin/templates/{}", template_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /admin/users/{user_id}/impersonate
+    pub async fn impersonate_user(&self, user_id: i32) -> Result<ImpersonationResponse, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/admin/users/{}/impersonate", user_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /admin/webhooks
+    pub async fn get_all_webhooks(&self) -> Result<Vec<Webhook>, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/admin/webhooks".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /admin/webhooks
+    pub async fn create_webhook(&self, body: &UpsertWebhook) -> Result<Webhook, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/admin/webhooks".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /admin/webhooks/events
+    pub async fn get_webhook_events(&self) -> Result<Vec<String>, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/admin/webhooks/events".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /admin/webhooks/{webhook_id}
+    pub async fn read_webhook(&self, webhook_id: i32) -> Result<Webhook, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/admin/webhooks/{}", webhook_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /admin/webhooks/{webhook_id}
+    pub async fn update_webhook(&self, webhook_id: i32, body: &UpsertWebhook) -> Result<Webhook, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/admin/webhooks/{}", webhook_id));
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // DELETE /admin/webhooks/{webhook_id}
+    pub async fn delete_webhook(&self, webhook_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/admin/webhooks/{}", webhook_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // GET /admin/webhooks/{webhook_id}/deliveries
+    pub async fn get_deliveries(&self, webhook_id: i32) -> Result<Vec<WebhookDelivery>, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/admin/webhooks/{}/deliveries", webhook_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /admin/webhooks/{webhook_id}/deliveries/{delivery_id}/redrive
+    pub async fn redrive_delivery(&self, webhook_id: i32, delivery_id: i32) -> Result<WebhookDelivery, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/admin/webhooks/{}/deliveries/{}/redrive", webhook_id, delivery_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /admin/webhooks/{webhook_id}/test
+    pub async fn test_webhook(&self, webhook_id: i32) -> Result<WebhookDelivery, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/admin/webhooks/{}/test", webhook_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /change-requests
+    pub async fn get_all_change_requests(&self, query: &GetAllChangeRequestsQuery) -> Result<Vec<ChangeRequest>, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/change-requests".to_string());
+        if let Some(value) = &query.status {
+            request.query.push(("status".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /change-requests/{change_request_id}
+    pub async fn read_change_request(&self, change_request_id: i32) -> Result<ChangeRequest, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/change-requests/{}", change_request_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /change-requests/{change_request_id}/approve
+    pub async fn approve_change_request(&self, change_request_id: i32) -> Result<ChangeRequest, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/change-requests/{}/approve", change_request_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /change-requests/{change_request_id}/reject
+    pub async fn reject_change_request(&self, change_request_id: i32) -> Result<ChangeRequest, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/change-requests/{}/reject", change_request_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /empires
+    pub async fn get_all_empires(&self, query: &GetAllEmpiresQuery) -> Result<Vec<Empire>, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/empires".to_string());
+        if let Some(value) = &query.include {
+            request.query.push(("include".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /empires
+    pub async fn create_empire(&self, query: &CreateEmpireQuery, body: &UpsertEmpire) -> Result<Empire, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/empires".to_string());
+        if let Some(value) = &query.template_id {
+            request.query.push(("template_id".to_string(), value.to_string()));
+        }
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /empires/search
+    pub async fn search_empires(&self, query: &SearchEmpiresQuery) -> Result<EmpirePage, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/empires/search".to_string());
+        if let Some(value) = &query.q {
+            request.query.push(("q".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.cursor {
+            request.query.push(("cursor".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.limit {
+            request.query.push(("limit".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.budget_ms {
+            request.query.push(("budget_ms".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.include {
+            request.query.push(("include".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /empires/{empire_id}
+    pub async fn read_empire(&self, empire_id: i32) -> Result<Empire, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/empires/{}", empire_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /empires/{empire_id}
+    pub async fn update_empire(&self, empire_id: i32, if_match: Option<&str>, body: &UpsertEmpire) -> Result<UpdateEmpireResponse, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/empires/{}", empire_id));
+        if let Some(value) = if_match {
+            request.headers.push(("If-Match".to_string(), value.to_string()));
+        }
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        match response.status {
+            200 => decode(&response).map(UpdateEmpireResponse::Ok),
+            202 => decode(&response).map(UpdateEmpireResponse::Accepted),
+            _ => Err(unexpected(response)),
+        }
+    }
+
+    // DELETE /empires/{empire_id}
+    pub async fn delete_empire(&self, empire_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/empires/{}", empire_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /empires/{empire_id}/archive
+    pub async fn archive_empire(&self, empire_id: i32) -> Result<Empire, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/empires/{}/archive", empire_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /empires/{empire_id}/clone
+    pub async fn clone_empire(&self, empire_id: i32, query: &CloneEmpireQuery) -> Result<Empire, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, format!("/empires/{}/clone", empire_id));
+        if let Some(value) = &query.include {
+            request.query.push(("include".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /empires/{empire_id}/publish
+    pub async fn publish_empire(&self, empire_id: i32, body: Option<&PublishEmpire>) -> Result<Empire, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, format!("/empires/{}/publish", empire_id));
+        if let Some(body) = body {
+            request.body = Some(encode(body)?);
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /empires/{empire_id}/ships
+    pub async fn get_empire_ships(&self, empire_id: i32) -> Result<Vec<Ship>, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/empires/{}/ships", empire_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /empires/{empire_id}/ships
+    pub async fn create_ship(&self, empire_id: i32, query: &CreateShipQuery, body: &UpsertShip) -> Result<Ship, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, format!("/empires/{}/ships", empire_id));
+        if let Some(value) = &query.template_id {
+            request.query.push(("template_id".to_string(), value.to_string()));
+        }
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /empires/{empire_id}/ships/reassign
+    pub async fn reassign_ships(&self, empire_id: i32, body: &ReassignShips) -> Result<ReassignmentOutcome, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, format!("/empires/{}/ships/reassign", empire_id));
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /empires/{empire_id}/unarchive
+    pub async fn unarchive_empire(&self, empire_id: i32) -> Result<Empire, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/empires/{}/unarchive", empire_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /locations
+    pub async fn get_all_locations(&self) -> Result<Vec<Location>, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/locations".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /locations
+    pub async fn create_location(&self, body: &UpsertLocation) -> Result<Location, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/locations".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /locations/search
+    pub async fn search_locations(&self, query: &SearchLocationsQuery) -> Result<LocationPage, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/locations/search".to_string());
+        if let Some(value) = &query.q {
+            request.query.push(("q".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.cursor {
+            request.query.push(("cursor".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.limit {
+            request.query.push(("limit".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.budget_ms {
+            request.query.push(("budget_ms".to_string(), value.to_string()));
+        }
+        if let Some(value) = &query.include {
+            request.query.push(("include".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /locations/{location_id}
+    pub async fn read_location(&self, location_id: i32) -> Result<Location, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/locations/{}", location_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /locations/{location_id}
+    pub async fn update_location(&self, location_id: i32, if_match: Option<&str>, body: &UpsertLocation) -> Result<UpdateLocationResponse, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/locations/{}", location_id));
+        if let Some(value) = if_match {
+            request.headers.push(("If-Match".to_string(), value.to_string()));
+        }
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        match response.status {
+            200 => decode(&response).map(UpdateLocationResponse::Ok),
+            202 => decode(&response).map(UpdateLocationResponse::Accepted),
+            _ => Err(unexpected(response)),
+        }
+    }
+
+    // DELETE /locations/{location_id}
+    pub async fn delete_location(&self, location_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/locations/{}", location_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /locations/{location_id}/clone
+    pub async fn clone_location(&self, location_id: i32) -> Result<Location, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/locations/{}/clone", location_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /locations/{location_id}/coordinates
+    pub async fn update_location_coordinates(&self, location_id: i32, body: &LocationCoordinates) -> Result<Location, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/locations/{}/coordinates", location_id));
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /map
+    pub async fn get_map(&self) -> Result<StarMap, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/map".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /templates
+    pub async fn get_all_templates(&self, query: &GetAllTemplatesQuery) -> Result<Vec<Template>, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/templates".to_string());
+        if let Some(value) = &query.resource {
+            request.query.push(("resource".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /users
+    pub async fn list_users(&self, query: &ListUsersQuery) -> Result<Vec<User>, ClientError> {
+        let mut request = ApiRequest::new(Method::Get, "/users".to_string());
+        if let Some(value) = &query.active {
+            request.query.push(("active".to_string(), value.to_string()));
+        }
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /users
+    pub async fn create_user(&self, body: &UpsertUser) -> Result<User, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/users".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /users/login
+    pub async fn login(&self, body: &LoginUser) -> Result<LoginResponse, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/users/login".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /users/me/sessions
+    pub async fn get_my_sessions(&self) -> Result<Vec<SessionSummary>, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/users/me/sessions".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /users/me/sessions/revoke-all
+    pub async fn revoke_all_my_sessions(&self) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Post, "/users/me/sessions/revoke-all".to_string());
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /users/me/sessions/revoke-current
+    pub async fn revoke_current_session(&self) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Post, "/users/me/sessions/revoke-current".to_string());
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // DELETE /users/me/sessions/{session_id}
+    pub async fn revoke_my_session(&self, session_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/users/me/sessions/{}", session_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // GET /users/me/two-factor
+    pub async fn get_two_factor_status(&self) -> Result<TwoFactorStatus, ClientError> {
+        let request = ApiRequest::new(Method::Get, "/users/me/two-factor".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /users/me/two-factor/confirm
+    pub async fn confirm_two_factor(&self, body: &TwoFactorCode) -> Result<RecoveryCodes, ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/users/me/two-factor/confirm".to_string());
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /users/me/two-factor/disable
+    pub async fn disable_two_factor(&self, body: &TwoFactorCode) -> Result<(), ClientError> {
+        let mut request = ApiRequest::new(Method::Post, "/users/me/two-factor/disable".to_string());
+        request.body = Some(encode(body)?);
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /users/me/two-factor/setup
+    pub async fn setup_two_factor(&self) -> Result<TwoFactorSetup, ClientError> {
+        let request = ApiRequest::new(Method::Post, "/users/me/two-factor/setup".to_string());
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // GET /users/{user_id}
+    pub async fn get_user(&self, user_id: i32) -> Result<User, ClientError> {
+        let request = ApiRequest::new(Method::Get, format!("/users/{}", user_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // PUT /users/{user_id}
+    pub async fn update_user(&self, user_id: i32, body: &UpsertUser) -> Result<User, ClientError> {
+        let mut request = ApiRequest::new(Method::Put, format!("/users/{}", user_id));
+        request.body = Some(encode(body)?);
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // DELETE /users/{user_id}
+    pub async fn delete_user(&self, user_id: i32) -> Result<(), ClientError> {
+        let request = ApiRequest::new(Method::Delete, format!("/users/{}", user_id));
+        self.send(request).await?;
+        Ok(())
+    }
+
+    // POST /users/{user_id}/deactivate
+    pub async fn deactivate_user(&self, user_id: i32) -> Result<User, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/users/{}/deactivate", user_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+
+    // POST /users/{user_id}/reactivate
+    pub async fn reactivate_user(&self, user_id: i32) -> Result<User, ClientError> {
+        let request = ApiRequest::new(Method::Post, format!("/users/{}/reactivate", user_id));
+        let response = self.send(request).await?;
+        decode(&response)
+    }
+}
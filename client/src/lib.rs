@@ -0,0 +1,200 @@
+use std::fmt;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+// Types and methods generated from openapi.json by 'cargo xtask codegen'
+mod generated;
+pub use generated::*;
+
+// Payload schema version the generated types describe, declared on every request
+pub const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+// Request built by the client for a transport to send. 'path' has its parameters filled in and is relative to the
+// base URL of the API, e.g. '/empires/3/ships'.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+}
+
+impl ApiRequest {
+    pub fn new(method: Method, path: String) -> ApiRequest {
+        ApiRequest {
+            method,
+            path,
+            query: Vec::new(),
+            headers: vec![("X-Api-Schema-Version".to_string(), SCHEMA_VERSION.to_string())],
+            body: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientError {
+    // The request could not be sent, or no response came back
+    Transport(String),
+    // The API responded with an error status, along with the 'error' of the body
+    Status { status: u16, error: String },
+    // The request body could not be encoded, or the response did not match the document
+    Decode(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Transport(err) => write!(f, "Request failed: {}", err),
+            ClientError::Status { status, error } => write!(f, "{} ({})", error, status),
+            ClientError::Decode(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+// Sends the requests of the client, e.g. with reqwest or with fetch in the browser. Implementations prepend the base
+// URL and add credentials - either an 'Authorization: Bearer' token or an X-Api-Key header.
+#[allow(async_fn_in_trait)]
+pub trait Transport {
+    async fn send(&self, request: ApiRequest) -> Result<ApiResponse, String>;
+}
+
+pub struct ApiClient<T> {
+    transport: T,
+}
+
+impl<T: Transport> ApiClient<T> {
+    pub fn new(transport: T) -> ApiClient<T> {
+        ApiClient { transport }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    // Sends the request, turning statuses outside of 2xx into errors
+    async fn send(&self, request: ApiRequest) -> Result<ApiResponse, ClientError> {
+        let response = self.transport.send(request).await.map_err(ClientError::Transport)?;
+        if (200..300).contains(&response.status) {
+            Ok(response)
+        } else {
+            Err(ClientError::Status { status: response.status, error: error_message(&response.body) })
+        }
+    }
+}
+
+// The 'error' of an error body, or the body itself for those that are not JSON
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<ApiError>(body).map(|body| body.error).unwrap_or_else(|_| body.to_string())
+}
+
+fn encode<B: Serialize>(body: &B) -> Result<Value, ClientError> {
+    serde_json::to_value(body).map_err(|err| ClientError::Decode(format!("Failed to encode request body: {}", err)))
+}
+
+fn decode<R: DeserializeOwned>(response: &ApiResponse) -> Result<R, ClientError> {
+    serde_json::from_str(&response.body).map_err(|err| ClientError::Decode(format!("Unexpected response body: {}", err)))
+}
+
+// A successful status the document does not list for the operation
+fn unexpected(response: ApiResponse) -> ClientError {
+    ClientError::Decode(format!("Unexpected status {}", response.status))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, future::Future, pin::pin, task::{Context, Poll, Waker}};
+    use serde_json::json;
+    use crate::{
+        ApiClient, ApiRequest, ApiResponse, ClientError, Method, SearchEmpiresQuery, Transport, UpdateLocationResponse,
+        UpsertLocation,
+    };
+
+    // Answers every request with the same response, keeping the requests for inspection
+    struct FakeTransport {
+        response: ApiResponse,
+        requests: RefCell<Vec<ApiRequest>>,
+    }
+
+    impl Transport for FakeTransport {
+        async fn send(&self, request: ApiRequest) -> Result<ApiResponse, String> {
+            self.requests.borrow_mut().push(request);
+            Ok(self.response.clone())
+        }
+    }
+
+    fn client(status: u16, body: &str) -> ApiClient<FakeTransport> {
+        ApiClient::new(FakeTransport {
+            response: ApiResponse { status, body: body.to_string() },
+            requests: RefCell::new(Vec::new()),
+        })
+    }
+
+    // The fake transport never suspends, so a single poll completes every call
+    fn block_on<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("Future did not complete"),
+        }
+    }
+
+    #[test]
+    fn requests_carry_path_query_headers_and_body() {
+        let client = client(200, r#"{"items": [], "partial": false, "next_cursor": null}"#);
+        let query = SearchEmpiresQuery { q: Some("red".to_string()), cursor: Some(40), ..SearchEmpiresQuery::default() };
+        let page = block_on(client.search_empires(&query)).unwrap();
+        assert!(page.items.is_empty() && !page.partial);
+
+        let body = UpsertLocation { star_system: "Sol".to_string(), area: "Core".to_string() };
+        let _ = block_on(client.update_location(7, Some("\"abc\""), &body));
+
+        let requests = client.transport().requests.borrow();
+        assert_eq!((requests[0].method, requests[0].path.as_str()), (Method::Get, "/empires/search"));
+        assert_eq!(requests[0].query, vec![("q".to_string(), "red".to_string()), ("cursor".to_string(), "40".to_string())]);
+        assert_eq!((requests[1].method, requests[1].path.as_str()), (Method::Put, "/locations/7"));
+        assert!(requests[1].headers.contains(&("If-Match".to_string(), "\"abc\"".to_string())));
+        assert!(requests[1].headers.contains(&("X-Api-Schema-Version".to_string(), "2".to_string())));
+        assert_eq!(requests[1].body, Some(json!({"star_system": "Sol", "area": "Core"})));
+    }
+
+    #[test]
+    fn responses_are_told_apart_by_status() {
+        let body = UpsertLocation { star_system: "Sol".to_string(), area: "Core".to_string() };
+        let change_request = r#"{"id": 1, "resource": "location", "record_id": 7, "changes": {}, "status": "pending", "created_at": "2026-10-16T12:00:00"}"#;
+
+        let submitted = block_on(client(202, change_request).update_location(7, None, &body)).unwrap();
+        assert!(matches!(submitted, UpdateLocationResponse::Accepted(change_request) if change_request.record_id == 7));
+
+        let conflict = block_on(client(412, r#"{"error": "Precondition failed", "current": {}}"#).update_location(7, None, &body));
+        assert_eq!(conflict.unwrap_err(), ClientError::Status { status: 412, error: "Precondition failed".to_string() });
+
+        assert_eq!(block_on(client(204, "").delete_location(7)), Ok(()));
+    }
+}
@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use web_sys::AbortSignal;
+use super::{authenticated_get, authenticated_request, handle_api_error, send_idempotent, SendTimed, API_BASE};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Webhook {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UpsertWebhook {
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+    // None when the endpoint could not be reached - see 'error'
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub redrive_of: Option<i32>,
+    pub delivered_at: String,
+}
+
+impl WebhookDelivery {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+// Webhook API functions
+pub async fn get_webhook_events(signal: Option<&AbortSignal>) -> Result<Vec<String>, String> {
+    let url = format!("{}/admin/webhooks/events", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn get_webhooks(signal: Option<&AbortSignal>) -> Result<Vec<Webhook>, String> {
+    let url = format!("{}/admin/webhooks", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn create_webhook(webhook: UpsertWebhook) -> Result<Webhook, String> {
+    let response = authenticated_request("POST", &format!("{}/admin/webhooks", API_BASE))?
+        .json(&webhook)
+        .map_err(|e| format!("Failed to serialize webhook: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn update_webhook(id: i32, webhook: UpsertWebhook) -> Result<Webhook, String> {
+    let response = authenticated_request("PUT", &format!("{}/admin/webhooks/{}", API_BASE, id))?
+        .json(&webhook)
+        .map_err(|e| format!("Failed to serialize webhook: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn delete_webhook(id: i32) -> Result<(), String> {
+    let response = authenticated_request("DELETE", &format!("{}/admin/webhooks/{}", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err("Failed to delete webhook".to_string())
+    }
+}
+
+pub async fn get_webhook_deliveries(id: i32, signal: Option<&AbortSignal>) -> Result<Vec<WebhookDelivery>, String> {
+    let url = format!("{}/admin/webhooks/{}/deliveries", API_BASE, id);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Sends a test event and returns the recorded delivery - which may well be a failed one
+pub async fn test_webhook(id: i32) -> Result<WebhookDelivery, String> {
+    let response = authenticated_request("POST", &format!("{}/admin/webhooks/{}/test", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn redrive_webhook_delivery(webhook_id: i32, delivery_id: i32) -> Result<WebhookDelivery, String> {
+    let url = format!("{}/admin/webhooks/{}/deliveries/{}/redrive", API_BASE, webhook_id, delivery_id);
+    let response = authenticated_request("POST", &url)?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    // Leading characters of the key, for telling keys apart - the full secret is only shown once
+    pub prefix: String,
+    pub role: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CreateApiKey {
+    pub name: String,
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CreatedApiKey {
+    pub api_key: ApiKey,
+    pub secret: String,
+}
+
+// API key functions
+pub async fn get_api_keys(signal: Option<&AbortSignal>) -> Result<Vec<ApiKey>, String> {
+    let url = format!("{}/admin/api-keys", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn create_api_key(api_key: CreateApiKey) -> Result<CreatedApiKey, String> {
+    let response = authenticated_request("POST", &format!("{}/admin/api-keys", API_BASE))?
+        .json(&api_key)
+        .map_err(|e| format!("Failed to serialize API key: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn revoke_api_key(id: i32) -> Result<(), String> {
+    let response = authenticated_request("DELETE", &format!("{}/admin/api-keys/{}", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err("Failed to revoke API key".to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DailyCounts {
+    pub day: String,
+    pub new_users: i64,
+    // Users at the end of the day
+    pub total_users: i64,
+    pub new_locations: i64,
+    pub new_empires: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatsHistory {
+    pub days: i64,
+    pub history: Vec<DailyCounts>,
+}
+
+// Stats functions
+pub async fn get_stats_history(days: i64, signal: Option<&AbortSignal>) -> Result<StatsHistory, String> {
+    let url = format!("{}/admin/stats/history?days={}", API_BASE, days);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Impersonation {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub user_id: i32,
+    pub email: String,
+}
+
+// Opens a session as the given user - see AuthContext::start_impersonation for switching to it
+pub async fn impersonate_user(id: i32) -> Result<Impersonation, String> {
+    let response = authenticated_request("POST", &format!("{}/admin/users/{}/impersonate", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
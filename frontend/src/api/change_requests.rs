@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use web_sys::AbortSignal;
+use super::{authenticated_get, authenticated_request, handle_api_error, send_idempotent, SendTimed, API_BASE};
+
+// Proposed value of a field, along with the value it replaces
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub from: Value,
+    pub to: Value,
+}
+
+// Edit of an empire or location by a writer, awaiting review
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChangeRequest {
+    pub id: i32,
+    pub resource: String,
+    pub record_id: i32,
+    pub changes: BTreeMap<String, FieldChange>,
+    pub status: String,
+    pub requested_by: Option<i32>,
+    pub reviewed_by: Option<i32>,
+    pub created_at: String,
+    pub reviewed_at: Option<String>,
+}
+
+// Field values as shown in the review queue - strings without their quotes
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+// Change request API functions
+pub async fn get_change_requests(status: &str, signal: Option<&AbortSignal>) -> Result<Vec<ChangeRequest>, String> {
+    let url = format!("{}/change-requests?status={}", API_BASE, status);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Approves or rejects the change request
+pub async fn review_change_request(id: i32, approve: bool) -> Result<ChangeRequest, String> {
+    let action = if approve { "approve" } else { "reject" };
+    let response = authenticated_request("POST", &format!("{}/change-requests/{}/{}", API_BASE, id, action))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
@@ -0,0 +1,88 @@
+use gloo_net::http::RequestBuilder;
+use empires_client::{ApiClient, ApiRequest, ApiResponse, ClientError, Method, ReassignShips, ReassignmentOutcome, Transport};
+use web_sys::AbortSignal;
+use super::{
+    authenticated_get, authenticated_request, send_idempotent, BatchItem, MultiStatus, ReassignmentReport, SendTimed,
+    Ship, API_BASE,
+};
+
+// Sends the requests of the generated client the same way as the hand-written calls - with the stored token,
+// timed and throttled, and with reads retried on transient failures
+struct BrowserTransport<'a> {
+    signal: Option<&'a AbortSignal>,
+}
+
+impl Transport for BrowserTransport<'_> {
+    async fn send(&self, request: ApiRequest) -> Result<ApiResponse, String> {
+        let url = format!("{}{}", API_BASE, request.path);
+        let build = || -> Result<RequestBuilder, String> {
+            let builder = match request.method {
+                Method::Get => authenticated_get(&url)?,
+                method => authenticated_request(method.as_str(), &url)?,
+            };
+            let builder = builder.query(request.query.iter().map(|(name, value)| (name.as_str(), value.as_str())));
+            Ok(request.headers.iter().fold(builder, |builder, (name, value)| builder.header(name, value)))
+        };
+
+        let response = match (&request.method, &request.body) {
+            (Method::Get, _) => send_idempotent(build, self.signal).await?,
+            (_, Some(body)) => build()?
+                .json(body)
+                .map_err(|e| format!("Failed to serialize request: {:?}", e))?
+                .send_timed()
+                .await
+                .map_err(|e| format!("{:?}", e))?,
+            (_, None) => build()?.send_timed().await.map_err(|e| format!("{:?}", e))?,
+        };
+
+        let status = response.status();
+        let body = response.text().await.map_err(|e| format!("Failed to read response: {:?}", e))?;
+        Ok(ApiResponse { status, body })
+    }
+}
+
+fn client(signal: Option<&AbortSignal>) -> ApiClient<BrowserTransport<'_>> {
+    ApiClient::new(BrowserTransport { signal })
+}
+
+// Words errors of the generated client like those of the hand-written calls
+fn error_message(err: ClientError) -> String {
+    match err {
+        ClientError::Status { status: 401, .. } => "Not authenticated - Please log in".to_string(),
+        ClientError::Status { error, .. } => format!("Request failed: {}", error),
+        ClientError::Transport(err) => err,
+        ClientError::Decode(err) => format!("Failed to parse response: {}", err),
+    }
+}
+
+pub async fn get_empire_ships(empire_id: i32, signal: Option<&AbortSignal>) -> Result<Vec<Ship>, String> {
+    let ships = client(signal).get_empire_ships(empire_id).await.map_err(error_message)?;
+    Ok(ships.into_iter().map(|ship| Ship {
+        id: ship.id,
+        name: ship.name,
+        category: ship.category,
+        description: ship.description,
+        empire_id: ship.empire_id,
+    }).collect())
+}
+
+pub async fn reassign_ships(empire_id: i32, target_empire_id: i32, ship_ids: Vec<i32>) -> Result<MultiStatus<ReassignmentReport>, String> {
+    let body = ReassignShips { target_empire_id, ship_ids };
+    let ReassignmentOutcome { reassignment_report, succeeded, failed, results } =
+        client(None).reassign_ships(empire_id, &body).await.map_err(error_message)?;
+
+    Ok(MultiStatus {
+        batch: ReassignmentReport {
+            source_empire_id: reassignment_report.source_empire_id,
+            target_empire_id: reassignment_report.target_empire_id,
+        },
+        succeeded: succeeded as usize,
+        failed: failed as usize,
+        results: results.into_iter().map(|item| BatchItem {
+            id: item.id,
+            status: item.status as u16,
+            code: item.code,
+            error: item.error,
+        }).collect(),
+    })
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use web_sys::AbortSignal;
+use super::{authenticated_get, authenticated_request, handle_api_error, send_idempotent, SendTimed, Location, API_BASE};
+
+// A location on the starmap with the number of empires and players found there
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MapLocation {
+    pub id: i32,
+    pub star_system: String,
+    pub area: String,
+    pub x: f64,
+    pub y: f64,
+    pub empire_count: i64,
+    pub player_count: i64,
+}
+
+// Locations sharing a star system, with counts summed over all of them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MapRegion {
+    pub name: String,
+    pub empire_count: i64,
+    pub player_count: i64,
+    pub locations: Vec<MapLocation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StarMap {
+    pub regions: Vec<MapRegion>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LocationCoordinates {
+    pub x: f64,
+    pub y: f64,
+}
+
+// Map API functions
+pub async fn get_map(signal: Option<&AbortSignal>) -> Result<StarMap, String> {
+    let url = format!("{}/map", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn set_location_coordinates(id: i32, coordinates: LocationCoordinates) -> Result<Location, String> {
+    let response = authenticated_request("PUT", &format!("{}/locations/{}/coordinates", API_BASE, id))?
+        .json(&coordinates)
+        .map_err(|e| format!("Failed to serialize coordinates: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
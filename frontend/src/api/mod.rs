@@ -1,9 +1,25 @@
-use gloo_net::http::Request;
+use std::{cell::{Cell, RefCell}, rc::Rc};
+use gloo_net::http::{Request, RequestBuilder, Response};
 use leptos::*;
 use serde::{Deserialize, Serialize};
+use crate::telemetry;
+use web_sys::{AbortController, AbortSignal};
+
+mod throttle;
+#[cfg(feature = "generated-client")]
+mod client;
+pub mod admin;
+pub mod change_requests;
+pub mod map;
+pub mod profile;
+pub mod templates;
+pub use throttle::is_throttled;
+// Ship calls go through the client generated from the backend's OpenAPI document when built with 'generated-client'
+#[cfg(feature = "generated-client")]
+pub use client::{get_empire_ships, reassign_ships};
 
 // Base API URL - adjust this to match your backend
-const API_BASE: &str = "http://localhost:3000";
+pub const API_BASE: &str = "http://localhost:3000";
 
 // Auth token management
 pub fn get_token() -> Option<String> {
@@ -24,14 +40,96 @@ pub fn clear_token() {
     if let Some(window) = web_sys::window() {
         if let Ok(Some(storage)) = window.local_storage() {
             let _ = storage.remove_item("auth_token");
+            // Signing out while impersonating signs the admin out as well
+            let _ = storage.remove_item("impersonator_token");
+            let _ = storage.remove_item("impersonated_email");
         }
     }
 }
 
+// Email of the user being impersonated, if any - kept in localStorage so that it survives reloads
+pub fn get_impersonated_email() -> Option<String> {
+    let window = web_sys::window()?;
+    let storage = window.local_storage().ok()??;
+    storage.get_item("impersonator_token").ok()??;
+    storage.get_item("impersonated_email").ok()?
+}
+
+// Swaps in the token of the impersonated user, keeping the admin's own token to switch back to
+pub fn store_impersonation(token: &str, email: &str) {
+    if let Some(window) = web_sys::window() {
+        if let Ok(Some(storage)) = window.local_storage() {
+            // Impersonating anew from an impersonated session must not lose the original admin token
+            if storage.get_item("impersonator_token").ok().flatten().is_none() {
+                if let Some(admin_token) = get_token() {
+                    let _ = storage.set_item("impersonator_token", &admin_token);
+                }
+            }
+            let _ = storage.set_item("impersonated_email", email);
+            let _ = storage.set_item("auth_token", token);
+        }
+    }
+}
+
+// Restores the admin's own token, returning whether there was one to restore
+pub fn restore_impersonator() -> bool {
+    let Some(storage) = web_sys::window().and_then(|window| window.local_storage().ok().flatten()) else {
+        return false;
+    };
+    let Some(admin_token) = storage.get_item("impersonator_token").ok().flatten() else {
+        return false;
+    };
+
+    let _ = storage.set_item("auth_token", &admin_token);
+    let _ = storage.remove_item("impersonator_token");
+    let _ = storage.remove_item("impersonated_email");
+    true
+}
+
 pub fn is_authenticated() -> bool {
     get_token().is_some()
 }
 
+// Hands out abort signals for a group of requests, e.g. those issued by a page. Renewing the scope aborts
+// everything issued so far, so that a stale response can never overwrite the result of a newer request.
+// Pages and components keep theirs with store_value and abort it in on_cleanup, so that their reads are
+// aborted when navigating away.
+#[derive(Clone, Default)]
+pub struct RequestScope {
+    controller: Rc<RefCell<Option<AbortController>>>,
+}
+
+impl RequestScope {
+    pub fn new() -> RequestScope {
+        RequestScope::default()
+    }
+
+    // Signal of the current generation of requests
+    pub fn signal(&self) -> Option<AbortSignal> {
+        let mut controller = self.controller.borrow_mut();
+        if controller.is_none() {
+            *controller = AbortController::new().ok();
+        }
+        controller.as_ref().map(AbortController::signal)
+    }
+
+    // Aborts the current generation and returns the signal of a fresh one
+    pub fn renew(&self) -> Option<AbortSignal> {
+        self.abort();
+        self.signal()
+    }
+
+    pub fn abort(&self) {
+        if let Some(controller) = self.controller.borrow_mut().take() {
+            controller.abort();
+        }
+    }
+}
+
+pub fn is_aborted(signal: &Option<AbortSignal>) -> bool {
+    signal.as_ref().map_or(false, AbortSignal::aborted)
+}
+
 // API Models
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct User {
@@ -39,12 +137,17 @@ pub struct User {
     pub fullname: String,
     pub email: String,
     pub role: String,
+    // Deactivated users keep their records but can no longer sign in
+    #[serde(default)]
+    pub deactivated: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otp: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -68,6 +171,11 @@ pub struct Location {
     pub id: i32,
     pub star_system: String,
     pub area: String,
+    // Position on the starmap - absent until the location has been placed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -83,6 +191,24 @@ pub struct Empire {
     pub slogan: String,
     pub location_id: i32,
     pub description: String,
+    // Archived empires are only listed on request
+    #[serde(default)]
+    pub archived: bool,
+    // Hidden from readers until then - None while staged
+    #[serde(default)]
+    pub published_at: Option<String>,
+}
+
+impl Empire {
+    // Label of an empire readers cannot see yet, either staged for an editor to publish or scheduled for later
+    pub fn unpublished_label(&self) -> Option<&'static str> {
+        match &self.published_at {
+            None => Some("Staged"),
+            // Timestamps are naive UTC
+            Some(published_at) if js_sys::Date::parse(&format!("{}Z", published_at)) > js_sys::Date::now() => Some("Scheduled"),
+            Some(_) => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -93,34 +219,133 @@ pub struct UpsertEmpire {
     pub description: String,
 }
 
-// Backend returns just the token string, not an object
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Ship {
+    pub id: i32,
+    pub name: String,
+    pub category: Option<String>,
+    pub description: Option<String>,
+    pub empire_id: i32,
+}
+
+// Outcome of a single item of a batch request - failed items carry an error code along with a readable message
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchItem {
+    pub id: i32,
+    pub status: u16,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl BatchItem {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+// Response of every batch endpoint, sent as 200 when all items succeeded and as 207 Multi-Status otherwise.
+// 'batch' holds the fields describing the batch as a whole.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MultiStatus<T> {
+    #[serde(flatten)]
+    pub batch: T,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReassignmentReport {
+    pub source_empire_id: i32,
+    pub target_empire_id: i32,
+}
+
+// One page of a cursor-paginated search. 'partial' is set when the backend ran out of time before
+// filling the page - the remainder is fetched by continuing from 'next_cursor' as usual.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub partial: bool,
+    pub next_cursor: Option<i32>,
+}
+
+// Failure modes of saving a record that was loaded with an ETag
+#[derive(Clone, Debug)]
+pub enum SaveError<T> {
+    // The record was modified on the server since it was loaded (412) - carries its current state
+    Conflict { current: T, etag: Option<String> },
+    // The edit was not applied, but submitted for review by an editor (202)
+    Submitted,
+    Failed(String),
+}
+
+#[derive(Deserialize)]
+struct ConflictResponse<T> {
+    current: T,
+}
+
+// Failure modes of logging in
+#[derive(Clone, Debug)]
+pub enum LoginError {
+    // The password was right, but the account has two-factor authentication enabled and no valid code was given
+    TwoFactorRequired(String),
+    Failed(String),
+}
+
+#[derive(Deserialize)]
+struct LoginErrorResponse {
+    error: String,
+    #[serde(default)]
+    two_factor_required: bool,
+}
+
+// Rows requested per page by the virtualized tables
+const PAGE_SIZE: &str = "100";
+
+// Payload schema version requested from the backend via the X-Api-Schema-Version header
+const SCHEMA_VERSION: &str = "2";
+
+// Login response as of schema version 2 - version 1 returned the bare token string
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LoginResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+}
 
 // API Functions
-pub async fn login(email: String, password: String) -> Result<String, String> {
-    let request = LoginRequest { email, password };
+pub async fn login(email: String, password: String, otp: Option<String>) -> Result<String, LoginError> {
+    let request = LoginRequest { email, password, otp };
     
     let response = Request::post(&format!("{}/users/login", API_BASE))
         .header("Content-Type", "application/json")
+        .header("X-Api-Schema-Version", SCHEMA_VERSION)
         .json(&request)
-        .map_err(|e| format!("Failed to create request: {:?}", e))?
-        .send()
+        .map_err(|e| LoginError::Failed(format!("Failed to create request: {:?}", e)))?
+        .send_timed()
         .await
-        .map_err(|e| format!("Request failed: {:?}", e))?;
+        .map_err(|e| LoginError::Failed(format!("Request failed: {:?}", e)))?;
 
     if response.ok() {
-        let token: String = response
+        let login_response: LoginResponse = response
             .json()
             .await
-            .map_err(|e| format!("Failed to parse response: {:?}", e))?;
+            .map_err(|e| LoginError::Failed(format!("Failed to parse response: {:?}", e)))?;
         
-        set_token(&token);
-        Ok(token)
+        set_token(&login_response.token);
+        Ok(login_response.token)
     } else {
         let error_text = response
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        Err(format!("Login failed: {}", error_text))
+
+        match serde_json::from_str::<LoginErrorResponse>(&error_text) {
+            Ok(error) if error.two_factor_required => Err(LoginError::TwoFactorRequired(error.error)),
+            _ => Err(LoginError::Failed(format!("Login failed: {}", error_text))),
+        }
     }
 }
 
@@ -131,7 +356,7 @@ pub async fn register(fullname: String, email: String, password: String, role: S
         .header("Content-Type", "application/json")
         .json(&request)
         .map_err(|e| format!("Failed to create request: {:?}", e))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -151,7 +376,7 @@ pub async fn register(fullname: String, email: String, password: String, role: S
 }
 
 // Helper function to create authenticated requests
-fn authenticated_request(method: &str, url: &str) -> Result<gloo_net::http::RequestBuilder, String> {
+fn authenticated_request(method: &str, url: &str) -> Result<RequestBuilder, String> {
     let token = get_token().ok_or("No authentication token found")?;
     
     let request = match method {
@@ -169,8 +394,30 @@ fn authenticated_request(method: &str, url: &str) -> Result<gloo_net::http::Requ
     Ok(request)
 }
 
+// Helper function to create authenticated GET requests
+fn authenticated_get(url: &str) -> Result<RequestBuilder, String> {
+    authenticated_request("GET", url).map_err(|auth_error| {
+        if auth_error.contains("No authentication token found") {
+            "Not authenticated - Please log in".to_string()
+        } else {
+            auth_error
+        }
+    })
+}
+
+// Helper function to create search requests against the cursor-paginated search endpoints
+fn search_request(url: &str, query: &str, cursor: Option<i32>) -> Result<RequestBuilder, String> {
+    let request = authenticated_get(url)?
+        .query([("q", query), ("limit", PAGE_SIZE)]);
+
+    match cursor {
+        Some(cursor) => Ok(request.query([("cursor", cursor.to_string())])),
+        None => Ok(request),
+    }
+}
+
 // Helper function to handle API response errors
-async fn handle_api_error(response: gloo_net::http::Response) -> String {
+async fn handle_api_error(response: Response) -> String {
     if response.status() == 401 {
         "Not authenticated - Please log in".to_string()
     } else {
@@ -182,17 +429,130 @@ async fn handle_api_error(response: gloo_net::http::Response) -> String {
     }
 }
 
+// Sending through these records the latency of every API call as seen by the browser (when telemetry is enabled),
+// and keeps requests within the backend's rate limit
+trait SendTimed {
+    async fn send_timed(self) -> Result<Response, gloo_net::Error>;
+}
+
+impl SendTimed for Request {
+    async fn send_timed(self) -> Result<Response, gloo_net::Error> {
+        throttle::wait_for_turn().await;
+
+        let method = self.method().to_string();
+        let url = self.url();
+        let started = telemetry::now();
+
+        let outcome = self.send().await;
+        if let Ok(response) = &outcome {
+            throttle::record(response);
+        }
+
+        // Status 0 marks requests that never got a response
+        let status = outcome.as_ref().map_or(0, |response| response.status());
+        telemetry::record_api_call(&method, &url, status, telemetry::now() - started);
+        outcome
+    }
+}
+
+impl SendTimed for RequestBuilder {
+    async fn send_timed(self) -> Result<Response, gloo_net::Error> {
+        self.build()?.send_timed().await
+    }
+}
+
+// Retry policy for idempotent reads hitting transient failures - 502/503 or no response at all,
+// which is what a brief backend restart looks like from the browser
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_DELAY_MS: f64 = 300.0;
+const MAX_DELAY_MS: f64 = 5000.0;
+
+thread_local! {
+    // Number of requests currently waiting for their next attempt. Set up by provide_request_signals, as a signal created
+    // under whichever component happens to ask for it first would be disposed along with that component.
+    static PENDING_RETRIES: Cell<Option<RwSignal<u32>>> = const { Cell::new(None) };
+}
+
+// Creates the signals reporting on requests in flight. Called once by App, whose owner lives as long as the page does.
+pub fn provide_request_signals() {
+    PENDING_RETRIES.with(|pending_retries| pending_retries.set(Some(create_rw_signal(0))));
+    throttle::provide_signal();
+}
+
+// Whether any request is currently being retried, for showing a "retrying..." state
+pub fn is_retrying() -> Signal<bool> {
+    let pending_retries = PENDING_RETRIES.with(Cell::get);
+    Signal::derive(move || pending_retries.is_some_and(|pending_retries| pending_retries.get() > 0))
+}
+
+fn update_pending_retries(update: impl FnOnce(&mut u32)) {
+    if let Some(pending_retries) = PENDING_RETRIES.with(Cell::get) {
+        pending_retries.update(update);
+    }
+}
+
+fn is_transient(status: u16) -> bool {
+    status == 502 || status == 503
+}
+
+// Exponential backoff with full jitter, so that clients recovering from the same outage do not retry in lockstep
+fn backoff_delay_ms(attempt: u32) -> u32 {
+    let ceiling = (BASE_DELAY_MS * 2f64.powi(attempt as i32 - 1)).min(MAX_DELAY_MS);
+    (js_sys::Math::random() * ceiling) as u32
+}
+
+// Resolves after the given number of milliseconds
+async fn sleep(ms: u32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+// Sends a GET request, retrying transient failures. The request is rebuilt by 'build' for every attempt,
+// as a RequestBuilder is consumed when sent.
+async fn send_idempotent<F>(build: F, signal: Option<&AbortSignal>) -> Result<Response, String>
+where
+    F: Fn() -> Result<RequestBuilder, String>,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let outcome = build()?.abort_signal(signal).send_timed().await;
+
+        let aborted = signal.map_or(false, AbortSignal::aborted);
+
+        // Rate limited - the next attempt is held back by the throttle until Retry-After has passed
+        let rate_limited = outcome.as_ref().map_or(false, |response| response.status() == 429);
+        if rate_limited && !aborted && attempt < MAX_ATTEMPTS {
+            continue;
+        }
+
+        let transient = match &outcome {
+            Ok(response) => is_transient(response.status()),
+            Err(_) => !aborted,
+        };
+
+        if !transient || attempt >= MAX_ATTEMPTS {
+            return outcome.map_err(|e| format!("Request failed: {:?}", e));
+        }
+
+        update_pending_retries(|count| *count += 1);
+        sleep(backoff_delay_ms(attempt)).await;
+        update_pending_retries(|count| *count -= 1);
+
+        if signal.map_or(false, AbortSignal::aborted) {
+            return Err("Request aborted".to_string());
+        }
+    }
+}
+
 // Location API functions
 pub async fn get_locations() -> Result<Vec<Location>, String> {
-    let response = match authenticated_request("GET", &format!("{}/locations", API_BASE)) {
-        Ok(req) => req.send().await.map_err(|e| format!("Request failed: {:?}", e))?,
-        Err(auth_error) => {
-            if auth_error.contains("No authentication token found") {
-                return Err("Not authenticated - Please log in".to_string());
-            }
-            return Err(auth_error);
-        }
-    };
+    let url = format!("{}/locations", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), None).await?;
 
     if response.ok() {
         let locations: Vec<Location> = response
@@ -205,11 +565,26 @@ pub async fn get_locations() -> Result<Vec<Location>, String> {
     }
 }
 
+pub async fn search_locations(query: &str, cursor: Option<i32>, signal: Option<&AbortSignal>) -> Result<Page<Location>, String> {
+    let url = format!("{}/locations/search", API_BASE);
+    let response = send_idempotent(|| search_request(&url, query, cursor), signal).await?;
+
+    if response.ok() {
+        let page: Page<Location> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))?;
+        Ok(page)
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
 pub async fn create_location(location: UpsertLocation) -> Result<Location, String> {
     let response = authenticated_request("POST", &format!("{}/locations", API_BASE))?
         .json(&location)
         .map_err(|e| format!("Failed to serialize location: {:?}", e))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -224,28 +599,60 @@ pub async fn create_location(location: UpsertLocation) -> Result<Location, Strin
     }
 }
 
-pub async fn update_location(id: i32, location: UpsertLocation) -> Result<Location, String> {
-    let response = authenticated_request("PUT", &format!("{}/locations/{}", API_BASE, id))?
-        .json(&location)
-        .map_err(|e| format!("Failed to serialize location: {:?}", e))?
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {:?}", e))?;
+// Fetches a single location along with its ETag, which is handed back on update to detect concurrent edits
+pub async fn get_location(id: i32, signal: Option<&AbortSignal>) -> Result<(Location, Option<String>), String> {
+    let url = format!("{}/locations/{}", API_BASE, id);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
 
     if response.ok() {
+        let etag = response.headers().get("etag");
         let location: Location = response
             .json()
             .await
             .map_err(|e| format!("Failed to parse response: {:?}", e))?;
+        Ok((location, etag))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn update_location(id: i32, location: UpsertLocation, etag: Option<String>) -> Result<Location, SaveError<Location>> {
+    let mut request = authenticated_request("PUT", &format!("{}/locations/{}", API_BASE, id))
+        .map_err(SaveError::Failed)?;
+    if let Some(etag) = etag {
+        request = request.header("If-Match", &etag);
+    }
+
+    let response = request
+        .json(&location)
+        .map_err(|e| SaveError::Failed(format!("Failed to serialize location: {:?}", e)))?
+        .send_timed()
+        .await
+        .map_err(|e| SaveError::Failed(format!("Request failed: {:?}", e)))?;
+
+    if response.status() == 202 {
+        Err(SaveError::Submitted)
+    } else if response.ok() {
+        let location: Location = response
+            .json()
+            .await
+            .map_err(|e| SaveError::Failed(format!("Failed to parse response: {:?}", e)))?;
         Ok(location)
+    } else if response.status() == 412 {
+        let etag = response.headers().get("etag");
+        let conflict: ConflictResponse<Location> = response
+            .json()
+            .await
+            .map_err(|e| SaveError::Failed(format!("Failed to parse response: {:?}", e)))?;
+        Err(SaveError::Conflict { current: conflict.current, etag })
     } else {
-        Err("Failed to update location".to_string())
+        Err(SaveError::Failed("Failed to update location".to_string()))
     }
 }
 
 pub async fn delete_location(id: i32) -> Result<(), String> {
     let response = authenticated_request("DELETE", &format!("{}/locations/{}", API_BASE, id))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -256,17 +663,27 @@ pub async fn delete_location(id: i32) -> Result<(), String> {
     }
 }
 
+// Copies the location, returning the copy - its area is marked with a "(copy)" suffix
+pub async fn clone_location(id: i32) -> Result<Location, String> {
+    let response = authenticated_request("POST", &format!("{}/locations/{}/clone", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
 // Empire API functions
 pub async fn get_empires() -> Result<Vec<Empire>, String> {
-    let response = match authenticated_request("GET", &format!("{}/empires", API_BASE)) {
-        Ok(req) => req.send().await.map_err(|e| format!("Request failed: {:?}", e))?,
-        Err(auth_error) => {
-            if auth_error.contains("No authentication token found") {
-                return Err("Not authenticated - Please log in".to_string());
-            }
-            return Err(auth_error);
-        }
-    };
+    let url = format!("{}/empires", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), None).await?;
 
     if response.ok() {
         let empires: Vec<Empire> = response
@@ -279,11 +696,29 @@ pub async fn get_empires() -> Result<Vec<Empire>, String> {
     }
 }
 
+pub async fn search_empires(query: &str, cursor: Option<i32>, include_archived: bool, signal: Option<&AbortSignal>) -> Result<Page<Empire>, String> {
+    let url = format!("{}/empires/search", API_BASE);
+    let response = send_idempotent(|| {
+        let request = search_request(&url, query, cursor)?;
+        Ok(if include_archived { request.query([("include", "archived")]) } else { request })
+    }, signal).await?;
+
+    if response.ok() {
+        let page: Page<Empire> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))?;
+        Ok(page)
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
 pub async fn create_empire(empire: UpsertEmpire) -> Result<Empire, String> {
     let response = authenticated_request("POST", &format!("{}/empires", API_BASE))?
         .json(&empire)
         .map_err(|e| format!("Failed to serialize empire: {:?}", e))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -298,28 +733,60 @@ pub async fn create_empire(empire: UpsertEmpire) -> Result<Empire, String> {
     }
 }
 
-pub async fn update_empire(id: i32, empire: UpsertEmpire) -> Result<Empire, String> {
-    let response = authenticated_request("PUT", &format!("{}/empires/{}", API_BASE, id))?
-        .json(&empire)
-        .map_err(|e| format!("Failed to serialize empire: {:?}", e))?
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {:?}", e))?;
+// Fetches a single empire along with its ETag, which is handed back on update to detect concurrent edits
+pub async fn get_empire(id: i32, signal: Option<&AbortSignal>) -> Result<(Empire, Option<String>), String> {
+    let url = format!("{}/empires/{}", API_BASE, id);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
 
     if response.ok() {
+        let etag = response.headers().get("etag");
         let empire: Empire = response
             .json()
             .await
             .map_err(|e| format!("Failed to parse response: {:?}", e))?;
+        Ok((empire, etag))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn update_empire(id: i32, empire: UpsertEmpire, etag: Option<String>) -> Result<Empire, SaveError<Empire>> {
+    let mut request = authenticated_request("PUT", &format!("{}/empires/{}", API_BASE, id))
+        .map_err(SaveError::Failed)?;
+    if let Some(etag) = etag {
+        request = request.header("If-Match", &etag);
+    }
+
+    let response = request
+        .json(&empire)
+        .map_err(|e| SaveError::Failed(format!("Failed to serialize empire: {:?}", e)))?
+        .send_timed()
+        .await
+        .map_err(|e| SaveError::Failed(format!("Request failed: {:?}", e)))?;
+
+    if response.status() == 202 {
+        Err(SaveError::Submitted)
+    } else if response.ok() {
+        let empire: Empire = response
+            .json()
+            .await
+            .map_err(|e| SaveError::Failed(format!("Failed to parse response: {:?}", e)))?;
         Ok(empire)
+    } else if response.status() == 412 {
+        let etag = response.headers().get("etag");
+        let conflict: ConflictResponse<Empire> = response
+            .json()
+            .await
+            .map_err(|e| SaveError::Failed(format!("Failed to parse response: {:?}", e)))?;
+        Err(SaveError::Conflict { current: conflict.current, etag })
     } else {
-        Err("Failed to update empire".to_string())
+        Err(SaveError::Failed("Failed to update empire".to_string()))
     }
 }
 
 pub async fn delete_empire(id: i32) -> Result<(), String> {
     let response = authenticated_request("DELETE", &format!("{}/empires/{}", API_BASE, id))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -330,17 +797,103 @@ pub async fn delete_empire(id: i32) -> Result<(), String> {
     }
 }
 
+// Copies the empire and, if requested, its ships, returning the copy - its name is marked with a "(copy)" suffix
+pub async fn clone_empire(id: i32, include_ships: bool) -> Result<Empire, String> {
+    let mut url = format!("{}/empires/{}/clone", API_BASE, id);
+    if include_ships {
+        url.push_str("?include=ships");
+    }
+
+    let response = authenticated_request("POST", &url)?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Archives the empire, or restores it when 'archived' is false
+pub async fn set_empire_archived(id: i32, archived: bool) -> Result<Empire, String> {
+    let action = if archived { "archive" } else { "unarchive" };
+    let response = authenticated_request("POST", &format!("{}/empires/{}/{}", API_BASE, id, action))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Publishes the empire right away, making it visible to readers
+pub async fn publish_empire(id: i32) -> Result<Empire, String> {
+    let response = authenticated_request("POST", &format!("{}/empires/{}/publish", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+#[cfg(not(feature = "generated-client"))]
+pub async fn get_empire_ships(empire_id: i32, signal: Option<&AbortSignal>) -> Result<Vec<Ship>, String> {
+    let url = format!("{}/empires/{}/ships", API_BASE, empire_id);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Moves the ships to the target empire. Ships that cannot be moved are reported per item rather than failing the request.
+#[cfg(not(feature = "generated-client"))]
+pub async fn reassign_ships(empire_id: i32, target_empire_id: i32, ship_ids: Vec<i32>) -> Result<MultiStatus<ReassignmentReport>, String> {
+    let response = authenticated_request("POST", &format!("{}/empires/{}/ships/reassign", API_BASE, empire_id))?
+        .json(&serde_json::json!({"target_empire_id": target_empire_id, "ship_ids": ship_ids}))
+        .map_err(|e| format!("Failed to serialize request: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    // 207 Multi-Status counts as ok, as it carries the outcome of every ship
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
 // User API functions
-pub async fn get_users() -> Result<Vec<User>, String> {
-    let response = match authenticated_request("GET", &format!("{}/users", API_BASE)) {
-        Ok(req) => req.send().await.map_err(|e| format!("Request failed: {:?}", e))?,
-        Err(auth_error) => {
-            if auth_error.contains("No authentication token found") {
-                return Err("Not authenticated - Please log in".to_string());
-            }
-            return Err(auth_error);
-        }
-    };
+pub async fn get_users(signal: Option<&AbortSignal>) -> Result<Vec<User>, String> {
+    let url = format!("{}/users", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
 
     if response.ok() {
         let users: Vec<User> = response
@@ -354,10 +907,8 @@ pub async fn get_users() -> Result<Vec<User>, String> {
 }
 
 pub async fn get_user(id: i32) -> Result<User, String> {
-    let response = authenticated_request("GET", &format!("{}/users/{}", API_BASE, id))?
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {:?}", e))?;
+    let url = format!("{}/users/{}", API_BASE, id);
+    let response = send_idempotent(|| authenticated_get(&url), None).await?;
 
     if response.ok() {
         let user: User = response
@@ -374,7 +925,7 @@ pub async fn update_user(id: i32, user: UpsertUser) -> Result<User, String> {
     let response = authenticated_request("PUT", &format!("{}/users/{}", API_BASE, id))?
         .json(&user)
         .map_err(|e| format!("Failed to serialize user: {:?}", e))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -389,9 +940,27 @@ pub async fn update_user(id: i32, user: UpsertUser) -> Result<User, String> {
     }
 }
 
+// Deactivates or reactivates the user, returning them as updated
+pub async fn set_user_active(id: i32, active: bool) -> Result<User, String> {
+    let action = if active { "reactivate" } else { "deactivate" };
+    let response = authenticated_request("POST", &format!("{}/users/{}/{}", API_BASE, id, action))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
 pub async fn delete_user(id: i32) -> Result<(), String> {
     let response = authenticated_request("DELETE", &format!("{}/users/{}", API_BASE, id))?
-        .send()
+        .send_timed()
         .await
         .map_err(|e| format!("Request failed: {:?}", e))?;
 
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use web_sys::AbortSignal;
+use super::{authenticated_get, authenticated_request, handle_api_error, send_idempotent, SendTimed, API_BASE};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Session {
+    pub id: i32,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: String,
+    pub last_seen_at: String,
+    // Admin who opened the session by impersonating the user, if any
+    #[serde(default)]
+    pub impersonated_by: Option<i32>,
+    // Whether this is the session of the browser making the request
+    pub current: bool,
+}
+
+// Session API functions
+pub async fn get_my_sessions(signal: Option<&AbortSignal>) -> Result<Vec<Session>, String> {
+    let url = format!("{}/users/me/sessions", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn revoke_session(id: i32) -> Result<(), String> {
+    let response = authenticated_request("DELETE", &format!("{}/users/me/sessions/{}", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err("Failed to revoke session".to_string())
+    }
+}
+
+// Signs out of the session the request is made with
+pub async fn revoke_current_session() -> Result<(), String> {
+    let response = authenticated_request("POST", &format!("{}/users/me/sessions/revoke-current", API_BASE))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Signs out every session, the current one included
+pub async fn revoke_all_sessions() -> Result<(), String> {
+    let response = authenticated_request("POST", &format!("{}/users/me/sessions/revoke-all", API_BASE))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TwoFactorSetup {
+    // Base32 secret, for authenticator apps that cannot scan the QR code
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TwoFactorCode {
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+// Two-factor API functions
+pub async fn get_two_factor_status(signal: Option<&AbortSignal>) -> Result<TwoFactorStatus, String> {
+    let url = format!("{}/users/me/two-factor", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+// Generates a new secret - nothing changes for logins until it has been confirmed
+pub async fn start_two_factor_setup() -> Result<TwoFactorSetup, String> {
+    let response = authenticated_request("POST", &format!("{}/users/me/two-factor/setup", API_BASE))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn confirm_two_factor(code: String) -> Result<RecoveryCodes, String> {
+    let response = authenticated_request("POST", &format!("{}/users/me/two-factor/confirm", API_BASE))?
+        .json(&TwoFactorCode { code })
+        .map_err(|e| format!("Failed to serialize code: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn disable_two_factor(code: String) -> Result<(), String> {
+    let response = authenticated_request("POST", &format!("{}/users/me/two-factor/disable", API_BASE))?
+        .json(&TwoFactorCode { code })
+        .map_err(|e| format!("Failed to serialize code: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use web_sys::AbortSignal;
+use super::{authenticated_get, authenticated_request, handle_api_error, send_idempotent, SendTimed, API_BASE};
+
+// Predefined field values for new empires or ships, picked from the Add dialogs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Template {
+    pub id: i32,
+    pub name: String,
+    pub resource: String,
+    pub fields: Map<String, Value>,
+    pub created_at: String,
+}
+
+impl Template {
+    // Value of a prefilled field as shown in a form input, if the template sets it
+    pub fn field(&self, name: &str) -> Option<String> {
+        match self.fields.get(name)? {
+            Value::String(value) => Some(value.clone()),
+            Value::Null => None,
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UpsertTemplate {
+    pub name: String,
+    pub resource: String,
+    pub fields: Map<String, Value>,
+}
+
+// Template API functions
+pub async fn get_templates(resource: &str, signal: Option<&AbortSignal>) -> Result<Vec<Template>, String> {
+    let url = format!("{}/templates?resource={}", API_BASE, resource);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn get_all_templates(signal: Option<&AbortSignal>) -> Result<Vec<Template>, String> {
+    let url = format!("{}/admin/templates", API_BASE);
+    let response = send_idempotent(|| authenticated_get(&url), signal).await?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn create_template(template: UpsertTemplate) -> Result<Template, String> {
+    let response = authenticated_request("POST", &format!("{}/admin/templates", API_BASE))?
+        .json(&template)
+        .map_err(|e| format!("Failed to serialize template: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn update_template(id: i32, template: UpsertTemplate) -> Result<Template, String> {
+    let response = authenticated_request("PUT", &format!("{}/admin/templates/{}", API_BASE, id))?
+        .json(&template)
+        .map_err(|e| format!("Failed to serialize template: {:?}", e))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {:?}", e))
+    } else {
+        Err(handle_api_error(response).await)
+    }
+}
+
+pub async fn delete_template(id: i32) -> Result<(), String> {
+    let response = authenticated_request("DELETE", &format!("{}/admin/templates/{}", API_BASE, id))?
+        .send_timed()
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err("Failed to delete template".to_string())
+    }
+}
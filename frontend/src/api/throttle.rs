@@ -0,0 +1,134 @@
+use std::{cell::{Cell, RefCell}, time::Duration};
+use gloo_net::http::Response;
+use leptos::*;
+use crate::telemetry;
+use super::sleep;
+
+// Requests are paced once the remaining quota drops to this share of the limit (but never below MIN_LOW_WATERMARK)
+const LOW_WATERMARK_RATIO: f64 = 0.1;
+const MIN_LOW_WATERMARK: u32 = 3;
+
+// Gap between queued requests while paced
+const PACED_SPACING_MS: f64 = 500.0;
+
+// Wait applied when a 429 arrives without a usable Retry-After header
+const DEFAULT_RETRY_AFTER_MS: f64 = 1000.0;
+
+// Rate limit budget as last reported by the backend through the X-RateLimit-* headers. Times are in
+// milliseconds on the performance.now() clock.
+#[derive(Default)]
+struct Budget {
+    limit: Option<u32>,
+    remaining: Option<u32>,
+    // No request is sent before this point - set from Retry-After
+    resume_at: f64,
+    // Earliest point at which the next paced request may be sent
+    next_slot: f64,
+}
+
+impl Budget {
+    fn is_low(&self) -> bool {
+        match (self.limit, self.remaining) {
+            (Some(limit), Some(remaining)) => {
+                let watermark = ((limit as f64 * LOW_WATERMARK_RATIO).ceil() as u32).max(MIN_LOW_WATERMARK);
+                remaining <= watermark
+            }
+            (None, Some(remaining)) => remaining <= MIN_LOW_WATERMARK,
+            _ => false,
+        }
+    }
+
+    fn is_throttled(&self, now: f64) -> bool {
+        self.resume_at > now || self.is_low()
+    }
+}
+
+thread_local! {
+    static BUDGET: RefCell<Budget> = RefCell::new(Budget::default());
+    // Created by provide_signal, for the same reason as the retry signal
+    static THROTTLED: Cell<Option<RwSignal<bool>>> = const { Cell::new(None) };
+}
+
+pub(super) fn provide_signal() {
+    THROTTLED.with(|throttled| throttled.set(Some(create_rw_signal(false))));
+}
+
+// Whether requests are currently being slowed down to stay within the rate limit
+pub fn is_throttled() -> Signal<bool> {
+    let throttled = THROTTLED.with(Cell::get);
+    Signal::derive(move || throttled.is_some_and(|throttled| throttled.get()))
+}
+
+fn set_throttled(value: bool) {
+    if let Some(throttled) = THROTTLED.with(Cell::get) {
+        throttled.set(value);
+    }
+}
+
+// Waits for this request's turn. Requests go straight out while the budget is healthy; once it runs low they are
+// queued and sent PACED_SPACING_MS apart, and after a 429 nothing is sent before Retry-After has passed.
+pub async fn wait_for_turn() {
+    let delay_ms = BUDGET.with(|budget| {
+        let mut budget = budget.borrow_mut();
+        let now = telemetry::now();
+
+        if !budget.is_throttled(now) {
+            return 0.0;
+        }
+
+        let slot = now.max(budget.resume_at).max(budget.next_slot);
+        budget.next_slot = slot + PACED_SPACING_MS;
+        slot - now
+    });
+
+    if delay_ms > 0.0 {
+        sleep(delay_ms as u32).await;
+    }
+}
+
+// Updates the budget from the rate limit headers of a response
+pub fn record(response: &Response) {
+    let headers = response.headers();
+    let header = |name: &str| headers.get(name).and_then(|value| value.trim().parse::<u32>().ok());
+    let now = telemetry::now();
+
+    let resume_in_ms = BUDGET.with(|budget| {
+        let mut budget = budget.borrow_mut();
+
+        if let Some(limit) = header("X-RateLimit-Limit") {
+            budget.limit = Some(limit);
+        }
+        if let Some(remaining) = header("X-RateLimit-Remaining") {
+            budget.remaining = Some(remaining);
+        }
+
+        if response.status() == 429 {
+            let retry_after_ms = header("Retry-After").map_or(DEFAULT_RETRY_AFTER_MS, |seconds| seconds as f64 * 1000.0);
+            budget.resume_at = budget.resume_at.max(now + retry_after_ms);
+            budget.remaining = Some(0);
+        }
+
+        set_throttled(budget.is_throttled(now));
+        (budget.resume_at > now).then(|| budget.resume_at - now)
+    });
+
+    // Clear the banner once the backend's window is over, even if no further request is made
+    if let Some(resume_in_ms) = resume_in_ms {
+        set_timeout(refresh, Duration::from_millis(resume_in_ms.ceil() as u64));
+    }
+}
+
+fn refresh() {
+    BUDGET.with(|budget| {
+        let mut budget = budget.borrow_mut();
+        let now = telemetry::now();
+
+        // Past Retry-After the window has reset - the quota is unknown until the next response reports it
+        if budget.resume_at > 0.0 && budget.resume_at <= now {
+            budget.resume_at = 0.0;
+            budget.remaining = None;
+        }
+
+        set_throttled(budget.is_throttled(now));
+    });
+}
@@ -0,0 +1,50 @@
+use leptos::*;
+use crate::api;
+
+// Authentication state shared by every page. Tokens live in localStorage, while this tracks whom an admin is impersonating
+// so that the banner offering to switch back shows up wherever they navigate.
+#[derive(Clone, Copy)]
+pub struct AuthContext {
+    impersonating: RwSignal<Option<String>>,
+}
+
+impl AuthContext {
+    // Email of the user being impersonated, if any
+    pub fn impersonating(&self) -> Signal<Option<String>> {
+        self.impersonating.into()
+    }
+
+    // Switches to the token of the impersonated user and reloads, so that no page keeps showing what the admin sees
+    pub fn start_impersonation(&self, token: &str, email: &str) {
+        api::store_impersonation(token, email);
+        self.impersonating.set(Some(email.to_string()));
+        reload_at("/");
+    }
+
+    // Revokes the impersonation session and restores the admin's own token. The admin is switched back even should revoking
+    // fail, as the session is theirs to leave - it then remains listed among the user's sessions until it expires.
+    pub fn switch_back(&self) {
+        let impersonating = self.impersonating;
+        spawn_local(async move {
+            let _ = api::profile::revoke_current_session().await;
+            if api::restore_impersonator() {
+                impersonating.set(None);
+                reload_at("/users");
+            }
+        });
+    }
+}
+
+fn reload_at(path: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().set_href(path);
+    }
+}
+
+pub fn provide_auth_context() {
+    provide_context(AuthContext { impersonating: create_rw_signal(api::get_impersonated_email()) });
+}
+
+pub fn use_auth() -> AuthContext {
+    expect_context::<AuthContext>()
+}
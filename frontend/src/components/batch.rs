@@ -0,0 +1,44 @@
+use leptos::*;
+use crate::api::BatchItem;
+
+// Outcome of a batch request: a summary line, followed by the items that failed along with why, so that they can be
+// dealt with one by one rather than retrying the whole batch. 'action' completes the summary, e.g. "ships moved".
+#[component]
+pub fn BatchResults(
+    results: Vec<BatchItem>,
+    action: &'static str,
+    #[prop(into)] item_label: Callback<i32, String>,
+) -> impl IntoView {
+    let succeeded = results.iter().filter(|item| item.is_success()).count();
+    let failures: Vec<BatchItem> = results.iter().filter(|item| !item.is_success()).cloned().collect();
+    let summary_class = if failures.is_empty() { "success" } else { "batch-partial" };
+
+    view! {
+        <div class="batch-results">
+            <div class=summary_class>{format!("{} of {} {}", succeeded, results.len(), action)}</div>
+
+            {(!failures.is_empty()).then(|| view! {
+                <table>
+                    <thead>
+                        <tr>
+                            <th>"Item"</th>
+                            <th>"Status"</th>
+                            <th>"Code"</th>
+                            <th>"Reason"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {failures.into_iter().map(|item| view! {
+                            <tr class="batch-failed">
+                                <td>{item_label.call(item.id)}</td>
+                                <td>{item.status}</td>
+                                <td><code>{item.code.unwrap_or_default()}</code></td>
+                                <td>{item.error.unwrap_or_default()}</td>
+                            </tr>
+                        }).collect_view()}
+                    </tbody>
+                </table>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,202 @@
+use leptos::*;
+
+// Drawing area in SVG units - charts scale to the width of their container
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 40.0;
+const MARGIN_RIGHT: f64 = 10.0;
+const MARGIN_TOP: f64 = 10.0;
+const MARGIN_BOTTOM: f64 = 24.0;
+
+const PLOT_WIDTH: f64 = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+const PLOT_HEIGHT: f64 = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+// A named sequence of values, one per label of the chart
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartSeries {
+    pub name: String,
+    pub color: String,
+    pub values: Vec<f64>,
+}
+
+impl ChartSeries {
+    pub fn new(name: &str, color: &str, values: Vec<f64>) -> ChartSeries {
+        ChartSeries { name: name.to_string(), color: color.to_string(), values }
+    }
+}
+
+// Rounds the largest value up to 1, 2 or 5 times a power of ten, so that gridlines land on readable numbers
+fn nice_max(series: &[ChartSeries]) -> f64 {
+    let max = series.iter().flat_map(|s| s.values.iter().copied()).fold(0.0, f64::max);
+    if max <= 0.0 {
+        return 1.0;
+    }
+
+    let magnitude = 10f64.powf(max.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0].into_iter().find(|step| max <= step * magnitude).unwrap_or(10.0);
+    step * magnitude
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 { format!("{}", value as i64) } else { format!("{:.1}", value) }
+}
+
+fn y_of(value: f64, max: f64) -> f64 {
+    MARGIN_TOP + PLOT_HEIGHT - value / max * PLOT_HEIGHT
+}
+
+// Gridlines with value labels, x labels at the start, middle and end, and a legend - shared by all chart types
+fn axes(labels: &[String], max: f64) -> impl IntoView {
+    let gridlines = [0.0, 0.5, 1.0].map(|fraction| {
+        let y = y_of(max * fraction, max);
+        view! {
+            <line class="chart-grid" x1=MARGIN_LEFT x2=WIDTH - MARGIN_RIGHT y1=y y2=y/>
+            <text class="chart-label" x=MARGIN_LEFT - 6.0 y=y + 4.0 text-anchor="end">{format_value(max * fraction)}</text>
+        }
+    });
+
+    let count = labels.len();
+    let mut label_indices = vec![0, count / 2, count.saturating_sub(1)];
+    label_indices.dedup();
+    let x_labels = label_indices.into_iter().filter(|index| *index < count).map(|index| {
+        let x = MARGIN_LEFT + (index as f64 + 0.5) * PLOT_WIDTH / count as f64;
+        view! {
+            <text class="chart-label" x=x y=HEIGHT - 6.0 text-anchor="middle">{labels[index].clone()}</text>
+        }
+    }).collect_view();
+
+    view! {
+        {gridlines.into_iter().collect_view()}
+        {x_labels}
+    }
+}
+
+fn legend(series: &[ChartSeries]) -> impl IntoView {
+    let entries = series.iter().map(|s| view! {
+        <span class="chart-legend-entry">
+            <span class="chart-swatch" style=format!("background: {}", s.color)></span>
+            {s.name.clone()}
+        </span>
+    }).collect_view();
+
+    view! { <div class="chart-legend">{entries}</div> }
+}
+
+// Invisible columns catching the pointer, one per label, and the tooltip listing every series' value at the hovered label
+fn hover_layer(labels: &[String], set_hovered: WriteSignal<Option<usize>>) -> impl IntoView {
+    let column_width = PLOT_WIDTH / labels.len().max(1) as f64;
+    (0..labels.len()).map(|index| view! {
+        <rect
+            class="chart-hover-target"
+            x=MARGIN_LEFT + index as f64 * column_width
+            y=MARGIN_TOP
+            width=column_width
+            height=PLOT_HEIGHT
+            on:mouseenter=move |_| set_hovered.set(Some(index))
+        />
+    }).collect_view()
+}
+
+fn tooltip(labels: Vec<String>, series: Vec<ChartSeries>, hovered: ReadSignal<Option<usize>>) -> impl IntoView {
+    move || hovered.get().filter(|index| *index < labels.len()).map(|index| {
+        let column_width = PLOT_WIDTH / labels.len() as f64;
+        let left = (MARGIN_LEFT + (index as f64 + 0.5) * column_width) / WIDTH * 100.0;
+        let rows = series.iter().map(|s| view! {
+            <div>
+                <span class="chart-swatch" style=format!("background: {}", s.color)></span>
+                {format!("{}: {}", s.name, s.values.get(index).copied().map(format_value).unwrap_or_default())}
+            </div>
+        }).collect_view();
+
+        view! {
+            <div class="chart-tooltip" style=format!("left: {:.2}%", left)>
+                <strong>{labels[index].clone()}</strong>
+                {rows}
+            </div>
+        }
+    })
+}
+
+// Line chart with one line per series, highlighting the values at the hovered label
+#[component]
+pub fn LineChart(labels: Vec<String>, series: Vec<ChartSeries>) -> impl IntoView {
+    let (hovered, set_hovered) = create_signal(None::<usize>);
+    let max = nice_max(&series);
+    let count = labels.len().max(1);
+    let x_of = move |index: usize| MARGIN_LEFT + (index as f64 + 0.5) * PLOT_WIDTH / count as f64;
+
+    let lines = series.iter().map(|s| {
+        let points = s.values.iter().enumerate()
+            .map(|(index, value)| format!("{:.1},{:.1}", x_of(index), y_of(*value, max)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        view! { <polyline class="chart-line" points=points stroke=s.color.clone()/> }
+    }).collect_view();
+
+    let markers = {
+        let series = series.clone();
+        move || hovered.get().map(|index| {
+            let x = x_of(index);
+            let dots = series.iter().filter_map(|s| s.values.get(index).map(|value| view! {
+                <circle class="chart-dot" cx=x cy=y_of(*value, max) r="4" fill=s.color.clone()/>
+            })).collect_view();
+            view! {
+                <line class="chart-guide" x1=x x2=x y1=MARGIN_TOP y2=MARGIN_TOP + PLOT_HEIGHT/>
+                {dots}
+            }
+        })
+    };
+
+    view! {
+        <div class="chart" on:mouseleave=move |_| set_hovered.set(None)>
+            <svg viewBox=format!("0 0 {} {}", WIDTH, HEIGHT)>
+                {axes(&labels, max)}
+                {lines}
+                {markers}
+                {hover_layer(&labels, set_hovered)}
+            </svg>
+            {tooltip(labels.clone(), series.clone(), hovered)}
+            {legend(&series)}
+        </div>
+    }
+}
+
+// Bar chart with the series' bars grouped side by side per label
+#[component]
+pub fn BarChart(labels: Vec<String>, series: Vec<ChartSeries>) -> impl IntoView {
+    let (hovered, set_hovered) = create_signal(None::<usize>);
+    let max = nice_max(&series);
+    let column_width = PLOT_WIDTH / labels.len().max(1) as f64;
+    // Bars of a group fill 80% of their column, leaving a gap between groups
+    let bar_width = column_width * 0.8 / series.len().max(1) as f64;
+
+    let bars = series.iter().enumerate().flat_map(|(series_index, s)| {
+        s.values.iter().enumerate().map(move |(index, value)| {
+            let x = MARGIN_LEFT + index as f64 * column_width + column_width * 0.1 + series_index as f64 * bar_width;
+            let y = y_of(*value, max);
+            view! {
+                <rect
+                    class="chart-bar"
+                    class:chart-bar-hovered=move || hovered.get() == Some(index)
+                    x=x
+                    y=y
+                    width=bar_width
+                    height=MARGIN_TOP + PLOT_HEIGHT - y
+                    fill=s.color.clone()
+                />
+            }
+        })
+    }).collect_view();
+
+    view! {
+        <div class="chart" on:mouseleave=move |_| set_hovered.set(None)>
+            <svg viewBox=format!("0 0 {} {}", WIDTH, HEIGHT)>
+                {axes(&labels, max)}
+                {bars}
+                {hover_layer(&labels, set_hovered)}
+            </svg>
+            {tooltip(labels.clone(), series.clone(), hovered)}
+            {legend(&series)}
+        </div>
+    }
+}
@@ -0,0 +1,100 @@
+use leptos::*;
+
+// A single field of a record whose save was rejected because the server copy changed in the meantime
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldConflict {
+    pub label: &'static str,
+    pub original: String,
+    pub mine: String,
+    pub theirs: String,
+}
+
+impl FieldConflict {
+    pub fn new(label: &'static str, original: impl ToString, mine: impl ToString, theirs: impl ToString) -> FieldConflict {
+        FieldConflict {
+            label,
+            original: original.to_string(),
+            mine: mine.to_string(),
+            theirs: theirs.to_string(),
+        }
+    }
+
+    pub fn edited_by_me(&self) -> bool {
+        self.mine != self.original
+    }
+}
+
+// Three-way merge of a single field: keep the user's value if they changed it, otherwise take the server's
+pub fn merge_field<T: PartialEq + Clone>(original: &T, mine: &T, theirs: &T) -> T {
+    if mine != original {
+        mine.clone()
+    } else {
+        theirs.clone()
+    }
+}
+
+#[component]
+pub fn ConflictDialog(
+    fields: Vec<FieldConflict>,
+    #[prop(into)] on_merge: Callback<()>,
+    #[prop(into)] on_overwrite: Callback<()>,
+    #[prop(into)] on_discard: Callback<()>,
+) -> impl IntoView {
+    view! {
+        <div class="form-container conflict-dialog">
+            <h3>"This record was changed by someone else"</h3>
+            <p>
+                "Your changes were not saved, as the record was modified after you started editing it. "
+                "Compare your edits with the current values and choose how to proceed."
+            </p>
+
+            <table>
+                <thead>
+                    <tr>
+                        <th>"Field"</th>
+                        <th>"Your edit"</th>
+                        <th>"Current value"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {fields.into_iter().map(|field| {
+                        let class = if field.mine != field.theirs { "conflict-differs" } else { "" };
+                        let edited = field.edited_by_me();
+                        view! {
+                            <tr class=class>
+                                <td>{field.label}</td>
+                                <td>
+                                    {field.mine}
+                                    {edited.then(|| view! { <span class="conflict-edited">" (edited)"</span> })}
+                                </td>
+                                <td>{field.theirs}</td>
+                            </tr>
+                        }
+                    }).collect_view()}
+                </tbody>
+            </table>
+
+            <div class="form-actions">
+                <button
+                    type="button"
+                    class="btn btn-primary"
+                    title="Keep your edited fields, take the current values for the rest and review before saving"
+                    on:click=move |_| on_merge.call(())
+                >
+                    "Merge"
+                </button>
+                <button
+                    type="button"
+                    class="btn btn-danger"
+                    title="Save your version, replacing the current values"
+                    on:click=move |_| on_overwrite.call(())
+                >
+                    "Overwrite"
+                </button>
+                <button type="button" class="btn btn-secondary" on:click=move |_| on_discard.call(())>
+                    "Discard my changes"
+                </button>
+            </div>
+        </div>
+    }
+}
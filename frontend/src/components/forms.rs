@@ -1,5 +1,6 @@
 use leptos::*;
-use crate::api::{self, Location, Empire, User, UpsertLocation, UpsertEmpire, UpsertUser};
+use crate::api::{self, Location, Empire, RequestScope, User, UpsertLocation, UpsertEmpire, UpsertUser};
+use crate::api::templates::{self as templates_api, Template};
 
 #[component]
 pub fn LoginForm() -> impl IntoView {
@@ -8,20 +9,33 @@ pub fn LoginForm() -> impl IntoView {
     let (error, set_error) = create_signal(None::<String>);
     let (loading, set_loading) = create_signal(false);
 
-    let login_action = create_action(move |(email, password): &(String, String)| {
+    // Second step, shown once the server asks for a two-factor code
+    let (needs_code, set_needs_code) = create_signal(false);
+    let (code, set_code) = create_signal(String::new());
+
+    let login_action = create_action(move |(email, password, otp): &(String, String, Option<String>)| {
         let email = email.clone();
         let password = password.clone();
+        let otp = otp.clone();
         async move {
             set_loading.set(true);
             set_error.set(None);
             
-            match api::login(email, password).await {
+            match api::login(email, password, otp.clone()).await {
                 Ok(_) => {
                     // Redirect to home page
                     let window = web_sys::window().unwrap();
                     window.location().set_href("/").unwrap();
                 },
-                Err(e) => {
+                Err(api::LoginError::TwoFactorRequired(message)) => {
+                    // Only report an error if a code was given - being asked for one is the expected second step
+                    if otp.is_some() {
+                        set_error.set(Some(message));
+                    }
+                    set_code.set(String::new());
+                    set_needs_code.set(true);
+                },
+                Err(api::LoginError::Failed(e)) => {
                     set_error.set(Some(e));
                 }
             }
@@ -31,42 +45,76 @@ pub fn LoginForm() -> impl IntoView {
 
     let on_submit = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
-        login_action.dispatch((email.get(), password.get()));
+        let otp = needs_code.get().then(|| code.get());
+        login_action.dispatch((email.get(), password.get(), otp));
+    };
+
+    let back_to_credentials = move |_| {
+        set_needs_code.set(false);
+        set_code.set(String::new());
+        set_error.set(None);
     };
 
     view! {
         <div class="form-container">
             <h2>"Login"</h2>
             <form on:submit=on_submit>
-                <div class="form-group">
-                    <label for="email">"Email:"</label>
-                    <input
-                        type="email"
-                        id="email"
-                        required
-                        prop:value=email
-                        on:input=move |ev| set_email.set(event_target_value(&ev))
-                    />
-                </div>
-                
-                <div class="form-group">
-                    <label for="password">"Password:"</label>
-                    <input
-                        type="password"
-                        id="password"
-                        required
-                        prop:value=password
-                        on:input=move |ev| set_password.set(event_target_value(&ev))
-                    />
-                </div>
+                <Show
+                    when=move || needs_code.get()
+                    fallback=move || view! {
+                        <div class="form-group">
+                            <label for="email">"Email:"</label>
+                            <input
+                                type="email"
+                                id="email"
+                                required
+                                prop:value=email
+                                on:input=move |ev| set_email.set(event_target_value(&ev))
+                            />
+                        </div>
+                        
+                        <div class="form-group">
+                            <label for="password">"Password:"</label>
+                            <input
+                                type="password"
+                                id="password"
+                                required
+                                prop:value=password
+                                on:input=move |ev| set_password.set(event_target_value(&ev))
+                            />
+                        </div>
+                    }
+                >
+                    <div class="form-group">
+                        <label for="otp">"Two-factor code:"</label>
+                        <input
+                            type="text"
+                            id="otp"
+                            required
+                            autofocus
+                            autocomplete="one-time-code"
+                            placeholder="123456"
+                            prop:value=code
+                            on:input=move |ev| set_code.set(event_target_value(&ev))
+                        />
+                        <p class="hint">"Enter the code from your authenticator app, or one of your recovery codes."</p>
+                    </div>
+                </Show>
                 
                 {move || error.get().map(|e| view! {
                     <div class="error">{e}</div>
                 })}
                 
                 <button type="submit" disabled=move || loading.get()>
-                    {move || if loading.get() { "Logging in..." } else { "Login" }}
+                    {move || match (loading.get(), needs_code.get()) {
+                        (true, _) => "Logging in...",
+                        (false, true) => "Verify",
+                        (false, false) => "Login",
+                    }}
                 </button>
+                <Show when=move || needs_code.get()>
+                    <button type="button" class="btn btn-secondary" on:click=back_to_credentials>"Back"</button>
+                </Show>
             </form>
         </div>
     }
@@ -260,6 +308,44 @@ pub fn EmpireForm(
         empire.as_ref().map(|e| e.description.clone()).unwrap_or_default()
     );
 
+    // Templates offered when adding an empire - picking one fills in the fields it defines
+    let (templates, set_templates) = create_signal(Vec::<Template>::new());
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    if empire.is_none() {
+        let signal = requests.with_value(RequestScope::signal);
+        spawn_local(async move {
+            // The picker is a convenience, so the form works just the same without it
+            if let Ok(list) = templates_api::get_templates("empire", signal.as_ref()).await {
+                set_templates.set(list);
+            }
+        });
+    }
+
+    let apply_template = move |ev: leptos::ev::Event| {
+        let Ok(template_id) = event_target_value(&ev).parse::<i32>() else {
+            return;
+        };
+        let Some(template) = templates.with_untracked(|list| list.iter().find(|t| t.id == template_id).cloned()) else {
+            return;
+        };
+
+        let fields = [
+            ("name", set_name),
+            ("slogan", set_slogan),
+            ("location_id", set_location_id),
+            ("description", set_description),
+        ];
+        for (field, set_field) in fields {
+            if let Some(value) = template.field(field) {
+                set_field.set(value);
+            }
+        }
+    };
+
     let handle_submit = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
         
@@ -283,6 +369,22 @@ pub fn EmpireForm(
         <div class="form-container">
             <h3>{if empire.is_some() { "Edit Empire" } else { "Add Empire" }}</h3>
             <form on:submit=handle_submit>
+                <Show when=move || templates.with(|list| !list.is_empty())>
+                    <div class="form-group">
+                        <label for="template">"Template:"</label>
+                        <select id="template" on:change=apply_template>
+                            <option value="">"None"</option>
+                            <For
+                                each=move || templates.get()
+                                key=|template| template.id
+                                children=|template| view! {
+                                    <option value=template.id.to_string()>{template.name}</option>
+                                }
+                            />
+                        </select>
+                    </div>
+                </Show>
+
                 <div class="form-group">
                     <label for="name">"Name:"</label>
                     <input
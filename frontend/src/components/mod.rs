@@ -1,2 +1,9 @@
 pub mod navbar;
-pub mod forms;
\ No newline at end of file
+pub mod forms;
+pub mod virtual_table;
+pub mod conflict;
+pub mod telemetry;
+pub mod timestamp;
+pub mod charts;
+pub mod batch;
+pub mod ships;
\ No newline at end of file
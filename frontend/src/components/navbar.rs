@@ -1,10 +1,14 @@
 use leptos::*;
 use leptos_router::*;
-use crate::api;
+use crate::{api, auth::use_auth};
 
 #[component]
 pub fn Navbar() -> impl IntoView {
     let (is_logged_in, set_is_logged_in) = create_signal(api::get_token().is_some());
+    let retrying = api::is_retrying();
+    let throttled = api::is_throttled();
+    let auth = use_auth();
+    let impersonating = auth.impersonating();
     
     let logout = move |_| {
         api::clear_token();
@@ -22,7 +26,9 @@ pub fn Navbar() -> impl IntoView {
                     view! {
                         <A href="/locations">"Locations"</A>
                         <A href="/empires">"Empires"</A>
+                        <A href="/map">"Map"</A>
                         <A href="/users">"Users"</A>
+                        <A href="/profile">"Profile"</A>
                         <button on:click=logout class="logout-btn">"Logout"</button>
                     }.into_view()
                 } else {
@@ -33,5 +39,17 @@ pub fn Navbar() -> impl IntoView {
                 }}
             </div>
         </nav>
+        {move || impersonating.get().map(|email| view! {
+            <div class="banner banner-impersonation">
+                <span>"Viewing as " {email}</span>
+                <button on:click=move |_| auth.switch_back() class="btn btn-small">"Switch back"</button>
+            </div>
+        })}
+        <Show when=move || retrying.get()>
+            <div class="banner banner-warning">"Having trouble reaching the server - retrying..."</div>
+        </Show>
+        <Show when=move || throttled.get() && !retrying.get()>
+            <div class="banner banner-subtle">"Lots of requests in a short time - slowing down a little to stay within limits"</div>
+        </Show>
     }
 }
\ No newline at end of file
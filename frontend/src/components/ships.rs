@@ -0,0 +1,142 @@
+use leptos::*;
+use crate::api::{self, Empire, MultiStatus, ReassignmentReport, RequestScope, Ship};
+use crate::components::batch::BatchResults;
+
+// Moves a selection of the empire's ships to another empire, listing afterwards the ships that could not be moved
+#[component]
+pub fn ReassignShipsDialog(
+    empire: Empire,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let empire_id = empire.id;
+    let (ships, set_ships) = create_signal(Vec::<Ship>::new());
+    let (selected, set_selected) = create_signal(Vec::<i32>::new());
+    let (target_empire_id, set_target_empire_id) = create_signal(String::new());
+    // The outcome of the last reassignment, along with the ships as they were listed when it was sent
+    let (outcome, set_outcome) = create_signal(None::<(MultiStatus<ReassignmentReport>, Vec<Ship>)>);
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_ships = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = api::get_empire_ships(empire_id, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_ships.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+    load_ships();
+
+    let toggle_ship = move |ship_id: i32, checked: bool| {
+        set_selected.update(|selected| {
+            selected.retain(|id| *id != ship_id);
+            if checked {
+                selected.push(ship_id);
+            }
+        });
+    };
+
+    let handle_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let Ok(target_empire_id) = target_empire_id.get_untracked().trim().parse::<i32>() else {
+            set_error.set(Some("Enter the ID of the empire to move the ships to".to_string()));
+            return;
+        };
+        let ship_ids = selected.get_untracked();
+        if ship_ids.is_empty() {
+            set_error.set(Some("Select at least one ship".to_string()));
+            return;
+        }
+
+        let listed = ships.get_untracked();
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = api::reassign_ships(empire_id, target_empire_id, ship_ids).await;
+            set_loading.set(false);
+
+            match result {
+                Ok(report) => {
+                    set_error.set(None);
+                    set_selected.set(Vec::new());
+                    set_outcome.set(Some((report, listed)));
+                    load_ships();
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <div class="form-container">
+            <h3>{format!("Move ships of {}", empire.name)}</h3>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            {move || outcome.get().map(|(report, listed)| {
+                let item_label = move |ship_id: i32| {
+                    listed.iter()
+                        .find(|ship| ship.id == ship_id)
+                        .map(|ship| format!("{} (#{})", ship.name, ship.id))
+                        .unwrap_or_else(|| format!("Ship #{}", ship_id))
+                };
+                view! {
+                    <BatchResults results=report.results action="ships moved" item_label=item_label/>
+                }
+            })}
+
+            <form on:submit=handle_submit>
+                <div class="form-group">
+                    <label for="target_empire_id">"Target empire ID:"</label>
+                    <input
+                        type="number"
+                        id="target_empire_id"
+                        required
+                        prop:value=target_empire_id
+                        on:input=move |ev| set_target_empire_id.set(event_target_value(&ev))
+                    />
+                </div>
+
+                <div class="form-group">
+                    {move || if ships.with(|ships| ships.is_empty()) {
+                        view! { <p>"This empire has no ships."</p> }.into_view()
+                    } else {
+                        ships.get().into_iter().map(|ship| {
+                            let ship_id = ship.id;
+                            view! {
+                                <label class="checkbox">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || selected.with(|selected| selected.contains(&ship_id))
+                                        on:change=move |ev| toggle_ship(ship_id, event_target_checked(&ev))
+                                    />
+                                    {format!("{} (#{})", ship.name, ship.id)}
+                                </label>
+                            }
+                        }).collect_view()
+                    }}
+                </div>
+
+                <div class="form-actions">
+                    <button type="submit" disabled=move || loading.get()>"Move"</button>
+                    <button type="button" on:click=move |_| on_close.call(())>"Close"</button>
+                </div>
+            </form>
+        </div>
+    }
+}
@@ -0,0 +1,34 @@
+use leptos::*;
+use leptos_router::*;
+use crate::telemetry;
+
+// Records how long each navigation takes to render. The first one is measured from the start of the page load.
+#[component]
+pub fn RouteTimings() -> impl IntoView {
+    let location = use_location();
+
+    create_effect(move |previous: Option<()>| {
+        let path = location.pathname.get();
+        let started = if previous.is_none() { 0.0 } else { telemetry::now() };
+
+        request_animation_frame(move || telemetry::record_navigation(&path, telemetry::now() - started));
+    });
+}
+
+#[component]
+pub fn TelemetryToggle() -> impl IntoView {
+    let (enabled, set_enabled) = create_signal(telemetry::is_enabled());
+
+    let toggle = move |ev| {
+        let checked = event_target_checked(&ev);
+        telemetry::set_enabled(checked);
+        set_enabled.set(checked);
+    };
+
+    view! {
+        <label class="telemetry-toggle">
+            <input type="checkbox" prop:checked=enabled on:change=toggle/>
+            " Share anonymous performance and error statistics"
+        </label>
+    }
+}
@@ -0,0 +1,89 @@
+use js_sys::{Array, Date, Intl, Object, Reflect};
+use leptos::*;
+use wasm_bindgen::JsValue;
+
+// Relative times switch to the next unit once they exceed the current one, e.g. '90 minutes ago' becomes '2 hours ago'
+const RELATIVE_UNITS: [(&str, f64); 6] = [
+    ("second", 60.0),
+    ("minute", 60.0),
+    ("hour", 24.0),
+    ("day", 7.0),
+    ("week", 4.35),
+    ("month", 12.0),
+];
+
+// Locale of the browser, which Intl formats dates and relative times for
+fn user_locale() -> String {
+    web_sys::window()
+        .and_then(|window| window.navigator().language())
+        .unwrap_or_else(|| "en-US".to_string())
+}
+
+// The backend serializes timestamps in UTC without an offset, which Date would otherwise read as local time
+fn parse_timestamp(value: &str) -> Option<Date> {
+    let has_offset = value.ends_with('Z') || value.get(19..).is_some_and(|rest| rest.contains(['+', '-']));
+    let iso = if has_offset { value.to_string() } else { format!("{}Z", value) };
+
+    let date = Date::new(&JsValue::from_str(&iso));
+    (!date.get_time().is_nan()).then_some(date)
+}
+
+fn options(entries: &[(&str, &str)]) -> Object {
+    let options = Object::new();
+    for (key, value) in entries {
+        let _ = Reflect::set(&options, &JsValue::from_str(key), &JsValue::from_str(value));
+    }
+    options
+}
+
+// Date and time in the user's locale and time zone, e.g. '16 Oct 2026, 14:05'
+fn format_absolute(date: &Date, locale: &str) -> String {
+    date.to_locale_string(locale, &options(&[("dateStyle", "medium"), ("timeStyle", "short")]))
+        .into()
+}
+
+// Distance from now in the largest fitting unit, e.g. '3 hours ago' or 'in 2 days'
+fn format_relative(date: &Date, locale: &str) -> String {
+    let mut value = (date.get_time() - Date::now()) / 1000.0;
+    let mut unit = "year";
+
+    for (candidate, size) in RELATIVE_UNITS {
+        if value.abs() < size {
+            unit = candidate;
+            break;
+        }
+        value /= size;
+    }
+
+    let locales = Array::of1(&JsValue::from_str(locale));
+    Intl::RelativeTimeFormat::new(&locales, &options(&[("numeric", "auto")]))
+        .format(value.round(), unit)
+        .into()
+}
+
+// Renders a backend timestamp as a localized date, with the time relative to now as a tooltip.
+// The tooltip is worked out when hovered, so it stays accurate on pages left open for a while.
+// Values that cannot be parsed are shown as they are.
+#[component]
+pub fn Timestamp(#[prop(into)] value: String) -> impl IntoView {
+    let locale = user_locale();
+
+    let Some(date) = parse_timestamp(&value) else {
+        return view! { <span class="timestamp">{value}</span> }.into_view();
+    };
+
+    let absolute = format_absolute(&date, &locale);
+    let iso: String = date.to_iso_string().into();
+    let (relative, set_relative) = create_signal(format_relative(&date, &locale));
+
+    view! {
+        <time
+            class="timestamp"
+            datetime=iso
+            title=relative
+            on:mouseenter=move |_| set_relative.set(format_relative(&date, &locale))
+        >
+            {absolute}
+        </time>
+    }.into_view()
+}
@@ -0,0 +1,114 @@
+use std::hash::Hash;
+use leptos::*;
+
+// Must match the row height in style.css (.virtual-table tbody tr)
+pub const ROW_HEIGHT: f64 = 56.0;
+
+// Rows rendered above and below the visible window so that fast scrolling does not reveal blank space
+const OVERSCAN: usize = 5;
+
+// Distance (in rows) from the bottom at which the next page is requested
+const LOAD_MORE_THRESHOLD: usize = 10;
+
+// Returns the [start, end) range of rows that need to be in the DOM for the given scroll position
+pub fn visible_range(scroll_top: f64, row_height: f64, viewport_height: f64, total_rows: usize) -> (usize, usize) {
+    let first_visible = (scroll_top / row_height).floor().max(0.0) as usize;
+    let visible_count = (viewport_height / row_height).ceil() as usize;
+
+    let start = first_visible.saturating_sub(OVERSCAN).min(total_rows);
+    let end = (first_visible + visible_count + OVERSCAN).min(total_rows);
+
+    (start, end)
+}
+
+// Table which only renders the rows inside its scroll viewport. Rows outside the window are replaced by two
+// spacer rows sized to keep the scrollbar accurate, and 'on_end_reached' fires when nearing the last row.
+#[component]
+pub fn VirtualTable<T, K, KF, RF, IV>(
+    #[prop(into)] rows: Signal<Vec<T>>,
+    columns: Vec<&'static str>,
+    row_key: KF,
+    render_row: RF,
+    #[prop(optional)] on_end_reached: Option<Callback<()>>,
+    #[prop(default = 560.0)] viewport_height: f64,
+) -> impl IntoView
+where
+    T: Clone + 'static,
+    K: Eq + Hash + 'static,
+    KF: Fn(&T) -> K + 'static,
+    RF: Fn(T) -> IV + 'static,
+    IV: IntoView + 'static,
+{
+    let (scroll_top, set_scroll_top) = create_signal(0.0);
+    let container = create_node_ref::<html::Div>();
+    let column_count = columns.len().to_string();
+
+    let window = create_memo(move |_| {
+        visible_range(scroll_top.get(), ROW_HEIGHT, viewport_height, rows.with(|rows| rows.len()))
+    });
+
+    // Jump back to the top when the rows are replaced by a shorter list, e.g. after a new search
+    create_effect(move |previous_len: Option<usize>| {
+        let len = rows.with(|rows| rows.len());
+        if previous_len.map_or(false, |previous_len| len < previous_len) {
+            if let Some(element) = container.get_untracked() {
+                element.set_scroll_top(0);
+            }
+            set_scroll_top.set(0.0);
+        }
+        len
+    });
+
+    let on_scroll = move |ev: ev::Event| {
+        let element = event_target::<web_sys::Element>(&ev);
+        let top = element.scroll_top() as f64;
+        set_scroll_top.set(top);
+
+        let total_rows = rows.with_untracked(|rows| rows.len());
+        let last_visible = ((top + viewport_height) / ROW_HEIGHT).ceil() as usize;
+        if last_visible + LOAD_MORE_THRESHOLD >= total_rows {
+            if let Some(on_end_reached) = on_end_reached.as_ref() {
+                on_end_reached.call(());
+            }
+        }
+    };
+
+    let top_spacer = move || format!("height: {}px", window.get().0 as f64 * ROW_HEIGHT);
+    let bottom_spacer = move || {
+        let remaining = rows.with(|rows| rows.len()).saturating_sub(window.get().1);
+        format!("height: {}px", remaining as f64 * ROW_HEIGHT)
+    };
+
+    view! {
+        <div
+            class="virtual-table"
+            node_ref=container
+            style=format!("height: {}px", viewport_height)
+            on:scroll=on_scroll
+        >
+            <table>
+                <thead>
+                    <tr>
+                        {columns.into_iter().map(|column| view! { <th>{column}</th> }).collect_view()}
+                    </tr>
+                </thead>
+                <tbody>
+                    <tr class="virtual-spacer" style=top_spacer>
+                        <td colspan=column_count.clone()></td>
+                    </tr>
+                    <For
+                        each=move || {
+                            let (start, end) = window.get();
+                            rows.with(|rows| rows[start..end].to_vec())
+                        }
+                        key=row_key
+                        children=render_row
+                    />
+                    <tr class="virtual-spacer" style=bottom_spacer>
+                        <td colspan=column_count></td>
+                    </tr>
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -3,28 +3,43 @@ use leptos_meta::*;
 use leptos_router::*;
 
 mod api;
+mod auth;
 mod components;
 mod pages;
+mod telemetry;
 
 use pages::*;
+use components::telemetry::RouteTimings;
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
+    auth::provide_auth_context();
+    api::provide_request_signals();
 
     view! {
         <Stylesheet id="leptos" href="/pkg/frontend.css"/>
         <Title text="API Frontend"/>
 
         <Router>
+            <RouteTimings/>
             <main>
                 <Routes>
                     <Route path="" view=HomePage/>
                     <Route path="/login" view=LoginPage/>
                     <Route path="/register" view=RegisterPage/>
                     <Route path="/locations" view=LocationsPage/>
+                    <Route path="/locations/:id" view=LocationDetailPage/>
+                    <Route path="/map" view=MapPage/>
                     <Route path="/empires" view=EmpiresPage/>
                     <Route path="/users" view=UsersPage/>
+                    <Route path="/change-requests" view=ReviewQueuePage/>
+                    <Route path="/admin" view=AdminDashboardPage/>
+                    <Route path="/admin/webhooks" view=WebhooksPage/>
+                    <Route path="/admin/api-keys" view=ApiKeysPage/>
+                    <Route path="/admin/templates" view=TemplatesPage/>
+                    <Route path="/profile" view=ProfilePage/>
+                    <Route path="/profile/sessions" view=ProfileSessionsPage/>
                 </Routes>
             </main>
         </Router>
@@ -33,6 +48,7 @@ pub fn App() -> impl IntoView {
 
 #[wasm_bindgen::prelude::wasm_bindgen(start)]
 pub fn main() {
-    console_error_panic_hook::set_once();
+    // Installs the panic hook, which forwards to console_error_panic_hook
+    telemetry::install();
     mount_to_body(|| view! { <App/> })
 }
\ No newline at end of file
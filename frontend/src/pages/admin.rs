@@ -0,0 +1,856 @@
+use leptos::*;
+use crate::api::{self, RequestScope};
+use crate::api::admin::{self as admin_api, ApiKey, CreateApiKey, StatsHistory, UpsertWebhook, Webhook, WebhookDelivery};
+use crate::api::templates::{self as templates_api, Template, UpsertTemplate};
+use crate::components::charts::{BarChart, ChartSeries, LineChart};
+use crate::components::navbar::Navbar;
+use crate::components::timestamp::Timestamp;
+
+#[component]
+pub fn WebhooksPage() -> impl IntoView {
+    let (webhooks, set_webhooks) = create_signal(Vec::<Webhook>::new());
+    let (event_types, set_event_types) = create_signal(Vec::<String>::new());
+    let (selected, set_selected) = create_signal(None::<Webhook>);
+    let (deliveries, set_deliveries) = create_signal(Vec::<WebhookDelivery>::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+
+    // Form state - 'editing' is None while registering a new endpoint
+    let (show_form, set_show_form) = create_signal(false);
+    let (editing, set_editing) = create_signal(None::<i32>);
+    let (url, set_url) = create_signal(String::new());
+    let (chosen_events, set_chosen_events) = create_signal(Vec::<String>::new());
+    let (enabled, set_enabled) = create_signal(true);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_webhooks = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = admin_api::get_webhooks(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_webhooks.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    let load_deliveries = move |webhook_id: i32| {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = admin_api::get_webhook_deliveries(webhook_id, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_deliveries.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Load webhooks and the available event types on mount
+    create_effect(move |_| {
+        load_webhooks();
+
+        let signal = requests.with_value(RequestScope::signal);
+        spawn_local(async move {
+            match admin_api::get_webhook_events(signal.as_ref()).await {
+                Ok(list) => set_event_types.set(list),
+                Err(e) if !api::is_aborted(&signal) => set_error.set(Some(e)),
+                Err(_) => {}
+            }
+        });
+    });
+
+    let open_form = move |webhook: Option<Webhook>| {
+        match webhook {
+            Some(webhook) => {
+                set_editing.set(Some(webhook.id));
+                set_url.set(webhook.url);
+                set_chosen_events.set(webhook.event_types);
+                set_enabled.set(webhook.enabled);
+            }
+            None => {
+                set_editing.set(None);
+                set_url.set(String::new());
+                set_chosen_events.set(Vec::new());
+                set_enabled.set(true);
+            }
+        }
+        set_error.set(None);
+        set_show_form.set(true);
+    };
+
+    let toggle_event = move |event_type: String| {
+        set_chosen_events.update(|chosen| {
+            if let Some(index) = chosen.iter().position(|chosen| *chosen == event_type) {
+                chosen.remove(index);
+            } else {
+                chosen.push(event_type);
+            }
+        });
+    };
+
+    let save_webhook = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let webhook = UpsertWebhook {
+            url: url.get_untracked(),
+            event_types: chosen_events.get_untracked(),
+            enabled: enabled.get_untracked(),
+        };
+        let editing = editing.get_untracked();
+
+        spawn_local(async move {
+            let result = match editing {
+                Some(id) => admin_api::update_webhook(id, webhook).await,
+                None => admin_api::create_webhook(webhook).await,
+            };
+
+            match result {
+                Ok(_) => {
+                    set_show_form.set(false);
+                    load_webhooks();
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let delete_webhook = move |id: i32| {
+        spawn_local(async move {
+            match admin_api::delete_webhook(id).await {
+                Ok(_) => {
+                    if selected.get_untracked().map_or(false, |webhook| webhook.id == id) {
+                        set_selected.set(None);
+                    }
+                    load_webhooks();
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let show_deliveries = move |webhook: Webhook| {
+        set_deliveries.set(Vec::new());
+        load_deliveries(webhook.id);
+        set_selected.set(Some(webhook));
+    };
+
+    // Test deliveries and redrives show up at the top of the history once recorded
+    let send_test = move |webhook: Webhook| {
+        spawn_local(async move {
+            match admin_api::test_webhook(webhook.id).await {
+                Ok(_) => {
+                    load_deliveries(webhook.id);
+                    set_selected.set(Some(webhook));
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let redrive = move |delivery: WebhookDelivery| {
+        spawn_local(async move {
+            match admin_api::redrive_webhook_delivery(delivery.webhook_id, delivery.id).await {
+                Ok(_) => load_deliveries(delivery.webhook_id),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Webhooks"</h1>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            <Show
+                when=move || show_form.get()
+                fallback=move || view! {
+                    <div class="actions">
+                        <button on:click=move |_| open_form(None) class="btn btn-primary">"Register Endpoint"</button>
+                    </div>
+                }
+            >
+                <div class="form-container">
+                    <h2>{move || if editing.get().is_some() { "Edit Webhook" } else { "Register Webhook" }}</h2>
+                    <form on:submit=save_webhook>
+                        <div class="form-group">
+                            <label for="webhook-url">"Endpoint URL:"</label>
+                            <input
+                                type="url"
+                                id="webhook-url"
+                                required
+                                placeholder="https://example.com/hooks/empires"
+                                prop:value=url
+                                on:input=move |ev| set_url.set(event_target_value(&ev))
+                            />
+                        </div>
+
+                        <fieldset class="form-group event-types">
+                            <legend>"Events:"</legend>
+                            <For
+                                each=move || event_types.get()
+                                key=|event_type| event_type.clone()
+                                children=move |event_type| {
+                                    let checked = {
+                                        let event_type = event_type.clone();
+                                        move || chosen_events.with(|chosen| chosen.contains(&event_type))
+                                    };
+                                    let toggled = event_type.clone();
+                                    view! {
+                                        <label class="checkbox">
+                                            <input
+                                                type="checkbox"
+                                                prop:checked=checked
+                                                on:change=move |_| toggle_event(toggled.clone())
+                                            />
+                                            {event_type}
+                                        </label>
+                                    }
+                                }
+                            />
+                        </fieldset>
+
+                        <label class="checkbox">
+                            <input
+                                type="checkbox"
+                                prop:checked=enabled
+                                on:change=move |ev| set_enabled.set(event_target_checked(&ev))
+                            />
+                            "Enabled"
+                        </label>
+
+                        <div class="form-actions">
+                            <button type="submit" class="btn btn-primary">"Save"</button>
+                            <button type="button" class="btn btn-secondary" on:click=move |_| set_show_form.set(false)>
+                                "Cancel"
+                            </button>
+                        </div>
+                    </form>
+                </div>
+            </Show>
+
+            <div class="data-table">
+                <Show when=move || !loading.get() fallback=|| view! { <div class="loading">"Loading..."</div> }>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>"URL"</th>
+                                <th>"Events"</th>
+                                <th>"Status"</th>
+                                <th>"Actions"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || webhooks.get()
+                                key=|webhook| (webhook.id, webhook.url.clone(), webhook.event_types.clone(), webhook.enabled)
+                                children=move |webhook| {
+                                    let id = webhook.id;
+                                    let for_edit = webhook.clone();
+                                    let for_history = webhook.clone();
+                                    let for_test = webhook.clone();
+                                    view! {
+                                        <tr>
+                                            <td>{webhook.url}</td>
+                                            <td>{webhook.event_types.join(", ")}</td>
+                                            <td>{if webhook.enabled { "Enabled" } else { "Disabled" }}</td>
+                                            <td class="actions">
+                                                <button
+                                                    class="btn btn-small btn-secondary"
+                                                    on:click=move |_| open_form(Some(for_edit.clone()))
+                                                >
+                                                    "Edit"
+                                                </button>
+                                                <button
+                                                    class="btn btn-small btn-secondary"
+                                                    on:click=move |_| show_deliveries(for_history.clone())
+                                                >
+                                                    "Deliveries"
+                                                </button>
+                                                <button
+                                                    class="btn btn-small btn-secondary"
+                                                    on:click=move |_| send_test(for_test.clone())
+                                                >
+                                                    "Send Test"
+                                                </button>
+                                                <button class="btn btn-small btn-danger" on:click=move |_| delete_webhook(id)>
+                                                    "Delete"
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </Show>
+            </div>
+
+            {move || selected.get().map(|webhook| view! {
+                <div class="data-table delivery-history">
+                    <h2>"Deliveries to " {webhook.url}</h2>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>"Sent"</th>
+                                <th>"Event"</th>
+                                <th>"Status"</th>
+                                <th>"Error"</th>
+                                <th>"Actions"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || deliveries.get()
+                                key=|delivery| delivery.id
+                                children=move |delivery| {
+                                    let class = if delivery.succeeded() { "delivery-ok" } else { "delivery-failed" };
+                                    let status = delivery.status_code.map_or("No response".to_string(), |status| status.to_string());
+                                    let event = match delivery.redrive_of {
+                                        Some(original) => format!("{} (redrive of #{})", delivery.event_type, original),
+                                        None => delivery.event_type.clone(),
+                                    };
+                                    let error = delivery.error.clone().unwrap_or_default();
+                                    let delivered_at = delivery.delivered_at.clone();
+                                    view! {
+                                        <tr class=class>
+                                            <td><Timestamp value=delivered_at/></td>
+                                            <td>{event}</td>
+                                            <td>{status}</td>
+                                            <td>{error}</td>
+                                            <td class="actions">
+                                                <button
+                                                    class="btn btn-small btn-secondary"
+                                                    title="Send this payload again"
+                                                    on:click=move |_| redrive(delivery.clone())
+                                                >
+                                                    "Redrive"
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </div>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn ApiKeysPage() -> impl IntoView {
+    let (api_keys, set_api_keys) = create_signal(Vec::<ApiKey>::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+    let (name, set_name) = create_signal(String::new());
+    let (role, set_role) = create_signal("READER".to_string());
+
+    // Secret of the key created last - shown once, and gone as soon as the page is left or it is dismissed
+    let (new_secret, set_new_secret) = create_signal(None::<(String, String)>);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_api_keys = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = admin_api::get_api_keys(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_api_keys.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    // Load API keys on mount
+    create_effect(move |_| load_api_keys());
+
+    let create_key = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let api_key = CreateApiKey { name: name.get_untracked(), role: role.get_untracked() };
+
+        spawn_local(async move {
+            match admin_api::create_api_key(api_key).await {
+                Ok(created) => {
+                    set_error.set(None);
+                    set_name.set(String::new());
+                    set_new_secret.set(Some((created.api_key.name, created.secret)));
+                    load_api_keys();
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let revoke_key = move |api_key: ApiKey| {
+        let confirmed = web_sys::window()
+            .and_then(|window| window.confirm_with_message(&format!(
+                "Revoke '{}'? Integrations using it will stop working immediately.", api_key.name
+            )).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+
+        spawn_local(async move {
+            match admin_api::revoke_api_key(api_key.id).await {
+                Ok(_) => load_api_keys(),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"API Keys"</h1>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            {move || new_secret.get().map(|(key_name, secret)| view! {
+                <div class="secret-reveal">
+                    <p>
+                        "Copy the key for " <strong>{key_name}</strong>
+                        " now - it is not stored and will not be shown again."
+                    </p>
+                    <input type="text" readonly class="secret-value" prop:value=secret on:focus=move |ev| {
+                        event_target::<web_sys::HtmlInputElement>(&ev).select();
+                    }/>
+                    <button class="btn btn-small btn-secondary" on:click=move |_| set_new_secret.set(None)>
+                        "I have copied it"
+                    </button>
+                </div>
+            })}
+
+            <form class="inline-form" on:submit=create_key>
+                <input
+                    type="text"
+                    required
+                    maxlength="100"
+                    placeholder="Name, e.g. Nightly sync"
+                    prop:value=name
+                    on:input=move |ev| set_name.set(event_target_value(&ev))
+                />
+                <select prop:value=role on:change=move |ev| set_role.set(event_target_value(&ev))>
+                    <option value="READER">"Reader"</option>
+                    <option value="WRITER">"Writer"</option>
+                    <option value="EDITOR">"Editor"</option>
+                    <option value="ADMIN">"Admin"</option>
+                </select>
+                <button type="submit" class="btn btn-primary">"Create Key"</button>
+            </form>
+
+            <div class="data-table">
+                <Show when=move || !loading.get() fallback=|| view! { <div class="loading">"Loading..."</div> }>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>"Name"</th>
+                                <th>"Key"</th>
+                                <th>"Role"</th>
+                                <th>"Created"</th>
+                                <th>"Last Used"</th>
+                                <th>"Actions"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || api_keys.get()
+                                key=|api_key| (api_key.id, api_key.last_used_at.clone(), api_key.revoked_at.clone())
+                                children=move |api_key| {
+                                    let revoked = api_key.revoked_at.is_some();
+                                    let last_used = match api_key.last_used_at.clone() {
+                                        Some(last_used_at) => view! { <Timestamp value=last_used_at/> }.into_view(),
+                                        None => "Never".into_view(),
+                                    };
+                                    let for_revoke = api_key.clone();
+                                    view! {
+                                        <tr class:revoked=revoked>
+                                            <td>{api_key.name}</td>
+                                            <td><code>{api_key.prefix}"…"</code></td>
+                                            <td>{api_key.role}</td>
+                                            <td><Timestamp value=api_key.created_at/></td>
+                                            <td>{last_used}</td>
+                                            <td class="actions">
+                                                {match api_key.revoked_at {
+                                                    Some(revoked_at) => view! { <span>"Revoked " <Timestamp value=revoked_at/></span> }.into_view(),
+                                                    None => view! {
+                                                        <button
+                                                            class="btn btn-small btn-danger"
+                                                            on:click=move |_| revoke_key(for_revoke.clone())
+                                                        >
+                                                            "Revoke"
+                                                        </button>
+                                                    }.into_view(),
+                                                }}
+                                            </td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn TemplatesPage() -> impl IntoView {
+    let (templates, set_templates) = create_signal(Vec::<Template>::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+
+    // Form state - 'editing' is None while defining a new template. Fields are edited as a JSON object.
+    let (show_form, set_show_form) = create_signal(false);
+    let (editing, set_editing) = create_signal(None::<i32>);
+    let (name, set_name) = create_signal(String::new());
+    let (resource, set_resource) = create_signal("empire".to_string());
+    let (fields, set_fields) = create_signal(String::new());
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_templates = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = templates_api::get_all_templates(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_templates.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    // Load templates on mount
+    create_effect(move |_| load_templates());
+
+    let open_form = move |template: Option<Template>| {
+        match template {
+            Some(template) => {
+                set_editing.set(Some(template.id));
+                set_name.set(template.name);
+                set_resource.set(template.resource);
+                set_fields.set(serde_json::to_string_pretty(&template.fields).unwrap_or_default());
+            }
+            None => {
+                set_editing.set(None);
+                set_name.set(String::new());
+                set_resource.set("empire".to_string());
+                set_fields.set("{}".to_string());
+            }
+        }
+        set_error.set(None);
+        set_show_form.set(true);
+    };
+
+    let save_template = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let fields = match serde_json::from_str(&fields.get_untracked()) {
+            Ok(fields) => fields,
+            Err(e) => {
+                set_error.set(Some(format!("Fields must be a JSON object: {}", e)));
+                return;
+            }
+        };
+        let template = UpsertTemplate { name: name.get_untracked(), resource: resource.get_untracked(), fields };
+        let editing = editing.get_untracked();
+
+        spawn_local(async move {
+            let result = match editing {
+                Some(id) => templates_api::update_template(id, template).await,
+                None => templates_api::create_template(template).await,
+            };
+
+            match result {
+                Ok(_) => {
+                    set_error.set(None);
+                    set_show_form.set(false);
+                    load_templates();
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let delete_template = move |id: i32| {
+        spawn_local(async move {
+            match templates_api::delete_template(id).await {
+                Ok(_) => load_templates(),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Templates"</h1>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            <Show
+                when=move || show_form.get()
+                fallback=move || view! {
+                    <div class="actions">
+                        <button on:click=move |_| open_form(None) class="btn btn-primary">"New Template"</button>
+                    </div>
+                }
+            >
+                <div class="form-container">
+                    <h2>{move || if editing.get().is_some() { "Edit Template" } else { "New Template" }}</h2>
+                    <form on:submit=save_template>
+                        <div class="form-group">
+                            <label for="template-name">"Name:"</label>
+                            <input
+                                type="text"
+                                id="template-name"
+                                required
+                                maxlength="100"
+                                prop:value=name
+                                on:input=move |ev| set_name.set(event_target_value(&ev))
+                            />
+                        </div>
+
+                        <div class="form-group">
+                            <label for="template-resource">"Used for:"</label>
+                            <select
+                                id="template-resource"
+                                prop:value=resource
+                                on:change=move |ev| set_resource.set(event_target_value(&ev))
+                            >
+                                <option value="empire">"Empires"</option>
+                                <option value="ship">"Ships"</option>
+                            </select>
+                        </div>
+
+                        <div class="form-group">
+                            <label for="template-fields">"Fields:"</label>
+                            <textarea
+                                id="template-fields"
+                                class="template-fields"
+                                placeholder=r#"{"slogan": "Amarr victor", "location_id": 1}"#
+                                prop:value=fields
+                                on:input=move |ev| set_fields.set(event_target_value(&ev))
+                            />
+                        </div>
+
+                        <div class="form-actions">
+                            <button type="submit" class="btn btn-primary">"Save"</button>
+                            <button type="button" class="btn btn-secondary" on:click=move |_| set_show_form.set(false)>
+                                "Cancel"
+                            </button>
+                        </div>
+                    </form>
+                </div>
+            </Show>
+
+            <div class="data-table">
+                <Show when=move || !loading.get() fallback=|| view! { <div class="loading">"Loading..."</div> }>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>"Name"</th>
+                                <th>"Used for"</th>
+                                <th>"Fields"</th>
+                                <th>"Created"</th>
+                                <th>"Actions"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || templates.get()
+                                key=|template| (template.id, template.name.clone(), template.resource.clone(), serde_json::to_string(&template.fields).unwrap_or_default())
+                                children=move |template| {
+                                    let id = template.id;
+                                    let for_edit = template.clone();
+                                    let prefilled = template.fields.keys().cloned().collect::<Vec<_>>().join(", ");
+                                    view! {
+                                        <tr>
+                                            <td>{template.name}</td>
+                                            <td>{template.resource}</td>
+                                            <td>{prefilled}</td>
+                                            <td><Timestamp value=template.created_at/></td>
+                                            <td class="actions">
+                                                <button
+                                                    class="btn btn-small btn-secondary"
+                                                    on:click=move |_| open_form(Some(for_edit.clone()))
+                                                >
+                                                    "Edit"
+                                                </button>
+                                                <button class="btn btn-small btn-danger" on:click=move |_| delete_template(id)>
+                                                    "Delete"
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+// Periods the dashboard can be switched between, in days
+const DASHBOARD_PERIODS: [i64; 3] = [7, 30, 90];
+
+#[component]
+pub fn AdminDashboardPage() -> impl IntoView {
+    let (days, set_days) = create_signal(30_i64);
+    let (stats, set_stats) = create_signal(None::<StatsHistory>);
+    let (error, set_error) = create_signal(None::<String>);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    // Reload whenever the period changes
+    create_effect(move |_| {
+        let days = days.get();
+        let Some(signal) = requests.try_with_value(RequestScope::renew) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = admin_api::get_stats_history(days, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(history) => {
+                    set_error.set(None);
+                    set_stats.set(Some(history));
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    });
+
+    let tiles = move || stats.get().map(|stats| {
+        let total_users = stats.history.last().map(|day| day.total_users).unwrap_or(0);
+        let sum = |count: fn(&admin_api::DailyCounts) -> i64| stats.history.iter().map(count).sum::<i64>();
+        let tiles = [
+            ("Users", total_users),
+            ("New users", sum(|day| day.new_users)),
+            ("New locations", sum(|day| day.new_locations)),
+            ("New empires", sum(|day| day.new_empires)),
+        ];
+
+        view! {
+            <div class="stat-tiles">
+                {tiles.into_iter().map(|(label, value)| view! {
+                    <div class="stat-tile">
+                        <span class="stat-value">{value}</span>
+                        <span class="stat-label">{label}</span>
+                    </div>
+                }).collect_view()}
+            </div>
+        }
+    });
+
+    let charts = move || stats.get().map(|stats| {
+        // Days are shown as month-day, the year being evident from context
+        let labels: Vec<String> = stats.history.iter().map(|day| day.day.get(5..).unwrap_or(&day.day).to_string()).collect();
+        let series_of = |name: &str, color: &str, count: fn(&admin_api::DailyCounts) -> i64| {
+            ChartSeries::new(name, color, stats.history.iter().map(|day| count(day) as f64).collect())
+        };
+
+        view! {
+            <section class="chart-panel">
+                <h2>"Users over time"</h2>
+                <LineChart labels=labels.clone() series=vec![series_of("Users", "#3498db", |day| day.total_users)]/>
+            </section>
+            <section class="chart-panel">
+                <h2>"Entities created per day"</h2>
+                <BarChart
+                    labels=labels
+                    series=vec![
+                        series_of("Users", "#3498db", |day| day.new_users),
+                        series_of("Locations", "#27ae60", |day| day.new_locations),
+                        series_of("Empires", "#e67e22", |day| day.new_empires),
+                    ]
+                />
+            </section>
+        }
+    });
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Admin Dashboard"</h1>
+
+            <div class="period-selector">
+                {DASHBOARD_PERIODS.into_iter().map(|period| view! {
+                    <button
+                        class="btn btn-small"
+                        class:btn-primary=move || days.get() == period
+                        class:btn-secondary=move || days.get() != period
+                        on:click=move |_| set_days.set(period)
+                    >
+                        {format!("{} days", period)}
+                    </button>
+                }).collect_view()}
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            <Show when=move || stats.with(Option::is_some) fallback=|| view! { <div class="loading">"Loading..."</div> }>
+                {tiles}
+                {charts}
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,381 @@
+use leptos::*;
+use leptos_router::*;
+use crate::api::{self, Empire as ApiEmpire, Location as ApiLocation, RequestScope};
+use crate::api::map::{self as map_api, LocationCoordinates, MapLocation, MapRegion};
+use crate::components::navbar::Navbar;
+
+// Starmap units between the outermost systems and the edge of the drawing
+const MAP_PADDING: f64 = 10.0;
+const ZOOM_LEVELS: [f64; 4] = [1.0, 2.0, 4.0, 8.0];
+
+// Regions are told apart by color, cycling through this palette
+const REGION_COLORS: [&str; 8] = ["#e67e22", "#3498db", "#2ecc71", "#9b59b6", "#e74c3c", "#1abc9c", "#f1c40f", "#ecf0f1"];
+
+fn region_color(index: usize) -> &'static str {
+    REGION_COLORS[index % REGION_COLORS.len()]
+}
+
+// Bounding box of every system, padded, and never collapsed to a point when there is a single system
+fn map_bounds(locations: &[MapLocation]) -> (f64, f64, f64, f64) {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for location in locations {
+        let (x, y) = (location.x, location.y);
+        min_x = min_x.min(x);
+        min_y = min_y.min(-y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(-y);
+    }
+
+    if locations.is_empty() {
+        return (-50.0, -50.0, 100.0, 100.0);
+    }
+
+    (
+        min_x - MAP_PADDING,
+        min_y - MAP_PADDING,
+        (max_x - min_x) + 2.0 * MAP_PADDING,
+        (max_y - min_y) + 2.0 * MAP_PADDING,
+    )
+}
+
+// Systems grow with the number of players, so that busy parts of the map stand out
+fn system_radius(location: &MapLocation, scale: f64) -> f64 {
+    (1.0 + (location.player_count as f64).sqrt()) * scale
+}
+
+#[component]
+pub fn MapPage() -> impl IntoView {
+    let (regions, set_regions) = create_signal(Vec::<MapRegion>::new());
+    let (highlighted_region, set_highlighted_region) = create_signal(None::<String>);
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+    let (zoom, set_zoom) = create_signal(0usize);
+    let (hovered, set_hovered) = create_signal(None::<MapLocation>);
+    let navigate = use_navigate();
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_map = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::renew) else {
+            return;
+        };
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = map_api::get_map(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(map) => {
+                    set_regions.set(map.regions);
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    // Load the map on mount
+    create_effect(move |_| load_map());
+
+    // Every system along with the index of its region, which determines its color
+    let locations = create_memo(move |_| regions.with(|regions| {
+        regions
+            .iter()
+            .enumerate()
+            .flat_map(|(index, region)| region.locations.iter().cloned().map(move |location| (index, location)))
+            .collect::<Vec<_>>()
+    }));
+    let all_locations = move || locations.with(|locations| locations.iter().map(|(_, l)| l.clone()).collect::<Vec<_>>());
+
+    // Zooming narrows the view box around the center of all systems
+    let view_box = move || {
+        let (x, y, width, height) = map_bounds(&all_locations());
+        let factor = ZOOM_LEVELS[zoom.get()];
+        let (zoomed_width, zoomed_height) = (width / factor, height / factor);
+        format!(
+            "{:.2} {:.2} {:.2} {:.2}",
+            x + (width - zoomed_width) / 2.0,
+            y + (height - zoomed_height) / 2.0,
+            zoomed_width,
+            zoomed_height,
+        )
+    };
+
+    // Keeps circles a constant size on screen regardless of the spread of the map and the zoom level
+    let marker_scale = move || {
+        let (_, _, width, height) = map_bounds(&all_locations());
+        width.max(height) / 200.0 / ZOOM_LEVELS[zoom.get()]
+    };
+
+    let totals = move || regions.with(|regions| (
+        regions.iter().map(|r| r.locations.len()).sum::<usize>(),
+        regions.iter().map(|r| r.empire_count).sum::<i64>(),
+        regions.iter().map(|r| r.player_count).sum::<i64>(),
+    ));
+
+    let systems = move || {
+        let scale = marker_scale();
+        locations.get().into_iter().map(|(region_index, location)| {
+            let (x, y) = (location.x, location.y);
+            let radius = system_radius(&location, scale);
+            let href = format!("/locations/{}", location.id);
+            let navigate = navigate.clone();
+            let claimed = location.empire_count > 0;
+            let region = location.star_system.clone();
+            let dimmed = move || highlighted_region.with(|highlighted| highlighted.as_ref().is_some_and(|name| *name != region));
+            let hovered_location = location.clone();
+
+            view! {
+                <g
+                    class="map-system"
+                    class:map-system-claimed=claimed
+                    class:map-system-dimmed=dimmed
+                    on:mouseenter=move |_| set_hovered.set(Some(hovered_location.clone()))
+                    on:mouseleave=move |_| set_hovered.set(None)
+                    on:click=move |_| navigate(&href, Default::default())
+                >
+                    <circle cx=x cy=-y r=radius style=format!("fill: {}", region_color(region_index))/>
+                    <text x=x y=-y - radius - scale font-size=scale * 4.0 text-anchor="middle">
+                        {location.area.clone()}
+                    </text>
+                </g>
+            }
+        }).collect_view()
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Starmap"</h1>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            <div class="map-toolbar">
+                <span>
+                    {move || {
+                        let (systems, empires, players) = totals();
+                        format!("{} systems, {} empires, {} players", systems, empires, players)
+                    }}
+                </span>
+                <div class="map-zoom">
+                    <button
+                        class="btn btn-secondary"
+                        disabled=move || zoom.get() == 0
+                        on:click=move |_| set_zoom.update(|zoom| *zoom = zoom.saturating_sub(1))
+                    >"−"</button>
+                    <span>{move || format!("{}x", ZOOM_LEVELS[zoom.get()])}</span>
+                    <button
+                        class="btn btn-secondary"
+                        disabled=move || zoom.get() + 1 == ZOOM_LEVELS.len()
+                        on:click=move |_| set_zoom.update(|zoom| *zoom = (*zoom + 1).min(ZOOM_LEVELS.len() - 1))
+                    >"+"</button>
+                    <button class="btn btn-secondary" on:click=move |_| load_map()>"Refresh"</button>
+                </div>
+            </div>
+
+            <Show
+                when=move || !loading.get() || !regions.with(Vec::is_empty)
+                fallback=|| view! { <p>"Loading starmap..."</p> }
+            >
+                <Show
+                    when=move || !regions.with(Vec::is_empty)
+                    fallback=|| view! { <p>"No locations have been placed on the map yet."</p> }
+                >
+                    <div class="map">
+                        <svg viewBox=view_box preserveAspectRatio="xMidYMid meet">
+                            {systems}
+                        </svg>
+                        {move || hovered.get().map(|location| view! {
+                            <div class="map-tooltip">
+                                <strong>{format!("{} - {}", location.star_system, location.area)}</strong>
+                                <div>{format!("Empires: {}", location.empire_count)}</div>
+                                <div>{format!("Players: {}", location.player_count)}</div>
+                            </div>
+                        })}
+                    </div>
+                    <table class="data-table map-regions">
+                        <thead>
+                            <tr>
+                                <th>"Region"</th>
+                                <th>"Systems"</th>
+                                <th>"Empires"</th>
+                                <th>"Players"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || regions.get().into_iter().enumerate().map(|(index, region)| {
+                                let name = region.name.clone();
+                                view! {
+                                    <tr
+                                        on:mouseenter=move |_| set_highlighted_region.set(Some(name.clone()))
+                                        on:mouseleave=move |_| set_highlighted_region.set(None)
+                                    >
+                                        <td>
+                                            <span class="chart-swatch" style=format!("background: {}", region_color(index))></span>
+                                            {region.name}
+                                        </td>
+                                        <td>{region.locations.len()}</td>
+                                        <td>{region.empire_count}</td>
+                                        <td>{region.player_count}</td>
+                                    </tr>
+                                }
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                </Show>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+pub fn LocationDetailPage() -> impl IntoView {
+    let params = use_params_map();
+    let location_id = move || params.with(|params| params.get("id").and_then(|id| id.parse::<i32>().ok()));
+
+    let (location, set_location) = create_signal(None::<ApiLocation>);
+    let (empires, set_empires) = create_signal(Vec::<ApiEmpire>::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (x, set_x) = create_signal(String::new());
+    let (y, set_y) = create_signal(String::new());
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    // Reload whenever the id in the path changes
+    create_effect(move |_| {
+        let Some(id) = location_id() else {
+            set_error.set(Some("Invalid location id".to_string()));
+            return;
+        };
+        let Some(signal) = requests.try_with_value(RequestScope::renew) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = api::get_location(id, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok((loaded, _)) => {
+                    set_x.set(loaded.x.map(|x| x.to_string()).unwrap_or_default());
+                    set_y.set(loaded.y.map(|y| y.to_string()).unwrap_or_default());
+                    set_location.set(Some(loaded));
+                    set_error.set(None);
+                }
+                Err(e) => {
+                    set_error.set(Some(e));
+                    return;
+                }
+            }
+
+            match api::get_empires().await {
+                Ok(all) => set_empires.set(all.into_iter().filter(|empire| empire.location_id == id).collect()),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    });
+
+    let save_coordinates = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let Some(id) = location_id() else {
+            return;
+        };
+        let (Ok(x), Ok(y)) = (x.get_untracked().trim().parse::<f64>(), y.get_untracked().trim().parse::<f64>()) else {
+            set_error.set(Some("Coordinates must be numbers".to_string()));
+            return;
+        };
+
+        spawn_local(async move {
+            match map_api::set_location_coordinates(id, LocationCoordinates { x, y }).await {
+                Ok(updated) => {
+                    set_location.set(Some(updated));
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <p><A href="/map">"← Back to the starmap"</A></p>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            {move || location.get().map(|location| view! {
+                <h1>{format!("{} - {}", location.star_system, location.area)}</h1>
+                <p>
+                    {match (location.x, location.y) {
+                        (Some(x), Some(y)) => format!("Coordinates: {}, {}", x, y),
+                        _ => "Not placed on the starmap".to_string(),
+                    }}
+                </p>
+            })}
+
+            <section class="profile-section">
+                <h2>"Empires"</h2>
+                <Show
+                    when=move || !empires.with(Vec::is_empty)
+                    fallback=|| view! { <p>"No empires are based here."</p> }
+                >
+                    <table class="data-table">
+                        <thead>
+                            <tr>
+                                <th>"Name"</th>
+                                <th>"Slogan"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || empires.get()
+                                key=|empire| empire.id
+                                children=move |empire| view! {
+                                    <tr>
+                                        <td>{empire.name}</td>
+                                        <td>{empire.slogan}</td>
+                                    </tr>
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </Show>
+            </section>
+
+            <section class="profile-section">
+                <h2>"Position"</h2>
+                <form class="inline-form" on:submit=save_coordinates>
+                    <input
+                        type="number"
+                        step="any"
+                        placeholder="x"
+                        prop:value=move || x.get()
+                        on:input=move |ev| set_x.set(event_target_value(&ev))
+                    />
+                    <input
+                        type="number"
+                        step="any"
+                        placeholder="y"
+                        prop:value=move || y.get()
+                        on:input=move |ev| set_y.set(event_target_value(&ev))
+                    />
+                    <button type="submit" class="btn btn-primary">"Save position"</button>
+                </form>
+            </section>
+        </div>
+    }
+}
@@ -1,9 +1,23 @@
 use leptos::*;
 use leptos_router::*;
 use crate::api;
-use crate::api::{Location as ApiLocation, Empire as ApiEmpire, User as ApiUser, UpsertLocation, UpsertEmpire, UpsertUser, is_authenticated};
+use crate::api::{Location as ApiLocation, Empire as ApiEmpire, User as ApiUser, UpsertLocation, UpsertEmpire, UpsertUser, RequestScope, is_authenticated};
+use crate::auth::use_auth;
 use crate::components::navbar::Navbar;
 use crate::components::forms::*;
+use crate::components::virtual_table::VirtualTable;
+use crate::components::conflict::{ConflictDialog, FieldConflict, merge_field};
+use crate::components::ships::ReassignShipsDialog;
+use crate::components::telemetry::TelemetryToggle;
+
+mod admin;
+mod map;
+mod profile;
+mod review;
+pub use admin::*;
+pub use map::*;
+pub use profile::*;
+pub use review::*;
 
 #[component]
 pub fn HomePage() -> impl IntoView {
@@ -20,8 +34,16 @@ pub fn HomePage() -> impl IntoView {
                         <div class="dashboard-links">
                             <A href="/locations" class="dashboard-link">"Manage Locations"</A>
                             <A href="/empires" class="dashboard-link">"Manage Empires"</A>
+                            <A href="/map" class="dashboard-link">"Starmap"</A>
                             <A href="/users" class="dashboard-link">"Manage Users"</A>
+                            <A href="/change-requests" class="dashboard-link">"Review Queue"</A>
+                            <A href="/admin" class="dashboard-link">"Admin Dashboard"</A>
+                            <A href="/admin/webhooks" class="dashboard-link">"Webhooks"</A>
+                            <A href="/admin/api-keys" class="dashboard-link">"API Keys"</A>
+                            <A href="/admin/templates" class="dashboard-link">"Templates"</A>
+                            <A href="/profile" class="dashboard-link">"My Profile"</A>
                         </div>
+                        <TelemetryToggle/>
                     </div>
                 }.into_view()
             } else {
@@ -70,90 +92,209 @@ pub fn LocationsPage() -> impl IntoView {
     let (locations, set_locations) = create_signal(Vec::<ApiLocation>::new());
     let (show_form, set_show_form) = create_signal(false);
     let (editing_location, set_editing_location) = create_signal(None::<ApiLocation>);
+    let (editing_etag, set_editing_etag) = create_signal(None::<String>);
+    // Values the form is prefilled with when they differ from the location being edited, e.g. after a merge
+    let (draft, set_draft) = create_signal(None::<ApiLocation>);
+    // The rejected edit along with the current server copy and its ETag
+    let (conflict, set_conflict) = create_signal(None::<(UpsertLocation, ApiLocation, Option<String>)>);
     let (form_data, set_form_data) = create_signal(None::<UpsertLocation>);
     let (cancel_form, set_cancel_form) = create_signal(false);
     let (error, set_error) = create_signal(None::<String>);
+    let (notice, set_notice) = create_signal(None::<String>);
     let (loading, set_loading) = create_signal(false);
     let (auth_state, set_auth_state) = create_signal(is_authenticated());
+    let (search, set_search) = create_signal(String::new());
+    let (next_cursor, set_next_cursor) = create_signal(None::<i32>);
 
     // Update auth state reactively
     create_effect(move |_| {
         set_auth_state.set(is_authenticated());
     });
 
-    // Load locations on mount
-    create_effect(move |_| {
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    // Fetches the first page of the current search when 'reset' is set, otherwise the page after the last one loaded
+    let load_locations = move |reset: bool| {
+        // Nothing to do once the page has been left, e.g. when a save completes after navigating away
+        if requests.try_with_value(|_| ()).is_none() {
+            return;
+        }
+
+        let cursor = if reset {
+            None
+        } else {
+            match next_cursor.get_untracked() {
+                Some(cursor) if !loading.get_untracked() => Some(cursor),
+                _ => return,
+            }
+        };
+
+        // A new first page supersedes whatever is still in flight, e.g. results for the previous search term
+        let signal = if reset {
+            requests.with_value(RequestScope::renew)
+        } else {
+            requests.with_value(RequestScope::signal)
+        };
+        let query = search.get_untracked();
+
+        set_loading.set(true);
         spawn_local(async move {
-            set_loading.set(true);
-            match api::get_locations().await {
-                Ok(locs) => set_locations.set(locs),
+            let result = api::search_locations(&query, cursor, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(page) => {
+                    set_next_cursor.set(page.next_cursor);
+                    if reset {
+                        set_locations.set(page.items);
+                    } else {
+                        set_locations.update(|locations| locations.extend(page.items));
+                    }
+                },
                 Err(e) => set_error.set(Some(e)),
             }
             set_loading.set(false);
         });
-    });
+    };
+
+    // Load locations on mount
+    create_effect(move |_| load_locations(true));
+
+    let close_form = move || {
+        set_show_form.set(false);
+        set_editing_location.set(None);
+        set_editing_etag.set(None);
+        set_draft.set(None);
+    };
+
+    // Creates or updates the location - updates are conditioned on the ETag the location was loaded with
+    let save_location = move |data: UpsertLocation| {
+        spawn_local(async move {
+            set_loading.set(true);
+            set_notice.set(None);
+            let result = if let Some(location) = editing_location.get_untracked() {
+                api::update_location(location.id, data.clone(), editing_etag.get_untracked()).await
+            } else {
+                api::create_location(data.clone()).await.map_err(api::SaveError::Failed)
+            };
+
+            set_loading.set(false);
+            match result {
+                Ok(_) => {
+                    load_locations(true);
+                    close_form();
+                },
+                Err(api::SaveError::Conflict { current, etag }) => set_conflict.set(Some((data, current, etag))),
+                Err(api::SaveError::Submitted) => {
+                    set_notice.set(Some("Your changes have been submitted for review".to_string()));
+                    close_form();
+                },
+                Err(api::SaveError::Failed(e)) => set_error.set(Some(e)),
+            }
+        });
+    };
 
     // Handle form submission
     create_effect(move |_| {
         if let Some(data) = form_data.get() {
-            spawn_local(async move {
-                set_loading.set(true);
-                let result = if let Some(location) = editing_location.get() {
-                    api::update_location(location.id, data).await
-                } else {
-                    api::create_location(data).await
-                };
-
-                match result {
-                    Ok(_) => {
-                        // Reload locations
-                        match api::get_locations().await {
-                            Ok(locs) => set_locations.set(locs),
-                            Err(e) => set_error.set(Some(e)),
-                        }
-                        set_show_form.set(false);
-                        set_editing_location.set(None);
-                    },
-                    Err(e) => set_error.set(Some(e)),
-                }
-                set_loading.set(false);
-            });
+            save_location(data);
             set_form_data.set(None);
         }
     });
 
+    // Conflict resolution: the current server copy becomes the base of any further edit
+    let merge_conflict = move || {
+        if let (Some((mine, current, etag)), Some(original)) = (conflict.get_untracked(), editing_location.get_untracked()) {
+            set_draft.set(Some(ApiLocation {
+                id: current.id,
+                star_system: merge_field(&original.star_system, &mine.star_system, &current.star_system),
+                area: merge_field(&original.area, &mine.area, &current.area),
+                // Coordinates are edited on the map, not in this form
+                x: current.x,
+                y: current.y,
+            }));
+            set_editing_location.set(Some(current));
+            set_editing_etag.set(etag);
+        }
+        set_conflict.set(None);
+    };
+
+    let overwrite_conflict = move || {
+        if let Some((mine, current, etag)) = conflict.get_untracked() {
+            set_editing_location.set(Some(current));
+            set_editing_etag.set(etag);
+            save_location(mine);
+        }
+        set_conflict.set(None);
+    };
+
+    let discard_conflict = move || {
+        set_conflict.set(None);
+        close_form();
+        load_locations(true);
+    };
+
     // Handle form cancel
     create_effect(move |_| {
         if cancel_form.get() {
-            set_show_form.set(false);
-            set_editing_location.set(None);
+            close_form();
             set_cancel_form.set(false);
         }
     });
 
     let add_location = move |_| {
-        set_editing_location.set(None);
+        close_form();
         set_show_form.set(true);
     };
 
+    // Edits start from a fresh copy so that its ETag reflects what the user actually sees
     let edit_location = move |location: ApiLocation| {
-        set_editing_location.set(Some(location));
-        set_show_form.set(true);
+        let signal = requests.with_value(RequestScope::signal);
+        spawn_local(async move {
+            let result = api::get_location(location.id, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok((current, etag)) => {
+                    set_draft.set(None);
+                    set_editing_location.set(Some(current));
+                    set_editing_etag.set(etag);
+                    set_show_form.set(true);
+                },
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Copies the location and opens the copy for editing, so that only the fields that differ need retyping
+    let duplicate_location_action = move |id: i32| {
+        spawn_local(async move {
+            match api::clone_location(id).await {
+                Ok(copy) => {
+                    load_locations(true);
+                    edit_location(copy);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
     };
 
     let delete_location_action = move |id: i32| {
         spawn_local(async move {
             set_loading.set(true);
-            match api::delete_location(id).await {
-                Ok(_) => {
-                    match api::get_locations().await {
-                        Ok(locs) => set_locations.set(locs),
-                        Err(e) => set_error.set(Some(e)),
-                    }
-                },
+            let result = api::delete_location(id).await;
+            set_loading.set(false);
+            match result {
+                Ok(_) => load_locations(true),
                 Err(e) => set_error.set(Some(e)),
             }
-            set_loading.set(false);
         });
     };
 
@@ -166,10 +307,28 @@ pub fn LocationsPage() -> impl IntoView {
                 <div class="error">{e}</div>
             })}
 
-            {move || if show_form.get() {
+            {move || notice.get().map(|n| view! {
+                <div class="success">{n}</div>
+            })}
+
+            {move || if let Some((mine, current, _)) = conflict.get() {
+                let original = editing_location.get_untracked().unwrap_or_else(|| current.clone());
+                let fields = vec![
+                    FieldConflict::new("Star System", &original.star_system, &mine.star_system, &current.star_system),
+                    FieldConflict::new("Area", &original.area, &mine.area, &current.area),
+                ];
+                view! {
+                    <ConflictDialog
+                        fields=fields
+                        on_merge=move |_| merge_conflict()
+                        on_overwrite=move |_| overwrite_conflict()
+                        on_discard=move |_| discard_conflict()
+                    />
+                }.into_view()
+            } else if show_form.get() {
                 view! {
                     <LocationForm
-                        location=editing_location.get()
+                        location=draft.get().or_else(|| editing_location.get())
                         on_submit=set_form_data
                         on_cancel=set_cancel_form
                     />
@@ -188,66 +347,78 @@ pub fn LocationsPage() -> impl IntoView {
                         }}
                     </div>
 
+                    <div class="search-bar">
+                        <input
+                            type="search"
+                            placeholder="Search star systems and areas..."
+                            prop:value=search
+                            on:input=move |ev| {
+                                set_search.set(event_target_value(&ev));
+                                load_locations(true);
+                            }
+                        />
+                    </div>
+
                     <div class="data-table">
-                        {move || if loading.get() {
-                            view! { <div class="loading">"Loading..."</div> }.into_view()
-                        } else {
-                            view! {
-                                <table>
-                                    <thead>
+                        // Only swap in the placeholder before the first page arrives, so that loading further
+                        // pages does not rebuild the table and reset its scroll position
+                        <Show
+                            when=move || !(loading.get() && locations.with(|locations| locations.is_empty()))
+                            fallback=|| view! { <div class="loading">"Loading..."</div> }
+                        >
+                            <VirtualTable
+                                rows=locations
+                                columns=vec!["ID", "Star System", "Area", "Actions"]
+                                row_key=|location: &ApiLocation| location.id
+                                render_row=move |location: ApiLocation| {
+                                    let edit_loc = std::rc::Rc::new(location.clone());
+                                    let delete_id = location.id;
+                                    let edit_loc_clone = edit_loc.clone();
+                                    view! {
                                         <tr>
-                                            <th>"ID"</th>
-                                            <th>"Star System"</th>
-                                            <th>"Area"</th>
-                                            <th>"Actions"</th>
+                                            <td>{location.id}</td>
+                                            <td>{location.star_system}</td>
+                                            <td><A href=format!("/locations/{}", location.id)>{location.area}</A></td>
+                                            <td class="actions">
+                                                <Show
+                                                    when=move || auth_state.get()
+                                                    fallback=move || view! {
+                                                        <button disabled class="btn btn-small btn-secondary" title="Please log in to edit">"Edit"</button>
+                                                        <button disabled class="btn btn-small btn-secondary" title="Please log in to delete">"Delete"</button>
+                                                    }
+                                                >
+                                                    <button 
+                                                        on:click={
+                                                            let edit_loc = edit_loc_clone.clone();
+                                                            move |_| edit_location((*edit_loc).clone())
+                                                        }
+                                                        class="btn btn-small btn-secondary"
+                                                    >
+                                                        "Edit"
+                                                    </button>
+                                                    <button
+                                                        on:click=move |_| duplicate_location_action(delete_id)
+                                                        class="btn btn-small btn-secondary"
+                                                    >
+                                                        "Duplicate"
+                                                    </button>
+                                                    <button 
+                                                        on:click=move |_| delete_location_action(delete_id)
+                                                        class="btn btn-small btn-danger"
+                                                    >
+                                                        "Delete"
+                                                    </button>
+                                                </Show>
+                                            </td>
                                         </tr>
-                                    </thead>
-                                    <tbody>
-                                        <For
-                                            each=move || locations.get()
-                                            key=|location| location.id
-                                            children=move |location| {
-                                                let edit_loc = std::rc::Rc::new(location.clone());
-                                                let delete_id = location.id;
-                                                let edit_loc_clone = edit_loc.clone();
-                                                view! {
-                                                    <tr>
-                                                        <td>{location.id}</td>
-                                                        <td>{location.star_system}</td>
-                                                        <td>{location.area}</td>
-                                                        <td class="actions">
-                                                            <Show
-                                                                when=move || auth_state.get()
-                                                                fallback=move || view! {
-                                                                    <button disabled class="btn btn-small btn-secondary" title="Please log in to edit">"Edit"</button>
-                                                                    <button disabled class="btn btn-small btn-secondary" title="Please log in to delete">"Delete"</button>
-                                                                }
-                                                            >
-                                                                <button 
-                                                                    on:click={
-                                                                        let edit_loc = edit_loc_clone.clone();
-                                                                        move |_| edit_location((*edit_loc).clone())
-                                                                    }
-                                                                    class="btn btn-small btn-secondary"
-                                                                >
-                                                                    "Edit"
-                                                                </button>
-                                                                <button 
-                                                                    on:click=move |_| delete_location_action(delete_id)
-                                                                    class="btn btn-small btn-danger"
-                                                                >
-                                                                    "Delete"
-                                                                </button>
-                                                            </Show>
-                                                        </td>
-                                                    </tr>
-                                                }
-                                            }
-                                        />
-                                    </tbody>
-                                </table>
-                            }.into_view()
-                        }}
+                                    }
+                                }
+                                on_end_reached=Callback::new(move |_| load_locations(false))
+                            />
+                        </Show>
+                        <Show when=move || loading.get() && !locations.with(|locations| locations.is_empty())>
+                            <div class="loading">"Loading more..."</div>
+                        </Show>
                     </div>
                 }.into_view()
             }}
@@ -260,90 +431,232 @@ pub fn EmpiresPage() -> impl IntoView {
     let (empires, set_empires) = create_signal(Vec::<ApiEmpire>::new());
     let (show_form, set_show_form) = create_signal(false);
     let (editing_empire, set_editing_empire) = create_signal(None::<ApiEmpire>);
+    let (editing_etag, set_editing_etag) = create_signal(None::<String>);
+    // Values the form is prefilled with when they differ from the empire being edited, e.g. after a merge
+    let (draft, set_draft) = create_signal(None::<ApiEmpire>);
+    // The rejected edit along with the current server copy and its ETag
+    let (conflict, set_conflict) = create_signal(None::<(UpsertEmpire, ApiEmpire, Option<String>)>);
+    // The empire whose ships are being moved to another
+    let (reassigning, set_reassigning) = create_signal(None::<ApiEmpire>);
     let (form_data, set_form_data) = create_signal(None::<UpsertEmpire>);
     let (cancel_form, set_cancel_form) = create_signal(false);
     let (error, set_error) = create_signal(None::<String>);
+    let (notice, set_notice) = create_signal(None::<String>);
     let (loading, set_loading) = create_signal(false);
     let (auth_state, set_auth_state) = create_signal(is_authenticated());
+    let (search, set_search) = create_signal(String::new());
+    let (show_archived, set_show_archived) = create_signal(false);
+    let (next_cursor, set_next_cursor) = create_signal(None::<i32>);
 
     // Update auth state reactively
     create_effect(move |_| {
         set_auth_state.set(is_authenticated());
     });
 
-    // Load empires on mount
-    create_effect(move |_| {
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    // Fetches the first page of the current search when 'reset' is set, otherwise the page after the last one loaded
+    let load_empires = move |reset: bool| {
+        // Nothing to do once the page has been left, e.g. when a save completes after navigating away
+        if requests.try_with_value(|_| ()).is_none() {
+            return;
+        }
+
+        let cursor = if reset {
+            None
+        } else {
+            match next_cursor.get_untracked() {
+                Some(cursor) if !loading.get_untracked() => Some(cursor),
+                _ => return,
+            }
+        };
+
+        // A new first page supersedes whatever is still in flight, e.g. results for the previous search term
+        let signal = if reset {
+            requests.with_value(RequestScope::renew)
+        } else {
+            requests.with_value(RequestScope::signal)
+        };
+        let query = search.get_untracked();
+        let include_archived = show_archived.get_untracked();
+
+        set_loading.set(true);
         spawn_local(async move {
-            set_loading.set(true);
-            match api::get_empires().await {
-                Ok(emps) => set_empires.set(emps),
+            let result = api::search_empires(&query, cursor, include_archived, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(page) => {
+                    set_next_cursor.set(page.next_cursor);
+                    if reset {
+                        set_empires.set(page.items);
+                    } else {
+                        set_empires.update(|empires| empires.extend(page.items));
+                    }
+                },
                 Err(e) => set_error.set(Some(e)),
             }
             set_loading.set(false);
         });
-    });
+    };
+
+    // Load empires on mount
+    create_effect(move |_| load_empires(true));
+
+    let close_form = move || {
+        set_show_form.set(false);
+        set_editing_empire.set(None);
+        set_editing_etag.set(None);
+        set_draft.set(None);
+    };
+
+    // Creates or updates the empire - updates are conditioned on the ETag the empire was loaded with
+    let save_empire = move |data: UpsertEmpire| {
+        spawn_local(async move {
+            set_loading.set(true);
+            set_notice.set(None);
+            let result = if let Some(empire) = editing_empire.get_untracked() {
+                api::update_empire(empire.id, data.clone(), editing_etag.get_untracked()).await
+            } else {
+                api::create_empire(data.clone()).await.map_err(api::SaveError::Failed)
+            };
+
+            set_loading.set(false);
+            match result {
+                Ok(_) => {
+                    load_empires(true);
+                    close_form();
+                },
+                Err(api::SaveError::Conflict { current, etag }) => set_conflict.set(Some((data, current, etag))),
+                Err(api::SaveError::Submitted) => {
+                    set_notice.set(Some("Your changes have been submitted for review".to_string()));
+                    close_form();
+                },
+                Err(api::SaveError::Failed(e)) => set_error.set(Some(e)),
+            }
+        });
+    };
 
     // Handle form submission
     create_effect(move |_| {
         if let Some(data) = form_data.get() {
-            spawn_local(async move {
-                set_loading.set(true);
-                let result = if let Some(empire) = editing_empire.get() {
-                    api::update_empire(empire.id, data).await
-                } else {
-                    api::create_empire(data).await
-                };
-
-                match result {
-                    Ok(_) => {
-                        // Reload empires
-                        match api::get_empires().await {
-                            Ok(emps) => set_empires.set(emps),
-                            Err(e) => set_error.set(Some(e)),
-                        }
-                        set_show_form.set(false);
-                        set_editing_empire.set(None);
-                    },
-                    Err(e) => set_error.set(Some(e)),
-                }
-                set_loading.set(false);
-            });
+            save_empire(data);
             set_form_data.set(None);
         }
     });
 
+    // Conflict resolution: the current server copy becomes the base of any further edit
+    let merge_conflict = move || {
+        if let (Some((mine, current, etag)), Some(original)) = (conflict.get_untracked(), editing_empire.get_untracked()) {
+            set_draft.set(Some(ApiEmpire {
+                id: current.id,
+                name: merge_field(&original.name, &mine.name, &current.name),
+                slogan: merge_field(&original.slogan, &mine.slogan, &current.slogan),
+                location_id: merge_field(&original.location_id, &mine.location_id, &current.location_id),
+                description: merge_field(&original.description, &mine.description, &current.description),
+                archived: current.archived,
+                published_at: current.published_at.clone(),
+            }));
+            set_editing_empire.set(Some(current));
+            set_editing_etag.set(etag);
+        }
+        set_conflict.set(None);
+    };
+
+    let overwrite_conflict = move || {
+        if let Some((mine, current, etag)) = conflict.get_untracked() {
+            set_editing_empire.set(Some(current));
+            set_editing_etag.set(etag);
+            save_empire(mine);
+        }
+        set_conflict.set(None);
+    };
+
+    let discard_conflict = move || {
+        set_conflict.set(None);
+        close_form();
+        load_empires(true);
+    };
+
     // Handle form cancel
     create_effect(move |_| {
         if cancel_form.get() {
-            set_show_form.set(false);
-            set_editing_empire.set(None);
+            close_form();
             set_cancel_form.set(false);
         }
     });
 
     let add_empire = move |_| {
-        set_editing_empire.set(None);
+        close_form();
         set_show_form.set(true);
     };
 
+    // Edits start from a fresh copy so that its ETag reflects what the user actually sees
     let edit_empire = move |empire: ApiEmpire| {
-        set_editing_empire.set(Some(empire));
-        set_show_form.set(true);
+        let signal = requests.with_value(RequestScope::signal);
+        spawn_local(async move {
+            let result = api::get_empire(empire.id, signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok((current, etag)) => {
+                    set_draft.set(None);
+                    set_editing_empire.set(Some(current));
+                    set_editing_etag.set(etag);
+                    set_show_form.set(true);
+                },
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Copies the empire along with its ships and opens the copy for editing
+    let duplicate_empire_action = move |id: i32| {
+        spawn_local(async move {
+            match api::clone_empire(id, true).await {
+                Ok(copy) => {
+                    load_empires(true);
+                    edit_empire(copy);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let archive_empire_action = move |id: i32, archived: bool| {
+        spawn_local(async move {
+            match api::set_empire_archived(id, archived).await {
+                Ok(_) => load_empires(true),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let publish_empire_action = move |id: i32| {
+        spawn_local(async move {
+            match api::publish_empire(id).await {
+                Ok(_) => load_empires(true),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
     };
 
     let delete_empire_action = move |id: i32| {
         spawn_local(async move {
             set_loading.set(true);
-            match api::delete_empire(id).await {
-                Ok(_) => {
-                    match api::get_empires().await {
-                        Ok(emps) => set_empires.set(emps),
-                        Err(e) => set_error.set(Some(e)),
-                    }
-                },
+            let result = api::delete_empire(id).await;
+            set_loading.set(false);
+            match result {
+                Ok(_) => load_empires(true),
                 Err(e) => set_error.set(Some(e)),
             }
-            set_loading.set(false);
         });
     };
 
@@ -356,10 +669,34 @@ pub fn EmpiresPage() -> impl IntoView {
                 <div class="error">{e}</div>
             })}
 
-            {move || if show_form.get() {
+            {move || notice.get().map(|n| view! {
+                <div class="success">{n}</div>
+            })}
+
+            {move || if let Some((mine, current, _)) = conflict.get() {
+                let original = editing_empire.get_untracked().unwrap_or_else(|| current.clone());
+                let fields = vec![
+                    FieldConflict::new("Name", &original.name, &mine.name, &current.name),
+                    FieldConflict::new("Slogan", &original.slogan, &mine.slogan, &current.slogan),
+                    FieldConflict::new("Location ID", &original.location_id, &mine.location_id, &current.location_id),
+                    FieldConflict::new("Description", &original.description, &mine.description, &current.description),
+                ];
+                view! {
+                    <ConflictDialog
+                        fields=fields
+                        on_merge=move |_| merge_conflict()
+                        on_overwrite=move |_| overwrite_conflict()
+                        on_discard=move |_| discard_conflict()
+                    />
+                }.into_view()
+            } else if let Some(empire) = reassigning.get() {
+                view! {
+                    <ReassignShipsDialog empire=empire on_close=move |_| set_reassigning.set(None)/>
+                }.into_view()
+            } else if show_form.get() {
                 view! {
                     <EmpireForm
-                        empire=editing_empire.get()
+                        empire=draft.get().or_else(|| editing_empire.get())
                         on_submit=set_form_data
                         on_cancel=set_cancel_form
                     />
@@ -378,70 +715,121 @@ pub fn EmpiresPage() -> impl IntoView {
                         }}
                     </div>
 
+                    <div class="search-bar">
+                        <input
+                            type="search"
+                            placeholder="Search names, slogans and descriptions..."
+                            prop:value=search
+                            on:input=move |ev| {
+                                set_search.set(event_target_value(&ev));
+                                load_empires(true);
+                            }
+                        />
+                        <label class="checkbox">
+                            <input
+                                type="checkbox"
+                                prop:checked=show_archived
+                                on:change=move |ev| {
+                                    set_show_archived.set(event_target_checked(&ev));
+                                    load_empires(true);
+                                }
+                            />
+                            "Show archived"
+                        </label>
+                    </div>
+
                     <div class="data-table">
-                        {move || if loading.get() {
-                            view! { <div class="loading">"Loading..."</div> }.into_view()
-                        } else {
-                            view! {
-                                <table>
-                                    <thead>
-                                        <tr>
-                                            <th>"ID"</th>
-                                            <th>"Name"</th>
-                                            <th>"Slogan"</th>
-                                            <th>"Location ID"</th>
-                                            <th>"Description"</th>
-                                            <th>"Actions"</th>
+                        // Only swap in the placeholder before the first page arrives, so that loading further
+                        // pages does not rebuild the table and reset its scroll position
+                        <Show
+                            when=move || !(loading.get() && empires.with(|empires| empires.is_empty()))
+                            fallback=|| view! { <div class="loading">"Loading..."</div> }
+                        >
+                            <VirtualTable
+                                rows=empires
+                                columns=vec!["ID", "Name", "Slogan", "Location ID", "Description", "Actions"]
+                                row_key=|empire: &ApiEmpire| empire.id
+                                render_row=move |empire: ApiEmpire| {
+                                    let edit_emp = std::rc::Rc::new(empire.clone());
+                                    let delete_id = empire.id;
+                                    let archived = empire.archived;
+                                    let unpublished = empire.unpublished_label();
+                                    let edit_emp_clone = edit_emp.clone();
+                                    view! {
+                                        <tr class:archived-row=archived>
+                                            <td>{empire.id}</td>
+                                            <td>
+                                                {empire.name}
+                                                {archived.then(|| view! { <span class="badge">"Archived"</span> })}
+                                                {unpublished.map(|label| view! { <span class="badge badge-unpublished">{label}</span> })}
+                                            </td>
+                                            <td>{empire.slogan}</td>
+                                            <td>{empire.location_id}</td>
+                                            <td>{empire.description}</td>
+                                            <td class="actions">
+                                                <Show
+                                                    when=move || auth_state.get()
+                                                    fallback=move || view! {
+                                                        <button disabled class="btn btn-small btn-secondary" title="Please log in to edit">"Edit"</button>
+                                                        <button disabled class="btn btn-small btn-secondary" title="Please log in to delete">"Delete"</button>
+                                                    }
+                                                >
+                                                    <button 
+                                                        on:click={
+                                                            let edit_emp = edit_emp_clone.clone();
+                                                            move |_| edit_empire((*edit_emp).clone())
+                                                        }
+                                                        class="btn btn-small btn-secondary"
+                                                    >
+                                                        "Edit"
+                                                    </button>
+                                                    <button
+                                                        on:click=move |_| duplicate_empire_action(delete_id)
+                                                        class="btn btn-small btn-secondary"
+                                                    >
+                                                        "Duplicate"
+                                                    </button>
+                                                    <button
+                                                        on:click={
+                                                            let empire = edit_emp_clone.clone();
+                                                            move |_| set_reassigning.set(Some((*empire).clone()))
+                                                        }
+                                                        class="btn btn-small btn-secondary"
+                                                    >
+                                                        "Move ships"
+                                                    </button>
+                                                    {unpublished.map(|_| view! {
+                                                        <button
+                                                            on:click=move |_| publish_empire_action(delete_id)
+                                                            class="btn btn-small btn-primary"
+                                                            title="Make the empire visible to readers now"
+                                                        >
+                                                            "Publish"
+                                                        </button>
+                                                    })}
+                                                    <button
+                                                        on:click=move |_| archive_empire_action(delete_id, !archived)
+                                                        class="btn btn-small btn-secondary"
+                                                    >
+                                                        {if archived { "Restore" } else { "Archive" }}
+                                                    </button>
+                                                    <button 
+                                                        on:click=move |_| delete_empire_action(delete_id)
+                                                        class="btn btn-small btn-danger"
+                                                    >
+                                                        "Delete"
+                                                    </button>
+                                                </Show>
+                                            </td>
                                         </tr>
-                                    </thead>
-                                    <tbody>
-                                        <For
-                                            each=move || empires.get()
-                                            key=|empire| empire.id
-                                            children=move |empire| {
-                                                let edit_emp = std::rc::Rc::new(empire.clone());
-                                                let delete_id = empire.id;
-                                                let edit_emp_clone = edit_emp.clone();
-                                                view! {
-                                                    <tr>
-                                                        <td>{empire.id}</td>
-                                                        <td>{empire.name}</td>
-                                                        <td>{empire.slogan}</td>
-                                                        <td>{empire.location_id}</td>
-                                                        <td>{empire.description}</td>
-                                                        <td class="actions">
-                                                            <Show
-                                                                when=move || auth_state.get()
-                                                                fallback=move || view! {
-                                                                    <button disabled class="btn btn-small btn-secondary" title="Please log in to edit">"Edit"</button>
-                                                                    <button disabled class="btn btn-small btn-secondary" title="Please log in to delete">"Delete"</button>
-                                                                }
-                                                            >
-                                                                <button 
-                                                                    on:click={
-                                                                        let edit_emp = edit_emp_clone.clone();
-                                                                        move |_| edit_empire((*edit_emp).clone())
-                                                                    }
-                                                                    class="btn btn-small btn-secondary"
-                                                                >
-                                                                    "Edit"
-                                                                </button>
-                                                                <button 
-                                                                    on:click=move |_| delete_empire_action(delete_id)
-                                                                    class="btn btn-small btn-danger"
-                                                                >
-                                                                    "Delete"
-                                                                </button>
-                                                            </Show>
-                                                        </td>
-                                                    </tr>
-                                                }
-                                            }
-                                        />
-                                    </tbody>
-                                </table>
-                            }.into_view()
-                        }}
+                                    }
+                                }
+                                on_end_reached=Callback::new(move |_| load_empires(false))
+                            />
+                        </Show>
+                        <Show when=move || loading.get() && !empires.with(|empires| empires.is_empty())>
+                            <div class="loading">"Loading more..."</div>
+                        </Show>
                     </div>
                 }.into_view()
             }}
@@ -465,11 +853,22 @@ pub fn UsersPage() -> impl IntoView {
         set_auth_state.set(is_authenticated());
     });
 
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
     // Load users on mount
     create_effect(move |_| {
+        let signal = requests.with_value(RequestScope::signal);
         spawn_local(async move {
             set_loading.set(true);
-            match api::get_users().await {
+            let result = api::get_users(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
                 Ok(user_list) => set_users.set(user_list),
                 Err(e) => set_error.set(Some(e)),
             }
@@ -491,7 +890,8 @@ pub fn UsersPage() -> impl IntoView {
                 match result {
                     Ok(_) => {
                         // Reload users
-                        match api::get_users().await {
+                        let signal = requests.try_with_value(RequestScope::signal).flatten();
+                        match api::get_users(signal.as_ref()).await {
                             Ok(user_list) => set_users.set(user_list),
                             Err(e) => set_error.set(Some(e)),
                         }
@@ -530,7 +930,8 @@ pub fn UsersPage() -> impl IntoView {
             set_loading.set(true);
             match api::delete_user(id).await {
                 Ok(_) => {
-                    match api::get_users().await {
+                    let signal = requests.try_with_value(RequestScope::signal).flatten();
+                    match api::get_users(signal.as_ref()).await {
                         Ok(user_list) => set_users.set(user_list),
                         Err(e) => set_error.set(Some(e)),
                     }
@@ -541,6 +942,29 @@ pub fn UsersPage() -> impl IntoView {
         });
     };
 
+    let auth = use_auth();
+    let impersonate_action = move |id: i32| {
+        spawn_local(async move {
+            match api::admin::impersonate_user(id).await {
+                Ok(impersonation) => auth.start_impersonation(&impersonation.token, &impersonation.email),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let set_active_action = move |id: i32, active: bool| {
+        spawn_local(async move {
+            match api::set_user_active(id, active).await {
+                Ok(updated) => set_users.update(|users| {
+                    if let Some(user) = users.iter_mut().find(|user| user.id == updated.id) {
+                        *user = updated;
+                    }
+                }),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
     view! {
         <Navbar/>
         <div class="container">
@@ -584,23 +1008,26 @@ pub fn UsersPage() -> impl IntoView {
                                             <th>"Full Name"</th>
                                             <th>"Email"</th>
                                             <th>"Role"</th>
+                                            <th>"Status"</th>
                                             <th>"Actions"</th>
                                         </tr>
                                     </thead>
                                     <tbody>
                                         <For
                                             each=move || users.get()
-                                            key=|user| user.id
+                                            key=|user| (user.id, user.deactivated)
                                             children=move |user| {
                                                 let edit_usr = std::rc::Rc::new(user.clone());
                                                 let delete_id = user.id;
+                                                let deactivated = user.deactivated;
                                                 let edit_usr_clone = edit_usr.clone();
                                                 view! {
-                                                    <tr>
+                                                    <tr class:deactivated-row=deactivated>
                                                         <td>{user.id}</td>
                                                         <td>{user.fullname}</td>
                                                         <td>{user.email}</td>
                                                         <td>{user.role}</td>
+                                                        <td>{if deactivated { "Deactivated" } else { "Active" }}</td>
                                                         <td class="actions">
                                                             <Show
                                                                 when=move || auth_state.get()
@@ -618,6 +1045,22 @@ pub fn UsersPage() -> impl IntoView {
                                                                 >
                                                                     "Edit"
                                                                 </button>
+                                                                <Show when=move || !deactivated>
+                                                                    <button
+                                                                        on:click=move |_| impersonate_action(delete_id)
+                                                                        class="btn btn-small btn-secondary"
+                                                                        title="See the application as this user does"
+                                                                    >
+                                                                        "Impersonate"
+                                                                    </button>
+                                                                </Show>
+                                                                <button
+                                                                    on:click=move |_| set_active_action(delete_id, deactivated)
+                                                                    class="btn btn-small btn-secondary"
+                                                                    title=if deactivated { "Allow signing in again" } else { "Lock out while keeping their records" }
+                                                                >
+                                                                    {if deactivated { "Reactivate" } else { "Deactivate" }}
+                                                                </button>
                                                                 <button 
                                                                     on:click=move |_| delete_user_action(delete_id)
                                                                     class="btn btn-small btn-danger"
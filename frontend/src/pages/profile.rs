@@ -0,0 +1,370 @@
+use leptos::*;
+use leptos_router::*;
+use qrcode::{render::svg, QrCode};
+use crate::api::{self, RequestScope};
+use crate::api::profile::{self as profile_api, Session, TwoFactorSetup};
+use crate::components::navbar::Navbar;
+use crate::components::timestamp::Timestamp;
+
+// Once the current session is revoked its token is useless, so the browser is signed out as well
+fn sign_out_locally() {
+    api::clear_token();
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().set_href("/login");
+    }
+}
+
+#[component]
+pub fn ProfilePage() -> impl IntoView {
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Profile"</h1>
+            <TwoFactorSettings/>
+            <section class="profile-section">
+                <h2>"Sessions"</h2>
+                <p>"See where you are signed in and sign out devices you no longer use."</p>
+                <A href="/profile/sessions" class="btn btn-secondary">"Manage sessions"</A>
+            </section>
+        </div>
+    }
+}
+
+// Steps of the two-factor enrollment wizard
+#[derive(Clone, Debug, PartialEq)]
+enum TwoFactorStep {
+    Loading,
+    Disabled,
+    // Scanning the QR code and entering a first code to prove the authenticator works
+    Verify(TwoFactorSetup),
+    // Shown exactly once, right after enabling
+    RecoveryCodes(Vec<String>),
+    Enabled { recovery_codes_remaining: i64 },
+}
+
+fn qr_code_svg(data: &str) -> Option<String> {
+    QrCode::new(data.as_bytes())
+        .ok()
+        .map(|code| code.render::<svg::Color>().min_dimensions(200, 200).build())
+}
+
+// Recovery codes as a plain text file, downloadable through a link without a round trip to the server
+fn recovery_codes_download_href(codes: &[String]) -> String {
+    let contents = format!(
+        "Recovery codes - each can be used once in place of an authenticator code\n\n{}\n",
+        codes.join("\n")
+    );
+    format!("data:text/plain;charset=utf-8,{}", String::from(js_sys::encode_uri_component(&contents)))
+}
+
+#[component]
+fn TwoFactorSettings() -> impl IntoView {
+    let (step, set_step) = create_signal(TwoFactorStep::Loading);
+    let (code, set_code) = create_signal(String::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (saved_codes, set_saved_codes) = create_signal(false);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_status = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = profile_api::get_two_factor_status(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(status) if status.enabled => set_step.set(TwoFactorStep::Enabled {
+                    recovery_codes_remaining: status.recovery_codes_remaining,
+                }),
+                Ok(_) => set_step.set(TwoFactorStep::Disabled),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    // Load the current status on mount
+    create_effect(move |_| load_status());
+
+    let start_setup = move |_| {
+        spawn_local(async move {
+            match profile_api::start_two_factor_setup().await {
+                Ok(setup) => {
+                    set_error.set(None);
+                    set_code.set(String::new());
+                    set_step.set(TwoFactorStep::Verify(setup));
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let verify_code = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let entered = code.get_untracked().trim().to_string();
+
+        spawn_local(async move {
+            match profile_api::confirm_two_factor(entered).await {
+                Ok(codes) => {
+                    set_error.set(None);
+                    set_code.set(String::new());
+                    set_saved_codes.set(false);
+                    set_step.set(TwoFactorStep::RecoveryCodes(codes.recovery_codes));
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let disable = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let entered = code.get_untracked().trim().to_string();
+
+        spawn_local(async move {
+            match profile_api::disable_two_factor(entered).await {
+                Ok(_) => {
+                    set_error.set(None);
+                    set_code.set(String::new());
+                    set_step.set(TwoFactorStep::Disabled);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let code_input = move || view! {
+        <input
+            type="text"
+            required
+            autocomplete="one-time-code"
+            placeholder="123456"
+            prop:value=code
+            on:input=move |ev| set_code.set(event_target_value(&ev))
+        />
+    };
+
+    view! {
+        <section class="profile-section two-factor">
+            <h2>"Two-factor authentication"</h2>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            {move || match step.get() {
+                TwoFactorStep::Loading => view! { <div class="loading">"Loading..."</div> }.into_view(),
+                TwoFactorStep::Disabled => view! {
+                    <p>"Protect your account with a code from an authenticator app in addition to your password."</p>
+                    <button class="btn btn-primary" on:click=start_setup>"Set up two-factor authentication"</button>
+                }.into_view(),
+                TwoFactorStep::Verify(setup) => view! {
+                    <ol class="wizard-steps">
+                        <li>
+                            "Scan this QR code with your authenticator app."
+                            {qr_code_svg(&setup.otpauth_uri).map(|svg| view! { <div class="qr-code" inner_html=svg></div> })}
+                            <p class="hint">"Can't scan it? Enter this key instead: " <code class="secret-value">{setup.secret}</code></p>
+                        </li>
+                        <li>
+                            "Enter the six-digit code the app shows to confirm."
+                            <form class="inline-form" on:submit=verify_code>
+                                {code_input}
+                                <button type="submit" class="btn btn-primary">"Verify"</button>
+                                <button type="button" class="btn btn-secondary" on:click=move |_| {
+                                    set_error.set(None);
+                                    set_step.set(TwoFactorStep::Disabled);
+                                }>"Cancel"</button>
+                            </form>
+                        </li>
+                    </ol>
+                }.into_view(),
+                TwoFactorStep::RecoveryCodes(codes) => {
+                    let remaining = codes.len() as i64;
+                    let href = recovery_codes_download_href(&codes);
+                    view! {
+                        <div class="secret-reveal">
+                            <p>
+                                "Two-factor authentication is on. Save these recovery codes somewhere safe - each can be used once "
+                                "to sign in if you lose your authenticator, and they will not be shown again."
+                            </p>
+                            <ul class="recovery-codes">
+                                {codes.into_iter().map(|code| view! { <li><code>{code}</code></li> }).collect_view()}
+                            </ul>
+                            <a
+                                class="btn btn-secondary"
+                                href=href
+                                download="recovery-codes.txt"
+                                on:click=move |_| set_saved_codes.set(true)
+                            >
+                                "Download recovery codes"
+                            </a>
+                            <label class="checkbox">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=saved_codes
+                                    on:change=move |ev| set_saved_codes.set(event_target_checked(&ev))
+                                />
+                                "I have saved my recovery codes"
+                            </label>
+                            <button
+                                class="btn btn-primary"
+                                disabled=move || !saved_codes.get()
+                                on:click=move |_| set_step.set(TwoFactorStep::Enabled { recovery_codes_remaining: remaining })
+                            >
+                                "Done"
+                            </button>
+                        </div>
+                    }.into_view()
+                }
+                TwoFactorStep::Enabled { recovery_codes_remaining } => view! {
+                    <p>
+                        "Two-factor authentication is on. You have " {recovery_codes_remaining}
+                        " unused recovery codes."
+                    </p>
+                    <form class="inline-form" on:submit=disable>
+                        {code_input}
+                        <button type="submit" class="btn btn-danger">"Turn off"</button>
+                    </form>
+                    <p class="hint">"Turning it off requires a current code or a recovery code."</p>
+                }.into_view(),
+            }}
+        </section>
+    }
+}
+
+#[component]
+pub fn ProfileSessionsPage() -> impl IntoView {
+    let (sessions, set_sessions) = create_signal(Vec::<Session>::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_sessions = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = profile_api::get_my_sessions(signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_sessions.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    // Load sessions on mount
+    create_effect(move |_| load_sessions());
+
+    let revoke_session = move |session: Session| {
+        if session.current {
+            let confirmed = web_sys::window()
+                .and_then(|window| window.confirm_with_message("This is the session you are using - revoking it signs you out here.").ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+        }
+
+        spawn_local(async move {
+            match profile_api::revoke_session(session.id).await {
+                Ok(_) if session.current => sign_out_locally(),
+                Ok(_) => load_sessions(),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    let sign_out_everywhere = move |_| {
+        let confirmed = web_sys::window()
+            .and_then(|window| window.confirm_with_message("Sign out on every device, including this one?").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+
+        spawn_local(async move {
+            match profile_api::revoke_all_sessions().await {
+                Ok(_) => sign_out_locally(),
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Active Sessions"</h1>
+
+            <div class="session-actions">
+                <p>"Devices currently signed in to your account. Revoke any you do not recognize."</p>
+                <button class="btn btn-danger" on:click=sign_out_everywhere>"Sign out everywhere"</button>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            <div class="data-table">
+                <Show when=move || !loading.get() fallback=|| view! { <div class="loading">"Loading..."</div> }>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>"Device"</th>
+                                <th>"IP Address"</th>
+                                <th>"Signed In"</th>
+                                <th>"Last Seen"</th>
+                                <th>"Actions"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || sessions.get()
+                                key=|session| (session.id, session.last_seen_at.clone())
+                                children=move |session| {
+                                    let device = session.user_agent.clone().unwrap_or_else(|| "Unknown device".to_string());
+                                    let ip_address = session.ip_address.clone().unwrap_or_else(|| "Unknown".to_string());
+                                    let for_revoke = session.clone();
+                                    view! {
+                                        <tr class:current-session=session.current>
+                                            <td>
+                                                {device}
+                                                {session.current.then(|| view! { <span class="badge">"This device"</span> })}
+                                                {session.impersonated_by.map(|_| view! { <span class="badge">"Opened by an admin"</span> })}
+                                            </td>
+                                            <td>{ip_address}</td>
+                                            <td><Timestamp value=session.created_at/></td>
+                                            <td><Timestamp value=session.last_seen_at/></td>
+                                            <td class="actions">
+                                                <button
+                                                    class="btn btn-small btn-danger"
+                                                    on:click=move |_| revoke_session(for_revoke.clone())
+                                                >
+                                                    "Revoke"
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </Show>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,112 @@
+use leptos::*;
+use crate::api::{self, RequestScope};
+use crate::api::change_requests::{self as change_requests_api, display_value, ChangeRequest};
+use crate::components::navbar::Navbar;
+use crate::components::timestamp::Timestamp;
+
+// Queue of edits submitted by writers, approved or rejected by editors
+#[component]
+pub fn ReviewQueuePage() -> impl IntoView {
+    let (change_requests, set_change_requests) = create_signal(Vec::<ChangeRequest>::new());
+    let (error, set_error) = create_signal(None::<String>);
+    let (loading, set_loading) = create_signal(false);
+
+    let requests = store_value(RequestScope::new());
+    on_cleanup(move || {
+        requests.try_with_value(RequestScope::abort);
+    });
+
+    let load_change_requests = move || {
+        let Some(signal) = requests.try_with_value(RequestScope::signal) else {
+            return;
+        };
+        spawn_local(async move {
+            set_loading.set(true);
+            let result = change_requests_api::get_change_requests("pending", signal.as_ref()).await;
+            if api::is_aborted(&signal) {
+                return;
+            }
+
+            match result {
+                Ok(list) => set_change_requests.set(list),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    // Load pending change requests on mount
+    create_effect(move |_| load_change_requests());
+
+    // Reviewed requests leave the queue - as do stale ones, which the reload keeps listed along with the error
+    let review = move |id: i32, approve: bool| {
+        spawn_local(async move {
+            match change_requests_api::review_change_request(id, approve).await {
+                Ok(_) => set_error.set(None),
+                Err(e) => set_error.set(Some(e)),
+            }
+            load_change_requests();
+        });
+    };
+
+    view! {
+        <Navbar/>
+        <div class="container">
+            <h1>"Review Queue"</h1>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">{e}</div>
+            })}
+
+            <Show when=move || !loading.get() fallback=|| view! { <div class="loading">"Loading..."</div> }>
+                <Show
+                    when=move || change_requests.with(|list| !list.is_empty())
+                    fallback=|| view! { <p>"No changes are awaiting review."</p> }
+                >
+                    <For
+                        each=move || change_requests.get()
+                        key=|change_request| change_request.id
+                        children=move |change_request| {
+                            let id = change_request.id;
+                            let record_link = format!("/{}s", change_request.resource);
+                            let requester = change_request.requested_by
+                                .map_or("an API key".to_string(), |user_id| format!("user #{}", user_id));
+                            view! {
+                                <section class="change-request">
+                                    <h2>
+                                        <a href=record_link>{format!("{} #{}", change_request.resource, change_request.record_id)}</a>
+                                    </h2>
+                                    <p class="change-request-meta">
+                                        "Requested by " {requester} " " <Timestamp value=change_request.created_at/>
+                                    </p>
+                                    <table class="change-diff">
+                                        <thead>
+                                            <tr>
+                                                <th>"Field"</th>
+                                                <th>"Current"</th>
+                                                <th>"Proposed"</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {change_request.changes.into_iter().map(|(field, change)| view! {
+                                                <tr>
+                                                    <td>{field}</td>
+                                                    <td class="diff-from">{display_value(&change.from)}</td>
+                                                    <td class="diff-to">{display_value(&change.to)}</td>
+                                                </tr>
+                                            }).collect_view()}
+                                        </tbody>
+                                    </table>
+                                    <div class="actions">
+                                        <button class="btn btn-small btn-primary" on:click=move |_| review(id, true)>"Approve"</button>
+                                        <button class="btn btn-small btn-danger" on:click=move |_| review(id, false)>"Reject"</button>
+                                    </div>
+                                </section>
+                            }
+                        }
+                    />
+                </Show>
+            </Show>
+        </div>
+    }
+}
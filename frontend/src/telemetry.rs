@@ -0,0 +1,159 @@
+use std::{cell::RefCell, time::Duration};
+use leptos::*;
+use serde::Serialize;
+use wasm_bindgen::JsCast;
+use crate::api::API_BASE;
+
+// Telemetry is strictly opt-in - nothing is recorded unless this flag is set in localStorage
+const OPT_IN_KEY: &str = "telemetry_opt_in";
+
+// Events are sent once this many have been buffered, or every FLUSH_INTERVAL, whichever comes first
+const BATCH_SIZE: usize = 20;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+// Upper bound on buffered events, so that an unreachable backend cannot make the buffer grow without bound
+const MAX_BUFFERED: usize = 200;
+const MAX_MESSAGE_LENGTH: usize = 500;
+
+// Events carry no user identifiers, tokens, query strings or record ids - paths are reduced to their route shape
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    Navigation { path: String, duration_ms: f64 },
+    ApiLatency { method: String, endpoint: String, status: u16, duration_ms: f64 },
+    Error { source: String, message: String },
+}
+
+#[derive(Serialize)]
+struct TelemetryBatch<'a> {
+    events: &'a [TelemetryEvent],
+}
+
+thread_local! {
+    static BUFFER: RefCell<Vec<TelemetryEvent>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn is_enabled() -> bool {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(OPT_IN_KEY).ok().flatten())
+        .map_or(false, |value| value == "true")
+}
+
+pub fn set_enabled(enabled: bool) {
+    if let Some(storage) = web_sys::window().and_then(|window| window.local_storage().ok().flatten()) {
+        let _ = if enabled {
+            storage.set_item(OPT_IN_KEY, "true")
+        } else {
+            storage.remove_item(OPT_IN_KEY)
+        };
+    }
+
+    if !enabled {
+        BUFFER.with(|buffer| buffer.borrow_mut().clear());
+    }
+}
+
+// Milliseconds since the page started loading
+pub fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+pub fn record(event: TelemetryEvent) {
+    if !is_enabled() {
+        return;
+    }
+
+    let buffered = BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        if buffer.len() < MAX_BUFFERED {
+            buffer.push(event);
+        }
+        buffer.len()
+    });
+
+    if buffered >= BATCH_SIZE {
+        flush();
+    }
+}
+
+pub fn record_navigation(path: &str, duration_ms: f64) {
+    record(TelemetryEvent::Navigation { path: route_shape(path), duration_ms });
+}
+
+pub fn record_api_call(method: &str, url: &str, status: u16, duration_ms: f64) {
+    let endpoint = url.strip_prefix(API_BASE).unwrap_or(url);
+    record(TelemetryEvent::ApiLatency {
+        method: method.to_string(),
+        endpoint: route_shape(endpoint),
+        status,
+        duration_ms,
+    });
+}
+
+pub fn record_error(source: &str, message: &str) {
+    record(TelemetryEvent::Error {
+        source: source.to_string(),
+        message: message.chars().take(MAX_MESSAGE_LENGTH).collect(),
+    });
+}
+
+// Reduces a path to its route shape: the query string is dropped and numeric segments become ':id'
+pub fn route_shape(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or("");
+
+    path.split('/')
+        .map(|segment| if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) { ":id" } else { segment })
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+// Sends the buffered events with navigator.sendBeacon, which is fire-and-forget and keeps working while the
+// page is being unloaded or after a panic has left the WASM module unusable
+pub fn flush() {
+    let events = BUFFER.with(|buffer| std::mem::take(&mut *buffer.borrow_mut()));
+    if events.is_empty() {
+        return;
+    }
+
+    let body = match serde_json::to_string(&TelemetryBatch { events: &events }) {
+        Ok(body) => body,
+        Err(_) => return,
+    };
+
+    if let Some(window) = web_sys::window() {
+        let _ = window.navigator().send_beacon_with_opt_str(&format!("{}/telemetry", API_BASE), Some(&body));
+    }
+}
+
+// Hooks up error reporting and periodic flushing - called once on startup
+pub fn install() {
+    // WASM panics are recorded and flushed right away, as the module cannot be relied upon afterwards
+    std::panic::set_hook(Box::new(|info| {
+        record_error("panic", &info.to_string());
+        flush();
+        console_error_panic_hook::hook(info);
+    }));
+
+    window_event_listener_untyped("error", |event| {
+        let message = event
+            .dyn_ref::<web_sys::ErrorEvent>()
+            .map(|error| error.message())
+            .unwrap_or_else(|| "Unknown error".to_string());
+        record_error("javascript", &message);
+    });
+
+    window_event_listener_untyped("unhandledrejection", |event| {
+        let reason = js_sys::Reflect::get(&event, &"reason".into())
+            .ok()
+            .and_then(|reason| reason.as_string())
+            .unwrap_or_else(|| "Unhandled promise rejection".to_string());
+        record_error("promise", &reason);
+    });
+
+    window_event_listener_untyped("pagehide", |_| flush());
+
+    set_interval(flush, FLUSH_INTERVAL);
+}
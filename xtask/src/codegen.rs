@@ -0,0 +1,467 @@
+use std::fmt::Write;
+use serde_json::{Map, Value};
+
+// Methods of the client, in the order operations are listed for each path
+const METHODS: [(&str, &str); 4] = [("get", "Get"), ("post", "Post"), ("put", "Put"), ("delete", "Delete")];
+
+const HEADER: &str = "// Generated by 'cargo xtask codegen' from openapi.json - do not edit by hand, regenerate instead.\n";
+
+// Generates the types and client methods of the client crate from the OpenAPI document of the backend. Only what
+// the document makes use of is supported - anything else fails the generation rather than producing a client that
+// silently differs from the API, so that the generator is extended along with the API.
+pub fn generate(document: &Value) -> Result<String, String> {
+    let schemas = document["components"]["schemas"].as_object().ok_or("The document has no component schemas")?;
+    let paths = document["paths"].as_object().ok_or("The document has no paths")?;
+
+    let mut operations = Vec::new();
+    for (path, item) in paths {
+        for (method, variant) in METHODS {
+            if let Some(operation) = item.get(method) {
+                operations.push(Operation::parse(path, variant, operation)?);
+            }
+        }
+    }
+
+    let mut types = String::new();
+    for (name, schema) in schemas {
+        types.push('\n');
+        types.push_str(&schema_type(name, schema)?);
+    }
+    for operation in &operations {
+        types.push_str(&operation.query_type());
+        types.push_str(&operation.response_type());
+    }
+
+    let methods: Vec<String> = operations.iter().map(Operation::method).collect();
+
+    let mut imports = vec!["decode", "encode"];
+    if operations.iter().any(|operation| operation.responses().is_err()) {
+        imports.push("unexpected");
+    }
+    imports.extend(["ApiClient", "ApiRequest", "ClientError", "Method", "Transport"]);
+
+    let mut out = String::from(HEADER);
+    out.push('\n');
+    out.push_str("use serde::{Deserialize, Serialize};\n");
+    let _ = writeln!(out, "use crate::{{{}}};", imports.join(", "));
+    out.push_str(&types);
+    out.push_str("\nimpl<T: Transport> ApiClient<T> {\n");
+    out.push_str(&methods.join("\n"));
+    out.push_str("}\n");
+    Ok(out)
+}
+
+// Rust type of a schema, leaving out whether it is nullable
+fn rust_type(schema: &Value) -> Result<String, String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference_name(reference);
+    }
+    // A nullable reference is described as the only member of 'allOf'
+    if let Some([single]) = schema.get("allOf").and_then(Value::as_array).map(Vec::as_slice) {
+        return rust_type(single);
+    }
+
+    let minimum = schema.get("minimum").and_then(Value::as_f64);
+    let unsigned = minimum.is_some_and(|minimum| minimum >= 0.0);
+    let rust_type = match (schema.get("type").and_then(Value::as_str), schema.get("format").and_then(Value::as_str)) {
+        (Some("string"), _) => "String".to_string(),
+        (Some("boolean"), _) => "bool".to_string(),
+        (Some("integer"), Some("int32")) if unsigned => "u32".to_string(),
+        (Some("integer"), Some("int32")) => "i32".to_string(),
+        (Some("integer"), _) if unsigned => "u64".to_string(),
+        (Some("integer"), _) => "i64".to_string(),
+        (Some("number"), Some("float")) => "f32".to_string(),
+        (Some("number"), _) => "f64".to_string(),
+        (Some("array"), _) => format!("Vec<{}>", rust_type(&schema["items"])?),
+        // Free-form objects, e.g. the fields of a template
+        (Some("object"), _) if schema.get("properties").is_none() => "serde_json::Map<String, serde_json::Value>".to_string(),
+        (None, _) if schema.as_object().is_some_and(Map::is_empty) => "serde_json::Value".to_string(),
+        _ => return Err(format!("Unsupported schema {}", schema)),
+    };
+    Ok(rust_type)
+}
+
+fn reference_name(reference: &str) -> Result<String, String> {
+    reference.strip_prefix("#/components/schemas/")
+        .map(str::to_string)
+        .ok_or_else(|| format!("Unsupported reference '{}'", reference))
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    schema.get("nullable").and_then(Value::as_bool).unwrap_or(false)
+}
+
+// Struct of a component schema. Properties of 'allOf' members are merged in, while referenced members are flattened.
+fn schema_type(name: &str, schema: &Value) -> Result<String, String> {
+    let parts = match schema.get("allOf").and_then(Value::as_array) {
+        Some(parts) => parts.iter().collect(),
+        None => vec![schema],
+    };
+
+    let mut fields = String::new();
+    for part in parts {
+        if let Some(reference) = part.get("$ref").and_then(Value::as_str) {
+            let referenced = reference_name(reference)?;
+            let _ = writeln!(fields, "    #[serde(flatten)]\n    pub {}: {},", snake_case(&referenced), referenced);
+            continue;
+        }
+        if part.get("type").and_then(Value::as_str) != Some("object") {
+            return Err(format!("Schema '{}' is not an object", name));
+        }
+
+        let properties = part.get("properties").and_then(Value::as_object).ok_or_else(|| format!("Schema '{}' has no properties", name))?;
+        let required: Vec<&str> = part.get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        // Required properties in the order they are declared in, then the optional ones
+        let mut names: Vec<&str> = required.iter().copied().filter(|property| properties.contains_key(*property)).collect();
+        names.extend(properties.keys().map(String::as_str).filter(|property| !required.contains(property)));
+
+        for property in names {
+            let property_schema = &properties[property];
+            let field_type = rust_type(property_schema)?;
+            if required.contains(&property) && !is_nullable(property_schema) {
+                let _ = writeln!(fields, "    pub {}: {},", field_name(property), field_type);
+            } else {
+                let _ = writeln!(
+                    fields,
+                    "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub {}: Option<{}>,",
+                    field_name(property),
+                    field_type,
+                );
+            }
+        }
+    }
+
+    Ok(format!("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct {} {{\n{}}}\n", name, fields))
+}
+
+struct Parameter {
+    name: String,
+    rust_type: String,
+    required: bool,
+}
+
+struct Operation {
+    id: String,
+    method: &'static str,
+    path: String,
+    path_parameters: Vec<Parameter>,
+    query: Vec<Parameter>,
+    headers: Vec<Parameter>,
+    // Type of the body, and whether it is required
+    body: Option<(String, bool)>,
+    // Successful statuses along with the type of their body, if they have one
+    successes: Vec<(u16, Option<String>)>,
+}
+
+impl Operation {
+    fn parse(path: &str, method: &'static str, operation: &Value) -> Result<Operation, String> {
+        let id = operation["operationId"].as_str().ok_or_else(|| format!("{} {} has no operationId", method, path))?.to_string();
+
+        let (mut path_parameters, mut query, mut headers) = (Vec::new(), Vec::new(), Vec::new());
+        for parameter in operation.get("parameters").and_then(Value::as_array).into_iter().flatten() {
+            let parsed = Parameter {
+                name: parameter["name"].as_str().ok_or_else(|| format!("Parameter of '{}' has no name", id))?.to_string(),
+                rust_type: rust_type(&parameter["schema"])?,
+                required: parameter["required"].as_bool().unwrap_or(false),
+            };
+            match parameter["in"].as_str() {
+                Some("path") => path_parameters.push(parsed),
+                Some("query") => query.push(parsed),
+                Some("header") if parsed.rust_type == "String" => headers.push(parsed),
+                _ => return Err(format!("Unsupported parameter '{}' of '{}'", parsed.name, id)),
+            }
+        }
+
+        let body = match operation.get("requestBody") {
+            Some(body) => {
+                let schema = body.pointer("/content/application~1json/schema").ok_or_else(|| format!("Body of '{}' is not JSON", id))?;
+                Some((rust_type(schema)?, body["required"].as_bool().unwrap_or(false)))
+            }
+            None => None,
+        };
+
+        let mut successes = Vec::new();
+        for (status, response) in operation["responses"].as_object().ok_or_else(|| format!("'{}' has no responses", id))? {
+            let status: u16 = status.parse().map_err(|_| format!("Unsupported status '{}' of '{}'", status, id))?;
+            if (200..300).contains(&status) {
+                let schema = response.pointer("/content/application~1json/schema");
+                successes.push((status, schema.map(rust_type).transpose()?));
+            }
+        }
+        if successes.is_empty() {
+            return Err(format!("'{}' has no successful response", id));
+        }
+
+        Ok(Operation { id, method, path: path.to_string(), path_parameters, query, headers, body, successes })
+    }
+
+    // The type returned on success, or the statuses to tell apart when they carry different bodies
+    fn responses(&self) -> Result<Option<String>, &[(u16, Option<String>)]> {
+        let (_, first) = &self.successes[0];
+        if self.successes.iter().all(|(_, body)| body == first) {
+            Ok(first.clone())
+        } else {
+            Err(&self.successes)
+        }
+    }
+
+    fn query_type_name(&self) -> String {
+        format!("{}Query", pascal_case(&self.id))
+    }
+
+    fn response_type_name(&self) -> String {
+        format!("{}Response", pascal_case(&self.id))
+    }
+
+    fn query_type(&self) -> String {
+        if self.query.is_empty() {
+            return String::new();
+        }
+
+        let mut fields = String::new();
+        for parameter in &self.query {
+            let field_type = if parameter.required { parameter.rust_type.clone() } else { format!("Option<{}>", parameter.rust_type) };
+            let _ = writeln!(fields, "    pub {}: {},", field_name(&parameter.name), field_type);
+        }
+        format!("\n#[derive(Debug, Clone, Default, PartialEq)]\npub struct {} {{\n{}}}\n", self.query_type_name(), fields)
+    }
+
+    fn response_type(&self) -> String {
+        let Err(successes) = self.responses() else {
+            return String::new();
+        };
+
+        let mut variants = String::new();
+        for (status, body) in successes {
+            match body {
+                Some(body) => { let _ = writeln!(variants, "    {}({}),", status_variant(*status), body); }
+                None => { let _ = writeln!(variants, "    {},", status_variant(*status)); }
+            }
+        }
+        format!("\n#[derive(Debug, Clone, PartialEq)]\npub enum {} {{\n{}}}\n", self.response_type_name(), variants)
+    }
+
+    fn method(&self) -> String {
+        let mut arguments = vec!["&self".to_string()];
+        for parameter in &self.path_parameters {
+            arguments.push(format!("{}: {}", field_name(&parameter.name), parameter.rust_type));
+        }
+        if !self.query.is_empty() {
+            arguments.push(format!("query: &{}", self.query_type_name()));
+        }
+        for header in &self.headers {
+            let header_type = if header.required { "&str" } else { "Option<&str>" };
+            arguments.push(format!("{}: {}", snake_case(&header.name), header_type));
+        }
+        match &self.body {
+            Some((body, true)) => arguments.push(format!("body: &{}", body)),
+            Some((body, false)) => arguments.push(format!("body: Option<&{}>", body)),
+            None => {}
+        }
+
+        let returned = match self.responses() {
+            Ok(Some(body)) => body,
+            Ok(None) => "()".to_string(),
+            Err(_) => self.response_type_name(),
+        };
+
+        let mut body = String::new();
+        let path = if self.path_parameters.is_empty() {
+            format!("\"{}\".to_string()", self.path)
+        } else {
+            let mut template = self.path.clone();
+            for parameter in &self.path_parameters {
+                template = template.replace(&format!("{{{}}}", parameter.name), "{}");
+            }
+            let values: Vec<String> = self.path_parameters.iter().map(|parameter| field_name(&parameter.name)).collect();
+            format!("format!(\"{}\", {})", template, values.join(", "))
+        };
+        let mutable = !self.query.is_empty() || !self.headers.is_empty() || self.body.is_some();
+        let _ = writeln!(body, "        let {}request = ApiRequest::new(Method::{}, {});", if mutable { "mut " } else { "" }, self.method, path);
+
+        for parameter in &self.query {
+            let field = field_name(&parameter.name);
+            if parameter.required {
+                let _ = writeln!(body, "        request.query.push((\"{}\".to_string(), query.{}.to_string()));", parameter.name, field);
+            } else {
+                let _ = writeln!(
+                    body,
+                    "        if let Some(value) = &query.{} {{\n            request.query.push((\"{}\".to_string(), value.to_string()));\n        }}",
+                    field,
+                    parameter.name,
+                );
+            }
+        }
+        for header in &self.headers {
+            let argument = snake_case(&header.name);
+            if header.required {
+                let _ = writeln!(body, "        request.headers.push((\"{}\".to_string(), {}.to_string()));", header.name, argument);
+            } else {
+                let _ = writeln!(
+                    body,
+                    "        if let Some(value) = {} {{\n            request.headers.push((\"{}\".to_string(), value.to_string()));\n        }}",
+                    argument,
+                    header.name,
+                );
+            }
+        }
+        match &self.body {
+            Some((_, true)) => { let _ = writeln!(body, "        request.body = Some(encode(body)?);"); }
+            Some((_, false)) => { let _ = writeln!(body, "        if let Some(body) = body {{\n            request.body = Some(encode(body)?);\n        }}"); }
+            None => {}
+        }
+
+        match self.responses() {
+            Ok(Some(_)) => { let _ = writeln!(body, "        let response = self.send(request).await?;\n        decode(&response)"); }
+            Ok(None) => { let _ = writeln!(body, "        self.send(request).await?;\n        Ok(())"); }
+            Err(successes) => {
+                let _ = writeln!(body, "        let response = self.send(request).await?;\n        match response.status {{");
+                for (status, response_body) in successes {
+                    let variant = format!("{}::{}", self.response_type_name(), status_variant(*status));
+                    match response_body {
+                        Some(_) => { let _ = writeln!(body, "            {} => decode(&response).map({}),", status, variant); }
+                        None => { let _ = writeln!(body, "            {} => Ok({}),", status, variant); }
+                    }
+                }
+                let _ = writeln!(body, "            _ => Err(unexpected(response)),\n        }}");
+            }
+        }
+
+        format!(
+            "    // {} {}\n    pub async fn {}({}) -> Result<{}, ClientError> {{\n{}    }}\n",
+            self.method.to_uppercase(),
+            self.path,
+            self.id,
+            arguments.join(", "),
+            returned,
+            body,
+        )
+    }
+}
+
+fn status_variant(status: u16) -> String {
+    match status {
+        200 => "Ok".to_string(),
+        201 => "Created".to_string(),
+        202 => "Accepted".to_string(),
+        204 => "NoContent".to_string(),
+        207 => "MultiStatus".to_string(),
+        _ => format!("Status{}", status),
+    }
+}
+
+fn field_name(name: &str) -> String {
+    match name {
+        "type" | "ref" | "match" | "mod" | "move" | "self" | "use" | "where" => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
+// 'ReassignmentReport' and 'If-Match' alike become 'reassignment_report' and 'if_match'
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c == '-' {
+            snake.push('_');
+        } else if c.is_ascii_uppercase() {
+            if i > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::codegen::{generate, schema_type, snake_case};
+
+    #[test]
+    fn schema_type_makes_optional_and_nullable_properties_options_and_flattens_references() {
+        let schema = json!({"allOf": [
+            {"$ref": "#/components/schemas/Session"},
+            {"type": "object", "required": ["current", "ended_at"], "properties": {
+                "current": {"type": "boolean"},
+                "ended_at": {"type": "string", "format": "date-time", "nullable": true},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            }}
+        ]});
+
+        assert_eq!(schema_type("SessionSummary", &schema).unwrap(), concat!(
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n",
+            "pub struct SessionSummary {\n",
+            "    #[serde(flatten)]\n",
+            "    pub session: Session,\n",
+            "    pub current: bool,\n",
+            "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+            "    pub ended_at: Option<String>,\n",
+            "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+            "    pub tags: Option<Vec<String>>,\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn generate_tells_successful_statuses_apart_only_when_their_bodies_differ() {
+        let document = json!({
+            "components": {"schemas": {}},
+            "paths": {
+                "/things/{thing_id}": {
+                    "put": {
+                        "operationId": "update_thing",
+                        "parameters": [
+                            {"name": "thing_id", "in": "path", "required": true, "schema": {"type": "integer", "format": "int32"}},
+                            {"name": "If-Match", "in": "header", "required": false, "schema": {"type": "string"}}
+                        ],
+                        "requestBody": {"required": true, "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Thing"}}}},
+                        "responses": {
+                            "200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Thing"}}}},
+                            "202": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Proposal"}}}},
+                            "404": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}}
+                        }
+                    },
+                    "delete": {"operationId": "delete_thing", "parameters": [
+                        {"name": "thing_id", "in": "path", "required": true, "schema": {"type": "integer", "format": "int32"}}
+                    ], "responses": {"204": {}}}
+                }
+            }
+        });
+
+        let generated = generate(&document).unwrap();
+        assert!(generated.contains("pub enum UpdateThingResponse {\n    Ok(Thing),\n    Accepted(Proposal),\n}"));
+        assert!(generated.contains(
+            "pub async fn update_thing(&self, thing_id: i32, if_match: Option<&str>, body: &Thing) -> Result<UpdateThingResponse, ClientError> {"
+        ));
+        assert!(generated.contains("            202 => decode(&response).map(UpdateThingResponse::Accepted),\n"));
+        assert!(generated.contains("pub async fn delete_thing(&self, thing_id: i32) -> Result<(), ClientError> {"));
+        assert!(generated.contains("        let request = ApiRequest::new(Method::Delete, format!(\"/things/{}\", thing_id));\n"));
+    }
+
+    #[test]
+    fn generate_rejects_what_it_cannot_describe() {
+        let document = json!({
+            "components": {"schemas": {"Color": {"type": "string", "enum": ["red", "blue"]}}},
+            "paths": {}
+        });
+        assert!(generate(&document).is_err());
+        assert_eq!(snake_case("ReassignmentReport"), "reassignment_report");
+        assert_eq!(snake_case("If-Match"), "if_match");
+    }
+}
@@ -0,0 +1,80 @@
+use std::{env, fs, path::{Path, PathBuf}, process::{self, Command}};
+use serde_json::Value;
+
+mod codegen;
+
+const USAGE: &str = "Usage: cargo xtask codegen [--check]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["codegen"] => codegen(false),
+        ["codegen", "--check"] => codegen(true),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("xtask lives in the repository root").to_path_buf()
+}
+
+// Has the backend write its OpenAPI document, then generates the client from it. With '--check' nothing is written,
+// but the task fails if either is out of date, e.g. for CI to catch handlers that changed without regenerating.
+fn codegen(check: bool) -> Result<(), String> {
+    let root = repository_root();
+    let document = openapi_document(&root)?;
+    let parsed: Value = serde_json::from_str(&document).map_err(|err| format!("Invalid OpenAPI document: {}", err))?;
+    let generated = codegen::generate(&parsed)?;
+
+    let outputs = [
+        (root.join("client").join("openapi.json"), document),
+        (root.join("client").join("src").join("generated.rs"), generated),
+    ];
+
+    if check {
+        let stale: Vec<String> = outputs.iter()
+            .filter(|(path, contents)| fs::read_to_string(path).ok().as_deref() != Some(contents.as_str()))
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if !stale.is_empty() {
+            return Err(format!("Out of date, run 'cargo xtask codegen' to regenerate: {}", stale.join(", ")));
+        }
+        println!("The client is up to date");
+        return Ok(());
+    }
+
+    for (path, contents) in &outputs {
+        fs::write(path, contents).map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+// Runs the 'openapi' command of the backend, which needs neither a database nor any configuration
+fn openapi_document(root: &Path) -> Result<String, String> {
+    let output = env::temp_dir().join(format!("empires-openapi-{}.json", process::id()));
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let status = Command::new(cargo)
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(root.join("backend").join("Cargo.toml"))
+        .args(["--", "openapi", "--format", "json", "--output"])
+        .arg(&output)
+        .stdout(process::Stdio::null())
+        .status()
+        .map_err(|err| format!("Failed to run the backend: {}", err))?;
+    if !status.success() {
+        return Err("The backend failed to write its OpenAPI document".to_string());
+    }
+
+    let document = fs::read_to_string(&output).map_err(|err| format!("Failed to read {}: {}", output.display(), err));
+    let _ = fs::remove_file(&output);
+    document
+}